@@ -0,0 +1,207 @@
+//! What changed in a game's save files since its previous backup
+//!
+//! Each backup records the size and modification time of every file under the game's
+//! resolved save paths; comparing with the previous record gives the short summary
+//! carried by `BackupEvent::BackupCompleted`.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+
+use crate::auto_backup::AutoBackupConfig;
+use crate::backup::archive::{self, ArchiveEntry, ArchiveLocation, SourceFile};
+use crate::game_manager::scanner::{PatternFilter, SaveScanner, ScanLimits};
+
+/// Names listed in a summary before the rest are only counted
+const MAX_NAMED_FILES: usize = 3;
+
+/// A resolved save path and the patterns of the location it came from
+pub struct SaveTarget {
+    pub location_pattern: String,
+    pub path: String,
+    pub filter: PatternFilter,
+}
+
+/// Size and modification time of each save file at the time of a backup
+#[derive(Debug, Clone, Default)]
+pub struct SaveSnapshot {
+    files: BTreeMap<PathBuf, (u64, Option<DateTime<Utc>>)>,
+}
+
+impl SaveSnapshot {
+    /// Record the files below each path that pass its location's patterns; paths that don't
+    /// exist on disk (e.g. registry keys) are skipped
+    pub fn capture(targets: &[SaveTarget], config: &AutoBackupConfig) -> Self {
+        let limits = ScanLimits {
+            max_files: config.max_backup_files,
+            max_total_bytes: config.max_backup_bytes,
+            ..ScanLimits::default()
+        };
+
+        let mut snapshot = Self::default();
+        for target in targets {
+            let Ok(root) = Path::new(&target.path).canonicalize() else {
+                continue;
+            };
+            for file in SaveScanner::collect_files(&root, &target.filter, &limits).files {
+                snapshot.files.insert(file.path, (file.size, file.modified));
+            }
+        }
+        snapshot
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    /// The captured files as archive sources, each under the first target path holding it.
+    /// Entries follow the `files/<location>/<relative path>` layout of exported archives.
+    pub fn archive_sources(&self, targets: &[SaveTarget]) -> (Vec<ArchiveLocation>, Vec<SourceFile>) {
+        let mut locations = Vec::new();
+        let mut sources = Vec::new();
+        let mut archived = HashSet::new();
+
+        for target in targets {
+            let Ok(root) = Path::new(&target.path).canonicalize() else {
+                continue;
+            };
+            let location_index = locations.len();
+            locations.push(ArchiveLocation {
+                location_pattern: target.location_pattern.clone(),
+                resolved_root: root.to_string_lossy().to_string(),
+            });
+
+            for (path, (size, _)) in &self.files {
+                // A location that is a single file is stored under its own name
+                let relative = if *path == root {
+                    Path::new(root.file_name().unwrap_or_default())
+                } else {
+                    match path.strip_prefix(&root) {
+                        Ok(relative) => relative,
+                        Err(_) => continue,
+                    }
+                };
+                if !archived.insert(path) {
+                    continue;
+                }
+
+                let (relative_path, raw_relative_path) = archive::manifest_relative_path(relative);
+                sources.push(SourceFile {
+                    entry: ArchiveEntry {
+                        archive_path: format!("files/{}/{}", location_index, relative_path),
+                        location_pattern: target.location_pattern.clone(),
+                        relative_path,
+                        size: *size,
+                        location_index: Some(location_index),
+                        raw_relative_path,
+                    },
+                    absolute_path: path.clone(),
+                });
+            }
+        }
+
+        (locations, sources)
+    }
+}
+
+/// Files added, modified or removed between two snapshots
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SaveChanges {
+    pub added: Vec<PathBuf>,
+    pub modified: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+}
+
+impl SaveChanges {
+    pub fn between(previous: &SaveSnapshot, current: &SaveSnapshot) -> Self {
+        let mut changes = Self::default();
+        for (path, state) in &current.files {
+            match previous.files.get(path) {
+                None => changes.added.push(path.clone()),
+                Some(previous_state) if previous_state != state => changes.modified.push(path.clone()),
+                Some(_) => {}
+            }
+        }
+        changes.removed = previous.files.keys()
+            .filter(|path| !current.files.contains_key(*path))
+            .cloned()
+            .collect();
+        changes
+    }
+
+    pub fn count(&self) -> usize {
+        self.added.len() + self.modified.len() + self.removed.len()
+    }
+
+    /// e.g. "2 files changed, save1.dat modified, save2.dat added"
+    pub fn summary(&self) -> String {
+        let count = self.count();
+        if count == 0 {
+            return "No save files changed".to_string();
+        }
+
+        let mut parts = vec![format!("{} file{} changed", count, if count == 1 { "" } else { "s" })];
+        let named = self.modified.iter().map(|p| (p, "modified"))
+            .chain(self.added.iter().map(|p| (p, "added")))
+            .chain(self.removed.iter().map(|p| (p, "removed")));
+        for (path, change) in named.take(MAX_NAMED_FILES) {
+            let name = path.file_name().unwrap_or(path.as_os_str()).to_string_lossy();
+            parts.push(format!("{} {}", name, change));
+        }
+        if count > MAX_NAMED_FILES {
+            parts.push(format!("{} more", count - MAX_NAMED_FILES));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Summary of a backup compared with the game's previous one, if any was taken
+pub fn describe_backup(previous: Option<&SaveSnapshot>, current: &SaveSnapshot) -> String {
+    match previous {
+        Some(previous) => SaveChanges::between(previous, current).summary(),
+        None => format!("First backup, {} save file{}", current.file_count(), if current.file_count() == 1 { "" } else { "s" }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(path: &str, filter: PatternFilter) -> SaveTarget {
+        SaveTarget { location_pattern: path.to_string(), path: path.to_string(), filter }
+    }
+
+    #[test]
+    fn test_summary_names_changed_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("save1.dat"), b"one").unwrap();
+        std::fs::write(dir.path().join("save2.dat"), b"two").unwrap();
+        std::fs::write(dir.path().join("options.ini"), b"opts").unwrap();
+        let paths = vec![
+            target(&dir.path().to_string_lossy(), PatternFilter::new(&None, &None)),
+            target("HKEY_CURRENT_USER\\Software\\Game", PatternFilter::new(&None, &None)),
+        ];
+        let config = AutoBackupConfig::default();
+
+        let first = SaveSnapshot::capture(&paths, &config);
+        assert_eq!(describe_backup(None, &first), "First backup, 3 save files");
+        assert_eq!(describe_backup(Some(&first), &first), "No save files changed");
+
+        std::fs::write(dir.path().join("save1.dat"), b"one, longer").unwrap();
+        std::fs::remove_file(dir.path().join("save2.dat")).unwrap();
+        std::fs::write(dir.path().join("save3.dat"), b"three").unwrap();
+        let second = SaveSnapshot::capture(&paths, &config);
+
+        let changes = SaveChanges::between(&first, &second);
+        assert_eq!((changes.added.len(), changes.modified.len(), changes.removed.len()), (1, 1, 1));
+        assert_eq!(
+            describe_backup(Some(&first), &second),
+            "3 files changed, save1.dat modified, save3.dat added, save2.dat removed"
+        );
+
+        // Files the location's patterns leave out of backups are left out of snapshots too
+        let filtered = vec![target(&dir.path().to_string_lossy(), PatternFilter::new(&Some(r#"["*.dat"]"#.to_string()), &Some(r#"["save3.*"]"#.to_string())))];
+        assert_eq!(SaveSnapshot::capture(&filtered, &config).file_count(), 1);
+    }
+}
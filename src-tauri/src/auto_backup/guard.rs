@@ -0,0 +1,81 @@
+//! Pre-backup safeguards against misconfigured save locations
+//!
+//! A save location typed as `/`, `C:\` or a home directory would otherwise
+//! archive the whole disk, so such paths are refused before any backup runs.
+
+use std::path::Path;
+
+use crate::auto_backup::{AutoBackupConfig, BackupError, BackupResult};
+use crate::game_manager::scanner::{PatternFilter, SaveScanner, ScanLimits};
+
+/// Check every resolved save path; paths that don't exist on disk (e.g. registry keys) are skipped
+pub fn check_backup_paths(paths: &[String], config: &AutoBackupConfig) -> BackupResult<()> {
+    for path in paths {
+        check_backup_path(Path::new(path), config)?;
+    }
+    Ok(())
+}
+
+/// Refuse filesystem roots, home directories (or their ancestors) and paths over the size limits
+pub fn check_backup_path(path: &Path, config: &AutoBackupConfig) -> BackupResult<()> {
+    let canonical = match path.canonicalize() {
+        Ok(p) => p,
+        Err(_) => return Ok(()),
+    };
+
+    if canonical.parent().is_none() {
+        return Err(BackupError::Configuration(format!(
+            "Save location '{}' resolves to the filesystem root; refusing to back up the whole disk",
+            path.display()
+        )));
+    }
+
+    if let Some(home) = home::home_dir().and_then(|h| h.canonicalize().ok()) {
+        if home.starts_with(&canonical) {
+            return Err(BackupError::Configuration(format!(
+                "Save location '{}' contains the home directory; point it at the game's save folder instead",
+                path.display()
+            )));
+        }
+    }
+
+    let limits = ScanLimits {
+        max_files: config.max_backup_files,
+        max_total_bytes: config.max_backup_bytes,
+        ..ScanLimits::default()
+    };
+    let scan = SaveScanner::collect_files(&canonical, &PatternFilter::new(&None, &None), &limits);
+
+    if scan.limit_reached {
+        return Err(BackupError::Configuration(format!(
+            "Save location '{}' exceeds the backup limits ({} files / {} bytes); check that it points at the save folder",
+            path.display(),
+            config.max_backup_files,
+            config.max_backup_bytes
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_refuses_root_and_oversized_paths() {
+        let config = AutoBackupConfig::default();
+        let root = if cfg!(windows) { "C:\\" } else { "/" };
+        assert!(matches!(check_backup_path(Path::new(root), &config), Err(BackupError::Configuration(_))));
+
+        let dir = TempDir::new().unwrap();
+        for i in 0..3 {
+            std::fs::write(dir.path().join(format!("slot{}.sav", i)), b"data").unwrap();
+        }
+        assert!(check_backup_path(dir.path(), &config).is_ok());
+
+        let strict = AutoBackupConfig { max_backup_files: 2, ..AutoBackupConfig::default() };
+        assert!(matches!(check_backup_path(dir.path(), &strict), Err(BackupError::Configuration(_))));
+    }
+}
@@ -1,12 +1,88 @@
+use once_cell::sync::Lazy;
+use serde::Serialize;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{OnceCell, RwLock};
 use tokio::task::JoinHandle;
 
 use crate::auto_backup::*;
 use crate::database::DatabaseConnection;
 use crate::detection::{GameIdentificationEngine, GameIdentification, IdentificationConfidence};
+use crate::detection::engine::CacheClearReport;
 use crate::manifest::ManifestResolver;
 
+/// How often active sessions are checked for a game process that has exited
+const SESSION_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Shared integration layer used by the Tauri commands
+static BACKUP_SERVICE: Lazy<OnceCell<Arc<BackupIntegrationLayer>>> = Lazy::new(OnceCell::new);
+
+/// Get the application's integration layer, creating it on first use
+pub async fn backup_service() -> Result<Arc<BackupIntegrationLayer>, String> {
+    BACKUP_SERVICE.get_or_try_init(|| async {
+        let db = crate::database::connection::ensure_database_ready().await?;
+        let db_conn = db.lock().await.shared_connection();
+        let manifest_resolver = ManifestResolver::new()
+            .map_err(|e| format!("Failed to load manifest resolver: {}", e))?;
+
+        let service = BackupIntegrationBuilder::new().build(db_conn, manifest_resolver);
+        // Monitoring runs for as long as the service exists; `shutdown` stops it on exit
+        if let Err(e) = service.start().await {
+            crate::logger::error("AUTO_BACKUP", "Failed to start game monitoring", Some(&e.to_string()));
+        }
+        Ok(Arc::new(service))
+    }).await.cloned()
+}
+
+/// The integration layer if a command has already created it
+pub fn backup_service_if_started() -> Option<Arc<BackupIntegrationLayer>> {
+    BACKUP_SERVICE.get().cloned()
+}
+
+/// Invalidate cached detection results after a game changes.
+///
+/// Only affects the running integration layer; `None` clears every cached result.
+pub async fn invalidate_detection_cache(game_id: Option<i64>) {
+    let Some(service) = backup_service_if_started() else {
+        return;
+    };
+
+    let engine = service.get_identification_engine().await;
+    let engine = engine.read().await;
+    match game_id {
+        Some(game_id) => engine.invalidate_game(game_id).await,
+        None => {
+            engine.clear_cache().await;
+        }
+    }
+}
+
+/// Clear the in-memory detection caches of the running integration layer and the
+/// PCGamingWiki response cache, so stale matches are looked up again.
+pub async fn clear_all_caches(db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>) -> Result<CacheClearReport, String> {
+    let mut report = match backup_service_if_started() {
+        Some(service) => {
+            let engine = service.get_identification_engine().await;
+            let engine = engine.read().await;
+            engine.clear_all_caches().await
+        }
+        None => CacheClearReport::default(),
+    };
+
+    let db_guard = db.lock().await;
+    let conn = db_guard.get_connection().await;
+    report.pcgw = crate::pcgaming_wiki::cache::PcgwCache::clear(&conn)
+        .map_err(|e| format!("Failed to clear PCGamingWiki cache: {}", e))?;
+    Ok(report)
+}
+
+/// An active session together with the name of the game being played
+#[derive(Debug, Clone, Serialize)]
+pub struct ActiveSessionSummary {
+    pub game_name: Option<String>,
+    #[serde(flatten)]
+    pub session: GameSession,
+}
+
 /// Integration layer that connects Game Identification Engine to Auto-Backup System
 pub struct BackupIntegrationLayer {
     identification_engine: Arc<RwLock<GameIdentificationEngine>>,
@@ -55,6 +131,7 @@ impl BackupIntegrationLayer {
 
         // Start the event processing loop
         self.start_event_processing().await?;
+        self.start_session_watch();
 
         Ok(())
     }
@@ -68,6 +145,7 @@ impl BackupIntegrationLayer {
         if let Some(task) = self.event_task.write().await.take() {
             task.abort();
         }
+        self.identification_engine.read().await.stop_monitoring().await;
 
         // Clean up all game sessions
         let sessions = self.backup_manager.get_active_sessions().await;
@@ -78,6 +156,31 @@ impl BackupIntegrationLayer {
         Ok(())
     }
 
+    /// Stop for application exit: take no more backup triggers, write a final backup for
+    /// each running game session, then refuse new backups and wait for the ones being
+    /// written. Returns `false` if that didn't finish within `timeout`.
+    pub async fn shutdown(&self, timeout: std::time::Duration) -> bool {
+        *self.running.write().await = false;
+        if let Some(task) = self.event_task.write().await.take() {
+            task.abort();
+        }
+        self.identification_engine.read().await.stop_monitoring().await;
+
+        let finished = tokio::time::timeout(timeout, async {
+            for session in self.backup_manager.get_active_sessions().await {
+                if let Err(e) = self.backup_manager.handle_game_exit(session.game_id).await {
+                    crate::logger::warn("AUTO_BACKUP", &format!("Final backup for game {} failed during shutdown", session.game_id), Some(&e.to_string()));
+                }
+            }
+            self.backup_manager.stop_accepting_backups();
+            self.backup_manager.wait_for_backups().await;
+        }).await.is_ok();
+
+        // Even after a timeout, nothing new should start while the process exits
+        self.backup_manager.stop_accepting_backups();
+        finished
+    }
+
     /// Process identification results and start backup sessions
     pub async fn handle_game_identification(&self, identification: GameIdentification) -> BackupResult<()> {
         // Only handle identifications we're confident about
@@ -98,11 +201,42 @@ impl BackupIntegrationLayer {
         Ok(())
     }
 
+    /// Start a backup session for a process the user identified by hand
+    pub async fn track_process_as_game(&self, pid: u32, game_id: i64) -> BackupResult<crate::detection::ManualIdentification> {
+        let manual = self.identification_engine.read().await
+            .record_manual_identification(pid, game_id).await?;
+
+        // The user's word is definitive, so bypass the confidence threshold
+        self.backup_manager.handle_game_identification(game_id, pid, 100.0).await?;
+
+        Ok(manual)
+    }
+
+    /// Stop monitoring a game and end its session without a final backup
+    pub async fn detach_game(&self, game_id: i64) -> BackupResult<bool> {
+        self.backup_manager.detach_game(game_id).await
+    }
+
     /// Manually trigger backup for a game
     pub async fn create_manual_backup(&self, game_id: i64) -> BackupResult<String> {
         self.backup_manager.create_manual_backup(game_id).await
     }
 
+    /// Manually back up every game with backups enabled, reporting each game's outcome
+    pub async fn backup_all_enabled(&self) -> BackupResult<Vec<GameBackupOutcome>> {
+        self.backup_manager.backup_all_enabled().await
+    }
+
+    /// Back up a running game now, ignoring the real-time debounce
+    pub async fn force_backup_now(&self, game_id: i64) -> BackupResult<ForcedBackup> {
+        self.backup_manager.force_backup_now(game_id).await
+    }
+
+    /// When the game's next real-time backup can happen, if it has an active session
+    pub async fn next_backup_time(&self, game_id: i64) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.backup_manager.next_backup_time(game_id).await
+    }
+
     /// Get backup statistics for a game
     pub async fn get_backup_stats(&self, game_id: i64) -> BackupResult<crate::auto_backup::retention::BackupStats> {
         self.backup_manager.get_backup_stats(game_id).await
@@ -123,6 +257,29 @@ impl BackupIntegrationLayer {
         self.backup_manager.get_active_sessions().await
     }
 
+    /// Save paths being watched for a game, or the ones that would be if it were running
+    pub async fn get_monitored_paths(&self, game_id: i64) -> BackupResult<MonitoredPaths> {
+        self.backup_manager.get_monitored_paths(game_id).await
+    }
+
+    /// Active sessions joined with their game names, oldest session first
+    pub async fn get_active_session_summaries(&self) -> Vec<ActiveSessionSummary> {
+        let mut sessions = self.get_active_sessions().await;
+        sessions.sort_by_key(|s| s.started_at);
+
+        let conn = self.backup_manager.db_conn.lock().await;
+        sessions.into_iter()
+            .map(|session| {
+                let game_name = conn.query_row(
+                    "SELECT name FROM games WHERE id = ?",
+                    [session.game_id],
+                    |row| row.get::<_, String>(0),
+                ).ok();
+                ActiveSessionSummary { game_name, session }
+            })
+            .collect()
+    }
+
     /// Check if the system is running
     pub async fn is_running(&self) -> bool {
         *self.running.read().await
@@ -166,6 +323,24 @@ impl BackupIntegrationLayer {
         Ok(())
     }
 
+    /// End sessions once their game process exits, until the layer stops
+    fn start_session_watch(&self) {
+        let backup_manager = self.backup_manager.clone();
+        let running = Arc::clone(&self.running);
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(SESSION_CHECK_INTERVAL).await;
+                if !*running.read().await {
+                    break;
+                }
+                for game_id in backup_manager.end_exited_sessions().await {
+                    crate::logger::info("AUTO_BACKUP", &format!("Game {} exited, ended its backup session", game_id), None);
+                }
+            }
+        });
+    }
+
     async fn handle_backup_event(backup_manager: &AutoBackupManager, event: BackupEvent) -> BackupResult<()> {
         match event {
             BackupEvent::BackupTriggered { game_id, backup_type } => {
@@ -1,12 +1,21 @@
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use crate::auto_backup::*;
 use crate::database::DatabaseConnection;
+use crate::detection::process_monitor::ProcessMonitor;
 use crate::manifest::ManifestResolver;
 use crate::auto_backup::retention::RetentionConfig;
+use crate::game_manager::naming::{NamingContext, NamingTemplate};
+use crate::game_manager::scanner::PatternFilter;
+use crate::backup::archive::{self, ArchiveFormat, SaveArchiveManifest};
+use futures::StreamExt;
+
+/// Games backed up at once by `backup_all_enabled`
+const BULK_BACKUP_CONCURRENCY: usize = 4;
 
 /// Main orchestrator for the auto-backup system
 #[derive(Clone)]
@@ -18,6 +27,46 @@ pub struct AutoBackupManager {
     pub config: AutoBackupConfig,
     pub game_sessions: Arc<RwLock<HashMap<i64, GameSession>>>,
     pub event_sender: tokio::sync::broadcast::Sender<BackupEvent>,
+    accepting_backups: Arc<AtomicBool>,
+    in_flight: Arc<InFlightBackups>,
+    /// Save files as of each game's latest backup, to describe what the next one changes
+    last_snapshots: Arc<RwLock<HashMap<i64, changes::SaveSnapshot>>>,
+}
+
+/// Counts backups being written so shutdown can wait for them to finish
+#[derive(Default)]
+struct InFlightBackups {
+    count: AtomicUsize,
+    idle: tokio::sync::Notify,
+}
+
+impl InFlightBackups {
+    fn start(self: &Arc<Self>) -> InFlightGuard {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        InFlightGuard(self.clone())
+    }
+
+    async fn wait_idle(&self) {
+        loop {
+            // Register before checking so a backup finishing in between still wakes us
+            let mut notified = std::pin::pin!(self.idle.notified());
+            notified.as_mut().enable();
+            if self.count.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+struct InFlightGuard(Arc<InFlightBackups>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.0.count.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
 }
 
 impl AutoBackupManager {
@@ -26,7 +75,15 @@ impl AutoBackupManager {
         manifest_resolver: ManifestResolver,
         config: AutoBackupConfig,
     ) -> Self {
-        let monitor = Arc::new(SaveMonitor::new());
+        let monitor = Arc::new(
+            SaveMonitor::new()
+                .with_registry_poll_interval(std::time::Duration::from_secs(config.registry_poll_interval))
+                .with_default_timing(monitor::MonitorTiming {
+                    debounce: std::time::Duration::from_secs(config.real_time_backup_delay),
+                    coalesce_window: std::time::Duration::from_millis(config.event_coalesce_window_ms),
+                })
+                .with_database(db_conn.clone())
+        );
         let retention_policy = RetentionPolicy::new(RetentionConfig::default())
             .with_database(db_conn.clone());
 
@@ -40,6 +97,9 @@ impl AutoBackupManager {
             config,
             game_sessions: Arc::new(RwLock::new(HashMap::new())),
             event_sender: tx,
+            accepting_backups: Arc::new(AtomicBool::new(true)),
+            in_flight: Arc::new(InFlightBackups::default()),
+            last_snapshots: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -65,9 +125,17 @@ impl AutoBackupManager {
             return Ok(()); // Already monitoring this game
         }
 
+        // The user stopped protecting this game
+        if !self.is_backup_enabled(game_id).await {
+            return Ok(());
+        }
+
         // Create new game session
         let mut session = GameSession::new(game_id, process_id);
 
+        session.real_time_backup_override = self.get_real_time_backup_override(game_id).await;
+        session.backup_delay_override = self.get_backup_delay_override(game_id).await;
+
         // Resolve save locations using manifest data
         let save_paths = self.resolve_save_locations(game_id).await?;
         session.monitored_paths = save_paths.clone();
@@ -77,7 +145,7 @@ impl AutoBackupManager {
 
         // Create initial backup (session start)
         if session.should_create_backup(&self.config, BackupType::SessionStart) {
-            let backup_id = format!("session_start_{}", Uuid::new_v4().simple());
+            let backup_id = self.new_backup_id(game_id, "session_start").await;
             self.create_backup(game_id, &backup_id, BackupType::SessionStart).await?;
             session.record_backup();
         }
@@ -95,6 +163,27 @@ impl AutoBackupManager {
         Ok(())
     }
 
+    /// End a game's session and stop monitoring it without a final backup.
+    /// Returns whether a session was active.
+    pub async fn detach_game(&self, game_id: i64) -> BackupResult<bool> {
+        let session = {
+            let mut sessions = self.game_sessions.write().await;
+            sessions.remove(&game_id)
+        };
+
+        let Some(session) = session else {
+            return Ok(false);
+        };
+
+        self.monitor.stop_monitoring_game(game_id).await?;
+        let _ = self.event_sender.send(BackupEvent::GameSessionEnded {
+            game_id,
+            session_id: session.session_id,
+        });
+
+        Ok(true)
+    }
+
     /// Handle game exit/stop monitoring
     pub async fn handle_game_exit(&self, game_id: i64) -> BackupResult<()> {
         // Get session
@@ -109,7 +198,7 @@ impl AutoBackupManager {
 
             // Create final backup
             if session.should_create_backup(&self.config, BackupType::SessionEnd) {
-                let backup_id = format!("session_end_{}", Uuid::new_v4().simple());
+                let backup_id = self.new_backup_id(game_id, "session_end").await;
                 self.create_backup(game_id, &backup_id, BackupType::SessionEnd).await?;
             }
 
@@ -126,8 +215,15 @@ impl AutoBackupManager {
     /// Handle backup trigger event from file monitoring
     pub async fn handle_backup_trigger(&self, game_id: i64, backup_type: BackupType) -> BackupResult<()> {
         // Check if we have an active session
-        if self.get_active_session(game_id).await.is_none() {
+        let Some(mut session) = self.get_active_session(game_id).await else {
             return Ok(()); // No active session for this game
+        };
+
+        // Re-read the per-game override so changes apply to running sessions
+        session.real_time_backup_override = self.get_real_time_backup_override(game_id).await;
+        session.backup_delay_override = self.get_backup_delay_override(game_id).await;
+        if !session.should_create_backup(&self.config, backup_type) {
+            return Ok(());
         }
 
         // Check with retention policy
@@ -143,8 +239,9 @@ impl AutoBackupManager {
         }
 
         // Create the backup
-        let backup_id = format!("{:?}_{}", backup_type, Uuid::new_v4().simple()).to_lowercase();
-        self.create_backup(game_id, &backup_id, backup_type).await?;
+        let backup_id = self.new_backup_id(game_id, &format!("{:?}", backup_type).to_lowercase()).await;
+        let changes = self.create_backup(game_id, &backup_id, backup_type).await?;
+        crate::logger::info("AUTO_BACKUP", &format!("Backup {} of game {}: {}", backup_id, game_id, changes), None);
 
         // Update session
         if let Some(session) = self.get_active_session(game_id).await.as_mut() {
@@ -155,6 +252,7 @@ impl AutoBackupManager {
         let _ = self.event_sender.send(BackupEvent::BackupCompleted {
             game_id,
             backup_id,
+            changes,
         });
 
         Ok(())
@@ -162,7 +260,7 @@ impl AutoBackupManager {
 
     /// Manually create backup for a game
     pub async fn create_manual_backup(&self, game_id: i64) -> BackupResult<String> {
-        let backup_id = format!("manual_{}", Uuid::new_v4().simple());
+        let backup_id = self.new_backup_id(game_id, "manual").await;
 
         // Check retention policy
         let (should_create, deleted_backup) = self.retention_policy.should_create_backup(game_id).await?;
@@ -180,6 +278,111 @@ impl AutoBackupManager {
         Ok(backup_id)
     }
 
+    /// Manually back up every active game with backups enabled, running sessions or not.
+    ///
+    /// At most `BULK_BACKUP_CONCURRENCY` backups run at once; a failing game is reported
+    /// in its outcome and doesn't stop the others.
+    pub async fn backup_all_enabled(&self) -> BackupResult<Vec<GameBackupOutcome>> {
+        let game_ids = {
+            let conn = self.db_conn.lock().await;
+            crate::game_manager::GameManager::get_backup_enabled_game_ids(&conn).map_err(BackupError::Manifest)?
+        };
+
+        let mut outcomes: Vec<GameBackupOutcome> = futures::stream::iter(game_ids)
+            .map(|game_id| async move {
+                match self.create_manual_backup(game_id).await {
+                    Ok(backup_id) => GameBackupOutcome { game_id, success: true, backup_id: Some(backup_id), error: None },
+                    Err(e) => {
+                        crate::logger::warn("AUTO_BACKUP", &format!("Bulk backup of game {} failed", game_id), Some(&e.to_string()));
+                        GameBackupOutcome { game_id, success: false, backup_id: None, error: Some(e.to_string()) }
+                    }
+                }
+            })
+            .buffer_unordered(BULK_BACKUP_CONCURRENCY)
+            .collect()
+            .await;
+
+        outcomes.sort_by_key(|outcome| outcome.game_id);
+        Ok(outcomes)
+    }
+
+    /// Take a real-time backup of a running game right away, skipping the debounce delay but
+    /// not the retention policy, and count it toward the game's session
+    pub async fn force_backup_now(&self, game_id: i64) -> BackupResult<ForcedBackup> {
+        if self.get_active_session(game_id).await.is_none() {
+            return Err(BackupError::Session(format!("Active session for game {} not found", game_id)));
+        }
+
+        let (should_create, deleted_backup) = self.retention_policy.should_create_backup(game_id).await?;
+        if let Some(deleted_id) = deleted_backup {
+            eprintln!("Auto-deleted old backup: {}", deleted_id);
+        }
+        if !should_create {
+            return Err(BackupError::Retention(
+                "Cannot create backup - retention policy prevents it".to_string()
+            ));
+        }
+
+        let backup_id = self.new_backup_id(game_id, "forced").await;
+        let changes = self.create_backup(game_id, &backup_id, BackupType::RealTime).await?;
+
+        // Record against the stored session, which may have ended while the backup ran
+        let session = {
+            let mut sessions = self.game_sessions.write().await;
+            sessions.get_mut(&game_id).map(|session| {
+                session.record_backup();
+                session.clone()
+            })
+        };
+
+        let _ = self.event_sender.send(BackupEvent::BackupCompleted {
+            game_id,
+            backup_id: backup_id.clone(),
+            changes: changes.clone(),
+        });
+
+        let session = session.ok_or_else(|| BackupError::Session(format!("Session of game {} ended during the backup", game_id)))?;
+        Ok(ForcedBackup {
+            game_id,
+            backup_id,
+            session_id: session.session_id,
+            session_backup_count: session.backup_count,
+            changes,
+        })
+    }
+
+    /// When the game's next real-time backup can happen, or `None` without an active session
+    pub async fn next_backup_time(&self, game_id: i64) -> Option<chrono::DateTime<chrono::Utc>> {
+        let mut session = self.get_active_session(game_id).await?;
+        // Re-read the per-game overrides, as the backup trigger does
+        session.real_time_backup_override = self.get_real_time_backup_override(game_id).await;
+        session.backup_delay_override = self.get_backup_delay_override(game_id).await;
+        session.next_backup_time(&self.config)
+    }
+
+    /// End, with their session-end backup, the sessions whose game process has exited.
+    /// Returns the games whose session ended.
+    pub async fn end_exited_sessions(&self) -> Vec<i64> {
+        let sessions: Vec<(i64, u32)> = self.get_active_sessions().await.into_iter()
+            .map(|session| (session.game_id, session.process_id))
+            .collect();
+        let exited: Vec<i64> = tokio::task::spawn_blocking(move || {
+            sessions.into_iter()
+                .filter(|(_, pid)| !ProcessMonitor::is_pid_alive(*pid))
+                .map(|(game_id, _)| game_id)
+                .collect()
+        })
+        .await
+        .unwrap_or_default();
+
+        for game_id in &exited {
+            if let Err(e) = self.handle_game_exit(*game_id).await {
+                crate::logger::warn("AUTO_BACKUP", &format!("Failed to end the session of game {} after its process exited", game_id), Some(&e.to_string()));
+            }
+        }
+        exited
+    }
+
     /// Get backup statistics for a game
     pub async fn get_backup_stats(&self, game_id: i64) -> BackupResult<crate::auto_backup::retention::BackupStats> {
         self.retention_policy.get_backup_stats(game_id).await
@@ -191,6 +394,29 @@ impl AutoBackupManager {
         sessions.values().cloned().collect()
     }
 
+    /// Paths monitored by the game's live session, or the ones a session would monitor
+    pub async fn get_monitored_paths(&self, game_id: i64) -> BackupResult<MonitoredPaths> {
+        if let Some(session) = self.get_active_session(game_id).await {
+            return Ok(MonitoredPaths { game_id, active: true, paths: session.monitored_paths });
+        }
+
+        Ok(MonitoredPaths {
+            game_id,
+            active: false,
+            paths: self.resolve_save_locations(game_id).await?,
+        })
+    }
+
+    /// Refuse new backups from now on; backups already being written carry on
+    pub fn stop_accepting_backups(&self) {
+        self.accepting_backups.store(false, Ordering::SeqCst);
+    }
+
+    /// Wait until no backup is being written
+    pub async fn wait_for_backups(&self) {
+        self.in_flight.wait_idle().await;
+    }
+
     /// Clean up old data
     pub async fn cleanup(&self) -> BackupResult<()> {
         // Clean up old event debouncing data
@@ -223,43 +449,152 @@ impl AutoBackupManager {
         Ok(())
     }
 
-    async fn resolve_save_locations(&self, game_id: i64) -> BackupResult<Vec<String>> {
-        // This would query the manifest resolver to get save paths for a game
-        // For now, return a placeholder - this needs to integrate with the actual manifest system
-        // TODO: Connect to ManifestResolver API
+    async fn is_backup_enabled(&self, game_id: i64) -> bool {
+        let conn = self.db_conn.lock().await;
+        crate::game_manager::GameManager::is_backup_enabled(&conn, game_id)
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to read backup enabled setting for game {}: {}", game_id, e);
+                true
+            })
+    }
 
-        // Placeholder implementation - in practice this would:
-        // 1. Look up game in database to get manifest_id
-        // 2. Use ManifestResolver to resolve actual save paths from manifest
-        // 3. Return list of concrete paths to monitor
+    async fn get_real_time_backup_override(&self, game_id: i64) -> Option<bool> {
+        let conn = self.db_conn.lock().await;
+        crate::game_manager::GameManager::get_real_time_backup_override(&conn, game_id)
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to read real-time backup setting for game {}: {}", game_id, e);
+                None
+            })
+    }
 
-        eprintln!("TODO: Resolve actual save locations for game_id: {}", game_id);
+    async fn get_backup_delay_override(&self, game_id: i64) -> Option<u64> {
+        let conn = self.db_conn.lock().await;
+        crate::game_manager::GameManager::get_monitor_settings(&conn, game_id)
+            .map(|settings| settings.debounce_seconds)
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to read backup timing for game {}: {}", game_id, e);
+                None
+            })
+    }
+
+    /// Id for a new backup: `<kind>_<uuid>`, or the game's naming template followed by a
+    /// short unique suffix when one is set
+    async fn new_backup_id(&self, game_id: i64, kind: &str) -> String {
+        let unique = Uuid::new_v4().simple().to_string();
+        let conn = self.db_conn.lock().await;
 
-        // For now, return empty list - will be replaced with proper manifest integration
-        Ok(Vec::new())
+        let template = crate::game_manager::GameManager::get_naming_template(&conn, game_id)
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to read naming template for game {}: {}", game_id, e);
+                None
+            });
+        let Some(template) = template else {
+            return format!("{}_{}", kind, unique);
+        };
+
+        let game_name: String = conn.query_row("SELECT name FROM games WHERE id = ?", [game_id], |row| row.get(0))
+            .unwrap_or_default();
+        let existing: i64 = conn.query_row("SELECT COUNT(*) FROM backups WHERE game_id = ?", [game_id], |row| row.get(0))
+            .unwrap_or(0);
+
+        let name = NamingTemplate::render_file_safe(&template, &NamingContext {
+            game: &game_name,
+            save: kind,
+            date: chrono::Utc::now(),
+            counter: existing as usize + 1,
+        });
+        format!("{}_{}", name, &unique[..8])
     }
 
-    async fn create_backup(&self, game_id: i64, backup_id: &str, backup_type: BackupType) -> BackupResult<()> {
-        // This would create the actual backup file
-        // For now, record the backup in the retention policy
+    async fn resolve_save_locations(&self, game_id: i64) -> BackupResult<Vec<String>> {
+        Ok(self.resolve_save_targets(game_id).await?.into_iter().map(|target| target.path).collect())
+    }
 
-        // TODO: Implement actual backup creation:
-        // 1. Find save locations for the game
-        // 2. Compress/backup the save files to a timestamped archive
-        // 3. Store in backup directory
-        // 4. Record in retention system
+    /// Each resolved save path with its location's `file_patterns` / `exclude_patterns`
+    async fn resolve_save_targets(&self, game_id: i64) -> BackupResult<Vec<changes::SaveTarget>> {
+        let locations = {
+            let conn = self.db_conn.lock().await;
+            crate::game_manager::GameManager::get_save_locations(&conn, game_id)
+                .map_err(BackupError::Manifest)?
+        };
 
-        let backup_path = format!("/tmp/backup_{}_{}.zip", game_id, backup_id); // Placeholder
+        let mut targets = Vec::new();
+        // Locations meant for another OS would only fail to resolve here
+        for location in locations.iter().filter(|l| crate::game_manager::GameManager::applies_to_current_platform(l)) {
+            if location.path_type == "registry" {
+                // Registry keys are polled by the monitor rather than resolved on disk
+                targets.push(changes::SaveTarget {
+                    location_pattern: location.path_pattern.clone(),
+                    path: location.path_pattern.clone(),
+                    filter: PatternFilter::new(&None, &None),
+                });
+            } else {
+                for path in crate::game_manager::GameManager::resolve_save_paths(location).map_err(BackupError::Manifest)? {
+                    targets.push(changes::SaveTarget {
+                        location_pattern: location.path_pattern.clone(),
+                        path,
+                        filter: PatternFilter::new(&location.file_patterns, &location.exclude_patterns),
+                    });
+                }
+            }
+        }
+
+        Ok(targets)
+    }
+
+    /// Returns a summary of what changed since the game's previous backup
+    async fn create_backup(&self, game_id: i64, backup_id: &str, backup_type: BackupType) -> BackupResult<String> {
+        // Counted before checking, so shutdown either waits for this backup or it sees the flag
+        let _in_flight = self.in_flight.start();
+        if !self.accepting_backups.load(Ordering::SeqCst) {
+            return Err(BackupError::Session("Backup service is shutting down".to_string()));
+        }
+
+        // Refuse roots, home directories and oversized paths before archiving anything
+        let save_targets = self.resolve_save_targets(game_id).await?;
+        let game = {
+            let conn = self.db_conn.lock().await;
+            crate::game_manager::GameManager::get_game_by_id(&conn, game_id).map_err(BackupError::Manifest)?
+        };
+        let backup_path = self.retention_policy.backup_path_for(game_id, backup_id).await;
+        let config = self.config.clone();
+        let archive_path = backup_path.clone();
+
+        let (snapshot, report) = tokio::task::spawn_blocking(move || {
+            let save_paths: Vec<String> = save_targets.iter().map(|target| target.path.clone()).collect();
+            guard::check_backup_paths(&save_paths, &config)?;
+            let snapshot = changes::SaveSnapshot::capture(&save_targets, &config);
+
+            let (locations, sources) = snapshot.archive_sources(&save_targets);
+            let mut manifest = SaveArchiveManifest::new(&game.name, &game.platform, game.platform_app_id.clone());
+            manifest.locations = locations;
+            manifest.files = sources.iter().map(|source| source.entry.clone()).collect();
+            manifest.save_modified_at = archive::newest_modified_at(&sources);
+
+            let report = archive::write_archive(&archive_path, &manifest, &sources, ArchiveFormat::TarZst, archive::DEFAULT_COMPRESSION_LEVEL, None)
+                .map_err(BackupError::Compression)?;
+            Ok::<_, BackupError>((snapshot, report))
+        })
+        .await
+        .map_err(|e| BackupError::Io(std::io::Error::other(e)))??;
+
+        if !report.skipped_files.is_empty() {
+            crate::logger::warn("AUTO_BACKUP", &format!("Backup {} of game {} left out {} unreadable files", backup_id, game_id, report.skipped_files.len()), None);
+        }
 
         self.retention_policy.record_backup(
             game_id,
             backup_id.to_string(),
             retention::BackupType::from(backup_type),
-            &backup_path,
-            None, // Size not known yet
+            &backup_path.to_string_lossy(),
+            Some(report.archive_size),
+            Some(report.original_size),
         ).await?;
 
-        Ok(())
+        let mut last_snapshots = self.last_snapshots.write().await;
+        let changes = changes::describe_backup(last_snapshots.get(&game_id), &snapshot);
+        last_snapshots.insert(game_id, snapshot);
+        Ok(changes)
     }
 
     async fn get_active_session(&self, game_id: i64) -> Option<GameSession> {
@@ -275,3 +610,110 @@ impl Drop for AutoBackupManager {
         eprintln!("AutoBackupManager shutting down - stopping all monitoring");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_wait_idle_waits_for_running_backups() {
+        let in_flight = Arc::new(InFlightBackups::default());
+        in_flight.wait_idle().await;
+
+        let guard = in_flight.start();
+        let waiter = tokio::spawn({
+            let in_flight = in_flight.clone();
+            async move { in_flight.wait_idle().await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        drop(guard);
+        tokio::time::timeout(std::time::Duration::from_secs(1), waiter).await.unwrap().unwrap();
+    }
+
+    /// A manager whose game 1 saves to `saves`, backing up below `backups`
+    fn manager_with_game(saves: &std::path::Path, backups: &std::path::Path) -> AutoBackupManager {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        crate::database::schema::DatabaseSchema::create_tables(&conn).unwrap();
+        crate::database::settings::AppSettings::set(&conn, "backup_root", &backups.to_string_lossy()).unwrap();
+        conn.execute("INSERT INTO games (name, platform) VALUES ('Game', 'other')", []).unwrap();
+        conn.execute(
+            "INSERT INTO save_locations (game_id, path_pattern, path_type) VALUES (1, ?, 'directory')",
+            [saves.to_string_lossy()],
+        ).unwrap();
+
+        AutoBackupManager::new(
+            Arc::new(tokio::sync::Mutex::new(conn)),
+            ManifestResolver::new().unwrap(),
+            AutoBackupConfig::default(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_manual_backup_writes_archive() {
+        let saves = tempfile::TempDir::new().unwrap();
+        let backups = tempfile::TempDir::new().unwrap();
+        std::fs::write(saves.path().join("slot1.sav"), b"save data").unwrap();
+
+        let manager = manager_with_game(saves.path(), backups.path());
+        let backup_id = manager.create_manual_backup(1).await.unwrap();
+
+        let (file_path, size_bytes, original_bytes): (String, i64, i64) = manager.db_conn.lock().await.query_row(
+            "SELECT file_path, size_bytes, original_bytes FROM backups WHERE backup_id = ?",
+            [&backup_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        ).unwrap();
+        let archive_path = std::path::Path::new(&file_path);
+        assert!(archive_path.starts_with(backups.path()));
+        assert_eq!(std::fs::metadata(archive_path).unwrap().len(), size_bytes as u64);
+        assert_eq!(original_bytes, 9);
+
+        let manifest = archive::read_manifest(archive_path).unwrap();
+        assert_eq!(manifest.files.len(), 1);
+        assert_eq!(manifest.files[0].relative_path, "slot1.sav");
+    }
+
+    #[tokio::test]
+    async fn test_forced_backup_writes_archive() {
+        let saves = tempfile::TempDir::new().unwrap();
+        let backups = tempfile::TempDir::new().unwrap();
+        std::fs::write(saves.path().join("slot1.sav"), b"save data").unwrap();
+
+        let manager = manager_with_game(saves.path(), backups.path());
+        manager.game_sessions.write().await.insert(1, GameSession::new(1, std::process::id()));
+
+        let forced = manager.force_backup_now(1).await.unwrap();
+        assert_eq!(forced.session_backup_count, 1);
+
+        let file_path: String = manager.db_conn.lock().await.query_row(
+            "SELECT file_path FROM backups WHERE backup_id = ?",
+            [&forced.backup_id],
+            |row| row.get(0),
+        ).unwrap();
+        let listed = archive::list_entries(std::path::Path::new(&file_path)).unwrap();
+        assert!(listed.contains(&("files/0/slot1.sav".to_string(), 9)));
+    }
+
+    #[tokio::test]
+    async fn test_end_exited_sessions() {
+        let saves = tempfile::TempDir::new().unwrap();
+        let backups = tempfile::TempDir::new().unwrap();
+        let manager = manager_with_game(saves.path(), backups.path());
+
+        let mut exited = std::process::Command::new(std::env::current_exe().unwrap())
+            .arg("--list")
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .unwrap();
+        let exited_pid = exited.id();
+        exited.wait().unwrap();
+
+        manager.game_sessions.write().await.insert(1, GameSession::new(1, exited_pid));
+        manager.game_sessions.write().await.insert(2, GameSession::new(2, std::process::id()));
+
+        assert_eq!(manager.end_exited_sessions().await, vec![1]);
+        let remaining: Vec<i64> = manager.get_active_sessions().await.iter().map(|s| s.game_id).collect();
+        assert_eq!(remaining, vec![2]);
+    }
+}
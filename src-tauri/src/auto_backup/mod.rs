@@ -2,10 +2,13 @@ pub mod manager;
 pub mod monitor;
 pub mod retention;
 pub mod integration;
+pub mod guard;
+pub mod changes;
 
 pub use manager::AutoBackupManager;
 pub use monitor::SaveMonitor;
 pub use retention::RetentionPolicy;
+pub use integration::{backup_service, backup_service_if_started, invalidate_detection_cache, clear_all_caches};
 
 /// Events emitted by the auto-backup system
 #[derive(Debug, Clone)]
@@ -13,7 +16,8 @@ pub enum BackupEvent {
     GameSessionStarted { game_id: i64, process_id: u32 },
     GameSessionEnded { game_id: i64, session_id: String },
     BackupTriggered { game_id: i64, backup_type: BackupType },
-    BackupCompleted { game_id: i64, backup_id: String },
+    /// `changes` summarises what changed since the game's previous backup
+    BackupCompleted { game_id: i64, backup_id: String, changes: String },
     BackupFailed { game_id: i64, error: String },
 }
 
@@ -45,6 +49,14 @@ pub struct AutoBackupConfig {
     pub enable_session_backups: bool,
     /// Whether to create session-end backups
     pub enable_final_backups: bool,
+    /// How often registry-based saves are polled for changes, in seconds
+    pub registry_poll_interval: u64,
+    /// File events within this many milliseconds count as one save (per-game overridable)
+    pub event_coalesce_window_ms: u64,
+    /// Refuse to back up a save path containing more files than this
+    pub max_backup_files: usize,
+    /// Refuse to back up a save path larger than this many bytes
+    pub max_backup_bytes: u64,
 }
 
 impl Default for AutoBackupConfig {
@@ -56,12 +68,16 @@ impl Default for AutoBackupConfig {
             enable_real_time_backup: true,
             enable_session_backups: true,
             enable_final_backups: true,
+            registry_poll_interval: 30,
+            event_coalesce_window_ms: 2000,
+            max_backup_files: 10_000,
+            max_backup_bytes: 5 * 1024 * 1024 * 1024, // 5 GB
         }
     }
 }
 
 /// Represents an active game session being monitored
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct GameSession {
     pub game_id: i64,
     pub session_id: String,
@@ -70,6 +86,10 @@ pub struct GameSession {
     pub last_backup_time: Option<chrono::DateTime<chrono::Utc>>,
     pub backup_count: usize,
     pub monitored_paths: Vec<String>,
+    /// Per-game override for real-time backups; None follows the global config
+    pub real_time_backup_override: Option<bool>,
+    /// Per-game real-time debounce in seconds; None follows `real_time_backup_delay`
+    pub backup_delay_override: Option<u64>,
 }
 
 impl GameSession {
@@ -82,20 +102,23 @@ impl GameSession {
             last_backup_time: None,
             backup_count: 0,
             monitored_paths: Vec::new(),
+            real_time_backup_override: None,
+            backup_delay_override: None,
         }
     }
 
     pub fn should_create_backup(&self, config: &AutoBackupConfig, backup_type: BackupType) -> bool {
         match backup_type {
             BackupType::RealTime => {
-                if !config.enable_real_time_backup {
+                if !self.real_time_backup_override.unwrap_or(config.enable_real_time_backup) {
                     return false;
                 }
 
                 // Check debouncing delay
                 if let Some(last_backup) = self.last_backup_time {
                     let seconds_since_last = (chrono::Utc::now() - last_backup).num_seconds();
-                    if seconds_since_last < config.real_time_backup_delay as i64 {
+                    let delay = self.backup_delay_override.unwrap_or(config.real_time_backup_delay);
+                    if seconds_since_last < delay as i64 {
                         return false;
                     }
                 }
@@ -107,6 +130,21 @@ impl GameSession {
         }
     }
 
+    /// Earliest time a save change leads to the next real-time backup: the last backup plus
+    /// the debounce delay, or the session start before any backup. `None` when real-time
+    /// backups are off for the game.
+    pub fn next_backup_time(&self, config: &AutoBackupConfig) -> Option<chrono::DateTime<chrono::Utc>> {
+        if !self.real_time_backup_override.unwrap_or(config.enable_real_time_backup) {
+            return None;
+        }
+
+        let delay = self.backup_delay_override.unwrap_or(config.real_time_backup_delay);
+        Some(match self.last_backup_time {
+            Some(last_backup) => last_backup + chrono::Duration::seconds(delay.min(u32::MAX as u64) as i64),
+            None => self.started_at,
+        })
+    }
+
     pub fn record_backup(&mut self) {
         self.last_backup_time = Some(chrono::Utc::now());
         self.backup_count += 1;
@@ -117,6 +155,36 @@ impl GameSession {
     }
 }
 
+/// A backup taken on request during a game session
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ForcedBackup {
+    pub game_id: i64,
+    pub backup_id: String,
+    pub session_id: String,
+    /// Backups taken in the session so far, this one included
+    pub session_backup_count: usize,
+    /// What changed since the game's previous backup
+    pub changes: String,
+}
+
+/// Outcome of one game's backup in a bulk backup; exactly one of `backup_id` and `error` is set
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct GameBackupOutcome {
+    pub game_id: i64,
+    pub success: bool,
+    pub backup_id: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Save paths watched for a game; `active` is false when no session is running and
+/// the paths are only what a new session would monitor
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct MonitoredPaths {
+    pub game_id: i64,
+    pub active: bool,
+    pub paths: Vec<String>,
+}
+
 /// Result type for backup operations
 pub type BackupResult<T> = Result<T, BackupError>;
 
@@ -162,3 +230,26 @@ impl From<BackupError> for String {
         error.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_backup_time_follows_debounce() {
+        let config = AutoBackupConfig::default();
+        let mut session = GameSession::new(1, 100);
+        assert_eq!(session.next_backup_time(&config), Some(session.started_at));
+
+        session.record_backup();
+        let last_backup = session.last_backup_time.unwrap();
+        let expected = last_backup + chrono::Duration::seconds(config.real_time_backup_delay as i64);
+        assert_eq!(session.next_backup_time(&config), Some(expected));
+
+        session.backup_delay_override = Some(5);
+        assert_eq!(session.next_backup_time(&config), Some(last_backup + chrono::Duration::seconds(5)));
+
+        session.real_time_backup_override = Some(false);
+        assert_eq!(session.next_backup_time(&config), None);
+    }
+}
@@ -1,20 +1,47 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
+use sha2::{Digest, Sha256};
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 use tokio::time::interval;
 use notify::RecommendedWatcher;
 use notify::RecursiveMode;
 use notify::Watcher;
 
 use crate::auto_backup::{BackupEvent, BackupType, BackupResult};
+use crate::database::DatabaseConnection;
+use crate::game_manager::scanner::PatternFilter;
+
+/// How file events for one game are turned into backup triggers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonitorTiming {
+    /// Minimum time between two triggers for the same game
+    pub debounce: Duration,
+    /// Events arriving within this window after the first one count as a single save
+    pub coalesce_window: Duration,
+}
+
+impl Default for MonitorTiming {
+    fn default() -> Self {
+        Self {
+            debounce: Duration::from_secs(10),
+            coalesce_window: Duration::from_millis(2000),
+        }
+    }
+}
 
 /// Handles real-time monitoring of save file directories
 pub struct SaveMonitor {
     monitors: Arc<RwLock<HashMap<String, MonitoredPath>>>,
     event_sender: tokio::sync::broadcast::Sender<BackupEvent>,
     debounced_events: Arc<RwLock<HashMap<String, tokio::time::Instant>>>,
+    registry_pollers: Arc<RwLock<HashMap<String, JoinHandle<()>>>>,
+    registry_poll_interval: Duration,
+    default_timing: MonitorTiming,
+    db_conn: Option<DatabaseConnection>,
 }
 
 impl SaveMonitor {
@@ -24,21 +51,96 @@ impl SaveMonitor {
             monitors: Arc::new(RwLock::new(HashMap::new())),
             event_sender: tx,
             debounced_events: Arc::new(RwLock::new(HashMap::new())),
+            registry_pollers: Arc::new(RwLock::new(HashMap::new())),
+            registry_poll_interval: Duration::from_secs(30),
+            default_timing: MonitorTiming::default(),
+            db_conn: None,
+        }
+    }
+
+    /// Set the debounce and coalesce window used for games without their own settings
+    pub fn with_default_timing(mut self, timing: MonitorTiming) -> Self {
+        self.default_timing = timing;
+        self
+    }
+
+    /// Read per-game timing overrides from `user_games`
+    pub fn with_database(mut self, db_conn: DatabaseConnection) -> Self {
+        self.db_conn = Some(db_conn);
+        self
+    }
+
+    /// Resolve a game's event timing, applying its per-game overrides to the defaults
+    pub async fn get_game_timing(&self, game_id: i64) -> MonitorTiming {
+        let Some(db_conn) = &self.db_conn else {
+            return self.default_timing;
+        };
+
+        let settings = {
+            let conn = db_conn.lock().await;
+            crate::game_manager::GameManager::get_monitor_settings(&conn, game_id)
+                .unwrap_or_else(|e| {
+                    eprintln!("Failed to read monitor settings for game {}: {}", game_id, e);
+                    Default::default()
+                })
+        };
+
+        MonitorTiming {
+            debounce: settings.debounce_seconds.map(Duration::from_secs).unwrap_or(self.default_timing.debounce),
+            coalesce_window: settings.coalesce_window_ms.map(Duration::from_millis).unwrap_or(self.default_timing.coalesce_window),
         }
     }
 
+    /// Exclude patterns from all of a game's save locations; matching paths never trigger backups
+    pub async fn get_game_exclude_filter(&self, game_id: i64) -> PatternFilter {
+        let Some(db_conn) = &self.db_conn else {
+            return PatternFilter::excluding(&[]);
+        };
+
+        let locations = {
+            let conn = db_conn.lock().await;
+            crate::game_manager::GameManager::get_save_locations(&conn, game_id)
+                .unwrap_or_else(|e| {
+                    eprintln!("Failed to read exclude patterns for game {}: {}", game_id, e);
+                    Vec::new()
+                })
+        };
+
+        let patterns: Vec<String> = locations.iter()
+            .flat_map(|location| crate::game_manager::utils::Utils::parse_patterns(&location.exclude_patterns))
+            .collect();
+        PatternFilter::excluding(&patterns)
+    }
+
+    /// Set how often registry-based saves are polled for changes
+    pub fn with_registry_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.registry_poll_interval = poll_interval;
+        self
+    }
+
     pub fn get_event_receiver(&self) -> tokio::sync::broadcast::Receiver<BackupEvent> {
         self.event_sender.subscribe()
     }
 
     /// Start monitoring a game session's save paths
     pub async fn start_monitoring_game(&self, game_id: i64, save_paths: Vec<String>) -> BackupResult<()> {
+        let timing = self.get_game_timing(game_id).await;
+        // One coalescer per game, so a save touching several directories is one trigger
+        let coalescer = TriggerCoalescer::new(game_id, timing, self.event_sender.clone());
+        let exclude_filter = Arc::new(self.get_game_exclude_filter(game_id).await);
+
         let mut monitors = self.monitors.write().await;
 
         for path_str in save_paths {
+            // Registry saves can't be watched with notify, so poll them instead
+            if is_registry_key(&path_str) {
+                self.start_polling_registry(game_id, path_str).await;
+                continue;
+            }
+
             let path = PathBuf::from(&path_str);
             if path.exists() && path.is_dir() {
-                self.start_monitoring_path(game_id, path, &mut monitors).await?;
+                self.start_monitoring_path(game_id, path, coalescer.clone(), exclude_filter.clone(), &mut monitors).await?;
             }
         }
 
@@ -61,13 +163,25 @@ impl SaveMonitor {
             }
         }
 
+        let mut pollers = self.registry_pollers.write().await;
+        pollers.retain(|key, handle| {
+            if key.starts_with(&format!("game_{}_", game_id)) {
+                handle.abort();
+                false
+            } else {
+                true
+            }
+        });
+
         Ok(())
     }
 
     /// Get currently monitored paths for a game
     pub async fn get_monitored_paths(&self, game_id: i64) -> Vec<String> {
         let monitors = self.monitors.read().await;
+        let pollers = self.registry_pollers.read().await;
         monitors.keys()
+            .chain(pollers.keys())
             .filter(|key| key.starts_with(&format!("game_{}_", game_id)))
             .map(|key| path_from_key(key))
             .collect()
@@ -94,27 +208,19 @@ impl SaveMonitor {
         &self,
         game_id: i64,
         path: PathBuf,
+        coalescer: TriggerCoalescer,
+        exclude_filter: Arc<PatternFilter>,
         monitors: &mut HashMap<String, MonitoredPath>,
     ) -> BackupResult<()> {
         let path_key = format!("game_{}_{}", game_id, path.display());
-        let game_id_clone = game_id;
         let event_sender = self.event_sender.clone();
+        let root = path.clone();
 
         // Create file watcher
-        let closure_event_sender = event_sender.clone();
         let mut watcher = RecommendedWatcher::new(
             move |result: Result<notify::Event, notify::Error>| {
                 match result {
-                    Ok(event) => {
-                        // Convert to our event system
-                        if should_handle_file_event(&event.kind) {
-                            let event = BackupEvent::BackupTriggered {
-                                game_id: game_id_clone,
-                                backup_type: BackupType::RealTime,
-                            };
-                            let _ = closure_event_sender.send(event);
-                        }
-                    }
+                    Ok(event) => handle_watch_event(&event, &root, &exclude_filter, &coalescer),
                     Err(e) => {
                         eprintln!("Watch error: {}", e);
                     }
@@ -144,6 +250,46 @@ impl SaveMonitor {
         Ok(())
     }
 
+    /// Periodically hash a registry key's exported data and trigger a backup when it changes
+    async fn start_polling_registry(&self, game_id: i64, registry_key: String) {
+        let poll_key = format!("game_{}_{}", game_id, registry_key);
+        let event_sender = self.event_sender.clone();
+        let poll_interval = self.registry_poll_interval;
+
+        let mut pollers = self.registry_pollers.write().await;
+        if pollers.contains_key(&poll_key) {
+            return;
+        }
+
+        let handle = tokio::spawn(async move {
+            let mut ticker = interval(poll_interval);
+            let mut last_hash: Option<Option<String>> = None;
+
+            loop {
+                ticker.tick().await;
+
+                let key = registry_key.clone();
+                let snapshot = tokio::task::spawn_blocking(move || read_registry_snapshot(&key))
+                    .await
+                    .ok()
+                    .flatten();
+                let hash = snapshot.map(|data| hex::encode(Sha256::digest(&data)));
+
+                if let Some(previous) = &last_hash {
+                    if *previous != hash {
+                        let _ = event_sender.send(BackupEvent::BackupTriggered {
+                            game_id,
+                            backup_type: BackupType::RealTime,
+                        });
+                    }
+                }
+                last_hash = Some(hash);
+            }
+        });
+
+        pollers.insert(poll_key, handle);
+    }
+
     /// Clean up old debounced events periodically
     pub async fn cleanup_old_events(&self) {
         let mut debounced = self.debounced_events.write().await;
@@ -178,6 +324,65 @@ impl SaveMonitorHandle {
     }
 }
 
+/// Turns bursts of file events for one game into a single debounced backup trigger.
+///
+/// The first event opens a coalesce window; the trigger fires when it closes (and
+/// not before `debounce` has passed since the previous trigger), so it captures the
+/// finished save rather than the first partial write.
+#[derive(Clone)]
+struct TriggerCoalescer {
+    game_id: i64,
+    timing: MonitorTiming,
+    pending: Arc<AtomicBool>,
+    last_triggered: Arc<std::sync::Mutex<Option<tokio::time::Instant>>>,
+    runtime: tokio::runtime::Handle,
+    event_sender: tokio::sync::broadcast::Sender<BackupEvent>,
+}
+
+impl TriggerCoalescer {
+    /// Must be created inside the Tokio runtime; `notify` may be called from any thread
+    fn new(game_id: i64, timing: MonitorTiming, event_sender: tokio::sync::broadcast::Sender<BackupEvent>) -> Self {
+        Self {
+            game_id,
+            timing,
+            pending: Arc::new(AtomicBool::new(false)),
+            last_triggered: Arc::new(std::sync::Mutex::new(None)),
+            runtime: tokio::runtime::Handle::current(),
+            event_sender,
+        }
+    }
+
+    fn notify(&self) {
+        // A trigger is already scheduled; this event is part of the same save
+        if self.pending.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let this = self.clone();
+        self.runtime.spawn(async move {
+            tokio::time::sleep(this.timing.coalesce_window).await;
+
+            let last = this.last_triggered.lock().ok().and_then(|l| *l);
+            if let Some(last) = last {
+                let wait = (last + this.timing.debounce).saturating_duration_since(tokio::time::Instant::now());
+                if !wait.is_zero() {
+                    tokio::time::sleep(wait).await;
+                }
+            }
+
+            if let Ok(mut last) = this.last_triggered.lock() {
+                *last = Some(tokio::time::Instant::now());
+            }
+            this.pending.store(false, Ordering::SeqCst);
+
+            let _ = this.event_sender.send(BackupEvent::BackupTriggered {
+                game_id: this.game_id,
+                backup_type: BackupType::RealTime,
+            });
+        });
+    }
+}
+
 /// Represents a monitored save directory
 #[derive(Debug)]
 struct MonitoredPath {
@@ -189,13 +394,67 @@ struct MonitoredPath {
 
 /// Extract path from monitor key
 fn path_from_key(key: &str) -> String {
-    if let Some(path_part) = key.split('_').nth(2) {
+    if let Some(path_part) = key.splitn(3, '_').nth(2) {
         path_part.to_string()
     } else {
         key.to_string()
     }
 }
 
+/// Whether a save path refers to a Windows registry key rather than a file
+fn is_registry_key(path: &str) -> bool {
+    let upper = path.to_uppercase();
+    ["HKEY_", "HKCU", "HKLM", "HKCR", "HKU"]
+        .iter()
+        .any(|prefix| upper.starts_with(prefix))
+}
+
+/// Export a registry key and its values as text so changes can be detected by hashing
+#[cfg(target_os = "windows")]
+fn read_registry_snapshot(key: &str) -> Option<Vec<u8>> {
+    let key = key.replace('/', "\\");
+    std::process::Command::new("reg")
+        .args(["query", &key, "/s"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| output.stdout)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn read_registry_snapshot(_key: &str) -> Option<Vec<u8>> {
+    None
+}
+
+/// Convert a watcher event into a backup trigger, coalescing bursts into one trigger
+fn handle_watch_event(event: &notify::Event, root: &Path, exclude_filter: &PatternFilter, coalescer: &TriggerCoalescer) {
+    if should_handle_file_event(&event.kind) && !is_excluded_event(event, root, exclude_filter) {
+        coalescer.notify();
+    }
+}
+
+/// An event is ignored when every path it touches lies in an excluded part of the save folder
+fn is_excluded_event(event: &notify::Event, root: &Path, exclude_filter: &PatternFilter) -> bool {
+    exclude_filter.has_exclusions()
+        && !event.paths.is_empty()
+        && event.paths.iter().all(|path| is_excluded_path(path, root, exclude_filter))
+}
+
+/// Match each component of `path` below `root`, and the relative path as a whole,
+/// so both `Mods` and `Mods/cache.dat` style patterns work
+fn is_excluded_path(path: &Path, root: &Path, exclude_filter: &PatternFilter) -> bool {
+    let Ok(relative) = path.strip_prefix(root) else {
+        return false;
+    };
+
+    let components: Vec<String> = relative.components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+
+    components.iter().any(|c| exclude_filter.is_excluded(c))
+        || exclude_filter.is_excluded(&components.join("/"))
+}
+
 /// Determine if a file event should trigger a backup
 fn should_handle_file_event(event_kind: &notify::EventKind) -> bool {
     match event_kind {
@@ -246,4 +505,58 @@ mod tests {
         let should3 = monitor.should_trigger_backup(123, "/test/path", 10).await;
         assert!(should3);
     }
+
+    #[tokio::test]
+    async fn test_coalescer_merges_event_bursts() {
+        let (tx, mut rx) = tokio::sync::broadcast::channel(10);
+        let timing = MonitorTiming {
+            debounce: Duration::from_millis(0),
+            coalesce_window: Duration::from_millis(50),
+        };
+        let coalescer = TriggerCoalescer::new(7, timing, tx);
+
+        for _ in 0..5 {
+            coalescer.notify();
+        }
+
+        let event = tokio::time::timeout(Duration::from_secs(1), rx.recv()).await
+            .expect("trigger should fire after the coalesce window")
+            .unwrap();
+        assert!(matches!(event, BackupEvent::BackupTriggered { game_id: 7, .. }));
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(rx.try_recv().is_err(), "burst should produce a single trigger");
+    }
+
+    #[tokio::test]
+    async fn test_excluded_subpath_events_are_dropped() {
+        let (tx, mut rx) = tokio::sync::broadcast::channel(10);
+        let timing = MonitorTiming {
+            debounce: Duration::from_millis(0),
+            coalesce_window: Duration::from_millis(20),
+        };
+        let coalescer = TriggerCoalescer::new(3, timing, tx);
+        let filter = PatternFilter::excluding(&["Mods".to_string(), "*.tmp".to_string()]);
+        let root = Path::new("/saves/game");
+        let modify = || notify::Event::new(notify::EventKind::Modify(notify::event::ModifyKind::Any));
+
+        handle_watch_event(&modify().add_path(root.join("Mods").join("cache.dat")), root, &filter, &coalescer);
+        handle_watch_event(&modify().add_path(root.join("slot1.tmp")), root, &filter, &coalescer);
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(rx.try_recv().is_err(), "excluded paths must not trigger a backup");
+
+        handle_watch_event(&modify().add_path(root.join("slot1.sav")), root, &filter, &coalescer);
+        let event = tokio::time::timeout(Duration::from_secs(1), rx.recv()).await
+            .expect("save file change should trigger a backup")
+            .unwrap();
+        assert!(matches!(event, BackupEvent::BackupTriggered { game_id: 3, .. }));
+    }
+
+    #[test]
+    fn test_registry_key_detection() {
+        assert!(is_registry_key("HKEY_CURRENT_USER/Software/Studio/Game"));
+        assert!(is_registry_key("HKCU\\Software\\Studio"));
+        assert!(!is_registry_key("/home/user/.local/share/game"));
+        assert!(!is_registry_key("C:\\Users\\Player\\Saved Games"));
+    }
 }
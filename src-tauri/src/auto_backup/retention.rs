@@ -1,11 +1,12 @@
 use std::collections::HashMap;
 use std::sync::Arc;
-use chrono::{Utc, DateTime};
+use chrono::{Duration, Utc, DateTime};
 use tokio::sync::RwLock;
 use rusqlite::params;
 
 use crate::database::{DatabaseConnection};
 use crate::auto_backup::{BackupResult, BackupType as GlobalBackupType};
+use crate::database::models::GameRetentionSettings;
 
 /// Manages retention policies for game backups
 #[derive(Clone)]
@@ -23,6 +24,8 @@ pub struct RetentionConfig {
     pub aggressive_compression: bool,
     /// Minimum age before a backup can be deleted (in hours)
     pub minimum_backup_age_hours: u64,
+    /// Total archive size a game's backups may use before the oldest are pruned
+    pub max_bytes_per_game: Option<u64>,
 }
 
 impl Default for RetentionConfig {
@@ -31,6 +34,19 @@ impl Default for RetentionConfig {
             max_backups_per_game: 3,
             aggressive_compression: true,
             minimum_backup_age_hours: 1, // 1 hour minimum
+            max_bytes_per_game: None,
+        }
+    }
+}
+
+impl RetentionConfig {
+    /// This config with a game's own limits taking precedence
+    pub fn with_overrides(&self, overrides: &GameRetentionSettings) -> Self {
+        Self {
+            max_backups_per_game: overrides.max_backups.unwrap_or(self.max_backups_per_game),
+            aggressive_compression: self.aggressive_compression,
+            minimum_backup_age_hours: overrides.min_age_hours.unwrap_or(self.minimum_backup_age_hours),
+            max_bytes_per_game: overrides.max_bytes.or(self.max_bytes_per_game),
         }
     }
 }
@@ -43,6 +59,8 @@ pub struct GameBackup {
     pub backup_type: BackupType,
     pub created_at: DateTime<Utc>,
     pub size_bytes: Option<u64>,
+    /// Total size of the archived files before compression
+    pub original_bytes: Option<u64>,
     pub compression_level: CompressionLevel,
     pub file_path: String,
 }
@@ -92,12 +110,32 @@ impl RetentionPolicy {
         self
     }
 
+    /// The retention config for one game: the global config with the game's overrides
+    pub async fn config_for(&self, game_id: i64) -> RetentionConfig {
+        let Some(conn) = &self.db_conn else {
+            return self.config.clone();
+        };
+
+        let overrides = {
+            let conn = conn.lock().await;
+            crate::game_manager::GameManager::get_retention_settings(&conn, game_id)
+        };
+        match overrides {
+            Ok(overrides) => self.config.with_overrides(&overrides),
+            Err(e) => {
+                crate::logger::warn("RETENTION", &format!("Failed to read retention settings for game {}, using the defaults", game_id), Some(&e));
+                self.config.clone()
+            }
+        }
+    }
+
     /// Check if a new backup should be created and handle retention
     pub async fn should_create_backup(&self, game_id: i64) -> BackupResult<(bool, Option<String>)> {
+        let config = self.config_for(game_id).await;
         let current_backups = self.get_game_backups(game_id).await?;
         let backup_count = current_backups.len();
 
-        if backup_count < self.config.max_backups_per_game {
+        if backup_count < config.max_backups_per_game {
             // Room for another backup
             Ok((true, None))
         } else {
@@ -105,7 +143,7 @@ impl RetentionPolicy {
             if let Some(oldest_backup) = current_backups.first() {
                 let age_hours = (Utc::now() - oldest_backup.created_at).num_hours() as u64;
 
-                if age_hours >= self.config.minimum_backup_age_hours {
+                if age_hours >= config.minimum_backup_age_hours {
                     // Old enough to delete
                     self.delete_backup(&oldest_backup.backup_id).await?;
                     self.clear_cache_for_game(game_id).await;
@@ -122,7 +160,7 @@ impl RetentionPolicy {
     }
 
     /// Record a new backup and apply retention policies
-    pub async fn record_backup(&self, game_id: i64, backup_id: String, backup_type: BackupType, file_path: &str, size_bytes: Option<u64>) -> BackupResult<()> {
+    pub async fn record_backup(&self, game_id: i64, backup_id: String, backup_type: BackupType, file_path: &str, size_bytes: Option<u64>, original_bytes: Option<u64>) -> BackupResult<()> {
         // Determine compression level based on backup type and age
         let compression_level = self.get_compression_level(backup_type, 0);
         let backup = GameBackup {
@@ -131,27 +169,40 @@ impl RetentionPolicy {
             backup_type,
             created_at: Utc::now(),
             size_bytes,
+            original_bytes,
             compression_level,
             file_path: file_path.to_string(),
         };
 
-        // Store in database if available
+        // Store in database if available; the cache is reloaded from there
         if let Some(conn) = &self.db_conn {
             self.insert_backup_record(conn, &backup).await?;
+            self.clear_cache_for_game(game_id).await;
+        } else {
+            self.update_cache(game_id, backup).await;
         }
 
-        // Apply retention cleanup
-        let deleted_backup = self.should_create_backup(game_id).await?;
-        if let (false, _) = deleted_backup {
-            // Retention prevented creation - should not happen here
+        // Enforce the limit right away, in case it was lowered since the last backup
+        let deleted = self.cleanup_old_backups(game_id).await?;
+        if !deleted.is_empty() {
+            crate::logger::info("RETENTION", &format!("Pruned {} backups for game {} after recording {}", deleted.len(), game_id, backup_id), None);
         }
 
-        // Update cache
-        self.update_cache(game_id, backup).await;
-
         Ok(())
     }
 
+    /// Archive path for a new backup below the configured backup root
+    pub async fn backup_path_for(&self, game_id: i64, backup_id: &str) -> std::path::PathBuf {
+        let game_dir = match &self.db_conn {
+            Some(conn) => {
+                let conn = conn.lock().await;
+                crate::backup::BackupLocation::game_directory(&conn, game_id)
+            }
+            None => crate::database::DatabasePaths::backup_directory().join(game_id.to_string()),
+        };
+        game_dir.join(format!("{}.tar.zst", backup_id))
+    }
+
     /// Get all backups for a game, sorted by creation time (oldest first)
     pub async fn get_game_backups(&self, game_id: i64) -> BackupResult<Vec<GameBackup>> {
         // Check cache first
@@ -198,26 +249,47 @@ impl RetentionPolicy {
 
     /// Clean up old backups beyond retention policy
     pub async fn cleanup_old_backups(&self, game_id: i64) -> BackupResult<Vec<String>> {
+        let config = self.config_for(game_id).await;
         let current_backups = self.get_game_backups(game_id).await?;
-        let mut deleted_backups = Vec::new();
-
-        if current_backups.len() > self.config.max_backups_per_game {
-            let to_delete = current_backups.len() - self.config.max_backups_per_game;
-
-            for backup in current_backups.iter().take(to_delete) {
-                self.delete_backup(&backup.backup_id).await?;
-                deleted_backups.push(backup.backup_id.clone());
-            }
+        let to_delete = Self::select_for_pruning(&current_backups, &config, Utc::now());
 
+        for backup_id in &to_delete {
+            self.delete_backup(backup_id).await?;
+        }
+        if !to_delete.is_empty() {
             // Clear cache to refresh
             self.clear_cache_for_game(game_id).await;
         }
 
-        Ok(deleted_backups)
+        Ok(to_delete)
+    }
+
+    /// Backups to delete, oldest first: everything beyond the count limit, then old enough
+    /// backups until the rest fit the size limit. The newest backup is always kept.
+    fn select_for_pruning(backups: &[GameBackup], config: &RetentionConfig, now: DateTime<Utc>) -> Vec<String> {
+        let over_count = backups.len().saturating_sub(config.max_backups_per_game);
+        let mut to_delete: Vec<String> = backups.iter().take(over_count).map(|b| b.backup_id.clone()).collect();
+
+        if let Some(max_bytes) = config.max_bytes_per_game {
+            let remaining = &backups[over_count..];
+            let mut total: u64 = remaining.iter().filter_map(|b| b.size_bytes).sum();
+            let min_age = Duration::hours(config.minimum_backup_age_hours as i64);
+
+            for backup in remaining.iter().take(remaining.len().saturating_sub(1)) {
+                if total <= max_bytes || now - backup.created_at < min_age {
+                    break;
+                }
+                total -= backup.size_bytes.unwrap_or(0);
+                to_delete.push(backup.backup_id.clone());
+            }
+        }
+
+        to_delete
     }
 
     /// Get backup statistics for a game
     pub async fn get_backup_stats(&self, game_id: i64) -> BackupResult<BackupStats> {
+        let config = self.config_for(game_id).await;
         let backups = self.get_game_backups(game_id).await?;
 
         let total_size = backups.iter()
@@ -233,7 +305,7 @@ impl RetentionPolicy {
             total_size_bytes: total_size,
             oldest_backup,
             newest_backup,
-            max_backups_allowed: self.config.max_backups_per_game,
+            max_backups_allowed: config.max_backups_per_game,
         })
     }
 
@@ -276,8 +348,8 @@ impl RetentionPolicy {
         let conn_guard = conn.lock().await;
         conn_guard.execute(
             r#"
-            INSERT INTO backups (backup_id, game_id, backup_type, created_at, file_path, compression_level)
-            VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO backups (backup_id, game_id, backup_type, created_at, file_path, compression_level, size_bytes, original_bytes)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
             "#,
             params![
                 &backup.backup_id,
@@ -285,7 +357,9 @@ impl RetentionPolicy {
                 serde_json::to_string(&backup.backup_type)?,
                 &backup.created_at.to_rfc3339(),
                 &backup.file_path,
-                serde_json::to_string(&backup.compression_level)?
+                serde_json::to_string(&backup.compression_level)?,
+                backup.size_bytes.map(|b| b as i64),
+                backup.original_bytes.map(|b| b as i64)
             ],
         )?;
 
@@ -295,7 +369,7 @@ impl RetentionPolicy {
     async fn load_game_backups_from_db(&self, conn: &DatabaseConnection, game_id: i64) -> BackupResult<Vec<GameBackup>> {
         let conn_guard = conn.lock().await;
         let mut stmt = conn_guard.prepare(
-            "SELECT backup_id, backup_type, created_at, file_path, compression_level FROM backups WHERE game_id = ? ORDER BY created_at ASC"
+            "SELECT backup_id, backup_type, created_at, file_path, compression_level, original_bytes FROM backups WHERE game_id = ? ORDER BY created_at ASC"
         )?;
 
         let mut backups = Vec::new();
@@ -306,11 +380,12 @@ impl RetentionPolicy {
                 row.get::<_, String>(2)?,  // created_at
                 row.get::<_, String>(3)?,  // file_path
                 row.get::<_, String>(4)?,  // compression_level
+                row.get::<_, Option<i64>>(5)?,  // original_bytes
             ))
         })?;
 
         for row in rows {
-            let (backup_id, type_str, created_at_str, file_path, level_str) = row?;
+            let (backup_id, type_str, created_at_str, file_path, level_str, original_bytes) = row?;
 
             let backup_type: BackupType = match serde_json::from_str(&type_str) {
                 Ok(bt) => bt,
@@ -328,6 +403,7 @@ impl RetentionPolicy {
                 backup_type,
                 created_at,
                 size_bytes: std::fs::metadata(&file_path).ok().map(|m| m.len()),
+                original_bytes: original_bytes.map(|b| b as u64),
                 compression_level,
                 file_path,
             };
@@ -341,7 +417,7 @@ impl RetentionPolicy {
     async fn get_backup_by_id(&self, conn: &DatabaseConnection, backup_id: &str) -> BackupResult<GameBackup> {
         let conn_guard = conn.lock().await;
         let mut stmt = conn_guard.prepare(
-            "SELECT game_id, backup_type, created_at, file_path FROM backups WHERE backup_id = ?"
+            "SELECT game_id, backup_type, created_at, file_path, original_bytes FROM backups WHERE backup_id = ?"
         )?;
 
         let backup = stmt.query_row(params![backup_id], |row| {
@@ -349,6 +425,7 @@ impl RetentionPolicy {
             let type_str: String = row.get(1)?;
             let created_at_str: String = row.get(2)?;
             let file_path: String = row.get(3)?;
+            let original_bytes: Option<i64> = row.get(4)?;
 
             let backup_type: BackupType = match serde_json::from_str(&type_str) {
                 Ok(bt) => bt,
@@ -365,6 +442,7 @@ impl RetentionPolicy {
                 backup_type,
                 created_at,
                 size_bytes: std::fs::metadata(&file_path).ok().map(|m| m.len()),
+                original_bytes: original_bytes.map(|b| b as u64),
                 compression_level: CompressionLevel::Balanced, // Default if not stored
                 file_path,
             })
@@ -416,6 +494,62 @@ mod tests {
         assert!(deleted.is_none());
     }
 
+    #[tokio::test]
+    async fn test_record_backup_prunes_after_limit_is_lowered() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE backups (backup_id TEXT NOT NULL UNIQUE, game_id INTEGER NOT NULL, backup_type TEXT NOT NULL,
+             created_at TEXT NOT NULL, file_path TEXT NOT NULL, compression_level TEXT NOT NULL,
+             size_bytes INTEGER, original_bytes INTEGER)",
+            [],
+        ).unwrap();
+        let db_conn: DatabaseConnection = Arc::new(tokio::sync::Mutex::new(conn));
+
+        let policy = RetentionPolicy::new(RetentionConfig { max_backups_per_game: 3, ..Default::default() })
+            .with_database(db_conn.clone());
+        for i in 0..3 {
+            policy.record_backup(1, format!("backup-{}", i), BackupType::Manual, "/nonexistent", None, None).await.unwrap();
+        }
+        assert_eq!(policy.get_game_backups(1).await.unwrap().len(), 3);
+
+        let lowered = RetentionPolicy::new(RetentionConfig { max_backups_per_game: 2, ..Default::default() })
+            .with_database(db_conn.clone());
+        lowered.record_backup(1, "backup-3".to_string(), BackupType::Manual, "/nonexistent", None, None).await.unwrap();
+
+        let remaining: Vec<String> = lowered.get_game_backups(1).await.unwrap()
+            .into_iter()
+            .map(|b| b.backup_id)
+            .collect();
+        assert_eq!(remaining, vec!["backup-2".to_string(), "backup-3".to_string()]);
+    }
+
+    #[test]
+    fn test_size_limit_prunes_oldest_old_enough_backups() {
+        let now = Utc::now();
+        let backup = |id: &str, hours_ago: i64| GameBackup {
+            backup_id: id.to_string(),
+            game_id: 1,
+            backup_type: BackupType::Manual,
+            created_at: now - Duration::hours(hours_ago),
+            size_bytes: Some(100),
+            original_bytes: None,
+            compression_level: CompressionLevel::Balanced,
+            file_path: "/nonexistent".to_string(),
+        };
+        let backups = vec![backup("a", 48), backup("b", 24), backup("c", 2), backup("d", 0)];
+
+        let config = RetentionConfig::default().with_overrides(&GameRetentionSettings {
+            max_backups: Some(10),
+            max_bytes: Some(150),
+            min_age_hours: Some(12),
+        });
+        // "c" is too young to go even though the game is still over its size limit
+        assert_eq!(RetentionPolicy::select_for_pruning(&backups, &config, now), vec!["a".to_string(), "b".to_string()]);
+
+        let config = RetentionConfig { max_backups_per_game: 3, ..RetentionConfig::default() };
+        assert_eq!(RetentionPolicy::select_for_pruning(&backups, &config, now), vec!["a".to_string()]);
+    }
+
     #[test]
     fn test_compression_levels() {
         let policy = RetentionPolicy::new(RetentionConfig {
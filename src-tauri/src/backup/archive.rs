@@ -0,0 +1,759 @@
+use crate::progress::ProgressReporter;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+/// Name of the manifest entry stored at the start of every archive
+pub const MANIFEST_ENTRY: &str = "manifest.json";
+/// Current manifest format version
+pub const MANIFEST_VERSION: u32 = 1;
+/// Default zstd level used for save archives
+pub const DEFAULT_COMPRESSION_LEVEL: i32 = 3;
+
+/// Container and compression of a save archive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ArchiveFormat {
+    /// tar compressed with zstd; also what `zstd` (the older name) refers to
+    #[default]
+    #[serde(rename = "tar.zst", alias = "zstd")]
+    TarZst,
+    #[serde(rename = "tar")]
+    Tar,
+    /// Deflate-compressed zip, for users opening backups with other tools
+    #[serde(rename = "zip")]
+    Zip,
+}
+
+impl ArchiveFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArchiveFormat::TarZst => "tar.zst",
+            ArchiveFormat::Tar => "tar",
+            ArchiveFormat::Zip => "zip",
+        }
+    }
+
+    /// File extension for archives of this format, without the leading dot
+    pub fn extension(&self) -> &'static str {
+        self.as_str()
+    }
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "zstd" | "tar.zst" | "tzst" => Ok(ArchiveFormat::TarZst),
+            "tar" => Ok(ArchiveFormat::Tar),
+            "zip" => Ok(ArchiveFormat::Zip),
+            other => Err(format!("Invalid archive format '{}' (expected zstd, tar, tar.zst or zip)", other)),
+        }
+    }
+
+    /// Detect the format from the archive's leading bytes, falling back to its extension
+    pub fn detect(path: &Path) -> Result<Self, String> {
+        // A tar header is 512 bytes, with its magic at offset 257
+        let mut header = Vec::with_capacity(512);
+        File::open(path)
+            .and_then(|file| file.take(512).read_to_end(&mut header))
+            .map_err(|e| format!("Failed to read archive '{}': {}", path.display(), e))?;
+
+        if header.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+            return Ok(ArchiveFormat::TarZst);
+        }
+        if header.starts_with(b"PK\x03\x04") || header.starts_with(b"PK\x05\x06") {
+            return Ok(ArchiveFormat::Zip);
+        }
+        if header.get(257..262) == Some(b"ustar".as_slice()) {
+            return Ok(ArchiveFormat::Tar);
+        }
+
+        let name = path.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+        [ArchiveFormat::TarZst, ArchiveFormat::Tar, ArchiveFormat::Zip]
+            .into_iter()
+            .find(|format| name.ends_with(&format!(".{}", format.extension())))
+            .ok_or_else(|| format!("Unrecognized archive format: {}", path.display()))
+    }
+}
+
+/// Outcome of writing an archive
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ArchiveWriteReport {
+    pub archive_size: u64,
+    /// Combined size of the files written to the archive, before compression
+    pub original_size: u64,
+    /// Files left out because they stayed locked or unreadable
+    pub skipped_files: Vec<SkippedFile>,
+    /// Archived files that were empty, which usually means the game was mid-save
+    pub zero_byte_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedFile {
+    pub path: String,
+    pub reason: String,
+}
+
+/// Describes the contents of a save archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveArchiveManifest {
+    pub format_version: u32,
+    pub game_name: String,
+    pub platform: String,
+    pub platform_app_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// When the newest archived file was last written, i.e. the in-game save time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub save_modified_at: Option<DateTime<Utc>>,
+    /// Save locations the files were collected from; empty in archives from older versions
+    #[serde(default)]
+    pub locations: Vec<ArchiveLocation>,
+    pub files: Vec<ArchiveEntry>,
+}
+
+/// A resolved save location the archived files were collected from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveLocation {
+    pub location_pattern: String,
+    /// Where the pattern resolved on the machine that created the archive
+    pub resolved_root: String,
+}
+
+/// A single file stored in an archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    /// Path of the entry inside the archive
+    pub archive_path: String,
+    /// Save location pattern the file was collected from
+    pub location_pattern: String,
+    /// Path relative to the resolved save location root
+    pub relative_path: String,
+    pub size: u64,
+    /// Index into the manifest's `locations`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_index: Option<usize>,
+    /// Exact relative path when it isn't valid UTF-8; `relative_path` then holds an
+    /// escaped form that is only used for display
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_relative_path: Option<RawPath>,
+}
+
+impl ArchiveEntry {
+    /// Where the file goes below its save location root, using the exact original
+    /// name when the archive was made on the same kind of system
+    pub fn relative_path_buf(&self) -> PathBuf {
+        self.raw_relative_path.as_ref()
+            .and_then(RawPath::to_path)
+            .unwrap_or_else(|| PathBuf::from(&self.relative_path))
+    }
+}
+
+/// A path that isn't valid UTF-8, kept in the native encoding of the system it came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RawPath {
+    /// Raw bytes of a Unix path
+    Unix(Vec<u8>),
+    /// UTF-16 code units of a Windows path, possibly with unpaired surrogates
+    Windows(Vec<u16>),
+}
+
+impl RawPath {
+    /// The native form of `path`, or `None` when it is valid UTF-8 and needs no help
+    pub fn from_path(path: &Path) -> Option<Self> {
+        if path.to_str().is_some() {
+            return None;
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            Some(RawPath::Unix(path.as_os_str().as_bytes().to_vec()))
+        }
+        #[cfg(windows)]
+        {
+            use std::os::windows::ffi::OsStrExt;
+            Some(RawPath::Windows(path.as_os_str().encode_wide().collect()))
+        }
+        #[cfg(not(any(unix, windows)))]
+        {
+            None
+        }
+    }
+
+    /// Rebuild the path, if it was recorded on the same kind of system
+    pub fn to_path(&self) -> Option<PathBuf> {
+        match self {
+            #[cfg(unix)]
+            RawPath::Unix(bytes) => {
+                use std::os::unix::ffi::OsStrExt;
+                Some(PathBuf::from(std::ffi::OsStr::from_bytes(bytes)))
+            }
+            #[cfg(windows)]
+            RawPath::Windows(units) => {
+                use std::os::windows::ffi::OsStringExt;
+                Some(PathBuf::from(std::ffi::OsString::from_wide(units)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Readable form with each invalid byte written as `%XX` (or unpaired surrogate as
+    /// `%uXXXX`), so distinct names stay distinct
+    pub fn escaped(&self) -> String {
+        let mut escaped = String::new();
+        match self {
+            RawPath::Unix(bytes) => {
+                for chunk in bytes.utf8_chunks() {
+                    escaped.push_str(chunk.valid());
+                    for byte in chunk.invalid() {
+                        escaped.push_str(&format!("%{:02X}", byte));
+                    }
+                }
+            }
+            RawPath::Windows(units) => {
+                for unit in char::decode_utf16(units.iter().copied()) {
+                    match unit {
+                        Ok(c) => escaped.push(c),
+                        Err(e) => escaped.push_str(&format!("%u{:04X}", e.unpaired_surrogate())),
+                    }
+                }
+            }
+        }
+        escaped
+    }
+}
+
+/// Relative path of a file as stored in a manifest: a `/`-separated string plus the raw
+/// path when the string alone can't reproduce the name. Logs when that happens, since
+/// older versions of the app would restore such files under a mangled name.
+pub fn manifest_relative_path(path: &Path) -> (String, Option<RawPath>) {
+    match RawPath::from_path(path) {
+        Some(raw) => {
+            let escaped = raw.escaped();
+            crate::logger::warn("BACKUP", "Save file name is not valid UTF-8, keeping its raw bytes for restore", Some(&escaped));
+            (escaped.replace('\\', "/"), Some(raw))
+        }
+        None => (path.to_string_lossy().replace('\\', "/"), None),
+    }
+}
+
+/// A file on disk queued for inclusion in an archive
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub absolute_path: PathBuf,
+    pub entry: ArchiveEntry,
+}
+
+impl SaveArchiveManifest {
+    pub fn new(game_name: &str, platform: &str, platform_app_id: Option<String>) -> Self {
+        Self {
+            format_version: MANIFEST_VERSION,
+            game_name: game_name.to_string(),
+            platform: platform.to_string(),
+            platform_app_id,
+            created_at: Utc::now(),
+            save_modified_at: None,
+            locations: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+
+    /// Check the manifest is one we understand and that no entry escapes its save root
+    pub fn validate(&self) -> Result<(), String> {
+        if self.format_version == 0 || self.format_version > MANIFEST_VERSION {
+            return Err(format!("Unsupported archive format version: {}", self.format_version));
+        }
+
+        for entry in &self.files {
+            if entry.location_index.is_some_and(|i| i >= self.locations.len()) {
+                return Err(format!("Archive entry {} refers to an unknown save location", entry.archive_path));
+            }
+            let raw_is_safe = entry.raw_relative_path.as_ref()
+                .and_then(RawPath::to_path)
+                .is_none_or(|path| is_safe_relative(&path));
+            if !is_safe_relative_path(&entry.relative_path) || !is_safe_relative_path(&entry.archive_path) || !raw_is_safe {
+                return Err(format!("Archive entry has an unsafe path: {}", entry.relative_path));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Modification time of the most recently written source file
+pub fn newest_modified_at(sources: &[SourceFile]) -> Option<DateTime<Utc>> {
+    sources.iter()
+        .filter_map(|s| std::fs::metadata(&s.absolute_path).and_then(|m| m.modified()).ok())
+        .max()
+        .map(DateTime::<Utc>::from)
+}
+
+/// Reject absolute paths and parent-directory components
+pub fn is_safe_relative_path(path: &str) -> bool {
+    is_safe_relative(Path::new(path))
+}
+
+fn is_safe_relative(path: &Path) -> bool {
+    !path.as_os_str().is_empty()
+        && path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// Write the manifest and source files into an archive of the given format.
+///
+/// Files that stay locked or unreadable after a few retries are left out of the archive
+/// and its manifest instead of failing the whole backup; empty files are archived but
+/// reported, since a game mid-save often leaves one behind.
+pub fn write_archive(
+    dest: &Path,
+    manifest: &SaveArchiveManifest,
+    sources: &[SourceFile],
+    format: ArchiveFormat,
+    compression_level: i32,
+    progress: Option<&ProgressReporter>,
+) -> Result<ArchiveWriteReport, String> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create archive directory: {}", e))?;
+    }
+
+    let mut report = ArchiveWriteReport::default();
+    let readable = read_sources(sources, &mut report);
+    report.original_size = readable.iter().map(|(_, data)| data.len() as u64).sum();
+    let mut manifest = manifest.clone();
+    manifest.files.retain(|entry| !report.skipped_files.iter().any(|s| s.path == entry.archive_path));
+
+    let file = File::create(dest)
+        .map_err(|e| format!("Failed to create archive '{}': {}", dest.display(), e))?;
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize archive manifest: {}", e))?;
+
+    match format {
+        ArchiveFormat::TarZst => {
+            let encoder = zstd::stream::write::Encoder::new(file, compression_level)
+                .map_err(|e| format!("Failed to start compression: {}", e))?;
+            let encoder = write_tar(encoder, &manifest, &manifest_json, &readable, progress)?;
+            encoder.finish()
+                .map_err(|e| format!("Failed to finish compression: {}", e))?;
+        }
+        ArchiveFormat::Tar => {
+            let file = write_tar(std::io::BufWriter::new(file), &manifest, &manifest_json, &readable, progress)?;
+            file.into_inner()
+                .map_err(|e| format!("Failed to finish archive: {}", e.error()))?;
+        }
+        ArchiveFormat::Zip => write_zip(file, &manifest_json, &readable, compression_level, progress)?,
+    }
+
+    if let Some(progress) = progress {
+        progress.finish();
+    }
+
+    report.archive_size = std::fs::metadata(dest)
+        .map(|m| m.len())
+        .map_err(|e| format!("Failed to read archive size: {}", e))?;
+    Ok(report)
+}
+
+/// Attempts at reading a save file the game may be holding a lock on
+const READ_ATTEMPTS: u32 = 4;
+const READ_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Whether an error means another process holds the file open exclusively or has locked
+/// part of it, which usually clears once the game finishes writing
+fn is_lock_error(e: &std::io::Error) -> bool {
+    // ERROR_SHARING_VIOLATION and ERROR_LOCK_VIOLATION; other systems only lock advisorily
+    cfg!(windows) && matches!(e.raw_os_error(), Some(32) | Some(33))
+}
+
+/// Open a file once and read it whole, retrying for a short while if it is locked
+fn read_with_retry(path: &Path) -> std::io::Result<Vec<u8>> {
+    let mut attempt = 1;
+    loop {
+        let result = File::open(path).and_then(|mut file| {
+            let mut data = Vec::new();
+            file.read_to_end(&mut data).map(|_| data)
+        });
+        match result {
+            Err(e) if is_lock_error(&e) && attempt < READ_ATTEMPTS => {
+                std::thread::sleep(READ_RETRY_DELAY);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Read the sources that can be read, recording skipped and empty files in `report`.
+///
+/// Each file is read in full before anything is written, so one that fails partway is
+/// left out of the manifest too rather than leaving a truncated entry in the archive.
+fn read_sources<'a>(sources: &'a [SourceFile], report: &mut ArchiveWriteReport) -> Vec<(&'a SourceFile, Vec<u8>)> {
+    let mut readable = Vec::with_capacity(sources.len());
+    for source in sources {
+        match read_with_retry(&source.absolute_path) {
+            Ok(data) => {
+                if data.is_empty() {
+                    crate::logger::warn("BACKUP", "Archiving an empty save file, the game may be mid-save", Some(&source.absolute_path.display().to_string()));
+                    report.zero_byte_files.push(source.entry.archive_path.clone());
+                }
+                readable.push((source, data));
+            }
+            Err(e) => {
+                crate::logger::warn("BACKUP", &format!("Skipping unreadable save file {}", source.absolute_path.display()), Some(&e.to_string()));
+                report.skipped_files.push(SkippedFile {
+                    path: source.entry.archive_path.clone(),
+                    reason: e.to_string(),
+                });
+            }
+        }
+    }
+    readable
+}
+
+/// Modification time of a source file for its tar header
+fn source_mtime(source: &SourceFile) -> u64 {
+    std::fs::metadata(&source.absolute_path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_secs())
+}
+
+fn write_tar<W: Write>(
+    writer: W,
+    manifest: &SaveArchiveManifest,
+    manifest_json: &[u8],
+    sources: &[(&SourceFile, Vec<u8>)],
+    progress: Option<&ProgressReporter>,
+) -> Result<W, String> {
+    let mut builder = tar::Builder::new(writer);
+
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest_json.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(manifest.created_at.timestamp().max(0) as u64);
+    header.set_cksum();
+    builder.append_data(&mut header, MANIFEST_ENTRY, manifest_json)
+        .map_err(|e| format!("Failed to write archive manifest: {}", e))?;
+
+    for (source, data) in sources {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(source_mtime(source));
+        header.set_cksum();
+        builder.append_data(&mut header, &source.entry.archive_path, data.as_slice())
+            .map_err(|e| format!("Failed to add '{}' to archive: {}", source.absolute_path.display(), e))?;
+        if let Some(progress) = progress {
+            progress.add_bytes(source.entry.size);
+            progress.file_done();
+        }
+    }
+
+    builder.into_inner()
+        .map_err(|e| format!("Failed to finish archive: {}", e))
+}
+
+fn write_zip(
+    file: File,
+    manifest_json: &[u8],
+    sources: &[(&SourceFile, Vec<u8>)],
+    compression_level: i32,
+    progress: Option<&ProgressReporter>,
+) -> Result<(), String> {
+    let mut zip = zip::ZipWriter::new(file);
+    // zstd levels run to 22, deflate only to 9
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .compression_level(Some(compression_level.clamp(0, 9) as i64));
+
+    zip.start_file(MANIFEST_ENTRY, options)
+        .map_err(|e| format!("Failed to write archive manifest: {}", e))?;
+    zip.write_all(manifest_json)
+        .map_err(|e| format!("Failed to write archive manifest: {}", e))?;
+
+    for (source, data) in sources {
+        zip.start_file(source.entry.archive_path.as_str(), options)
+            .map_err(|e| format!("Failed to add '{}' to archive: {}", source.absolute_path.display(), e))?;
+        zip.write_all(data)
+            .map_err(|e| format!("Failed to add '{}' to archive: {}", source.absolute_path.display(), e))?;
+        if let Some(progress) = progress {
+            progress.add_bytes(source.entry.size);
+            progress.file_done();
+        }
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finish archive: {}", e))?;
+    Ok(())
+}
+
+/// Visit the entries of an archive of any supported format, in order, until `visit`
+/// returns `false`
+fn visit_entries<F>(path: &Path, mut visit: F) -> Result<(), String>
+where
+    F: FnMut(&str, &mut dyn Read) -> Result<bool, String>,
+{
+    let format = ArchiveFormat::detect(path)?;
+    let file = File::open(path)
+        .map_err(|e| format!("Failed to open archive '{}': {}", path.display(), e))?;
+
+    if format == ArchiveFormat::Zip {
+        let mut zip = zip::ZipArchive::new(std::io::BufReader::new(file))
+            .map_err(|e| format!("Failed to read zip archive: {}", e))?;
+        for index in 0..zip.len() {
+            let mut entry = zip.by_index(index)
+                .map_err(|e| format!("Corrupt archive entry: {}", e))?;
+            if entry.is_dir() {
+                continue;
+            }
+            let entry_path = entry.name().to_string();
+            if !visit(&entry_path, &mut entry)? {
+                break;
+            }
+        }
+        return Ok(());
+    }
+
+    let reader: Box<dyn Read> = match format {
+        ArchiveFormat::TarZst => Box::new(zstd::stream::read::Decoder::new(file)
+            .map_err(|e| format!("Failed to read compressed archive: {}", e))?),
+        _ => Box::new(std::io::BufReader::new(file)),
+    };
+    let mut archive = tar::Archive::new(reader);
+    let entries = archive.entries()
+        .map_err(|e| format!("Failed to read archive entries: {}", e))?;
+
+    for entry in entries {
+        let mut entry = entry.map_err(|e| format!("Corrupt archive entry: {}", e))?;
+        let entry_path = entry.path()
+            .map_err(|e| format!("Invalid archive entry path: {}", e))?
+            .to_string_lossy()
+            .to_string();
+        if !visit(&entry_path, &mut entry)? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Read only the manifest from an archive
+pub fn read_manifest(path: &Path) -> Result<SaveArchiveManifest, String> {
+    let mut manifest = None;
+    visit_entries(path, |entry_path, entry| {
+        if entry_path != MANIFEST_ENTRY {
+            return Ok(true);
+        }
+        let mut content = String::new();
+        entry.read_to_string(&mut content)
+            .map_err(|e| format!("Failed to read archive manifest: {}", e))?;
+        let parsed: SaveArchiveManifest = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid archive manifest: {}", e))?;
+        parsed.validate()?;
+        manifest = Some(parsed);
+        Ok(false)
+    })?;
+
+    manifest.ok_or_else(|| format!("Archive '{}' has no manifest", path.display()))
+}
+
+/// Path and size of every file in an archive, manifest included, taken from the zip
+/// central directory or the tar headers without extracting any file data
+pub fn list_entries(path: &Path) -> Result<Vec<(String, u64)>, String> {
+    let format = ArchiveFormat::detect(path)?;
+    let file = File::open(path)
+        .map_err(|e| format!("Failed to open archive '{}': {}", path.display(), e))?;
+    let mut listed = Vec::new();
+
+    if format == ArchiveFormat::Zip {
+        let mut zip = zip::ZipArchive::new(std::io::BufReader::new(file))
+            .map_err(|e| format!("Failed to read zip archive: {}", e))?;
+        for index in 0..zip.len() {
+            let entry = zip.by_index_raw(index)
+                .map_err(|e| format!("Corrupt archive entry: {}", e))?;
+            if !entry.is_dir() {
+                listed.push((entry.name().to_string(), entry.size()));
+            }
+        }
+        return Ok(listed);
+    }
+
+    let reader: Box<dyn Read> = match format {
+        ArchiveFormat::TarZst => Box::new(zstd::stream::read::Decoder::new(file)
+            .map_err(|e| format!("Failed to read compressed archive: {}", e))?),
+        _ => Box::new(std::io::BufReader::new(file)),
+    };
+    let mut archive = tar::Archive::new(reader);
+    let entries = archive.entries()
+        .map_err(|e| format!("Failed to read archive entries: {}", e))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Corrupt archive entry: {}", e))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let entry_path = entry.path()
+            .map_err(|e| format!("Invalid archive entry path: {}", e))?
+            .to_string_lossy()
+            .to_string();
+        listed.push((entry_path, entry.size()));
+    }
+    Ok(listed)
+}
+
+/// Extract archive entries, letting `target_for` decide where each one goes.
+/// Entries for which it returns `None` are skipped. Returns the number of files written.
+pub fn extract_archive<F>(path: &Path, mut target_for: F) -> Result<usize, String>
+where
+    F: FnMut(&str) -> Option<PathBuf>,
+{
+    let mut written = 0;
+    visit_entries(path, |entry_path, entry| {
+        if entry_path == MANIFEST_ENTRY || !is_safe_relative_path(entry_path) {
+            return Ok(true);
+        }
+
+        if let Some(target) = target_for(entry_path) {
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create directory '{}': {}", parent.display(), e))?;
+            }
+            let mut out = File::create(&target)
+                .map_err(|e| format!("Failed to create '{}': {}", target.display(), e))?;
+            std::io::copy(entry, &mut out)
+                .map_err(|e| format!("Failed to extract '{}': {}", entry_path, e))?;
+            written += 1;
+        }
+        Ok(true)
+    })?;
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rejects_unsafe_paths() {
+        assert!(is_safe_relative_path("slot1/save.dat"));
+        assert!(!is_safe_relative_path("../outside.dat"));
+        assert!(!is_safe_relative_path("/etc/passwd"));
+        assert!(!is_safe_relative_path(""));
+    }
+
+    #[test]
+    fn test_archive_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let save_file = temp_dir.path().join("slot1.sav");
+        std::fs::write(&save_file, b"save data").unwrap();
+
+        let entry = ArchiveEntry {
+            archive_path: "files/0/slot1.sav".to_string(),
+            location_pattern: "/saves".to_string(),
+            relative_path: "slot1.sav".to_string(),
+            size: 9,
+            location_index: None,
+            raw_relative_path: None,
+        };
+        let mut manifest = SaveArchiveManifest::new("Test Game", "steam", None);
+        manifest.files.push(entry.clone());
+
+        let saved_at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        std::fs::File::options().write(true).open(&save_file).unwrap()
+            .set_modified(saved_at.into()).unwrap();
+
+        let archive_path = temp_dir.path().join("export.tar.zst");
+        let sources = vec![SourceFile { absolute_path: save_file, entry }];
+        manifest.save_modified_at = newest_modified_at(&sources);
+        write_archive(&archive_path, &manifest, &sources, ArchiveFormat::TarZst, DEFAULT_COMPRESSION_LEVEL, None).unwrap();
+
+        let read_back = read_manifest(&archive_path).unwrap();
+        assert_eq!(read_back.game_name, "Test Game");
+        assert_eq!(read_back.files.len(), 1);
+        assert_eq!(read_back.save_modified_at, Some(saved_at));
+
+        let out_dir = temp_dir.path().join("restored");
+        let written = extract_archive(&archive_path, |p| Some(out_dir.join(p))).unwrap();
+        assert_eq!(written, 1);
+        assert_eq!(std::fs::read(out_dir.join("files/0/slot1.sav")).unwrap(), b"save data");
+    }
+
+    #[test]
+    fn test_every_format_round_trips_and_is_detected() {
+        let temp_dir = TempDir::new().unwrap();
+        let save_file = temp_dir.path().join("slot1.sav");
+        std::fs::write(&save_file, b"save data").unwrap();
+
+        let entry = ArchiveEntry {
+            archive_path: "files/0/slot1.sav".to_string(),
+            location_pattern: "/saves".to_string(),
+            relative_path: "slot1.sav".to_string(),
+            size: 9,
+            location_index: None,
+            raw_relative_path: None,
+        };
+        let mut manifest = SaveArchiveManifest::new("Test Game", "steam", None);
+        manifest.files.push(entry.clone());
+        let sources = vec![SourceFile { absolute_path: save_file, entry }];
+
+        for format in [ArchiveFormat::TarZst, ArchiveFormat::Tar, ArchiveFormat::Zip] {
+            // No extension, so detection has to go by the header
+            let archive_path = temp_dir.path().join(format!("export-{:?}", format));
+            write_archive(&archive_path, &manifest, &sources, format, DEFAULT_COMPRESSION_LEVEL, None).unwrap();
+            assert_eq!(ArchiveFormat::detect(&archive_path).unwrap(), format);
+            assert_eq!(read_manifest(&archive_path).unwrap().files.len(), 1);
+            let listed = list_entries(&archive_path).unwrap();
+            assert!(listed.contains(&("files/0/slot1.sav".to_string(), 9)));
+            assert!(listed.iter().any(|(path, _)| path == MANIFEST_ENTRY));
+
+            let out_dir = temp_dir.path().join(format!("restored-{:?}", format));
+            assert_eq!(extract_archive(&archive_path, |p| Some(out_dir.join(p))).unwrap(), 1);
+            assert_eq!(std::fs::read(out_dir.join("files/0/slot1.sav")).unwrap(), b"save data");
+        }
+
+        assert_eq!(ArchiveFormat::parse("zstd").unwrap(), ArchiveFormat::TarZst);
+        assert!(ArchiveFormat::parse("rar").is_err());
+    }
+
+    #[test]
+    fn test_unreadable_files_are_skipped_and_empty_files_flagged() {
+        let temp_dir = TempDir::new().unwrap();
+        let empty = temp_dir.path().join("empty.sav");
+        std::fs::write(&empty, b"").unwrap();
+
+        let source = |name: &str, absolute_path: PathBuf| SourceFile {
+            absolute_path,
+            entry: ArchiveEntry {
+                archive_path: format!("files/0/{}", name),
+                location_pattern: "/saves".to_string(),
+                relative_path: name.to_string(),
+                size: 0,
+                location_index: None,
+                raw_relative_path: None,
+            },
+        };
+        // A directory opens fine on Unix but fails once read, like a file that errors mid-copy
+        let unreadable = temp_dir.path().join("unreadable.sav");
+        std::fs::create_dir(&unreadable).unwrap();
+        let sources = vec![
+            source("empty.sav", empty),
+            source("gone.sav", temp_dir.path().join("gone.sav")),
+            source("unreadable.sav", unreadable),
+        ];
+        let mut manifest = SaveArchiveManifest::new("Test Game", "steam", None);
+        manifest.files = sources.iter().map(|s| s.entry.clone()).collect();
+
+        let archive_path = temp_dir.path().join("export.tar.zst");
+        let report = write_archive(&archive_path, &manifest, &sources, ArchiveFormat::TarZst, DEFAULT_COMPRESSION_LEVEL, None).unwrap();
+        assert_eq!(report.zero_byte_files, vec!["files/0/empty.sav".to_string()]);
+        assert_eq!(report.original_size, 0);
+        let skipped: Vec<&str> = report.skipped_files.iter().map(|s| s.path.as_str()).collect();
+        assert_eq!(skipped, vec!["files/0/gone.sav", "files/0/unreadable.sav"]);
+
+        let read_back = read_manifest(&archive_path).unwrap();
+        assert_eq!(read_back.files.len(), 1);
+        assert_eq!(read_back.files[0].relative_path, "empty.sav");
+    }
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::database::connection::Database;
+use crate::game_manager::scanner::configured_follow_symlinks;
+use crate::game_manager::GameManager;
+use super::portable::PortableSaves;
+
+/// zstd levels compared by the benchmark: fast, balanced and maximum
+pub const BENCHMARK_LEVELS: [i32; 3] = [3, 9, 19];
+
+/// Upper bound on the save data read for a benchmark
+const MAX_SAMPLE_BYTES: u64 = 32 * 1024 * 1024;
+
+/// A level is only recommended if it compresses within this factor of the fastest level's time
+const MAX_SLOWDOWN: f64 = 4.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompressionSample {
+    pub level: i32,
+    pub compressed_bytes: u64,
+    /// Original size divided by compressed size
+    pub ratio: f64,
+    pub duration_ms: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompressionBenchmark {
+    pub game_id: i64,
+    pub sample_files: usize,
+    pub sample_bytes: u64,
+    pub results: Vec<CompressionSample>,
+    pub recommended_level: i32,
+    /// Whether `recommended_level` was written to the game's compression setting
+    pub applied: bool,
+}
+
+/// Compress a sample of the game's saves at each benchmark level and recommend one.
+/// With `apply` the recommendation is stored in `user_games.compression_level`.
+pub async fn benchmark_compression(
+    db: &Arc<tokio::sync::Mutex<Database>>,
+    game_id: i64,
+    apply: bool,
+) -> Result<CompressionBenchmark, String> {
+    let (locations, follow_symlinks) = {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+        GameManager::get_game_by_id(&conn, game_id)?;
+        (GameManager::get_save_locations(&conn, game_id)?, configured_follow_symlinks(&conn))
+    };
+
+    let (sample_files, sample_bytes, results) = tokio::task::spawn_blocking(move || {
+        let sources = PortableSaves::collect_save_files(&locations, follow_symlinks);
+        let (sample_files, sample) = read_sample(sources.iter().map(|s| s.absolute_path.as_path()))?;
+        if sample.is_empty() {
+            return Err("No save data found to benchmark".to_string());
+        }
+
+        let results = BENCHMARK_LEVELS.iter()
+            .map(|&level| compress_sample(&sample, level))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((sample_files, sample.len() as u64, results))
+    })
+    .await
+    .map_err(|e| format!("Compression benchmark task failed: {}", e))??;
+
+    let recommended_level = recommend_level(&results);
+
+    if apply {
+        GameManager::set_compression_level(db, game_id, recommended_level).await?;
+        crate::logger::info("BACKUP", &format!("Compression level for game {} set to {}", game_id, recommended_level), None);
+    }
+
+    Ok(CompressionBenchmark {
+        game_id,
+        sample_files,
+        sample_bytes,
+        results,
+        recommended_level,
+        applied: apply,
+    })
+}
+
+/// Concatenate files until the sample limit; the last file may be truncated
+fn read_sample<'a>(paths: impl Iterator<Item = &'a std::path::Path>) -> Result<(usize, Vec<u8>), String> {
+    let mut sample = Vec::new();
+    let mut files = 0;
+
+    for path in paths {
+        let remaining = MAX_SAMPLE_BYTES - sample.len() as u64;
+        if remaining == 0 {
+            break;
+        }
+
+        let file = match std::fs::File::open(path) {
+            Ok(f) => f,
+            Err(e) => {
+                crate::logger::warn("BACKUP", &format!("Skipping {} in compression benchmark", path.display()), Some(&e.to_string()));
+                continue;
+            }
+        };
+        file.take(remaining).read_to_end(&mut sample)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        files += 1;
+    }
+
+    Ok((files, sample))
+}
+
+fn compress_sample(sample: &[u8], level: i32) -> Result<CompressionSample, String> {
+    let started = Instant::now();
+    let compressed = zstd::bulk::compress(sample, level)
+        .map_err(|e| format!("Compression at level {} failed: {}", level, e))?;
+    let duration = started.elapsed();
+
+    Ok(CompressionSample {
+        level,
+        compressed_bytes: compressed.len() as u64,
+        ratio: sample.len() as f64 / compressed.len().max(1) as f64,
+        duration_ms: duration.as_secs_f64() * 1000.0,
+    })
+}
+
+/// Smallest output among the levels that stay within `MAX_SLOWDOWN` of the fastest one;
+/// ties go to the lower level
+fn recommend_level(results: &[CompressionSample]) -> i32 {
+    let fastest = results.iter()
+        .map(|r| r.duration_ms)
+        .fold(f64::INFINITY, f64::min);
+
+    results.iter()
+        .filter(|r| r.duration_ms <= fastest.max(1.0) * MAX_SLOWDOWN)
+        .min_by(|a, b| a.compressed_bytes.cmp(&b.compressed_bytes).then(a.level.cmp(&b.level)))
+        .map(|r| r.level)
+        .unwrap_or(super::archive::DEFAULT_COMPRESSION_LEVEL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(level: i32, compressed_bytes: u64, duration_ms: f64) -> CompressionSample {
+        CompressionSample { level, compressed_bytes, ratio: 1000.0 / compressed_bytes as f64, duration_ms }
+    }
+
+    #[test]
+    fn test_recommend_level_skips_slow_levels() {
+        let results = vec![sample(3, 400, 10.0), sample(9, 350, 30.0), sample(19, 300, 200.0)];
+        assert_eq!(recommend_level(&results), 9);
+
+        // No gain from higher levels keeps the fast one
+        let flat = vec![sample(3, 400, 10.0), sample(9, 400, 12.0), sample(19, 400, 15.0)];
+        assert_eq!(recommend_level(&flat), 3);
+    }
+
+    #[test]
+    fn test_compress_sample_reports_ratio() {
+        let data = vec![b'a'; 64 * 1024];
+        let result = compress_sample(&data, 3).unwrap();
+        assert!(result.ratio > 10.0);
+        assert_eq!(result.level, 3);
+    }
+}
@@ -0,0 +1,307 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::backup::location::BackupLocation;
+use crate::database::connection::Database;
+use crate::game_manager::GameManager;
+use crate::git_manager::GitSaveManager;
+
+/// Sub-folder of a game's cold storage folder holding its backup archives
+const BACKUPS_DIR: &str = "backups";
+/// Bare repository holding a game's save branches while in cold storage
+const BRANCHES_REPO: &str = "branches.git";
+
+/// (original, copy) pairs produced while moving archives
+type CopiedFiles = Vec<(PathBuf, PathBuf)>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColdStorageResult {
+    pub game_id: i64,
+    /// The game's folder inside the cold storage directory
+    pub storage_path: String,
+    pub files_moved: usize,
+    pub bytes_moved: u64,
+    pub records_updated: usize,
+    pub branches_moved: Vec<String>,
+}
+
+/// Moves a game's backup archives and save branches to slower storage and back, keeping
+/// the hot backup directory and the master repository lean for games rarely played.
+pub struct ColdStorage;
+
+impl ColdStorage {
+    /// The folder a game uses inside a cold storage directory
+    pub fn game_folder(archive_dir: &Path, game_id: i64) -> PathBuf {
+        archive_dir.join(format!("game-{}", game_id))
+    }
+
+    /// Move the game's archives to `archive_dir/game-<id>/backups` and its branches to a
+    /// bare repository next to them.
+    ///
+    /// Files are copied and branches pushed first; the database paths are rewritten in one
+    /// transaction, and only then are the originals and the branches removed.
+    pub async fn archive_to(db: &Arc<tokio::sync::Mutex<Database>>, game_id: i64, archive_dir: &Path) -> Result<ColdStorageResult, String> {
+        let archive_dir = BackupLocation::validate_root(archive_dir)?;
+        let folder = Self::game_folder(&archive_dir, game_id);
+        if folder.exists() {
+            return Err(format!("{} already exists", folder.display()));
+        }
+
+        let planned = {
+            let db_guard = db.lock().await;
+            let conn = db_guard.get_connection().await;
+            GameManager::get_game_by_id(&conn, game_id)?;
+            if let Some(existing) = GameManager::get_cold_storage_path(&conn, game_id)? {
+                return Err(format!("Game is already in cold storage at {}", existing));
+            }
+
+            let backups_dir = folder.join(BACKUPS_DIR);
+            Self::backup_files(&conn, game_id)?
+                .into_iter()
+                .filter(|path| path.is_file())
+                .map(|path| {
+                    let target = backups_dir.join(path.file_name().unwrap_or_default());
+                    (path, target)
+                })
+                .collect::<CopiedFiles>()
+        };
+
+        std::fs::create_dir_all(&folder)
+            .map_err(|e| format!("Failed to create {}: {}", folder.display(), e))?;
+        let copied = match Self::copy_files(planned).await {
+            Ok(copied) => copied,
+            Err(e) => {
+                Self::discard_folder(&folder);
+                return Err(e);
+            }
+        };
+
+        let git_manager = GitSaveManager::new(db.clone());
+        let branches_moved = match git_manager.export_game_branches(game_id, &folder.join(BRANCHES_REPO)).await {
+            Ok(branches) => branches,
+            Err(e) => {
+                Self::discard_folder(&folder);
+                return Err(e);
+            }
+        };
+
+        let storage_path = folder.to_string_lossy().to_string();
+        let records_updated = {
+            let db_guard = db.lock().await;
+            let conn = db_guard.get_connection().await;
+            match Self::rewrite_paths(&conn, game_id, &copied, Some(&storage_path)) {
+                Ok(count) => count,
+                Err(e) => {
+                    Self::discard_folder(&folder);
+                    crate::logger::error("BACKUP", "Failed to update backup paths, cold storage move rolled back", Some(&e));
+                    return Err(e);
+                }
+            }
+        };
+
+        if !branches_moved.is_empty() {
+            if let Err(e) = git_manager.remove_game_branches(game_id).await {
+                crate::logger::warn("BACKUP", &format!("Branches of game {} were archived but not removed", game_id), Some(&e));
+            }
+        }
+        Self::remove_originals(&copied);
+
+        let result = Self::result(game_id, storage_path, &copied, records_updated, branches_moved);
+        crate::logger::info("BACKUP", &format!("Moved game {} to cold storage: {} files, {} branches", game_id, result.files_moved, result.branches_moved.len()),
+            Some(&result.storage_path));
+        Ok(result)
+    }
+
+    /// Bring a game back from cold storage into the current backup root and the master
+    /// repository. `archive_dir` defaults to where the game was archived to.
+    pub async fn unarchive_from(db: &Arc<tokio::sync::Mutex<Database>>, game_id: i64, archive_dir: Option<&Path>) -> Result<ColdStorageResult, String> {
+        let (folder, planned) = {
+            let db_guard = db.lock().await;
+            let conn = db_guard.get_connection().await;
+            GameManager::get_game_by_id(&conn, game_id)?;
+
+            let folder = match archive_dir {
+                Some(dir) => Self::game_folder(dir, game_id),
+                None => GameManager::get_cold_storage_path(&conn, game_id)?
+                    .map(PathBuf::from)
+                    .ok_or_else(|| "Game is not in cold storage".to_string())?,
+            };
+            if !folder.is_dir() {
+                return Err(format!("Cold storage folder {} not found", folder.display()));
+            }
+
+            let target_dir = BackupLocation::game_directory(&conn, game_id);
+            let planned = std::fs::read_dir(folder.join(BACKUPS_DIR))
+                .map(|entries| entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.is_file())
+                    .map(|p| {
+                        let target = target_dir.join(p.file_name().unwrap_or_default());
+                        (p, target)
+                    })
+                    .collect::<CopiedFiles>())
+                .unwrap_or_default();
+            (folder, planned)
+        };
+
+        let copied = Self::copy_files(planned).await?;
+
+        let bundle = folder.join(BRANCHES_REPO);
+        let branches_moved = if bundle.exists() {
+            match GitSaveManager::new(db.clone()).import_game_branches(game_id, &bundle).await {
+                Ok(branches) => branches,
+                Err(e) => {
+                    Self::remove_copies(&copied);
+                    return Err(e);
+                }
+            }
+        } else {
+            Vec::new()
+        };
+
+        let records_updated = {
+            let db_guard = db.lock().await;
+            let conn = db_guard.get_connection().await;
+            match Self::rewrite_paths(&conn, game_id, &copied, None) {
+                Ok(count) => count,
+                Err(e) => {
+                    Self::remove_copies(&copied);
+                    crate::logger::error("BACKUP", "Failed to update backup paths, restore from cold storage rolled back", Some(&e));
+                    return Err(e);
+                }
+            }
+        };
+
+        if let Err(e) = std::fs::remove_dir_all(&folder) {
+            crate::logger::warn("BACKUP", &format!("Failed to remove cold storage folder {}", folder.display()), Some(&e.to_string()));
+        }
+
+        let result = Self::result(game_id, folder.to_string_lossy().to_string(), &copied, records_updated, branches_moved);
+        crate::logger::info("BACKUP", &format!("Restored game {} from cold storage: {} files, {} branches", game_id, result.files_moved, result.branches_moved.len()),
+            Some(&result.storage_path));
+        Ok(result)
+    }
+
+    /// Every archive path recorded for a game
+    fn backup_files(conn: &Connection, game_id: i64) -> Result<Vec<PathBuf>, String> {
+        let mut stmt = conn.prepare(
+            "SELECT file_path FROM backups WHERE game_id = ?1
+             UNION SELECT sv.backup_path FROM save_versions sv
+                JOIN detected_saves ds ON ds.id = sv.detected_save_id
+                WHERE ds.game_id = ?1"
+        ).map_err(|e| format!("Failed to prepare backup query: {}", e))?;
+
+        let paths = stmt.query_map([game_id], |row| row.get::<_, String>(0))
+            .map_err(|e| format!("Failed to query backups: {}", e))?
+            .filter_map(|r| r.ok())
+            .map(PathBuf::from)
+            .collect();
+        Ok(paths)
+    }
+
+    /// Copy each pair on the blocking pool, removing the copies again if one fails
+    async fn copy_files(planned: CopiedFiles) -> Result<CopiedFiles, String> {
+        tokio::task::spawn_blocking(move || {
+            let mut copied = Vec::with_capacity(planned.len());
+            for (from, to) in planned {
+                let result = if to.exists() {
+                    Err(format!("{} already exists", to.display()))
+                } else {
+                    to.parent()
+                        .map_or(Ok(()), std::fs::create_dir_all)
+                        .and_then(|_| std::fs::copy(&from, &to))
+                        .map(|_| ())
+                        .map_err(|e| format!("Failed to copy {}: {}", from.display(), e))
+                };
+
+                if let Err(e) = result {
+                    Self::remove_copies(&copied);
+                    crate::logger::error("BACKUP", "Failed to copy backups", Some(&e));
+                    return Err(e);
+                }
+                copied.push((from, to));
+            }
+            Ok(copied)
+        })
+        .await
+        .map_err(|e| format!("Backup copy task failed: {}", e))?
+    }
+
+    /// Point the backup records at the copies and record (or clear) the cold storage folder
+    fn rewrite_paths(conn: &Connection, game_id: i64, copied: &[(PathBuf, PathBuf)], storage_path: Option<&str>) -> Result<usize, String> {
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let mut updated = 0;
+
+        for (from, to) in copied {
+            let from = from.to_string_lossy();
+            let to = to.to_string_lossy();
+            updated += tx.execute("UPDATE backups SET file_path = ? WHERE file_path = ?", params![to, from])
+                .map_err(|e| format!("Failed to update backup path: {}", e))?;
+            updated += tx.execute("UPDATE save_versions SET backup_path = ? WHERE backup_path = ?", params![to, from])
+                .map_err(|e| format!("Failed to update save version path: {}", e))?;
+        }
+
+        GameManager::set_cold_storage_path(&tx, game_id, storage_path)?;
+        tx.commit().map_err(|e| format!("Failed to commit backup paths: {}", e))?;
+        Ok(updated)
+    }
+
+    fn result(game_id: i64, storage_path: String, copied: &CopiedFiles, records_updated: usize, branches_moved: Vec<String>) -> ColdStorageResult {
+        ColdStorageResult {
+            game_id,
+            storage_path,
+            files_moved: copied.len(),
+            bytes_moved: copied.iter().filter_map(|(_, to)| std::fs::metadata(to).ok()).map(|m| m.len()).sum(),
+            records_updated,
+            branches_moved,
+        }
+    }
+
+    fn remove_copies(copied: &[(PathBuf, PathBuf)]) {
+        for (_, to) in copied {
+            let _ = std::fs::remove_file(to);
+        }
+    }
+
+    fn remove_originals(copied: &[(PathBuf, PathBuf)]) {
+        for (from, _) in copied {
+            if let Err(e) = std::fs::remove_file(from) {
+                crate::logger::warn("BACKUP", &format!("Failed to remove {}", from.display()), Some(&e.to_string()));
+            }
+        }
+    }
+
+    /// Remove a half-written cold storage folder
+    fn discard_folder(folder: &Path) {
+        if let Err(e) = std::fs::remove_dir_all(folder) {
+            crate::logger::warn("BACKUP", &format!("Failed to clean up {}", folder.display()), Some(&e.to_string()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_copy_files_rolls_back_on_conflict() {
+        let from = TempDir::new().unwrap();
+        let to = TempDir::new().unwrap();
+        std::fs::write(from.path().join("a.tar.zst"), b"a").unwrap();
+        std::fs::write(from.path().join("b.tar.zst"), b"b").unwrap();
+        std::fs::write(to.path().join("b.tar.zst"), b"existing").unwrap();
+
+        let planned = vec![
+            (from.path().join("a.tar.zst"), to.path().join("backups").join("a.tar.zst")),
+            (from.path().join("b.tar.zst"), to.path().join("b.tar.zst")),
+        ];
+        assert!(ColdStorage::copy_files(planned).await.is_err());
+        assert!(!to.path().join("backups").join("a.tar.zst").exists());
+        assert_eq!(std::fs::read(to.path().join("b.tar.zst")).unwrap(), b"existing");
+    }
+}
@@ -0,0 +1,174 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::database::connection::Database;
+use super::archive;
+
+/// A file that differs between two backups. Sizes are `None` on the side it is missing from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileDifference {
+    pub path: String,
+    pub size_a: Option<u64>,
+    pub size_b: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupComparison {
+    pub backup_a: String,
+    pub backup_b: String,
+    pub added: Vec<FileDifference>,
+    pub removed: Vec<FileDifference>,
+    pub changed: Vec<FileDifference>,
+    pub unchanged_count: usize,
+}
+
+/// Size and content hash of each file in an archive, keyed by its save-relative path
+type ArchiveContents = BTreeMap<String, (u64, String)>;
+
+/// Resolve a backup reference to an archive on disk.
+///
+/// Accepts a `backups.backup_id`, a `save_versions.id`, or a path to an archive.
+pub fn resolve_backup_reference(conn: &Connection, reference: &str) -> Result<PathBuf, String> {
+    let from_backups: Option<String> = conn.query_row(
+        "SELECT file_path FROM backups WHERE backup_id = ?",
+        params![reference],
+        |row| row.get(0),
+    ).optional().map_err(|e| format!("Failed to look up backup {}: {}", reference, e))?;
+    if let Some(path) = from_backups {
+        return Ok(PathBuf::from(path));
+    }
+
+    if let Ok(version_id) = reference.parse::<i64>() {
+        let from_versions: Option<String> = conn.query_row(
+            "SELECT backup_path FROM save_versions WHERE id = ?",
+            params![version_id],
+            |row| row.get(0),
+        ).optional().map_err(|e| format!("Failed to look up save version {}: {}", version_id, e))?;
+        if let Some(path) = from_versions {
+            return Ok(PathBuf::from(path));
+        }
+    }
+
+    let path = PathBuf::from(reference);
+    if path.is_file() {
+        Ok(path)
+    } else {
+        Err(format!("Backup not found: {}", reference))
+    }
+}
+
+/// Compare the files stored in two backups
+pub async fn compare_backups(db: &Arc<tokio::sync::Mutex<Database>>, backup_a: &str, backup_b: &str) -> Result<BackupComparison, String> {
+    let (path_a, path_b) = {
+        let db_guard = db.lock().await;
+        let conn = db_guard.get_connection().await;
+        (resolve_backup_reference(&conn, backup_a)?, resolve_backup_reference(&conn, backup_b)?)
+    };
+
+    let (contents_a, contents_b) = tokio::task::spawn_blocking(move || {
+        Ok::<_, String>((read_archive_contents(&path_a)?, read_archive_contents(&path_b)?))
+    })
+    .await
+    .map_err(|e| format!("Backup comparison task failed: {}", e))??;
+
+    let mut comparison = diff_contents(&contents_a, &contents_b);
+    comparison.backup_a = backup_a.to_string();
+    comparison.backup_b = backup_b.to_string();
+    Ok(comparison)
+}
+
+/// Extract an archive into a temporary directory and hash every file in it.
+/// The temporary directory is removed when this returns.
+fn read_archive_contents(archive_path: &Path) -> Result<ArchiveContents, String> {
+    let manifest = archive::read_manifest(archive_path)?;
+    let temp_dir = tempfile::TempDir::new()
+        .map_err(|e| format!("Failed to create temporary directory: {}", e))?;
+
+    archive::extract_archive(archive_path, |entry_path| Some(temp_dir.path().join(entry_path)))?;
+
+    // Archive paths carry a per-archive location index, so key files by where they restore to
+    let display_names: BTreeMap<&str, String> = manifest.files.iter()
+        .map(|e| (e.archive_path.as_str(), format!("{}/{}", e.location_pattern, e.relative_path)))
+        .collect();
+
+    let mut contents = ArchiveContents::new();
+    for entry in walkdir::WalkDir::new(temp_dir.path()).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let archive_path = entry.path().strip_prefix(temp_dir.path())
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+        let name = display_names.get(archive_path.as_str()).cloned().unwrap_or(archive_path);
+
+        let mut file = std::fs::File::open(entry.path())
+            .map_err(|e| format!("Failed to open extracted file {}: {}", entry.path().display(), e))?;
+        let mut hasher = Sha256::new();
+        let size = std::io::copy(&mut file, &mut hasher)
+            .map_err(|e| format!("Failed to hash extracted file {}: {}", entry.path().display(), e))?;
+
+        contents.insert(name, (size, hex::encode(hasher.finalize())));
+    }
+
+    Ok(contents)
+}
+
+fn diff_contents(a: &ArchiveContents, b: &ArchiveContents) -> BackupComparison {
+    let mut comparison = BackupComparison {
+        backup_a: String::new(),
+        backup_b: String::new(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+        unchanged_count: 0,
+    };
+
+    for (path, (size_a, hash_a)) in a {
+        match b.get(path) {
+            None => comparison.removed.push(FileDifference { path: path.clone(), size_a: Some(*size_a), size_b: None }),
+            Some((size_b, hash_b)) if hash_b != hash_a => {
+                comparison.changed.push(FileDifference { path: path.clone(), size_a: Some(*size_a), size_b: Some(*size_b) });
+            }
+            Some(_) => comparison.unchanged_count += 1,
+        }
+    }
+
+    for (path, (size_b, _)) in b {
+        if !a.contains_key(path) {
+            comparison.added.push(FileDifference { path: path.clone(), size_a: None, size_b: Some(*size_b) });
+        }
+    }
+
+    comparison
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_contents() {
+        let a: ArchiveContents = [
+            ("saves/slot1.sav".to_string(), (10, "aaa".to_string())),
+            ("saves/slot2.sav".to_string(), (20, "bbb".to_string())),
+            ("saves/options.ini".to_string(), (5, "ccc".to_string())),
+        ].into_iter().collect();
+        let b: ArchiveContents = [
+            ("saves/slot1.sav".to_string(), (12, "aab".to_string())),
+            ("saves/options.ini".to_string(), (5, "ccc".to_string())),
+            ("saves/slot3.sav".to_string(), (30, "ddd".to_string())),
+        ].into_iter().collect();
+
+        let diff = diff_contents(&a, &b);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].size_b, Some(12));
+        assert_eq!(diff.removed[0].path, "saves/slot2.sav");
+        assert_eq!(diff.added[0].path, "saves/slot3.sav");
+        assert_eq!(diff.unchanged_count, 1);
+    }
+}
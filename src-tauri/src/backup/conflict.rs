@@ -0,0 +1,216 @@
+use std::path::Path;
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::auto_backup::GameSession;
+use crate::commands::error::{CommandError, CommandResult};
+use crate::database::connection::Database;
+use crate::database::models::SaveLocation;
+use crate::detection::process_monitor::ProcessMonitor;
+use crate::game_manager::GameManager;
+use super::portable::PortableSaves;
+
+/// Raised when the save on disk has been modified after the backup being restored
+#[derive(Debug, Clone, Serialize)]
+pub struct ConflictWarning {
+    pub game_id: i64,
+    pub current_modified_at: DateTime<Utc>,
+    pub backup_created_at: DateTime<Utc>,
+    /// Save files modified after the backup was taken
+    pub newer_files: Vec<String>,
+    pub message: String,
+}
+
+/// Outcome of a restore that may have been blocked by a conflict
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum RestoreOutcome<T: Serialize> {
+    Restored { result: T },
+    Conflict { conflict: ConflictWarning },
+}
+
+/// Compare on-disk save modification times against a backup's creation time
+pub fn check_restore_conflict(
+    game_id: i64,
+    locations: &[SaveLocation],
+    backup_created_at: DateTime<Utc>,
+) -> Option<ConflictWarning> {
+    let mut newest: Option<DateTime<Utc>> = None;
+    let mut newer_files = Vec::new();
+
+    // A restore writes through symlinks, so look at whatever they point to
+    for source in PortableSaves::collect_save_files(locations, true) {
+        let Some(modified) = file_modified_at(&source.absolute_path) else { continue };
+
+        if modified > backup_created_at {
+            newer_files.push(source.absolute_path.to_string_lossy().to_string());
+        }
+        if newest.map_or(true, |n| modified > n) {
+            newest = Some(modified);
+        }
+    }
+
+    let current_modified_at = newest?;
+    if newer_files.is_empty() {
+        return None;
+    }
+
+    Some(ConflictWarning {
+        game_id,
+        current_modified_at,
+        backup_created_at,
+        message: format!(
+            "Current save was modified at {} which is newer than the backup from {}. Restore with force to overwrite.",
+            current_modified_at.to_rfc3339(),
+            backup_created_at.to_rfc3339()
+        ),
+        newer_files,
+    })
+}
+
+/// Load the game's save locations and check them for a restore conflict
+pub async fn check_game_conflict(
+    db: &Arc<tokio::sync::Mutex<Database>>,
+    game_id: i64,
+    backup_created_at: DateTime<Utc>,
+) -> Result<Option<ConflictWarning>, String> {
+    let locations = {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+        GameManager::get_save_locations(&conn, game_id)?
+    };
+
+    let warning = check_restore_conflict(game_id, &locations, backup_created_at);
+    if let Some(w) = &warning {
+        crate::logger::warn("BACKUP", &format!("Restore conflict for game {}", game_id), Some(&w.message));
+    }
+    Ok(warning)
+}
+
+/// Process id of the game's backup session, if it has one and that process is still alive
+async fn live_session_pid(sessions: &[GameSession], game_id: i64) -> Option<u32> {
+    let pid = sessions.iter().find(|s| s.game_id == game_id)?.process_id;
+    let alive = tokio::task::spawn_blocking(move || ProcessMonitor::is_pid_alive(pid))
+        .await
+        .unwrap_or(false);
+    if !alive {
+        crate::logger::debug("BACKUP", &format!("Session process {} of game {} has exited", pid, game_id), None);
+    }
+    alive.then_some(pid)
+}
+
+/// Process id of the running game: its backup session's process while that is alive,
+/// otherwise a running process matching the game's stored process names and executables
+pub async fn running_game_pid(db: &Arc<tokio::sync::Mutex<Database>>, game_id: i64) -> Result<Option<u32>, String> {
+    if let Some(service) = crate::auto_backup::backup_service_if_started() {
+        if let Some(pid) = live_session_pid(&service.get_active_sessions().await, game_id).await {
+            return Ok(Some(pid));
+        }
+    }
+
+    let (names, executables) = {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+        let game = GameManager::get_game_by_id(&conn, game_id)?;
+        let identifiers = GameManager::get_game_identifiers(&conn, game_id)?;
+
+        let mut names: Vec<String> = identifiers.into_iter()
+            .filter(|i| i.identifier_type == "process_name")
+            .map(|i| i.identifier_value)
+            .collect();
+        let mut executables: Vec<String> = game.executable_path.into_iter().collect();
+        if let Some(map) = game.platform_executables.as_deref()
+            .and_then(|json| serde_json::from_str::<std::collections::HashMap<String, Vec<String>>>(json).ok())
+        {
+            executables.extend(map.into_values().flatten());
+        }
+        names.extend(executables.iter()
+            .filter_map(|e| Path::new(e).file_name())
+            .map(|n| n.to_string_lossy().to_string()));
+        (names, executables)
+    };
+
+    if names.is_empty() && executables.is_empty() {
+        return Ok(None);
+    }
+
+    tokio::task::spawn_blocking(move || ProcessMonitor::find_matching_process(&names, &executables))
+    .await
+    .map_err(|e| format!("Process scan failed: {}", e))
+}
+
+/// Refuse with `CommandError::GameRunning` while the game is running; restoring under a
+/// running game leaves its in-memory state out of step with the files
+pub async fn ensure_game_not_running(db: &Arc<tokio::sync::Mutex<Database>>, game_id: i64) -> CommandResult<()> {
+    match running_game_pid(db, game_id).await? {
+        Some(pid) => {
+            crate::logger::warn("BACKUP", &format!("Refused restore for game {} while it runs as process {}", game_id, pid), None);
+            Err(CommandError::GameRunning("Game is running; close it before restoring.".to_string()))
+        }
+        None => Ok(()),
+    }
+}
+
+fn file_modified_at(path: &Path) -> Option<DateTime<Utc>> {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(DateTime::<Utc>::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn location_for(path: &Path) -> SaveLocation {
+        SaveLocation {
+            id: 1,
+            game_id: 1,
+            path_pattern: path.to_string_lossy().to_string(),
+            path_type: "directory".to_string(),
+            platform: None,
+            save_type: "auto".to_string(),
+            file_patterns: None,
+            exclude_patterns: None,
+            is_relative_to_user: false,
+            environment_variable: None,
+            priority: 5,
+            detection_method: Some("manual".to_string()),
+            community_confirmed: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_conflict_when_save_newer_than_backup() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("slot1.sav"), b"progress").unwrap();
+        let locations = vec![location_for(temp_dir.path())];
+
+        let old_backup = Utc::now() - chrono::Duration::hours(1);
+        assert!(check_restore_conflict(1, &locations, old_backup).is_some());
+
+        let new_backup = Utc::now() + chrono::Duration::hours(1);
+        assert!(check_restore_conflict(1, &locations, new_backup).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_session_of_exited_process_is_not_running() {
+        let exited = std::process::Command::new(std::env::current_exe().unwrap())
+            .arg("--list")
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .unwrap();
+        let exited_pid = exited.id();
+        let mut exited = exited;
+        exited.wait().unwrap();
+
+        let sessions = vec![GameSession::new(1, exited_pid), GameSession::new(2, std::process::id())];
+        assert_eq!(live_session_pid(&sessions, 1).await, None);
+        assert_eq!(live_session_pid(&sessions, 2).await, Some(std::process::id()));
+        assert_eq!(live_session_pid(&sessions, 3).await, None);
+    }
+}
@@ -0,0 +1,215 @@
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::database::connection::Database;
+use super::archive::{self, ArchiveEntry, MANIFEST_ENTRY};
+use super::compare::resolve_backup_reference;
+use super::portable::PortableSaves;
+
+/// A file stored in a backup archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupContentEntry {
+    /// Path below the save location the file restores to
+    pub path: String,
+    pub size: u64,
+    /// Save location the file was collected from; `None` for entries missing from the manifest
+    pub location_pattern: Option<String>,
+    /// Name of the entry inside the archive
+    pub archive_path: String,
+}
+
+/// Result of restoring a single file from a backup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileRestoreResult {
+    pub backup_id: String,
+    pub relative_path: String,
+    pub restored_to: String,
+    pub size: u64,
+    /// Copy of the file that was overwritten, kept next to it
+    pub pre_restore_copy: Option<String>,
+}
+
+/// List the files in a backup without extracting them.
+///
+/// Accepts the same references as `compare_backups`: a backup id, a save version id or an
+/// archive path.
+pub async fn list_backup_contents(db: &Arc<tokio::sync::Mutex<Database>>, backup_id: &str) -> Result<Vec<BackupContentEntry>, String> {
+    let archive_path = {
+        let db_guard = db.lock().await;
+        let conn = db_guard.get_connection().await;
+        resolve_backup_reference(&conn, backup_id)?
+    };
+
+    tokio::task::spawn_blocking(move || {
+        let manifest = archive::read_manifest(&archive_path)?;
+        let listed = archive::list_entries(&archive_path)?;
+        Ok(describe_entries(&manifest.files, listed))
+    })
+    .await
+    .map_err(|e| format!("Backup listing task failed: {}", e))?
+}
+
+/// Restore one file from a backup, leaving the rest of the save folder alone.
+///
+/// `relative_path` is a `path` or `archive_path` from `list_backup_contents`. The file goes
+/// to `dest` when given (into it, if it is a directory), otherwise to where a full restore
+/// would put it. A file already there is first copied to `<name>.pre-restore-<timestamp>`.
+/// Callers check `backup_game` for a running game before restoring without `dest`.
+pub async fn restore_file(
+    db: &Arc<tokio::sync::Mutex<Database>>,
+    backup_id: &str,
+    relative_path: &str,
+    dest: Option<&Path>,
+) -> Result<FileRestoreResult, String> {
+    let (archive_path, game_id) = {
+        let db_guard = db.lock().await;
+        let conn = db_guard.get_connection().await;
+        (resolve_backup_reference(&conn, backup_id)?, backup_game_id(&conn, backup_id)?)
+    };
+
+    let manifest_path = archive_path.clone();
+    let manifest = tokio::task::spawn_blocking(move || archive::read_manifest(&manifest_path))
+        .await
+        .map_err(|e| format!("Restore task failed: {}", e))??;
+    let entry = find_entry(&manifest.files, relative_path)?.clone();
+
+    let target = match dest {
+        Some(dest) if dest.is_dir() => dest.join(entry.relative_path_buf().file_name().unwrap_or_default()),
+        Some(dest) => dest.to_path_buf(),
+        None => {
+            let game_id = game_id
+                .ok_or_else(|| "Invalid restore request: the backup is not linked to a game, pass a destination".to_string())?;
+            let (game, locations) = PortableSaves::load_game_and_locations(db, game_id).await?;
+            PortableSaves::restore_targets(&game.name, &locations, &manifest)?
+                .remove(&entry.archive_path)
+                .ok_or_else(|| format!("No restore location for {}", entry.relative_path))?
+        }
+    };
+
+    let backup_ref = backup_id.to_string();
+    tokio::task::spawn_blocking(move || {
+        let pre_restore_copy = if target.is_file() {
+            let mut copy_name = target.file_name().unwrap_or_default().to_os_string();
+            copy_name.push(format!(".pre-restore-{}", Utc::now().format("%Y%m%d-%H%M%S")));
+            let copy = target.with_file_name(copy_name);
+            std::fs::copy(&target, &copy)
+                .map_err(|e| format!("Failed to keep a copy of {}: {}", target.display(), e))?;
+            Some(copy)
+        } else {
+            None
+        };
+
+        let written = archive::extract_archive(&archive_path, |p| (p == entry.archive_path).then(|| target.clone()))?;
+        if written == 0 {
+            return Err(format!("{} is listed in the manifest but missing from the archive", entry.relative_path));
+        }
+
+        let size = std::fs::metadata(&target).map(|m| m.len()).unwrap_or(entry.size);
+        crate::logger::info("BACKUP", &format!("Restored {} from backup {}", entry.relative_path, backup_ref), Some(&target.display().to_string()));
+        Ok(FileRestoreResult {
+            backup_id: backup_ref,
+            relative_path: entry.relative_path,
+            restored_to: target.to_string_lossy().to_string(),
+            size,
+            pre_restore_copy: pre_restore_copy.map(|p| p.to_string_lossy().to_string()),
+        })
+    })
+    .await
+    .map_err(|e| format!("Restore task failed: {}", e))?
+}
+
+/// Game a backup belongs to, `None` when it is not linked to one
+pub async fn backup_game(db: &Arc<tokio::sync::Mutex<Database>>, backup_id: &str) -> Result<Option<i64>, String> {
+    let db_guard = db.lock().await;
+    let conn = db_guard.get_connection().await;
+    backup_game_id(&conn, backup_id)
+}
+
+/// The game a backup reference belongs to, when it names a recorded backup or save version
+fn backup_game_id(conn: &Connection, reference: &str) -> Result<Option<i64>, String> {
+    let from_backups: Option<i64> = conn.query_row(
+        "SELECT game_id FROM backups WHERE backup_id = ?",
+        params![reference],
+        |row| row.get(0),
+    ).optional().map_err(|e| format!("Failed to look up backup {}: {}", reference, e))?;
+    if from_backups.is_some() {
+        return Ok(from_backups);
+    }
+
+    let Ok(version_id) = reference.parse::<i64>() else { return Ok(None) };
+    conn.query_row(
+        "SELECT ds.game_id FROM save_versions sv JOIN detected_saves ds ON ds.id = sv.detected_save_id WHERE sv.id = ?",
+        params![version_id],
+        |row| row.get(0),
+    ).optional().map_err(|e| format!("Failed to look up save version {}: {}", version_id, e))
+}
+
+/// The manifest entry for `relative_path`, which may also be an archive path. A relative
+/// path stored under more than one save location has to be given by archive path.
+fn find_entry<'a>(files: &'a [ArchiveEntry], relative_path: &str) -> Result<&'a ArchiveEntry, String> {
+    let wanted = relative_path.replace('\\', "/");
+    if let Some(entry) = files.iter().find(|e| e.archive_path == wanted) {
+        return Ok(entry);
+    }
+
+    let mut matches = files.iter().filter(|e| e.relative_path == wanted);
+    match (matches.next(), matches.next()) {
+        (Some(entry), None) => Ok(entry),
+        (Some(_), Some(_)) => Err(format!("Invalid file: {} is in more than one save location, use its archive path", relative_path)),
+        (None, _) => Err(format!("File {} not found in backup", relative_path)),
+    }
+}
+
+/// Pair each archive entry with its manifest record, in archive order
+fn describe_entries(manifest_files: &[ArchiveEntry], listed: Vec<(String, u64)>) -> Vec<BackupContentEntry> {
+    let by_archive_path: HashMap<&str, &ArchiveEntry> = manifest_files.iter()
+        .map(|e| (e.archive_path.as_str(), e))
+        .collect();
+
+    listed.into_iter()
+        .filter(|(archive_path, _)| archive_path != MANIFEST_ENTRY)
+        .map(|(archive_path, size)| {
+            let entry = by_archive_path.get(archive_path.as_str());
+            BackupContentEntry {
+                path: entry.map_or_else(|| archive_path.clone(), |e| e.relative_path.clone()),
+                size,
+                location_pattern: entry.map(|e| e.location_pattern.clone()),
+                archive_path,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(archive_path: &str, relative_path: &str) -> ArchiveEntry {
+        ArchiveEntry {
+            archive_path: archive_path.to_string(),
+            location_pattern: "/saves".to_string(),
+            relative_path: relative_path.to_string(),
+            size: 1,
+            location_index: None,
+            raw_relative_path: None,
+        }
+    }
+
+    #[test]
+    fn test_find_entry_by_relative_or_archive_path() {
+        let files = vec![
+            entry("files/0/slot1.sav", "slot1.sav"),
+            entry("files/0/settings.ini", "settings.ini"),
+            entry("files/1/settings.ini", "settings.ini"),
+        ];
+
+        assert_eq!(find_entry(&files, "slot1.sav").unwrap().archive_path, "files/0/slot1.sav");
+        assert_eq!(find_entry(&files, "files/1/settings.ini").unwrap().archive_path, "files/1/settings.ini");
+        assert!(find_entry(&files, "settings.ini").unwrap_err().starts_with("Invalid"));
+        assert!(find_entry(&files, "slot2.sav").unwrap_err().contains("not found"));
+    }
+}
@@ -0,0 +1,247 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use walkdir::WalkDir;
+
+use crate::database::connection::{Database, DatabasePaths};
+use crate::database::settings::AppSettings;
+
+const BACKUP_ROOT_KEY: &str = "backup_root";
+
+/// (original, copy) pairs produced while relocating archives
+type CopiedFiles = Vec<(PathBuf, PathBuf)>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupRootInfo {
+    pub backup_root: String,
+    pub is_default: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveBackupsResult {
+    pub old_root: String,
+    pub new_root: String,
+    pub files_moved: usize,
+    pub bytes_moved: u64,
+    pub records_updated: usize,
+}
+
+/// Where backup archives are written, configurable through the `backup_root` setting
+pub struct BackupLocation;
+
+impl BackupLocation {
+    /// The configured backup root, or the app data backup directory when unset
+    pub fn current_root(conn: &Connection) -> PathBuf {
+        match AppSettings::get(conn, BACKUP_ROOT_KEY) {
+            Ok(Some(root)) => PathBuf::from(root),
+            Ok(None) => DatabasePaths::backup_directory(),
+            Err(e) => {
+                crate::logger::warn("BACKUP", "Failed to read backup_root setting, using default", Some(&e.to_string()));
+                DatabasePaths::backup_directory()
+            }
+        }
+    }
+
+    pub fn root_info(conn: &Connection) -> BackupRootInfo {
+        let configured = AppSettings::get(conn, BACKUP_ROOT_KEY).ok().flatten();
+        BackupRootInfo {
+            backup_root: Self::current_root(conn).to_string_lossy().to_string(),
+            is_default: configured.is_none(),
+        }
+    }
+
+    /// Directory holding one game's archives below the current root
+    pub fn game_directory(conn: &Connection, game_id: i64) -> PathBuf {
+        Self::current_root(conn).join(game_id.to_string())
+    }
+
+    /// Check that `path` is an existing, writable directory and return its canonical form
+    pub fn validate_root(path: &Path) -> Result<PathBuf, String> {
+        let canonical = path.canonicalize()
+            .map_err(|e| format!("Backup directory {} is not accessible: {}", path.display(), e))?;
+
+        if !canonical.is_dir() {
+            return Err(format!("Backup location {} is not a directory", canonical.display()));
+        }
+
+        let probe = canonical.join(format!(".save-steward-write-test-{}", std::process::id()));
+        std::fs::write(&probe, b"probe")
+            .map_err(|e| format!("Backup directory {} is not writable: {}", canonical.display(), e))?;
+        let _ = std::fs::remove_file(&probe);
+
+        Ok(canonical)
+    }
+
+    /// Point new backups at `path` without touching existing archives
+    pub async fn set_root(db: &Arc<tokio::sync::Mutex<Database>>, path: &Path) -> Result<BackupRootInfo, String> {
+        let root = Self::validate_root(path)?;
+
+        let db_guard = db.lock().await;
+        let conn = db_guard.get_connection().await;
+        AppSettings::set(&conn, BACKUP_ROOT_KEY, &root.to_string_lossy())
+            .map_err(|e| format!("Failed to save backup_root: {}", e))?;
+
+        crate::logger::info("BACKUP", "Backup root updated", Some(&root.display().to_string()));
+        Ok(Self::root_info(&conn))
+    }
+
+    /// Relocate every archive below the current root to `new_root` and switch the setting.
+    ///
+    /// Files are copied first and the database paths are rewritten in a single transaction.
+    /// If anything fails the copies are removed and the old root stays in use; the originals
+    /// are only deleted once the transaction has committed.
+    pub async fn move_backups(db: &Arc<tokio::sync::Mutex<Database>>, new_root: &Path) -> Result<MoveBackupsResult, String> {
+        let new_root = Self::validate_root(new_root)?;
+
+        let db_guard = db.lock().await;
+        let conn = db_guard.get_connection().await;
+        let old_root = Self::current_root(&conn);
+
+        if let Ok(old_canonical) = old_root.canonicalize() {
+            if old_canonical == new_root {
+                return Err("Backups are already stored in this directory".to_string());
+            }
+            if new_root.starts_with(&old_canonical) || old_canonical.starts_with(&new_root) {
+                return Err("The new backup directory cannot be inside the current one, or contain it".to_string());
+            }
+        }
+
+        let (from, to) = (old_root.clone(), new_root.clone());
+        let copy_result = tokio::task::spawn_blocking(move || Self::copy_tree(&from, &to))
+            .await
+            .map_err(|e| format!("Backup copy task failed: {}", e))?;
+
+        let copied = match copy_result {
+            Ok(copied) => copied,
+            Err((copied, e)) => {
+                Self::remove_files(copied.iter().map(|(_, to)| to));
+                crate::logger::error("BACKUP", "Failed to copy backups to new root", Some(&e));
+                return Err(e);
+            }
+        };
+
+        let records_updated = match Self::rewrite_paths(&conn, &copied, &new_root) {
+            Ok(count) => count,
+            Err(e) => {
+                Self::remove_files(copied.iter().map(|(_, to)| to));
+                crate::logger::error("BACKUP", "Failed to update backup paths, move rolled back", Some(&e.to_string()));
+                return Err(format!("Failed to update backup paths: {}", e));
+            }
+        };
+
+        Self::remove_files(copied.iter().map(|(from, _)| from));
+        Self::remove_empty_dirs(&old_root);
+
+        let result = MoveBackupsResult {
+            old_root: old_root.to_string_lossy().to_string(),
+            new_root: new_root.to_string_lossy().to_string(),
+            files_moved: copied.len(),
+            bytes_moved: copied.iter().filter_map(|(_, to)| std::fs::metadata(to).ok()).map(|m| m.len()).sum(),
+            records_updated,
+        };
+
+        crate::logger::info("BACKUP", &format!("Moved {} backup files", result.files_moved),
+            Some(&format!("{} -> {}", result.old_root, result.new_root)));
+        Ok(result)
+    }
+
+    /// Copy every file below `from` into the same relative location under `to`.
+    /// On failure the files copied so far are returned alongside the error.
+    fn copy_tree(from: &Path, to: &Path) -> Result<CopiedFiles, (CopiedFiles, String)> {
+        let mut copied = Vec::new();
+        if !from.exists() {
+            return Ok(copied);
+        }
+
+        for entry in WalkDir::new(from) {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => return Err((copied, format!("Failed to read backup directory: {}", e))),
+            };
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let relative = entry.path().strip_prefix(from).unwrap_or(entry.path());
+            let target = to.join(relative);
+            if target.exists() {
+                return Err((copied, format!("{} already exists in the new backup directory", relative.display())));
+            }
+            if let Some(parent) = target.parent() {
+                if let Err(e) = std::fs::create_dir_all(parent) {
+                    return Err((copied, format!("Failed to create {}: {}", parent.display(), e)));
+                }
+            }
+            if let Err(e) = std::fs::copy(entry.path(), &target) {
+                return Err((copied, format!("Failed to copy {}: {}", entry.path().display(), e)));
+            }
+
+            copied.push((entry.into_path(), target));
+        }
+
+        Ok(copied)
+    }
+
+    fn rewrite_paths(conn: &Connection, copied: &[(PathBuf, PathBuf)], new_root: &Path) -> rusqlite::Result<usize> {
+        let tx = conn.unchecked_transaction()?;
+        let mut updated = 0;
+
+        for (from, to) in copied {
+            let from = from.to_string_lossy();
+            let to = to.to_string_lossy();
+            updated += tx.execute("UPDATE backups SET file_path = ? WHERE file_path = ?", params![to, from])?;
+            updated += tx.execute("UPDATE save_versions SET backup_path = ? WHERE backup_path = ?", params![to, from])?;
+        }
+
+        AppSettings::set(&tx, BACKUP_ROOT_KEY, &new_root.to_string_lossy())?;
+        tx.commit()?;
+        Ok(updated)
+    }
+
+    fn remove_files<'a>(paths: impl Iterator<Item = &'a PathBuf>) {
+        for path in paths {
+            if let Err(e) = std::fs::remove_file(path) {
+                crate::logger::warn("BACKUP", &format!("Failed to remove {}", path.display()), Some(&e.to_string()));
+            }
+        }
+    }
+
+    /// Remove the now-empty per-game directories left behind in the old root
+    fn remove_empty_dirs(root: &Path) {
+        for entry in WalkDir::new(root).contents_first(true).min_depth(1).into_iter().filter_map(|e| e.ok()) {
+            if entry.file_type().is_dir() {
+                let _ = std::fs::remove_dir(entry.path());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_root_rejects_missing_and_files() {
+        let dir = TempDir::new().unwrap();
+        assert!(BackupLocation::validate_root(dir.path()).is_ok());
+        assert!(BackupLocation::validate_root(&dir.path().join("missing")).is_err());
+
+        let file = dir.path().join("file.txt");
+        std::fs::write(&file, b"x").unwrap();
+        assert!(BackupLocation::validate_root(&file).is_err());
+    }
+
+    #[test]
+    fn test_copy_tree_keeps_relative_layout() {
+        let from = TempDir::new().unwrap();
+        let to = TempDir::new().unwrap();
+        std::fs::create_dir(from.path().join("7")).unwrap();
+        std::fs::write(from.path().join("7").join("a.tar.zst"), b"archive").unwrap();
+
+        let copied = BackupLocation::copy_tree(from.path(), to.path()).unwrap();
+        assert_eq!(copied.len(), 1);
+        assert!(to.path().join("7").join("a.tar.zst").exists());
+    }
+}
@@ -0,0 +1,23 @@
+//! Save archive handling for Save Steward
+//!
+//! Bundles a game's resolved save files into self-describing `.tar.zst`
+//! (or, per game, plain `.tar` or `.zip`) archives that can be restored later
+//! or moved to another machine.
+
+pub mod archive;
+pub mod benchmark;
+pub mod cold_storage;
+pub mod compare;
+pub mod conflict;
+pub mod contents;
+pub mod location;
+pub mod portable;
+pub mod savings;
+
+pub use archive::SaveArchiveManifest;
+pub use cold_storage::{ColdStorage, ColdStorageResult};
+pub use conflict::RestoreOutcome;
+pub use contents::{BackupContentEntry, FileRestoreResult};
+pub use location::BackupLocation;
+pub use portable::PortableSaves;
+pub use savings::CompressionReport;
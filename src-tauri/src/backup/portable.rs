@@ -0,0 +1,414 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use chrono::Utc;
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use crate::database::connection::Database;
+use crate::database::models::{Game, SaveLocation};
+use crate::game_manager::scanner::{configured_follow_symlinks, SaveScanner};
+use crate::game_manager::GameManager;
+use crate::progress::{ProgressReporter, ProgressSink};
+use super::archive::{self, ArchiveEntry, ArchiveFormat, ArchiveLocation, SaveArchiveManifest, SkippedFile, SourceFile};
+use super::conflict::{check_restore_conflict, RestoreOutcome};
+use super::location::BackupLocation;
+
+/// Result of exporting a game's saves
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportResult {
+    pub game_id: i64,
+    pub archive_path: String,
+    pub file_count: usize,
+    pub archive_size: u64,
+    pub archive_format: ArchiveFormat,
+    /// Locked or unreadable files that were left out
+    pub skipped_files: Vec<SkippedFile>,
+    /// Empty files that were archived anyway; the game may have been mid-save
+    pub zero_byte_files: Vec<String>,
+}
+
+/// Result of importing a save archive
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportResult {
+    pub game_id: i64,
+    pub files_restored: usize,
+    pub safety_backup_path: Option<String>,
+}
+
+/// Export and import of a game's saves as a portable archive
+pub struct PortableSaves;
+
+impl PortableSaves {
+    /// Bundle the game's currently resolved save files into `dest_path`
+    pub async fn export_save(
+        db: &Arc<tokio::sync::Mutex<Database>>,
+        game_id: i64,
+        dest_path: &Path,
+    ) -> Result<ExportResult, String> {
+        let (game, locations) = Self::load_game_and_locations(db, game_id).await?;
+
+        let (archive_locations, sources) = Self::collect_save_sources(&locations, Self::follow_symlinks(db).await);
+        if sources.is_empty() {
+            return Err(format!("No save files found for '{}'", game.name));
+        }
+
+        let (compression_level, archive_format) = {
+            let conn_guard = db.lock().await;
+            let conn = conn_guard.get_connection().await;
+            (
+                GameManager::get_compression_level(&conn, game_id)?.unwrap_or(archive::DEFAULT_COMPRESSION_LEVEL),
+                GameManager::get_archive_format(&conn, game_id)?.unwrap_or_default(),
+            )
+        };
+
+        let mut manifest = SaveArchiveManifest::new(&game.name, &game.platform, game.platform_app_id.clone());
+        manifest.locations = archive_locations;
+        manifest.files = sources.iter().map(|s| s.entry.clone()).collect();
+        manifest.save_modified_at = archive::newest_modified_at(&sources);
+
+        let dest = dest_path.to_path_buf();
+        let source_count = sources.len();
+        let report = tokio::task::spawn_blocking(move || {
+            let progress = ProgressReporter::new("export_save", Some(game_id), ProgressSink::frontend());
+            progress.add_total(sources.len(), sources.iter().map(|s| s.entry.size).sum());
+            archive::write_archive(&dest, &manifest, &sources, archive_format, compression_level, Some(&progress))
+        })
+        .await
+        .map_err(|e| format!("Export task failed: {}", e))??;
+
+        let file_count = source_count - report.skipped_files.len();
+        crate::logger::info("BACKUP", &format!("Exported {} save files for game {} ({} skipped)", file_count, game_id, report.skipped_files.len()), Some(&dest_path.display().to_string()));
+
+        Ok(ExportResult {
+            game_id,
+            archive_path: dest_path.to_string_lossy().to_string(),
+            file_count,
+            archive_size: report.archive_size,
+            archive_format,
+            skipped_files: report.skipped_files,
+            zero_byte_files: report.zero_byte_files,
+        })
+    }
+
+    /// Restore files from an exported archive into the game's resolved save locations.
+    /// The current saves are archived first so the import can be undone. Unless `force`
+    /// is set, the import is refused when the saves on disk are newer than the archive;
+    /// callers check that the game isn't running.
+    pub async fn import_save(
+        db: &Arc<tokio::sync::Mutex<Database>>,
+        game_id: i64,
+        archive_path: &Path,
+        force: bool,
+    ) -> Result<RestoreOutcome<ImportResult>, String> {
+        let (game, locations) = Self::load_game_and_locations(db, game_id).await?;
+
+        let manifest_path = archive_path.to_path_buf();
+        let manifest = tokio::task::spawn_blocking(move || archive::read_manifest(&manifest_path))
+            .await
+            .map_err(|e| format!("Import task failed: {}", e))??;
+
+        if manifest.files.is_empty() {
+            return Err("Archive contains no save files".to_string());
+        }
+
+        if !force {
+            if let Some(conflict) = check_restore_conflict(game_id, &locations, manifest.created_at) {
+                return Ok(RestoreOutcome::Conflict { conflict });
+            }
+        }
+
+        if manifest.game_name != game.name {
+            crate::logger::warn("BACKUP", &format!("Importing saves exported from '{}' into '{}'", manifest.game_name, game.name), None);
+        }
+
+        // Map each archived file to a destination on this machine
+        let targets = Self::restore_targets(&game.name, &locations, &manifest)?;
+
+        // Safety backup of whatever is currently on disk
+        let safety_backup_path = Self::create_safety_backup(db, &game, &locations).await?;
+
+        let source = archive_path.to_path_buf();
+        let files_restored = tokio::task::spawn_blocking(move || {
+            archive::extract_archive(&source, |entry_path| targets.get(entry_path).cloned())
+        })
+        .await
+        .map_err(|e| format!("Import task failed: {}", e))??;
+
+        crate::logger::info("BACKUP", &format!("Imported {} save files for game {}", files_restored, game_id), Some(&archive_path.display().to_string()));
+
+        Ok(RestoreOutcome::Restored {
+            result: ImportResult {
+                game_id,
+                files_restored,
+                safety_backup_path,
+            },
+        })
+    }
+
+    pub async fn load_game_and_locations(
+        db: &Arc<tokio::sync::Mutex<Database>>,
+        game_id: i64,
+    ) -> Result<(Game, Vec<SaveLocation>), String> {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+
+        let game = GameManager::get_game_by_id(&conn, game_id)?;
+        let locations = GameManager::get_save_locations(&conn, game_id)?;
+        Ok((game, locations))
+    }
+
+    /// Resolve each file-based save location for this OS to a concrete root directory or file
+    fn resolved_roots(locations: &[SaveLocation]) -> Vec<(String, PathBuf)> {
+        locations.iter()
+            .filter(|loc| loc.path_type != "registry" && GameManager::applies_to_current_platform(loc))
+            .flat_map(|loc| {
+                GameManager::resolve_save_paths(loc)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(move |path| (loc.path_pattern.clone(), PathBuf::from(path)))
+            })
+            .collect()
+    }
+
+    async fn follow_symlinks(db: &Arc<tokio::sync::Mutex<Database>>) -> bool {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+        configured_follow_symlinks(&conn)
+    }
+
+    /// Collect all existing files under the game's resolved save locations.
+    ///
+    /// Roots are resolved to their real path first. When `follow_symlinks` is off, symlinked
+    /// roots and entries are skipped; when on, symlink loops are broken by the walker.
+    pub fn collect_save_files(locations: &[SaveLocation], follow_symlinks: bool) -> Vec<SourceFile> {
+        Self::collect_save_sources(locations, follow_symlinks).1
+    }
+
+    /// Like `collect_save_files`, also returning the resolved roots the files came from.
+    /// Each entry's `location_index` points into the returned locations.
+    pub fn collect_save_sources(locations: &[SaveLocation], follow_symlinks: bool) -> (Vec<ArchiveLocation>, Vec<SourceFile>) {
+        let mut archive_locations = Vec::new();
+        let mut sources = Vec::new();
+
+        for (index, (pattern, root)) in Self::resolved_roots(locations).into_iter().enumerate() {
+            let Some(root) = SaveScanner::resolve_scan_root(&root, follow_symlinks) else { continue };
+            if !root.is_file() && !root.is_dir() {
+                continue;
+            }
+
+            let location_index = archive_locations.len();
+            archive_locations.push(ArchiveLocation {
+                location_pattern: pattern.clone(),
+                resolved_root: root.to_string_lossy().to_string(),
+            });
+
+            if root.is_file() {
+                let (name, raw_relative_path) = archive::manifest_relative_path(Path::new(root.file_name().unwrap_or_default()));
+                let size = root.metadata().map(|m| m.len()).unwrap_or(0);
+                sources.push(SourceFile {
+                    entry: ArchiveEntry {
+                        archive_path: format!("files/{}/{}", index, name),
+                        location_pattern: pattern,
+                        relative_path: name,
+                        size,
+                        location_index: Some(location_index),
+                        raw_relative_path,
+                    },
+                    absolute_path: root,
+                });
+                continue;
+            }
+
+            for entry in WalkDir::new(&root).follow_links(follow_symlinks).into_iter().filter_map(|e| e.ok()) {
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let Ok(relative) = entry.path().strip_prefix(&root) else { continue };
+                let (relative_path, raw_relative_path) = archive::manifest_relative_path(relative);
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+
+                sources.push(SourceFile {
+                    entry: ArchiveEntry {
+                        archive_path: format!("files/{}/{}", index, relative_path),
+                        location_pattern: pattern.clone(),
+                        relative_path,
+                        size,
+                        location_index: Some(location_index),
+                        raw_relative_path,
+                    },
+                    absolute_path: entry.path().to_path_buf(),
+                });
+            }
+        }
+
+        (archive_locations, sources)
+    }
+
+    /// Map each archive entry to where it restores on this machine, keyed by `archive_path`.
+    ///
+    /// Entries recorded against a source location go to the matching resolved root here: the
+    /// n-th root of a pattern on the source machine maps to the n-th root of that pattern on
+    /// this one. Entries whose pattern no longer resolves go to the first resolved location.
+    pub fn restore_targets(game_name: &str, locations: &[SaveLocation], manifest: &SaveArchiveManifest) -> Result<HashMap<String, PathBuf>, String> {
+        let roots = Self::resolved_roots(locations);
+        let fallback_root = roots.first().map(|(_, root)| root.clone())
+            .ok_or_else(|| format!("No resolvable save location for '{}'", game_name))?;
+
+        let mut targets = HashMap::new();
+        for entry in &manifest.files {
+            // Which occurrence of its pattern the entry's source root was
+            let occurrence = entry.location_index
+                .map(|i| manifest.locations.iter().take(i).filter(|l| l.location_pattern == entry.location_pattern).count())
+                .unwrap_or(0);
+
+            let mut candidates = roots.iter().filter(|(pattern, _)| *pattern == entry.location_pattern);
+            let root = candidates.clone().nth(occurrence)
+                .or_else(|| candidates.next())
+                .map(|(_, root)| root.clone())
+                .unwrap_or_else(|| fallback_root.clone());
+
+            // A location that is a single file restores onto that file
+            let relative_path = entry.relative_path_buf();
+            let target = if root.file_name().is_some_and(|name| Path::new(name) == relative_path) && !root.is_dir() {
+                root
+            } else {
+                root.join(relative_path)
+            };
+            targets.insert(entry.archive_path.clone(), target);
+        }
+
+        Ok(targets)
+    }
+
+    /// Archive whatever is currently on disk so a restore can be undone
+    pub async fn create_safety_backup(db: &Arc<tokio::sync::Mutex<Database>>, game: &Game, locations: &[SaveLocation]) -> Result<Option<String>, String> {
+        let (archive_locations, sources) = Self::collect_save_sources(locations, Self::follow_symlinks(db).await);
+        if sources.is_empty() {
+            return Ok(None);
+        }
+
+        let mut manifest = SaveArchiveManifest::new(&game.name, &game.platform, game.platform_app_id.clone());
+        manifest.locations = archive_locations;
+        manifest.files = sources.iter().map(|s| s.entry.clone()).collect();
+        manifest.save_modified_at = archive::newest_modified_at(&sources);
+
+        let (game_dir, archive_format) = {
+            let db_guard = db.lock().await;
+            let conn = db_guard.get_connection().await;
+            (BackupLocation::game_directory(&conn, game.id), GameManager::get_archive_format(&conn, game.id)?.unwrap_or_default())
+        };
+        let dest = game_dir
+            .join(format!("pre_import_{}.{}", Utc::now().format("%Y%m%d-%H%M%S"), archive_format.extension()));
+        let dest_clone = dest.clone();
+
+        tokio::task::spawn_blocking(move || {
+            archive::write_archive(&dest_clone, &manifest, &sources, archive_format, archive::DEFAULT_COMPRESSION_LEVEL, None)
+        })
+        .await
+        .map_err(|e| format!("Safety backup task failed: {}", e))?
+        .map_err(|e| {
+            crate::logger::error("BACKUP", "Failed to create pre-import safety backup", Some(&e));
+            format!("Pre-import safety backup failed: {}", e)
+        })?;
+
+        crate::logger::info("BACKUP", &format!("Created pre-import safety backup for game {}", game.id), Some(&dest.display().to_string()));
+        Ok(Some(dest.to_string_lossy().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn location_for(path: &Path) -> SaveLocation {
+        SaveLocation {
+            id: 1,
+            game_id: 1,
+            path_pattern: path.to_string_lossy().to_string(),
+            path_type: "directory".to_string(),
+            platform: None,
+            save_type: "auto".to_string(),
+            file_patterns: None,
+            exclude_patterns: None,
+            is_relative_to_user: false,
+            environment_variable: None,
+            priority: 5,
+            detection_method: Some("manual".to_string()),
+            community_confirmed: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_two_location_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let appdata = temp_dir.path().join("AppData").join("Game");
+        let documents = temp_dir.path().join("Documents").join("My Games");
+        std::fs::create_dir_all(appdata.join("profiles")).unwrap();
+        std::fs::create_dir_all(&documents).unwrap();
+        // Same relative name in both locations must not collide
+        std::fs::write(appdata.join("settings.ini"), b"appdata settings").unwrap();
+        std::fs::write(appdata.join("profiles").join("slot1.sav"), b"slot one").unwrap();
+        std::fs::write(documents.join("settings.ini"), b"documents settings").unwrap();
+
+        let locations = vec![location_for(&appdata), location_for(&documents)];
+        let (archive_locations, sources) = PortableSaves::collect_save_sources(&locations, true);
+        assert_eq!(archive_locations.len(), 2);
+        assert_eq!(sources.len(), 3);
+
+        let mut manifest = SaveArchiveManifest::new("Test Game", "steam", None);
+        manifest.locations = archive_locations;
+        manifest.files = sources.iter().map(|s| s.entry.clone()).collect();
+
+        let archive_path = temp_dir.path().join("backup.tar.zst");
+        archive::write_archive(&archive_path, &manifest, &sources, ArchiveFormat::TarZst, archive::DEFAULT_COMPRESSION_LEVEL, None).unwrap();
+
+        std::fs::remove_dir_all(&appdata).unwrap();
+        std::fs::remove_dir_all(&documents).unwrap();
+        std::fs::create_dir_all(&appdata).unwrap();
+        std::fs::create_dir_all(&documents).unwrap();
+
+        let read_back = archive::read_manifest(&archive_path).unwrap();
+        read_back.validate().unwrap();
+        let targets = PortableSaves::restore_targets("Test Game", &locations, &read_back).unwrap();
+        let written = archive::extract_archive(&archive_path, |p| targets.get(p).cloned()).unwrap();
+
+        assert_eq!(written, 3);
+        assert_eq!(std::fs::read(appdata.join("settings.ini")).unwrap(), b"appdata settings");
+        assert_eq!(std::fs::read(appdata.join("profiles").join("slot1.sav")).unwrap(), b"slot one");
+        assert_eq!(std::fs::read(documents.join("settings.ini")).unwrap(), b"documents settings");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_non_utf8_file_name_restores_exactly() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let saves = temp_dir.path().join("saves");
+        std::fs::create_dir_all(&saves).unwrap();
+        let name = std::ffi::OsStr::from_bytes(b"slot\xff.sav");
+        std::fs::write(saves.join(name), b"latin-1 name").unwrap();
+
+        let locations = vec![location_for(&saves)];
+        let (archive_locations, sources) = PortableSaves::collect_save_sources(&locations, true);
+        assert_eq!(sources[0].entry.relative_path, "slot%FF.sav");
+
+        let mut manifest = SaveArchiveManifest::new("Test Game", "steam", None);
+        manifest.locations = archive_locations;
+        manifest.files = sources.iter().map(|s| s.entry.clone()).collect();
+        let archive_path = temp_dir.path().join("backup.tar.zst");
+        archive::write_archive(&archive_path, &manifest, &sources, ArchiveFormat::TarZst, archive::DEFAULT_COMPRESSION_LEVEL, None).unwrap();
+        std::fs::remove_file(saves.join(name)).unwrap();
+
+        let read_back = archive::read_manifest(&archive_path).unwrap();
+        read_back.validate().unwrap();
+        let targets = PortableSaves::restore_targets("Test Game", &locations, &read_back).unwrap();
+        archive::extract_archive(&archive_path, |p| targets.get(p).cloned()).unwrap();
+
+        assert_eq!(std::fs::read(saves.join(name)).unwrap(), b"latin-1 name");
+    }
+}
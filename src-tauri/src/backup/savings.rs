@@ -0,0 +1,207 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::backup::archive;
+use crate::database::connection::Database;
+use crate::game_manager::GameManager;
+
+/// How much space compression saves across a game's backups
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompressionReport {
+    pub game_id: i64,
+    /// Backups whose sizes are known and included in the totals
+    pub backups_counted: usize,
+    /// Backups left out because neither stored sizes nor the archive were available
+    pub backups_skipped: usize,
+    pub original_bytes: u64,
+    pub compressed_bytes: u64,
+    /// `compressed_bytes / original_bytes`; 0 when nothing was counted
+    pub ratio: f64,
+    pub bytes_saved: u64,
+}
+
+/// Which table a backup row came from, so filled-in sizes can be written back
+#[derive(Debug, Clone)]
+enum BackupRow {
+    Backup(String),
+    SaveVersion(i64),
+}
+
+#[derive(Debug, Clone)]
+struct BackupSizes {
+    row: BackupRow,
+    path: String,
+    compressed: Option<u64>,
+    original: Option<u64>,
+}
+
+impl BackupSizes {
+    /// Fill in missing sizes from the archive on disk: the file size is the compressed size
+    /// and the manifest lists the original size of every file. Returns whether anything
+    /// was filled in.
+    fn fill_from_archive(&mut self) -> bool {
+        let path = Path::new(&self.path);
+        let mut changed = false;
+        if self.compressed.is_none() {
+            self.compressed = std::fs::metadata(path).ok().map(|m| m.len());
+            changed |= self.compressed.is_some();
+        }
+        if self.original.is_none() {
+            self.original = archive::read_manifest(path).ok()
+                .map(|manifest| manifest.files.iter().map(|f| f.size).sum());
+            changed |= self.original.is_some();
+        }
+        changed
+    }
+}
+
+impl CompressionReport {
+    /// Sum the compressed and original sizes of every backup of a game.
+    ///
+    /// Sizes recorded at backup time are used as they are; for older backups they are read
+    /// from the archive and stored so the next report doesn't have to open it again.
+    pub async fn for_game(db: &Arc<tokio::sync::Mutex<Database>>, game_id: i64) -> Result<Self, String> {
+        let rows = db.lock().await.with_connection(move |conn| {
+            GameManager::get_game_by_id(conn, game_id)?;
+            Self::load_sizes(conn, game_id)
+        }).await??;
+
+        let rows = tokio::task::spawn_blocking(move || {
+            rows.into_iter()
+                .map(|mut sizes| {
+                    let changed = sizes.fill_from_archive();
+                    (sizes, changed)
+                })
+                .collect::<Vec<_>>()
+        })
+        .await
+        .map_err(|e| format!("Compression report task failed: {}", e))?;
+
+        let filled: Vec<BackupSizes> = rows.iter()
+            .filter(|(_, changed)| *changed)
+            .map(|(sizes, _)| sizes.clone())
+            .collect();
+        if !filled.is_empty() {
+            let stored = db.lock().await.with_connection(move |conn| Self::store_sizes(conn, &filled)).await?;
+            if let Err(e) = stored {
+                crate::logger::warn("BACKUP", &format!("Failed to store backup sizes for game {}", game_id), Some(&e));
+            }
+        }
+
+        Ok(Self::summarize(game_id, rows.into_iter().map(|(sizes, _)| sizes)))
+    }
+
+    fn summarize(game_id: i64, rows: impl IntoIterator<Item = BackupSizes>) -> Self {
+        let mut report = Self { game_id, ..Self::default() };
+        for sizes in rows {
+            match (sizes.compressed, sizes.original) {
+                (Some(compressed), Some(original)) => {
+                    report.backups_counted += 1;
+                    report.compressed_bytes += compressed;
+                    report.original_bytes += original;
+                }
+                _ => report.backups_skipped += 1,
+            }
+        }
+
+        if report.original_bytes > 0 {
+            report.ratio = report.compressed_bytes as f64 / report.original_bytes as f64;
+        }
+        report.bytes_saved = report.original_bytes.saturating_sub(report.compressed_bytes);
+        report
+    }
+
+    fn load_sizes(conn: &Connection, game_id: i64) -> Result<Vec<BackupSizes>, String> {
+        let to_size = |value: Option<i64>| value.map(|v| v.max(0) as u64);
+        let mut rows = Vec::new();
+
+        let mut stmt = conn.prepare("SELECT backup_id, file_path, size_bytes, original_bytes FROM backups WHERE game_id = ?")
+            .map_err(|e| format!("Failed to prepare backup query: {}", e))?;
+        let backups = stmt.query_map([game_id], |row| Ok(BackupSizes {
+            row: BackupRow::Backup(row.get(0)?),
+            path: row.get(1)?,
+            compressed: to_size(row.get(2)?),
+            original: to_size(row.get(3)?),
+        })).map_err(|e| format!("Failed to query backups: {}", e))?;
+        rows.extend(backups.filter_map(|r| r.ok()));
+
+        let mut stmt = conn.prepare(
+            "SELECT sv.id, sv.backup_path, sv.compressed_size, sv.original_size FROM save_versions sv
+                JOIN detected_saves ds ON ds.id = sv.detected_save_id
+                WHERE ds.game_id = ?"
+        ).map_err(|e| format!("Failed to prepare save version query: {}", e))?;
+        let versions = stmt.query_map([game_id], |row| Ok(BackupSizes {
+            row: BackupRow::SaveVersion(row.get(0)?),
+            path: row.get(1)?,
+            compressed: to_size(row.get(2)?),
+            original: to_size(row.get(3)?),
+        })).map_err(|e| format!("Failed to query save versions: {}", e))?;
+        rows.extend(versions.filter_map(|r| r.ok()));
+
+        Ok(rows)
+    }
+
+    fn store_sizes(conn: &Connection, rows: &[BackupSizes]) -> Result<(), String> {
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        for sizes in rows {
+            let compressed = sizes.compressed.map(|b| b as i64);
+            let original = sizes.original.map(|b| b as i64);
+            let result = match &sizes.row {
+                BackupRow::Backup(backup_id) => tx.execute(
+                    "UPDATE backups SET size_bytes = ?, original_bytes = ? WHERE backup_id = ?",
+                    params![compressed, original, backup_id],
+                ),
+                BackupRow::SaveVersion(id) => tx.execute(
+                    "UPDATE save_versions SET compressed_size = ?, original_size = ? WHERE id = ?",
+                    params![compressed, original, id],
+                ),
+            };
+            result.map_err(|e| format!("Failed to store backup size: {}", e))?;
+        }
+        tx.commit().map_err(|e| format!("Failed to commit backup sizes: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sizes(compressed: Option<u64>, original: Option<u64>) -> BackupSizes {
+        BackupSizes { row: BackupRow::SaveVersion(1), path: "/nonexistent".to_string(), compressed, original }
+    }
+
+    #[test]
+    fn test_summarize_skips_backups_without_sizes() {
+        let report = CompressionReport::summarize(7, vec![
+            sizes(Some(250), Some(1000)),
+            sizes(Some(250), Some(1000)),
+            sizes(Some(100), None),
+        ]);
+        assert_eq!(report.backups_counted, 2);
+        assert_eq!(report.backups_skipped, 1);
+        assert_eq!(report.original_bytes, 2000);
+        assert_eq!(report.compressed_bytes, 500);
+        assert_eq!(report.bytes_saved, 1500);
+        assert!((report.ratio - 0.25).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_summarize_empty_has_zero_ratio() {
+        let report = CompressionReport::summarize(7, Vec::new());
+        assert_eq!(report.backups_counted, 0);
+        assert_eq!(report.ratio, 0.0);
+    }
+
+    #[test]
+    fn test_rows_without_an_archive_are_skipped() {
+        // Recorded sizes don't count when the archive they describe was never written
+        let mut phantom = sizes(None, Some(1000));
+        assert!(!phantom.fill_from_archive());
+        let report = CompressionReport::summarize(7, vec![phantom, sizes(Some(250), Some(1000))]);
+        assert_eq!((report.backups_counted, report.backups_skipped), (1, 1));
+        assert_eq!(report.original_bytes, 1000);
+    }
+}
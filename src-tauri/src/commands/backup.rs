@@ -0,0 +1,436 @@
+use crate::backup::archive::ArchiveFormat;
+use crate::backup::conflict::ensure_game_not_running;
+use crate::backup::{BackupContentEntry, BackupLocation, ColdStorage, ColdStorageResult, CompressionReport, FileRestoreResult, PortableSaves};
+use crate::database::models::{BackupTimelineDay, LocationDrift};
+use crate::game_manager::GameManager;
+use super::error::{CommandError, CommandResult};
+use std::path::PathBuf;
+
+#[tauri::command]
+pub async fn export_save(game_id: i64, dest_path: String) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to ensure database ready for export_save", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let result = match PortableSaves::export_save(&db_conn, game_id, &PathBuf::from(&dest_path)).await {
+        Ok(r) => r,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", &format!("Failed to export saves for game {}", game_id), Some(&e));
+            return Err(e.into());
+        }
+    };
+
+    Ok(serde_json::to_value(result)?)
+}
+
+#[tauri::command]
+pub async fn import_save(game_id: i64, archive_path: String, force: Option<bool>) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to ensure database ready for import_save", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let force = force.unwrap_or(false);
+    if !force {
+        ensure_game_not_running(&db_conn, game_id).await?;
+    }
+
+    let result = match PortableSaves::import_save(&db_conn, game_id, &PathBuf::from(&archive_path), force).await {
+        Ok(r) => r,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", &format!("Failed to import saves for game {}", game_id), Some(&e));
+            return Err(e.into());
+        }
+    };
+
+    Ok(serde_json::to_value(result)?)
+}
+
+#[tauri::command]
+pub async fn get_backup_root() -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to ensure database ready for get_backup_root", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let info = {
+        let db_guard = db_conn.lock().await;
+        let conn = db_guard.get_connection().await;
+        BackupLocation::root_info(&conn)
+    };
+
+    Ok(serde_json::to_value(info)?)
+}
+
+#[tauri::command]
+pub async fn set_backup_root(path: String) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to ensure database ready for set_backup_root", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let info = match BackupLocation::set_root(&db_conn, &PathBuf::from(&path)).await {
+        Ok(info) => info,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", &format!("Failed to set backup root to {}", path), Some(&e));
+            return Err(e.into());
+        }
+    };
+
+    Ok(serde_json::to_value(info)?)
+}
+
+#[tauri::command]
+pub async fn move_backups(new_root: String) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to ensure database ready for move_backups", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let result = match BackupLocation::move_backups(&db_conn, &PathBuf::from(&new_root)).await {
+        Ok(r) => r,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", &format!("Failed to move backups to {}", new_root), Some(&e));
+            return Err(e.into());
+        }
+    };
+
+    Ok(serde_json::to_value(result)?)
+}
+
+#[tauri::command]
+pub async fn get_active_sessions() -> CommandResult<serde_json::Value> {
+    let service = match crate::auto_backup::backup_service().await {
+        Ok(service) => service,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to get backup service for get_active_sessions", Some(&e));
+            return Err(CommandError::Internal(format!("Backup service unavailable: {}", e)));
+        }
+    };
+
+    let sessions = service.get_active_session_summaries().await;
+
+    Ok(serde_json::to_value(sessions)?)
+}
+
+#[tauri::command]
+pub async fn get_monitored_paths(game_id: i64) -> CommandResult<serde_json::Value> {
+    let service = match crate::auto_backup::backup_service().await {
+        Ok(service) => service,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to get backup service for get_monitored_paths", Some(&e));
+            return Err(CommandError::Internal(format!("Backup service unavailable: {}", e)));
+        }
+    };
+
+    let monitored = match service.get_monitored_paths(game_id).await {
+        Ok(m) => m,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", &format!("Failed to get monitored paths for game {}", game_id), Some(&e.to_string()));
+            return Err(e.into());
+        }
+    };
+
+    Ok(serde_json::to_value(monitored)?)
+}
+
+#[tauri::command]
+pub async fn force_backup_now(game_id: i64) -> CommandResult<crate::auto_backup::ForcedBackup> {
+    let service = match crate::auto_backup::backup_service().await {
+        Ok(service) => service,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to get backup service for force_backup_now", Some(&e));
+            return Err(CommandError::Internal(format!("Backup service unavailable: {}", e)));
+        }
+    };
+
+    match service.force_backup_now(game_id).await {
+        Ok(backup) => {
+            crate::logger::info("BACKUP_COMMAND", &format!("Forced backup {} for game {}", backup.backup_id, game_id), None);
+            Ok(backup)
+        }
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", &format!("Failed to force backup for game {}", game_id), Some(&e.to_string()));
+            Err(e.into())
+        }
+    }
+}
+
+/// Back up every game with backups enabled, returning each game's outcome. One game
+/// failing doesn't stop the rest.
+#[tauri::command]
+pub async fn backup_all_enabled() -> CommandResult<Vec<crate::auto_backup::GameBackupOutcome>> {
+    let service = match crate::auto_backup::backup_service().await {
+        Ok(service) => service,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to get backup service for backup_all_enabled", Some(&e));
+            return Err(CommandError::Internal(format!("Backup service unavailable: {}", e)));
+        }
+    };
+
+    match service.backup_all_enabled().await {
+        Ok(outcomes) => {
+            let failed = outcomes.iter().filter(|o| !o.success).count();
+            crate::logger::info("BACKUP_COMMAND", &format!("Backed up {} of {} games", outcomes.len() - failed, outcomes.len()), None);
+            Ok(outcomes)
+        }
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to back up enabled games", Some(&e.to_string()));
+            Err(e.into())
+        }
+    }
+}
+
+/// When a running game's next real-time backup can happen, for the UI's countdown.
+/// `None` when the game has no active session or real-time backups are off for it.
+#[tauri::command]
+pub async fn next_backup_time(game_id: i64) -> CommandResult<Option<chrono::DateTime<chrono::Utc>>> {
+    let service = match crate::auto_backup::backup_service().await {
+        Ok(service) => service,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to get backup service for next_backup_time", Some(&e));
+            return Err(CommandError::Internal(format!("Backup service unavailable: {}", e)));
+        }
+    };
+
+    Ok(service.next_backup_time(game_id).await)
+}
+
+#[tauri::command]
+pub async fn benchmark_compression(game_id: i64, apply: Option<bool>) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to ensure database ready for benchmark_compression", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let benchmark = match crate::backup::benchmark::benchmark_compression(&db_conn, game_id, apply.unwrap_or(false)).await {
+        Ok(b) => b,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", &format!("Compression benchmark failed for game {}", game_id), Some(&e));
+            return Err(e.into());
+        }
+    };
+
+    Ok(serde_json::to_value(benchmark)?)
+}
+
+/// Backups per day between `from` and `to` (`YYYY-MM-DD`, inclusive), defaulting to the last year
+#[tauri::command]
+pub async fn get_backup_timeline(game_id: i64, from: Option<String>, to: Option<String>) -> CommandResult<Vec<BackupTimelineDay>> {
+    let parse_date = |value: &str| {
+        chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map_err(|_| CommandError::InvalidInput(format!("Invalid date '{}', expected YYYY-MM-DD", value)))
+    };
+    let to = match to.as_deref() {
+        Some(value) => parse_date(value)?,
+        None => chrono::Utc::now().date_naive(),
+    };
+    let from = match from.as_deref() {
+        Some(value) => parse_date(value)?,
+        None => to - chrono::Duration::days(365),
+    };
+    if from > to {
+        return Err(CommandError::InvalidInput(format!("Invalid range: {} is after {}", from, to)));
+    }
+
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to ensure database ready for get_backup_timeline", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let db_guard = db_conn.lock().await;
+    let conn = db_guard.get_connection().await;
+    super::game::require_game(&conn, game_id)?;
+    Ok(GameManager::get_backup_timeline(&conn, game_id, &from.to_string(), &to.to_string())?)
+}
+
+/// Choose `zstd`/`tar.zst`, `tar` or `zip` for a game's archives; `None` restores the default
+#[tauri::command]
+pub async fn set_archive_format(game_id: i64, format: Option<String>) -> CommandResult<serde_json::Value> {
+    let format = format.as_deref().map(ArchiveFormat::parse).transpose().map_err(CommandError::InvalidInput)?;
+
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to ensure database ready for set_archive_format", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    if let Err(e) = GameManager::set_archive_format(&db_conn, game_id, format).await {
+        crate::logger::error("BACKUP_COMMAND", &format!("Failed to set archive format for game {}", game_id), Some(&e));
+        return Err(e.into());
+    }
+
+    Ok(serde_json::json!({ "game_id": game_id, "archive_format": format.unwrap_or_default() }))
+}
+
+#[tauri::command]
+pub async fn get_archive_format(game_id: i64) -> CommandResult<ArchiveFormat> {
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
+
+    let db_guard = db_conn.lock().await;
+    let conn = db_guard.get_connection().await;
+    Ok(GameManager::get_archive_format(&conn, game_id)?.unwrap_or_default())
+}
+
+#[tauri::command]
+pub async fn compare_backups(backup_a: String, backup_b: String) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to ensure database ready for compare_backups", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let comparison = match crate::backup::compare::compare_backups(&db_conn, &backup_a, &backup_b).await {
+        Ok(c) => c,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", &format!("Failed to compare backups {} and {}", backup_a, backup_b), Some(&e));
+            return Err(e.into());
+        }
+    };
+
+    Ok(serde_json::to_value(comparison)?)
+}
+
+/// Move a game's backup archives and save branches into `archive_dir` for cold storage
+#[tauri::command]
+pub async fn archive_to(game_id: i64, archive_dir: String) -> CommandResult<ColdStorageResult> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to ensure database ready for archive_to", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    ColdStorage::archive_to(&db_conn, game_id, &PathBuf::from(&archive_dir)).await.map_err(|e| {
+        crate::logger::error("BACKUP_COMMAND", &format!("Failed to move game {} to {}", game_id, archive_dir), Some(&e));
+        e.into()
+    })
+}
+
+/// Bring a game back from cold storage. Without `archive_dir` the recorded location is used.
+#[tauri::command]
+pub async fn unarchive_from(game_id: i64, archive_dir: Option<String>) -> CommandResult<ColdStorageResult> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to ensure database ready for unarchive_from", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let archive_dir = archive_dir.map(PathBuf::from);
+    ColdStorage::unarchive_from(&db_conn, game_id, archive_dir.as_deref()).await.map_err(|e| {
+        crate::logger::error("BACKUP_COMMAND", &format!("Failed to restore game {} from cold storage", game_id), Some(&e));
+        e.into()
+    })
+}
+
+/// Check whether a game's saves moved away from its configured save locations, listing
+/// directories with recent saves the user may want to switch to
+#[tauri::command]
+pub async fn detect_location_drift(game_id: i64) -> CommandResult<Vec<LocationDrift>> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to ensure database ready for detect_location_drift", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let drift = GameManager::detect_location_drift(&db_conn, game_id).await.map_err(|e| {
+        crate::logger::error("BACKUP_COMMAND", &format!("Failed to check save location drift for game {}", game_id), Some(&e));
+        CommandError::from(e)
+    })?;
+
+    if drift.iter().any(|d| d.drifted) {
+        crate::logger::warn("BACKUP_COMMAND", &format!("Save locations of game {} appear to have moved", game_id), None);
+    }
+    Ok(drift)
+}
+
+/// Total original and compressed size of a game's backups, showing what compression saves
+#[tauri::command]
+pub async fn compression_report(game_id: i64) -> CommandResult<CompressionReport> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to ensure database ready for compression_report", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    CompressionReport::for_game(&db_conn, game_id).await.map_err(|e| {
+        crate::logger::error("BACKUP_COMMAND", &format!("Failed to build compression report for game {}", game_id), Some(&e));
+        CommandError::from(e)
+    })
+}
+
+/// List the files stored in a backup without extracting it, for browsing before a restore
+#[tauri::command]
+pub async fn list_backup_contents(backup_id: String) -> CommandResult<Vec<BackupContentEntry>> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to ensure database ready for list_backup_contents", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    crate::backup::contents::list_backup_contents(&db_conn, &backup_id).await.map_err(|e| {
+        crate::logger::error("BACKUP_COMMAND", &format!("Failed to list contents of backup {}", backup_id), Some(&e));
+        CommandError::from(e)
+    })
+}
+
+/// Restore a single file from a backup to its save location, or to `dest` when given.
+/// The file being replaced is kept next to it. Restoring into a running game's save location
+/// is refused unless `force` is true.
+#[tauri::command]
+pub async fn restore_file(backup_id: String, relative_path: String, dest: Option<String>, force: Option<bool>) -> CommandResult<FileRestoreResult> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("BACKUP_COMMAND", "Failed to ensure database ready for restore_file", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let dest = dest.filter(|d| !d.trim().is_empty()).map(PathBuf::from);
+    if dest.is_none() && !force.unwrap_or(false) {
+        if let Some(game_id) = crate::backup::contents::backup_game(&db_conn, &backup_id).await? {
+            ensure_game_not_running(&db_conn, game_id).await?;
+        }
+    }
+
+    crate::backup::contents::restore_file(&db_conn, &backup_id, &relative_path, dest.as_deref()).await.map_err(|e| {
+        crate::logger::error("BACKUP_COMMAND", &format!("Failed to restore {} from backup {}", relative_path, backup_id), Some(&e));
+        CommandError::from(e)
+    })
+}
@@ -0,0 +1,123 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+/// Error returned by Tauri commands.
+///
+/// Serialized as `{ "code": "NOT_FOUND", "message": "..." }` so the frontend can branch on
+/// the category and still show the message.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CommandError {
+    #[error("{0}")]
+    NotFound(String),
+    #[error("Database unavailable: {0}")]
+    DatabaseUnavailable(String),
+    #[error("{0}")]
+    InvalidInput(String),
+    #[error("{0}")]
+    Internal(String),
+    /// The user stopped a long-running operation
+    #[error("{0}")]
+    Cancelled(String),
+    /// A restore was refused because the game is running
+    #[error("{0}")]
+    GameRunning(String),
+}
+
+pub type CommandResult<T> = Result<T, CommandError>;
+
+impl CommandError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            CommandError::NotFound(_) => "NOT_FOUND",
+            CommandError::DatabaseUnavailable(_) => "DATABASE_UNAVAILABLE",
+            CommandError::InvalidInput(_) => "INVALID_INPUT",
+            CommandError::Internal(_) => "INTERNAL",
+            CommandError::Cancelled(_) => "CANCELLED",
+            CommandError::GameRunning(_) => "GAME_RUNNING",
+        }
+    }
+}
+
+impl Serialize for CommandError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("CommandError", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+/// Errors the lower layers return as plain strings. Commands that know better, e.g. that a
+/// lookup found nothing, build the specific variant themselves.
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        CommandError::Internal(message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+impl From<rusqlite::Error> for CommandError {
+    fn from(e: rusqlite::Error) -> Self {
+        match e {
+            rusqlite::Error::QueryReturnedNoRows => CommandError::NotFound("No matching record".to_string()),
+            e => CommandError::Internal(format!("Database error: {}", e)),
+        }
+    }
+}
+
+impl From<serde_json::Error> for CommandError {
+    fn from(e: serde_json::Error) -> Self {
+        CommandError::Internal(format!("Serialization error: {}", e))
+    }
+}
+
+impl From<crate::auto_backup::BackupError> for CommandError {
+    fn from(e: crate::auto_backup::BackupError) -> Self {
+        match e {
+            crate::auto_backup::BackupError::Database(e) => e.into(),
+            crate::auto_backup::BackupError::Detection(e) => e.into(),
+            e => CommandError::Internal(e.to_string()),
+        }
+    }
+}
+
+impl From<crate::detection::DetectionError> for CommandError {
+    fn from(e: crate::detection::DetectionError) -> Self {
+        match e {
+            crate::detection::DetectionError::DatabaseError(e) => e.into(),
+            e => CommandError::Internal(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_errors_are_internal() {
+        assert_eq!(CommandError::from("Game with id 4 not found".to_string()).code(), "INTERNAL");
+        assert_eq!(CommandError::from("Failed to checkout branch 'g1-a': Git operation cancelled".to_string()).code(), "INTERNAL");
+    }
+
+    #[test]
+    fn test_missing_row_is_not_found() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        let missing = conn.query_row("SELECT 1 WHERE 0", [], |row| row.get::<_, i64>(0)).unwrap_err();
+        assert_eq!(CommandError::from(missing).code(), "NOT_FOUND");
+        let detection = crate::detection::DetectionError::DatabaseError(rusqlite::Error::QueryReturnedNoRows);
+        assert_eq!(CommandError::from(crate::auto_backup::BackupError::Detection(detection)).code(), "NOT_FOUND");
+    }
+
+    #[test]
+    fn test_serializes_code_and_message() {
+        let json = serde_json::to_value(CommandError::DatabaseUnavailable("locked".to_string())).unwrap();
+        assert_eq!(json["code"], "DATABASE_UNAVAILABLE");
+        assert_eq!(json["message"], "Database unavailable: locked");
+    }
+}
@@ -1,36 +1,65 @@
-use crate::database::models::AddGameRequest;
+use crate::database::models::{AddGameRequest, Game, GameMonitorSettings, GameRetentionSettings, MetadataEnrichment, PcgwLocationRefresh, SaveLocationAudit, SuggestedLocation};
 use crate::game_manager::GameManager;
+use crate::game_manager::naming::NamingTemplate;
+use crate::game_manager::validation::{FieldError, GameRequestValidation};
+use super::error::{CommandError, CommandResult};
 use crate::pcgaming_wiki::PcgwClient;
+use std::collections::HashMap;
 use std::sync::Arc;
 
+/// The game with this id, or `NotFound`
+pub(super) fn require_game(conn: &rusqlite::Connection, game_id: i64) -> CommandResult<Game> {
+    GameManager::find_game_by_id(conn, game_id)?
+        .ok_or_else(|| CommandError::NotFound(format!("Game {} not found", game_id)))
+}
+
+/// Normalize and check a game request, failing with every problem found as `InvalidInput`
+fn require_valid_request(request: &mut AddGameRequest) -> CommandResult<()> {
+    GameManager::validate_game_request(request)
+        .map_err(|errors| CommandError::InvalidInput(GameRequestValidation::describe(&errors)))
+}
+
+/// Add a game. A game with the same platform and app id is refused with "game already
+/// exists (id N)", or with `return_existing` returned instead of adding a duplicate.
 #[tauri::command]
-pub async fn add_manual_game(request: AddGameRequest) -> Result<serde_json::Value, String> {
+pub async fn add_manual_game(mut request: AddGameRequest, return_existing: Option<bool>) -> CommandResult<serde_json::Value> {
+    require_valid_request(&mut request)?;
+
     // Ensure database is ready using flag file approach
-    let db_conn = crate::database::connection::ensure_database_ready().await?;
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
 
     // Add the game
-    let result = GameManager::add_manual_game(&db_conn, request).await?;
+    let result = GameManager::add_manual_game(&db_conn, request, return_existing.unwrap_or(false)).await?;
 
-    Ok(serde_json::to_value(result).map_err(|e| format!("Serialization error: {}", e))?)
+    Ok(serde_json::to_value(result)?)
 }
 
+/// Check a game request before submitting it, returning every field problem found.
+/// An empty list means `add_manual_game` and `update_game_sync` will accept it.
 #[tauri::command]
-pub async fn add_manual_game_sync(request: AddGameRequest) -> Result<serde_json::Value, String> {
+pub async fn validate_game_request(mut request: AddGameRequest) -> CommandResult<Vec<FieldError>> {
+    Ok(GameManager::validate_game_request(&mut request).err().unwrap_or_default())
+}
+
+#[tauri::command]
+pub async fn add_manual_game_sync(mut request: AddGameRequest, return_existing: Option<bool>) -> CommandResult<serde_json::Value> {
+    require_valid_request(&mut request)?;
+
     // Ensure database is ready using flag file approach
     let db_conn = match crate::database::connection::ensure_database_ready().await {
         Ok(conn) => conn,
         Err(e) => {
             crate::logger::error("GAME_COMMAND", "Failed to ensure database ready", Some(&e));
-            return Err(format!("Database unavailable: {}", e));
+            return Err(CommandError::DatabaseUnavailable(e));
         }
     };
 
     // Add the game
-    let result = match GameManager::add_manual_game(&db_conn, request).await {
+    let result = match GameManager::add_manual_game(&db_conn, request, return_existing.unwrap_or(false)).await {
         Ok(r) => r,
         Err(e) => {
             crate::logger::error("GAME_COMMAND", "Failed to add manual game", Some(&e));
-            return Err(e);
+            return Err(e.into());
         }
     };
 
@@ -38,28 +67,28 @@ pub async fn add_manual_game_sync(request: AddGameRequest) -> Result<serde_json:
         Ok(v) => Ok(v),
         Err(e) => {
             crate::logger::error("GAME_COMMAND", "Failed to serialize game result", Some(&e.to_string()));
-            Err(format!("Serialization error: {}", e))
+            Err(e.into())
         }
     }
 }
 
 #[tauri::command]
-pub async fn get_all_games() -> Result<serde_json::Value, String> {
+pub async fn get_all_games() -> CommandResult<serde_json::Value> {
     // Ensure database is ready using flag file approach
     let db_conn = match crate::database::connection::ensure_database_ready().await {
         Ok(conn) => conn,
         Err(e) => {
             crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for get_all_games", Some(&e));
-            return Err(format!("Database unavailable: {}", e));
+            return Err(CommandError::DatabaseUnavailable(e));
         }
     };
 
-    // Get all games
-    let games = match GameManager::get_all_games(&db_conn).await {
+    // Get all games, with their favorite flag
+    let games = match GameManager::get_game_list(&db_conn).await {
         Ok(g) => g,
         Err(e) => {
             crate::logger::error("GAME_COMMAND", "Failed to get all games", Some(&e));
-            return Err(e);
+            return Err(e.into());
         }
     };
 
@@ -67,19 +96,82 @@ pub async fn get_all_games() -> Result<serde_json::Value, String> {
         Ok(v) => Ok(v),
         Err(e) => {
             crate::logger::error("GAME_COMMAND", "Failed to serialize games list", Some(&e.to_string()));
-            Err(format!("Serialization error: {}", e))
+            Err(e.into())
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn set_favorite(game_id: i64, favorite: bool) -> CommandResult<()> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for set_favorite", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    match GameManager::set_favorite(&db_conn, game_id, favorite).await {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", &format!("Failed to set favorite for game {}", game_id), Some(&e));
+            Err(e.into())
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn games_without_save_locations() -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for games_without_save_locations", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let games = match GameManager::games_without_save_locations(&db_conn).await {
+        Ok(g) => g,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to list games without save locations", Some(&e));
+            return Err(e.into());
+        }
+    };
+
+    Ok(serde_json::to_value(games)?)
+}
+
+/// How every active game's save locations resolve here: `Resolved`, `UnresolvedPlaceholder`,
+/// `PathMissing`, or `NoLocations` for games that will never be backed up
+#[tauri::command]
+pub async fn audit_save_locations() -> CommandResult<Vec<SaveLocationAudit>> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for audit_save_locations", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    match GameManager::audit_save_locations(&db_conn).await {
+        Ok(rows) => Ok(rows),
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to audit save locations", Some(&e));
+            Err(e.into())
         }
     }
 }
 
 #[tauri::command]
-pub async fn update_game_sync(game_id: i64, request: AddGameRequest) -> Result<serde_json::Value, String> {
+pub async fn update_game_sync(game_id: i64, mut request: AddGameRequest) -> CommandResult<serde_json::Value> {
+    require_valid_request(&mut request)?;
+
     // Ensure database is ready using flag file approach
     let db_conn = match crate::database::connection::ensure_database_ready().await {
         Ok(conn) => conn,
         Err(e) => {
             crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for update_game", Some(&e));
-            return Err(format!("Database unavailable: {}", e));
+            return Err(CommandError::DatabaseUnavailable(e));
         }
     };
 
@@ -88,44 +180,431 @@ pub async fn update_game_sync(game_id: i64, request: AddGameRequest) -> Result<s
         Ok(r) => r,
         Err(e) => {
             crate::logger::error("GAME_COMMAND", &format!("Failed to update game {}", game_id), Some(&e));
-            return Err(e);
+            return Err(e.into());
         }
     };
 
+    // Edited paths and identifiers must not be shadowed by cached detection results
+    crate::auto_backup::invalidate_detection_cache(Some(game_id)).await;
+
     match serde_json::to_value(result) {
         Ok(v) => Ok(v),
         Err(e) => {
             crate::logger::error("GAME_COMMAND", "Failed to serialize updated game", Some(&e.to_string()));
-            Err(format!("Serialization error: {}", e))
+            Err(e.into())
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_game_detail(game_id: i64) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for get_game_detail", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let detail = match GameManager::get_game_detail(&db_conn, game_id).await {
+        Ok(d) => d,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", &format!("Failed to get detail for game {}", game_id), Some(&e));
+            return Err(e.into());
+        }
+    };
+
+    match serde_json::to_value(detail) {
+        Ok(v) => Ok(v),
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to serialize game detail", Some(&e.to_string()));
+            Err(e.into())
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_save_locations_detailed(game_id: i64) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for get_save_locations_detailed", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let locations = match GameManager::get_save_locations_detailed(&db_conn, game_id).await {
+        Ok(l) => l,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", &format!("Failed to get save locations for game {}", game_id), Some(&e));
+            return Err(e.into());
+        }
+    };
+
+    for sync in locations.iter().filter_map(|l| l.cloud_sync.as_ref()) {
+        crate::logger::warn("GAME_COMMAND", &format!("Save location of game {} is inside a {} folder", game_id, sync.provider), Some(&sync.sync_root));
+    }
+
+    match serde_json::to_value(locations) {
+        Ok(v) => Ok(v),
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to serialize save locations", Some(&e.to_string()));
+            Err(e.into())
+        }
+    }
+}
+
+/// Change which of a game's save locations wins when several are configured. Returns the
+/// game's save locations, highest priority first.
+#[tauri::command]
+pub async fn set_save_location_priority(location_id: i64, priority: i32) -> CommandResult<Vec<crate::database::models::SaveLocation>> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for set_save_location_priority", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    GameManager::validate_priority(priority).map_err(CommandError::InvalidInput)?;
+    match GameManager::set_save_location_priority(&db_conn, location_id, priority).await {
+        Ok(locations) => Ok(locations),
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", &format!("Failed to set priority of save location {}", location_id), Some(&e));
+            Err(e.into())
+        }
+    }
+}
+
+/// Turn real-time backups on or off for one game. Passing `None` clears the
+/// override so the game follows the global setting again.
+#[tauri::command]
+pub async fn set_real_time_backup(game_id: i64, enabled: Option<bool>) -> CommandResult<()> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for set_real_time_backup", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    match GameManager::set_real_time_backup_override(&db_conn, game_id, enabled).await {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", &format!("Failed to update real-time backup setting for game {}", game_id), Some(&e));
+            Err(e.into())
+        }
+    }
+}
+
+/// Stop (or resume) automatically protecting a game. Existing backups are kept; disabling
+/// also ends a running session and stops monitoring its save paths.
+#[tauri::command]
+pub async fn set_backup_enabled(game_id: i64, enabled: bool) -> CommandResult<()> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for set_backup_enabled", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    if let Err(e) = GameManager::set_backup_enabled(&db_conn, game_id, enabled).await {
+        crate::logger::error("GAME_COMMAND", &format!("Failed to update backup enabled setting for game {}", game_id), Some(&e));
+        return Err(e.into());
+    }
+
+    if !enabled {
+        if let Some(service) = crate::auto_backup::backup_service_if_started() {
+            if let Err(e) = service.detach_game(game_id).await {
+                crate::logger::warn("GAME_COMMAND", &format!("Failed to end the active session for game {}", game_id), Some(&e.to_string()));
+            }
+        }
+    }
+
+    crate::logger::info("GAME_COMMAND", &format!("Automatic backups {} for game {}", if enabled { "enabled" } else { "disabled" }, game_id), None);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_backup_timing(game_id: i64, debounce_seconds: Option<u64>, coalesce_window_ms: Option<u64>) -> CommandResult<()> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for set_backup_timing", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let settings = GameMonitorSettings { debounce_seconds, coalesce_window_ms };
+    match GameManager::set_monitor_settings(&db_conn, game_id, settings).await {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", &format!("Failed to set backup timing for game {}", game_id), Some(&e));
+            Err(e.into())
+        }
+    }
+}
+
+/// Override how many backups of a game are kept, how many bytes they may use and how old a
+/// backup must be before it can be pruned. `None` fields follow the global retention config.
+#[tauri::command]
+pub async fn set_game_retention(game_id: i64, config: GameRetentionSettings) -> CommandResult<()> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for set_game_retention", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    GameManager::validate_retention_settings(&config).map_err(CommandError::InvalidInput)?;
+    match GameManager::set_retention_settings(&db_conn, game_id, config).await {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", &format!("Failed to set retention for game {}", game_id), Some(&e));
+            Err(e.into())
+        }
+    }
+}
+
+/// Set the template used to name the game's checkpoint branches and backups, using
+/// `{game}`, `{save}`, `{date}` and `{counter}`. `None` restores the default names.
+#[tauri::command]
+pub async fn set_naming_template(game_id: i64, template: Option<String>) -> CommandResult<()> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for set_naming_template", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let template = template.filter(|t| !t.trim().is_empty());
+    if let Some(template) = &template {
+        NamingTemplate::validate(template).map_err(CommandError::InvalidInput)?;
+    }
+    match GameManager::set_naming_template(&db_conn, game_id, template.as_deref()).await {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", &format!("Failed to set naming template for game {}", game_id), Some(&e));
+            Err(e.into())
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_naming_template(game_id: i64) -> CommandResult<Option<String>> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for get_naming_template", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let db_guard = db_conn.lock().await;
+    let conn = db_guard.get_connection().await;
+    Ok(GameManager::get_naming_template(&conn, game_id)?)
+}
+
+#[tauri::command]
+pub async fn set_platform_executables(game_id: i64, map: HashMap<String, String>) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for set_platform_executables", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let game = match GameManager::set_platform_executables(&db_conn, game_id, &map).await {
+        Ok(g) => g,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", &format!("Failed to set platform executables for game {}", game_id), Some(&e));
+            return Err(e.into());
+        }
+    };
+
+    crate::auto_backup::invalidate_detection_cache(Some(game_id)).await;
+
+    match serde_json::to_value(game) {
+        Ok(v) => Ok(v),
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to serialize updated game", Some(&e.to_string()));
+            Err(e.into())
         }
     }
 }
 
 #[tauri::command]
-pub async fn delete_game_sync(game_id: i64) -> Result<(), String> {
+pub async fn rescan_saves(game_id: i64) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for rescan_saves", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let summary = match GameManager::rescan_saves(&db_conn, game_id).await {
+        Ok(s) => s,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", &format!("Failed to rescan saves for game {}", game_id), Some(&e));
+            return Err(e.into());
+        }
+    };
+
+    crate::logger::info("GAME_COMMAND", &format!(
+        "Rescanned {} files ({} bytes) for game {} in {} ms",
+        summary.files_scanned, summary.total_bytes, game_id, summary.elapsed_ms
+    ), None);
+
+    Ok(serde_json::to_value(summary)?)
+}
+
+#[tauri::command]
+pub async fn refresh_icon(game_id: i64) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for refresh_icon", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let result = match GameManager::refresh_icon(&db_conn, game_id).await {
+        Ok(r) => r,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", &format!("Failed to refresh icon for game {}", game_id), Some(&e));
+            return Err(e.into());
+        }
+    };
+
+    Ok(serde_json::to_value(result)?)
+}
+
+#[tauri::command]
+pub async fn delete_game_sync(game_id: i64) -> CommandResult<()> {
     // Ensure database is ready using flag file approach
     let db_conn = match crate::database::connection::ensure_database_ready().await {
         Ok(conn) => conn,
         Err(e) => {
             crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for delete_game", Some(&e));
-            return Err(format!("Database unavailable: {}", e));
+            return Err(CommandError::DatabaseUnavailable(e));
         }
     };
 
     // Delete the game
     match GameManager::delete_game(&db_conn, game_id).await {
-        Ok(()) => Ok(()),
+        Ok(()) => {
+            crate::auto_backup::invalidate_detection_cache(Some(game_id)).await;
+            Ok(())
+        }
         Err(e) => {
             crate::logger::error("GAME_COMMAND", &format!("Failed to delete game {}", game_id), Some(&e));
-            Err(e)
+            Err(e.into())
         }
     }
 }
 
+/// Hide a game without deleting it. Its backups and history stay, but it leaves the
+/// library and is no longer monitored or backed up.
 #[tauri::command]
-pub async fn search_pcgw_games(query: String) -> Result<serde_json::Value, String> {
+pub async fn archive_game(game_id: i64) -> CommandResult<()> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for archive_game", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    if let Err(e) = GameManager::archive_game(&db_conn, game_id).await {
+        crate::logger::error("GAME_COMMAND", &format!("Failed to archive game {}", game_id), Some(&e));
+        return Err(e.into());
+    }
+
+    if let Some(service) = crate::auto_backup::backup_service_if_started() {
+        if let Err(e) = service.detach_game(game_id).await {
+            crate::logger::warn("GAME_COMMAND", &format!("Failed to end the active session for game {}", game_id), Some(&e.to_string()));
+        }
+    }
+    crate::auto_backup::invalidate_detection_cache(Some(game_id)).await;
+
+    crate::logger::info("GAME_COMMAND", &format!("Archived game {}", game_id), None);
+    Ok(())
+}
+
+/// Bring an archived game back into the library
+#[tauri::command]
+pub async fn unarchive_game(game_id: i64) -> CommandResult<()> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for unarchive_game", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    if let Err(e) = GameManager::unarchive_game(&db_conn, game_id).await {
+        crate::logger::error("GAME_COMMAND", &format!("Failed to unarchive game {}", game_id), Some(&e));
+        return Err(e.into());
+    }
+    crate::auto_backup::invalidate_detection_cache(Some(game_id)).await;
+
+    crate::logger::info("GAME_COMMAND", &format!("Unarchived game {}", game_id), None);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_archived_games() -> CommandResult<Vec<crate::database::models::Game>> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for get_archived_games", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let conn_guard = db_conn.lock().await;
+    let conn = conn_guard.get_connection().await;
+    GameManager::get_archived_games(&conn).map_err(|e| {
+        crate::logger::error("GAME_COMMAND", "Failed to list archived games", Some(&e));
+        e.into()
+    })
+}
+
+/// Forget everything detection has learned about a game so it can re-learn from scratch
+#[tauri::command]
+pub async fn reset_identifiers(game_id: i64) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for reset_identifiers", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let removed = {
+        let db_guard = db_conn.lock().await;
+        let conn = db_guard.get_connection().await;
+        GameManager::delete_game_identifiers(&conn, game_id).map_err(|e| {
+            crate::logger::error("GAME_COMMAND", &format!("Failed to reset identifiers for game {}", game_id), Some(&e));
+            e
+        })?
+    };
+
+    crate::auto_backup::invalidate_detection_cache(Some(game_id)).await;
+    crate::logger::info("GAME_COMMAND", &format!("Removed {} identifiers for game {}", removed, game_id), None);
+
+    Ok(serde_json::json!({ "game_id": game_id, "removed": removed }))
+}
+
+#[tauri::command]
+pub async fn search_pcgw_games(query: String) -> CommandResult<serde_json::Value> {
     // Ensure database is ready using flag file approach
-    let db_conn = crate::database::connection::ensure_database_ready().await?;
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
     
     let client = PcgwClient::new();
     let cache_key = format!("search:{}", query);
@@ -154,7 +633,7 @@ pub async fn search_pcgw_games(query: String) -> Result<serde_json::Value, Strin
     
     // 4. Parse and return
     let response: crate::pcgaming_wiki::models::CargoQueryResponse<crate::pcgaming_wiki::models::PcgwGameInfo> = serde_json::from_str(&response_text).map_err(|e| e.to_string())?;
-    let results: Vec<crate::pcgaming_wiki::models::GameSearchResult> = response.cargoquery.into_iter().map(|item| {
+    let mut results: Vec<crate::pcgaming_wiki::models::GameSearchResult> = response.cargoquery.into_iter().map(|item| {
             let info = item.title;
 
             // Parse Steam AppID - take the first one (main game, not DLC)
@@ -177,6 +656,11 @@ pub async fn search_pcgw_games(query: String) -> Result<serde_json::Value, Strin
                 cover_image_url: None, // Will be populated separately via wikitext
             }
     }).collect();
+
+    // The names above are only the query; prefer the Steam store name where there is an app id
+    let steam_client = crate::detection::platform::PlatformApiClient::new()
+        .with_database(db_conn.lock().await.shared_connection());
+    GameManager::resolve_search_result_names(&mut results, &query, &steam_client).await;
     
     println!("[DEBUG] PGWK Search Results: {:?}", results);
     
@@ -184,10 +668,19 @@ pub async fn search_pcgw_games(query: String) -> Result<serde_json::Value, Strin
 }
 
 #[tauri::command]
-pub async fn get_pcgw_save_locations(game_name: String) -> Result<serde_json::Value, String> {
+pub async fn get_pcgw_save_locations(game_name: String) -> CommandResult<serde_json::Value> {
     // Ensure database is ready using flag file approach
-    let db_conn = crate::database::connection::ensure_database_ready().await?;
-    
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
+
+    let result = fetch_pcgw_save_locations(&db_conn, &game_name).await?;
+    Ok(serde_json::to_value(result).map_err(|e| e.to_string())?)
+}
+
+/// PCGW save locations for a game, from the cache when it has them
+async fn fetch_pcgw_save_locations(
+    db_conn: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+    game_name: &str,
+) -> Result<crate::pcgaming_wiki::models::SaveLocationResult, String> {
     let client = PcgwClient::new();
     let cache_key = format!("save_loc:{}", game_name);
     
@@ -196,13 +689,12 @@ pub async fn get_pcgw_save_locations(game_name: String) -> Result<serde_json::Va
         let conn_guard = db_conn.lock().await;
         let conn = conn_guard.get_connection().await;
         if let Ok(Some(cached_json)) = crate::pcgaming_wiki::cache::PcgwCache::get(&conn, &cache_key) {
-             let result = client.parse_save_locations_json(&cached_json).map_err(|e| e.to_string())?;
-             return Ok(serde_json::to_value(result).map_err(|e| e.to_string())?);
+             return client.parse_save_locations_json(&cached_json).map_err(|e| e.to_string());
         }
     }
     
     // 2. Fetch from API
-    let response_text = client.fetch_save_locations_raw(&game_name).await.map_err(|e| e.to_string())?;
+    let response_text = client.fetch_save_locations_raw(game_name).await.map_err(|e| e.to_string())?;
     
     // 3. Cache response
     {
@@ -212,13 +704,156 @@ pub async fn get_pcgw_save_locations(game_name: String) -> Result<serde_json::Va
     }
     
     // 4. Parse and return
-    let result = client.parse_save_locations_json(&response_text).map_err(|e| e.to_string())?;
-    
-    Ok(serde_json::to_value(result).map_err(|e| e.to_string())?)
+    client.parse_save_locations_json(&response_text).map_err(|e| e.to_string())
+}
+
+/// Re-fetch a game's save locations from its PCGW page (through the cache) and compare
+/// them with the configured ones. Games without a stored mapping use their name as the
+/// page name, which is then stored.
+#[tauri::command]
+pub async fn refresh_pcgw_locations(game_id: i64) -> CommandResult<PcgwLocationRefresh> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for refresh_pcgw_locations", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let page_name = {
+        let conn_guard = db_conn.lock().await;
+        let conn = conn_guard.get_connection().await;
+        let game = require_game(&conn, game_id)?;
+        GameManager::get_pcgw_page_name(&conn, game_id)?.unwrap_or(game.name)
+    };
+
+    let result = match fetch_pcgw_save_locations(&db_conn, &page_name).await {
+        Ok(result) => result,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", &format!("Failed to fetch PCGW save locations for '{}'", page_name), Some(&e));
+            return Err(CommandError::Internal(format!("Failed to fetch PCGW save locations: {}", e)));
+        }
+    };
+    let pcgw_locations = GameManager::convert_pcgw_locations(&result);
+
+    let conn_guard = db_conn.lock().await;
+    let conn = conn_guard.get_connection().await;
+    let configured = GameManager::get_save_locations(&conn, game_id)?;
+    let locations = GameManager::diff_pcgw_locations(&pcgw_locations, &configured);
+    GameManager::set_pcgw_mapping(&conn, game_id, &page_name)?;
+
+    Ok(PcgwLocationRefresh {
+        game_id,
+        changed: locations.iter().any(|l| l.status != "unchanged"),
+        pcgw_page_name: page_name,
+        locations,
+    })
+}
+
+/// Fill in a game's developer, publisher, genre, release date and cover image from the
+/// infobox of its PCGW page (through the cache). Values already set are kept unless
+/// `overwrite` is true. Games without a stored mapping use their name as the page name,
+/// which is then stored.
+#[tauri::command]
+pub async fn enrich_metadata(game_id: i64, overwrite: Option<bool>) -> CommandResult<MetadataEnrichment> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", "Failed to ensure database ready for enrich_metadata", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let page_name = {
+        let conn_guard = db_conn.lock().await;
+        let conn = conn_guard.get_connection().await;
+        let game = require_game(&conn, game_id)?;
+        GameManager::get_pcgw_page_name(&conn, game_id)?.unwrap_or(game.name)
+    };
+
+    let info = match fetch_pcgw_metadata(&db_conn, &page_name).await {
+        Ok(Some(info)) => info,
+        Ok(None) => return Err(CommandError::NotFound(format!("No PCGW infobox for '{}'", page_name))),
+        Err(e) => {
+            crate::logger::error("GAME_COMMAND", &format!("Failed to fetch PCGW metadata for '{}'", page_name), Some(&e));
+            return Err(CommandError::Internal(format!("Failed to fetch PCGW metadata: {}", e)));
+        }
+    };
+    let metadata = GameManager::metadata_from_pcgw(&info);
+
+    let conn_guard = db_conn.lock().await;
+    let conn = conn_guard.get_connection().await;
+    let updated_fields = GameManager::apply_game_metadata(&conn, game_id, &metadata, overwrite.unwrap_or(false))?;
+    GameManager::set_pcgw_mapping(&conn, game_id, &page_name)?;
+
+    Ok(MetadataEnrichment {
+        game_id,
+        pcgw_page_name: page_name,
+        updated_fields,
+        game: require_game(&conn, game_id)?,
+    })
+}
+
+async fn fetch_pcgw_metadata(
+    db_conn: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+    page_name: &str,
+) -> Result<Option<crate::pcgaming_wiki::models::PcgwGameMetadata>, String> {
+    let client = PcgwClient::new();
+    let cache_key = format!("metadata:{}", page_name);
+
+    {
+        let conn_guard = db_conn.lock().await;
+        let conn = conn_guard.get_connection().await;
+        if let Ok(Some(cached_json)) = crate::pcgaming_wiki::cache::PcgwCache::get(&conn, &cache_key) {
+            return client.parse_metadata_json(&cached_json).map_err(|e| e.to_string());
+        }
+    }
+
+    let response_text = client.fetch_metadata_raw(page_name).await.map_err(|e| e.to_string())?;
+
+    {
+        let conn_guard = db_conn.lock().await;
+        let conn = conn_guard.get_connection().await;
+        let _ = crate::pcgaming_wiki::cache::PcgwCache::set(&conn, &cache_key, &response_text, 7);
+    }
+
+    client.parse_metadata_json(&response_text).map_err(|e| e.to_string())
+}
+
+/// Every `{{placeholder}}` path templates can use on this machine, with its current value
+/// and source, for building templates from a pick-list
+#[tauri::command]
+pub async fn list_available_placeholders() -> CommandResult<Vec<crate::manifest::resolver::PlaceholderInfo>> {
+    Ok(GameManager::available_placeholders())
+}
+
+/// Candidate save locations for a game being set up: PCGW's documented ones merged with
+/// common save directories, each marked with whether it exists here, most likely first
+#[tauri::command]
+pub async fn suggest_save_locations(game_name: String) -> CommandResult<Vec<SuggestedLocation>> {
+    if game_name.trim().is_empty() {
+        return Err(CommandError::InvalidInput("Game name must not be empty".to_string()));
+    }
+
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
+
+    // Without PCGW (offline, unknown game) the local guesses are still useful
+    let pcgw = match fetch_pcgw_save_locations(&db_conn, &game_name).await {
+        Ok(result) => Some(result),
+        Err(e) => {
+            crate::logger::warn("GAME_COMMAND", &format!("No PCGW save locations for '{}'", game_name), Some(&e));
+            None
+        }
+    };
+
+    let suggestions = tokio::task::spawn_blocking(move || GameManager::suggest_save_locations(&game_name, pcgw.as_ref()))
+        .await
+        .map_err(|e| CommandError::Internal(format!("Save location suggestion task failed: {}", e)))?;
+    Ok(suggestions)
 }
 
 #[tauri::command]
-pub async fn detect_game_executable(folder_path: String, game_name: String) -> Result<String, String> {
+pub async fn detect_game_executable(folder_path: String, game_name: String) -> CommandResult<String> {
     // First try to find stored executable data
     let db_path = crate::database::connection::DatabasePaths::database_file();
     if let Ok(db) = crate::database::connection::Database::new(&db_path).await {
@@ -275,7 +910,7 @@ pub async fn detect_game_executable(folder_path: String, game_name: String) -> R
     }
 
     // Fallback: directory scanning with OS-aware logic
-    detect_executable_in_directory(folder_path, game_name) // Pass arguments directly
+    detect_executable_in_directory(folder_path, game_name).map_err(CommandError::from) // Pass arguments directly
 }
 
 // Helper function, not a command itself, but used by detect_game_executable
@@ -1,80 +1,192 @@
+use crate::backup::conflict::{check_game_conflict, ensure_game_not_running};
+use crate::backup::RestoreOutcome;
 use crate::git_manager::GitSaveManager;
+use crate::git_manager::cloud::CloudCredentials;
+use crate::git_manager::operation::GitOperation;
+use crate::git_manager::repair::RepoHealthReport;
+use crate::git_manager::repository::CommitAuthor;
+use crate::git_manager::types::{CloudProvider, CredentialCheck, SaveSlot};
+use super::error::{CommandError, CommandResult};
+
+/// Page size for `get_git_history_range` when the caller doesn't pass one
+const DEFAULT_HISTORY_LIMIT: usize = 50;
+
+fn parse_timestamp(value: &str) -> CommandResult<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+        .map_err(|e| CommandError::InvalidInput(format!("Invalid timestamp format: {}", e)))
+}
+
+/// `Cancelled` when the operation was stopped through `cancel_git_operation`, otherwise `Internal`
+fn operation_error(operation: &GitOperation, context: &str, e: String) -> CommandError {
+    let message = format!("{}: {}", context, e);
+    if operation.is_cancelled() {
+        CommandError::Cancelled(message)
+    } else {
+        CommandError::Internal(message)
+    }
+}
 
 #[tauri::command]
-pub async fn enable_git_for_game(_game_id: i64) -> Result<String, String> {
+pub async fn enable_git_for_game(_game_id: i64) -> CommandResult<String> {
     // Ensure database is ready using flag file approach
-    let db_conn = crate::database::connection::ensure_database_ready().await?;
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
 
     // Initialize Git repository
     let git_manager = GitSaveManager::new(db_conn.clone());
-    git_manager.initialize_master_repo().await
-        .map_err(|e| format!("Failed to initialize Git repository: {}", e))
+    Ok(git_manager.initialize_master_repo().await
+        .map_err(|e| format!("Failed to initialize Git repository: {}", e))?)
 }
 
 #[tauri::command]
-pub async fn create_save_checkpoint(game_id: i64, message: String) -> Result<String, String> {
+pub async fn create_save_checkpoint(game_id: i64, message: String) -> CommandResult<String> {
     // Ensure database is ready using flag file approach
-    let db_conn = crate::database::connection::ensure_database_ready().await?;
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
 
     // Create save checkpoint
     let git_manager = GitSaveManager::new(db_conn.clone());
-    git_manager.create_save_checkpoint(game_id, &message).await
-        .map_err(|e| format!("Failed to create save checkpoint: {}", e))
+    let operation = GitOperation::start(game_id);
+    git_manager.create_save_checkpoint(&operation, &message).await
+        .map_err(|e| operation_error(&operation, "Failed to create save checkpoint", e))
 }
 
 #[tauri::command]
-pub async fn create_save_branch(game_id: i64, branch_name: String, description: Option<String>) -> Result<(), String> {
+pub async fn create_save_branch(game_id: i64, branch_name: String, description: Option<String>) -> CommandResult<()> {
     // Ensure database is ready using flag file approach
-    let db_conn = crate::database::connection::ensure_database_ready().await?;
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
 
     // Create save branch
     let git_manager = GitSaveManager::new(db_conn.clone());
-    git_manager.create_save_branch(game_id, &branch_name, description.as_deref()).await
-        .map_err(|e| format!("Failed to create save branch: {}", e))
+    let operation = GitOperation::start(game_id);
+    git_manager.create_save_branch(&operation, &branch_name, description.as_deref()).await
+        .map_err(|e| operation_error(&operation, "Failed to create save branch", e))
 }
 
 #[tauri::command]
-pub async fn switch_save_branch(game_id: i64, branch_name: String) -> Result<(), String> {
+pub async fn switch_save_branch(game_id: i64, branch_name: String) -> CommandResult<()> {
     // Ensure database is ready using flag file approach
-    let db_conn = crate::database::connection::ensure_database_ready().await?;
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
 
     // Switch save branch
     let git_manager = GitSaveManager::new(db_conn.clone());
-    git_manager.switch_save_branch(game_id, &branch_name).await
-        .map_err(|e| format!("Failed to switch save branch: {}", e))
+    let operation = GitOperation::start(game_id);
+    git_manager.switch_save_branch(&operation, &branch_name).await
+        .map_err(|e| operation_error(&operation, "Failed to switch save branch", e))
 }
 
 #[tauri::command]
-pub async fn restore_to_commit(game_id: i64, commit_hash: String) -> Result<(), String> {
+pub async fn restore_to_commit(game_id: i64, commit_hash: String, force: Option<bool>) -> CommandResult<serde_json::Value> {
     // Ensure database is ready using flag file approach
-    let db_conn = crate::database::connection::ensure_database_ready().await?;
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
+
+    let git_manager = GitSaveManager::new(db_conn.clone());
+
+    // Refuse to overwrite saves of a running game, or saves newer than the commit, unless forced
+    if !force.unwrap_or(false) {
+        ensure_game_not_running(&db_conn, game_id).await?;
+        let commit_time = git_manager.get_commit_time(&commit_hash).await
+            .map_err(|e| format!("Failed to restore to commit: {}", e))?;
+        if let Some(conflict) = check_game_conflict(&db_conn, game_id, commit_time).await? {
+            let outcome: RestoreOutcome<()> = RestoreOutcome::Conflict { conflict };
+            return Ok(serde_json::to_value(outcome)?);
+        }
+    }
 
     // Restore to commit
+    let operation = GitOperation::start(game_id);
+    git_manager.restore_to_commit(&operation, &commit_hash).await
+        .map_err(|e| operation_error(&operation, "Failed to restore to commit", e))?;
+
+    Ok(serde_json::to_value(RestoreOutcome::Restored { result: commit_hash })?)
+}
+
+#[tauri::command]
+pub async fn restore_branch_tip(game_id: i64, branch_name: String, force: Option<bool>) -> CommandResult<serde_json::Value> {
+    // Ensure database is ready using flag file approach
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
+
     let git_manager = GitSaveManager::new(db_conn.clone());
-    git_manager.restore_to_commit(game_id, &commit_hash).await
-        .map_err(|e| format!("Failed to restore to commit: {}", e))
+
+    // Refuse to overwrite saves of a running game, or saves newer than the branch tip, unless forced
+    if !force.unwrap_or(false) {
+        ensure_game_not_running(&db_conn, game_id).await?;
+        let (_, commit_time) = git_manager.get_branch_tip(&branch_name).await
+            .map_err(|e| format!("Failed to restore branch tip: {}", e))?;
+        if let Some(conflict) = check_game_conflict(&db_conn, game_id, commit_time).await? {
+            let outcome: RestoreOutcome<()> = RestoreOutcome::Conflict { conflict };
+            return Ok(serde_json::to_value(outcome)?);
+        }
+    }
+
+    let operation = GitOperation::start(game_id);
+    let result = git_manager.restore_branch_tip(&operation, &branch_name).await
+        .map_err(|e| {
+            crate::logger::error("GIT_COMMAND", &format!("Failed to restore tip of '{}' for game {}", branch_name, game_id), Some(&e));
+            operation_error(&operation, "Failed to restore branch tip", e)
+        })?;
+
+    Ok(serde_json::to_value(RestoreOutcome::Restored { result })?)
 }
 
+/// Restore the commit nearest to `timestamp`. With `match_save_time`, "nearest" is judged
+/// by when the game last wrote its saves before each checkpoint instead of commit time.
 #[tauri::command]
-pub async fn restore_to_timestamp(game_id: i64, timestamp: String) -> Result<String, String> {
+pub async fn restore_to_timestamp(game_id: i64, timestamp: String, force: Option<bool>, match_save_time: Option<bool>) -> CommandResult<serde_json::Value> {
     // Ensure database is ready using flag file approach
-    let db_conn = crate::database::connection::ensure_database_ready().await?;
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
 
     // Parse timestamp
-    let target_time = chrono::DateTime::parse_from_rfc3339(&timestamp)
-        .map(|dt| dt.with_timezone(&chrono::Utc))
-        .map_err(|e| format!("Invalid timestamp format: {}", e))?;
+    let target_time = parse_timestamp(&timestamp)?;
+    let match_save_time = match_save_time.unwrap_or(false);
 
-    // Restore to timestamp
     let git_manager = GitSaveManager::new(db_conn.clone());
-    git_manager.restore_to_timestamp(game_id, target_time).await
-        .map_err(|e| format!("Failed to restore to timestamp: {}", e))
+
+    // Refuse to overwrite saves of a running game, or saves newer than the target commit, unless forced
+    if !force.unwrap_or(false) {
+        ensure_game_not_running(&db_conn, game_id).await?;
+        let save_time_match = if match_save_time {
+            git_manager.find_closest_save_time_commit(game_id, target_time).await
+                .map_err(|e| format!("Failed to restore to timestamp: {}", e))?
+        } else {
+            None
+        };
+        let backup_time = match save_time_match {
+            Some((_, saved_at)) => saved_at,
+            None => {
+                let (_, _, commit_time) = git_manager.find_closest_commit(target_time).await
+                    .map_err(|e| format!("Failed to restore to timestamp: {}", e))?;
+                commit_time
+            }
+        };
+        if let Some(conflict) = check_game_conflict(&db_conn, game_id, backup_time).await? {
+            let outcome: RestoreOutcome<String> = RestoreOutcome::Conflict { conflict };
+            return Ok(serde_json::to_value(outcome)?);
+        }
+    }
+
+    // Restore to timestamp
+    let operation = GitOperation::start(game_id);
+    let message = if match_save_time {
+        git_manager.restore_to_save_time(&operation, target_time).await
+    } else {
+        git_manager.restore_to_timestamp(&operation, target_time).await
+    }.map_err(|e| operation_error(&operation, "Failed to restore to timestamp", e))?;
+
+    Ok(serde_json::to_value(RestoreOutcome::Restored { result: message })?)
+}
+
+/// Stop the game's running checkpoint, branch switch or restore. Progress of those is sent
+/// on the `operation-progress` event; the stopped command fails with a `CANCELLED` error.
+/// Returns whether an operation was running.
+#[tauri::command]
+pub async fn cancel_git_operation(game_id: i64) -> CommandResult<bool> {
+    Ok(GitSaveManager::cancel_operation(game_id))
 }
 
 #[tauri::command]
-pub async fn get_git_history(game_id: i64, _branch: Option<String>) -> Result<serde_json::Value, String> {
+pub async fn get_git_history(game_id: i64, _branch: Option<String>) -> CommandResult<serde_json::Value> {
     // Ensure database is ready using flag file approach
-    let db_conn = crate::database::connection::ensure_database_ready().await?;
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
 
     // Get save history
     let git_manager = GitSaveManager::new(db_conn.clone());
@@ -82,19 +194,120 @@ pub async fn get_git_history(game_id: i64, _branch: Option<String>) -> Result<se
         .map_err(|e| format!("Failed to get git history: {}", e))?;
 
     // Convert to JSON
-    serde_json::to_value(history).map_err(|e| format!("Serialization error: {}", e))
+    Ok(serde_json::to_value(history)?)
+}
+
+/// Save branches of every game, for a cross-game overview of save slots
+#[tauri::command]
+pub async fn list_all_save_slots() -> CommandResult<Vec<SaveSlot>> {
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
+
+    let git_manager = GitSaveManager::new(db_conn.clone());
+    Ok(git_manager.list_save_slots().await
+        .map_err(|e| format!("Failed to list save slots: {}", e))?)
+}
+
+/// Commits within an optional `from`/`to` window (RFC 3339), newest first and capped at
+/// `limit`, so the UI can page through long histories.
+#[tauri::command]
+pub async fn get_git_history_range(
+    game_id: i64,
+    branch: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    limit: Option<usize>,
+) -> CommandResult<serde_json::Value> {
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
+
+    let from = from.as_deref().map(parse_timestamp).transpose()?;
+    let to = to.as_deref().map(parse_timestamp).transpose()?;
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err(CommandError::InvalidInput(format!("Invalid date range: {} is after {}", from, to)));
+        }
+    }
+
+    let git_manager = GitSaveManager::new(db_conn.clone());
+    let commits = git_manager.get_history_range(game_id, branch.as_deref(), from, to, limit.unwrap_or(DEFAULT_HISTORY_LIMIT)).await
+        .map_err(|e| format!("Failed to get git history: {}", e))?;
+
+    Ok(serde_json::to_value(commits)?)
+}
+
+/// Check the master repository for corruption. With `rebuild`, a damaged repository is
+/// moved aside and reinitialized from the working tree and the recorded branches.
+#[tauri::command]
+pub async fn repair_master_repo(rebuild: Option<bool>) -> CommandResult<RepoHealthReport> {
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
+
+    let git_manager = GitSaveManager::new(db_conn.clone());
+    let report = git_manager.repair_master_repo(rebuild.unwrap_or(false)).await
+        .map_err(|e| {
+            crate::logger::error("GIT_COMMAND", "Failed to repair master repository", Some(&e));
+            format!("Failed to repair master repository: {}", e)
+        })?;
+
+    if !report.healthy {
+        crate::logger::warn("GIT_COMMAND", "Master repository is damaged", Some(&report.problems.join("; ")));
+    }
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn get_git_author() -> CommandResult<CommitAuthor> {
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
+
+    let db_guard = db_conn.lock().await;
+    let conn = db_guard.get_connection().await;
+    Ok(CommitAuthor::load(&conn))
+}
+
+/// Set the name and email recorded on save commits; `None` falls back to the git config
+/// or the built-in Save Steward identity
+#[tauri::command]
+pub async fn set_git_author(name: Option<String>, email: Option<String>) -> CommandResult<CommitAuthor> {
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
+
+    let author = CommitAuthor {
+        name: name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()),
+        email: email.map(|e| e.trim().to_string()).filter(|e| !e.is_empty()),
+    };
+    author.validate().map_err(CommandError::InvalidInput)?;
+
+    let db_guard = db_conn.lock().await;
+    let conn = db_guard.get_connection().await;
+    author.save(&conn)?;
+
+    crate::logger::info("GIT_COMMAND", "Updated git commit author", Some(&format!("{:?}", author)));
+    Ok(author)
+}
+
+/// Verify a provider token before configuring cloud sync
+#[tauri::command]
+pub async fn test_cloud_credentials(provider: String, credentials: CloudCredentials) -> CommandResult<CredentialCheck> {
+    let provider = CloudProvider::parse(&provider)
+        .ok_or_else(|| CommandError::InvalidInput(format!("Unknown cloud provider: {}", provider)))?;
+
+    let check = crate::git_manager::cloud::test_credentials(provider, &credentials).await?;
+    if !check.valid {
+        crate::logger::warn("GIT_COMMAND", "Cloud credentials were rejected", Some(&check.message));
+    }
+    Ok(check)
 }
 
 #[tauri::command]
-pub async fn sync_to_cloud(game_id: i64) -> Result<serde_json::Value, String> {
+pub async fn sync_to_cloud(game_id: i64) -> CommandResult<serde_json::Value> {
     // Ensure database is ready using flag file approach
-    let db_conn = crate::database::connection::ensure_database_ready().await?;
+    let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
 
-    // Sync to cloud
+    // Push the game's save branches to its configured remote
     let git_manager = GitSaveManager::new(db_conn.clone());
     let sync_result = git_manager.sync_to_cloud(game_id).await
-        .map_err(|e| format!("Failed to sync to cloud: {}", e))?;
+        .map_err(|e| {
+            crate::logger::error("GIT_COMMAND", &format!("Cloud sync failed for game {}", game_id), Some(&e));
+            format!("Failed to sync to cloud: {}", e)
+        })?;
 
     // Convert to JSON
-    serde_json::to_value(sync_result).map_err(|e| format!("Serialization error: {}", e))
+    Ok(serde_json::to_value(sync_result)?)
 }
@@ -1,3 +1,5 @@
+pub mod backup;
+pub mod error;
 pub mod game;
 pub mod git;
 pub mod system;
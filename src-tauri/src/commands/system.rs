@@ -1,4 +1,9 @@
-use crate::launch_utils::launch_game_enhanced;
+use crate::database::maintenance::{CachePurgeResult, DatabaseMaintenance};
+use crate::detection::ignored::{IgnoredProcess, IgnoredProcesses};
+use crate::manifest::steam_users::{self, SteamUser};
+use crate::launch_utils::{launch_game_enhanced, launch_via_url, launcher_url, LaunchResult};
+use std::path::PathBuf;
+use super::error::{CommandError, CommandResult};
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -7,7 +12,7 @@ pub fn greet(name: &str) -> String {
 }
 
 #[tauri::command]
-pub async fn identify_game_by_pid(pid: u32) -> Result<serde_json::Value, String> {
+pub async fn identify_game_by_pid(pid: u32) -> CommandResult<serde_json::Value> {
     // Initialize database connection and manifest resolver
     // This would normally be injected as a dependency
     // For now, return a placeholder response
@@ -19,7 +24,243 @@ pub async fn identify_game_by_pid(pid: u32) -> Result<serde_json::Value, String>
 }
 
 #[tauri::command]
-pub async fn scan_running_games() -> Result<serde_json::Value, String> {
+pub async fn get_identification_candidates(pid: u32) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("DETECTION_COMMAND", "Failed to ensure database ready for get_identification_candidates", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let manifest_resolver = crate::manifest::ManifestResolver::new()
+        .map_err(|e| format!("Failed to load manifest resolver: {}", e))?;
+    let shared_conn = db_conn.lock().await.shared_connection();
+    let engine = crate::detection::GameIdentificationEngine::new(shared_conn, manifest_resolver);
+
+    let candidates = match engine.get_identification_candidates(pid).await {
+        Ok(c) => c,
+        Err(e) => {
+            crate::logger::error("DETECTION_COMMAND", &format!("Failed to get identification candidates for pid {}", pid), Some(&e.to_string()));
+            return Err(e.into());
+        }
+    };
+
+    Ok(serde_json::to_value(candidates)?)
+}
+
+#[tauri::command]
+pub async fn track_process_as_game(pid: u32, game_id: i64) -> CommandResult<serde_json::Value> {
+    let service = match crate::auto_backup::backup_service().await {
+        Ok(service) => service,
+        Err(e) => {
+            crate::logger::error("DETECTION_COMMAND", "Failed to get backup service for track_process_as_game", Some(&e));
+            return Err(CommandError::Internal(format!("Backup service unavailable: {}", e)));
+        }
+    };
+
+    let manual = match service.track_process_as_game(pid, game_id).await {
+        Ok(m) => m,
+        Err(e) => {
+            crate::logger::error("DETECTION_COMMAND", &format!("Failed to track pid {} as game {}", pid, game_id), Some(&e.to_string()));
+            return Err(e.into());
+        }
+    };
+
+    crate::logger::info("DETECTION_COMMAND", &format!("Tracking pid {} as game {}", pid, game_id),
+        Some(&format!("Recorded identifiers: {}", manual.recorded_identifiers.join(", "))));
+
+    Ok(serde_json::to_value(manual)?)
+}
+
+#[tauri::command]
+pub async fn get_min_executable_size() -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("DETECTION_COMMAND", "Failed to ensure database ready for get_min_executable_size", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let bytes = {
+        let db_guard = db_conn.lock().await;
+        let conn = db_guard.get_connection().await;
+        crate::detection::executable_analysis::configured_min_executable_size(&conn)
+    };
+
+    Ok(serde_json::json!({ "min_executable_size": bytes }))
+}
+
+#[tauri::command]
+pub async fn set_min_executable_size(bytes: u64) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("DETECTION_COMMAND", "Failed to ensure database ready for set_min_executable_size", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    {
+        let db_guard = db_conn.lock().await;
+        let conn = db_guard.get_connection().await;
+        if let Err(e) = crate::database::settings::AppSettings::set(&conn, crate::detection::executable_analysis::MIN_EXECUTABLE_SIZE_SETTING, &bytes.to_string()) {
+            crate::logger::error("DETECTION_COMMAND", "Failed to save minimum executable size", Some(&e.to_string()));
+            return Err(CommandError::Internal(format!("Failed to save setting: {}", e)));
+        }
+    }
+
+    // Apply to the running detection engine, if there is one
+    if let Some(service) = crate::auto_backup::backup_service_if_started() {
+        let engine = service.get_identification_engine().await;
+        let applied = engine.read().await.set_min_executable_size(bytes).await;
+        if let Err(e) = applied {
+            crate::logger::warn("DETECTION_COMMAND", "Failed to apply minimum executable size to the running engine", Some(&e.to_string()));
+        }
+    }
+
+    crate::logger::info("DETECTION_COMMAND", &format!("Minimum game executable size set to {} bytes", bytes), None);
+    Ok(serde_json::json!({ "min_executable_size": bytes }))
+}
+
+#[tauri::command]
+pub async fn get_follow_symlinks() -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("SYSTEM_COMMAND", "Failed to ensure database ready for get_follow_symlinks", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let follow = {
+        let db_guard = db_conn.lock().await;
+        let conn = db_guard.get_connection().await;
+        crate::game_manager::scanner::configured_follow_symlinks(&conn)
+    };
+
+    Ok(serde_json::json!({ "follow_symlinks": follow }))
+}
+
+#[tauri::command]
+pub async fn set_follow_symlinks(follow: bool) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("SYSTEM_COMMAND", "Failed to ensure database ready for set_follow_symlinks", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    {
+        let db_guard = db_conn.lock().await;
+        let conn = db_guard.get_connection().await;
+        if let Err(e) = crate::database::settings::AppSettings::set(&conn, crate::game_manager::scanner::FOLLOW_SYMLINKS_SETTING, &follow.to_string()) {
+            crate::logger::error("SYSTEM_COMMAND", "Failed to save follow_symlinks setting", Some(&e.to_string()));
+            return Err(CommandError::Internal(format!("Failed to save setting: {}", e)));
+        }
+    }
+
+    crate::logger::info("SYSTEM_COMMAND", &format!("Symlink following {}", if follow { "enabled" } else { "disabled" }), None);
+    Ok(serde_json::json!({ "follow_symlinks": follow }))
+}
+
+#[tauri::command]
+pub async fn clear_detection_cache(game_id: Option<i64>) -> CommandResult<()> {
+    crate::auto_backup::invalidate_detection_cache(game_id).await;
+
+    let scope = game_id.map(|id| format!("game {}", id)).unwrap_or_else(|| "all games".to_string());
+    crate::logger::info("DETECTION_COMMAND", &format!("Cleared detection cache for {}", scope), None);
+    Ok(())
+}
+
+/// Hash the executables of games with stored executable hashes and cache their
+/// identifications, so the first scan after startup or `clear_all_caches` is fast.
+/// Reports how many entries were warmed.
+#[tauri::command]
+pub async fn warm_detection_cache() -> CommandResult<crate::detection::engine::CacheWarmReport> {
+    let service = match crate::auto_backup::backup_service().await {
+        Ok(service) => service,
+        Err(e) => {
+            crate::logger::error("DETECTION_COMMAND", "Failed to get backup service for warm_detection_cache", Some(&e));
+            return Err(CommandError::Internal(format!("Backup service unavailable: {}", e)));
+        }
+    };
+
+    let engine = service.get_identification_engine().await;
+    let report = engine.read().await.warm_cache().await.map_err(|e| {
+        crate::logger::error("DETECTION_COMMAND", "Failed to warm detection cache", Some(&e.to_string()));
+        CommandError::Internal(format!("Failed to warm detection cache: {}", e))
+    })?;
+
+    crate::logger::info("DETECTION_COMMAND", &format!("Warmed {} detection cache entries", report.warmed), Some(&format!("{:?}", report)));
+    Ok(report)
+}
+
+/// Clear the detection, platform, executable and runtime caches plus cached PCGamingWiki
+/// responses, reporting how many entries each held
+#[tauri::command]
+pub async fn clear_all_caches() -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("SYSTEM_COMMAND", "Failed to ensure database ready for clear_all_caches", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let report = crate::auto_backup::clear_all_caches(&db_conn).await.map_err(|e| {
+        crate::logger::error("SYSTEM_COMMAND", "Failed to clear caches", Some(&e));
+        e
+    })?;
+
+    crate::logger::info("SYSTEM_COMMAND", "Cleared all caches", Some(&format!("{:?}", report)));
+    Ok(serde_json::to_value(report)?)
+}
+
+/// Purge the on-disk caches: every cached PCGamingWiki response and expired Steam store
+/// responses. The in-memory caches are left to `clear_all_caches`.
+#[tauri::command]
+pub async fn clear_caches() -> CommandResult<CachePurgeResult> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("SYSTEM_COMMAND", "Failed to ensure database ready for clear_caches", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let db_guard = db_conn.lock().await;
+    let result = db_guard.with_connection(|conn| DatabaseMaintenance::purge_caches(conn)).await
+        .and_then(|r| r)
+        .map_err(|e| {
+            crate::logger::error("SYSTEM_COMMAND", "Failed to purge cache tables", Some(&e));
+            e
+        })?;
+
+    crate::logger::info("SYSTEM_COMMAND", "Purged cache tables", Some(&format!("{:?}", result)));
+    Ok(result)
+}
+
+/// Whether a game is running: a backup session counts while its process is alive, otherwise
+/// running processes are matched against the game's stored process names and executables
+#[tauri::command]
+pub async fn is_game_running(game_id: i64) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("DETECTION_COMMAND", "Failed to ensure database ready for is_game_running", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let pid = crate::backup::conflict::running_game_pid(&db_conn, game_id).await?;
+    Ok(serde_json::json!({ "running": pid.is_some(), "pid": pid }))
+}
+
+#[tauri::command]
+pub async fn scan_running_games() -> CommandResult<serde_json::Value> {
     // Placeholder implementation
     Ok(serde_json::json!({
         "running_games": [],
@@ -27,8 +268,346 @@ pub async fn scan_running_games() -> Result<serde_json::Value, String> {
     }))
 }
 
+/// Running games found by the last scan if it is at most `max_age_secs` old, scanning
+/// again first otherwise. Cheap to poll; `None` until a scan has succeeded.
+#[tauri::command]
+pub async fn get_last_scan_results(max_age_secs: u64) -> CommandResult<Option<Vec<crate::detection::GameIdentification>>> {
+    let service = match crate::auto_backup::backup_service().await {
+        Ok(service) => service,
+        Err(e) => {
+            crate::logger::error("DETECTION_COMMAND", "Failed to get backup service for get_last_scan_results", Some(&e));
+            return Err(CommandError::Internal(format!("Backup service unavailable: {}", e)));
+        }
+    };
+
+    let max_age = chrono::Duration::seconds(max_age_secs.min(u32::MAX as u64) as i64);
+    let engine = service.get_identification_engine().await;
+    let results = engine.read().await.recent_scan(max_age).await;
+    Ok(results)
+}
+
+/// Logged identifications plus per-game confidence statistics, least certain games first.
+///
+/// `since_days` limits both to recent detections; `limit` caps the entries returned.
+#[tauri::command]
+pub async fn get_detection_history(game_id: Option<i64>, since_days: Option<u32>, limit: Option<usize>) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("DETECTION_COMMAND", "Failed to ensure database ready for get_detection_history", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let since = since_days.map(|days| chrono::Utc::now() - chrono::Duration::days(days as i64));
+    let db_guard = db_conn.lock().await;
+    let conn = db_guard.get_connection().await;
+    let history = crate::detection::history::DetectionHistory::entries(&conn, game_id, since, Some(limit.unwrap_or(200)))
+        .and_then(|entries| {
+            let mut summary = crate::detection::history::DetectionHistory::summary(&conn, since)?;
+            summary.retain(|s| game_id.is_none() || s.game_id == game_id);
+            Ok((entries, summary))
+        });
+    let (entries, summary) = history.map_err(|e| {
+        crate::logger::error("DETECTION_COMMAND", "Failed to load detection history", Some(&e.to_string()));
+        CommandError::Internal(format!("Failed to load detection history: {}", e))
+    })?;
+
+    Ok(serde_json::json!({
+        "enabled": crate::detection::history::is_enabled(&conn),
+        "summary": summary,
+        "entries": entries,
+    }))
+}
+
+#[tauri::command]
+pub async fn set_detection_history_enabled(enabled: bool) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("DETECTION_COMMAND", "Failed to ensure database ready for set_detection_history_enabled", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    {
+        let db_guard = db_conn.lock().await;
+        let conn = db_guard.get_connection().await;
+        if let Err(e) = crate::database::settings::AppSettings::set(&conn, crate::detection::history::DETECTION_HISTORY_SETTING, &enabled.to_string()) {
+            crate::logger::error("DETECTION_COMMAND", "Failed to save log_detection_history setting", Some(&e.to_string()));
+            return Err(CommandError::Internal(format!("Failed to save setting: {}", e)));
+        }
+    }
+
+    crate::logger::info("DETECTION_COMMAND", &format!("Detection history logging {}", if enabled { "enabled" } else { "disabled" }), None);
+    Ok(serde_json::json!({ "enabled": enabled }))
+}
+
+/// Processes skipped by detection because they were dismissed as not being games
+#[tauri::command]
+pub async fn list_ignored_processes() -> CommandResult<Vec<IgnoredProcess>> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("DETECTION_COMMAND", "Failed to ensure database ready for list_ignored_processes", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let db_guard = db_conn.lock().await;
+    let conn = db_guard.get_connection().await;
+    IgnoredProcesses::list(&conn).map_err(|e| {
+        crate::logger::error("DETECTION_COMMAND", "Failed to load ignored processes", Some(&e.to_string()));
+        CommandError::Internal(format!("Failed to load ignored processes: {}", e))
+    })
+}
+
+/// Stop detecting a process as a game, by name (e.g. `Discord.exe`), executable hash or both.
+/// Cached identifications are dropped so the next scan leaves it out.
+#[tauri::command]
+pub async fn add_ignored_process(process_name: Option<String>, executable_hash: Option<String>, reason: Option<String>) -> CommandResult<IgnoredProcess> {
+    let is_blank = |value: &Option<String>| value.as_deref().map(str::trim).unwrap_or_default().is_empty();
+    if is_blank(&process_name) && is_blank(&executable_hash) {
+        return Err(CommandError::InvalidInput("A process name or executable hash is required".to_string()));
+    }
+
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("DETECTION_COMMAND", "Failed to ensure database ready for add_ignored_process", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let entry = {
+        let db_guard = db_conn.lock().await;
+        let conn = db_guard.get_connection().await;
+        IgnoredProcesses::add(&conn, process_name.as_deref(), executable_hash.as_deref(), reason.as_deref()).map_err(|e| {
+            crate::logger::error("DETECTION_COMMAND", "Failed to add ignored process", Some(&e.to_string()));
+            CommandError::Internal(format!("Failed to add ignored process: {}", e))
+        })?
+    };
+    crate::auto_backup::invalidate_detection_cache(None).await;
+
+    crate::logger::info("DETECTION_COMMAND", &format!("Ignoring process {}", entry.process_name.as_deref().or(entry.executable_hash.as_deref()).unwrap_or_default()), None);
+    Ok(entry)
+}
+
+/// Detect a previously ignored process again
+#[tauri::command]
+pub async fn remove_ignored_process(id: i64) -> CommandResult<()> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("DETECTION_COMMAND", "Failed to ensure database ready for remove_ignored_process", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let removed = {
+        let db_guard = db_conn.lock().await;
+        let conn = db_guard.get_connection().await;
+        IgnoredProcesses::remove(&conn, id).map_err(|e| {
+            crate::logger::error("DETECTION_COMMAND", "Failed to remove ignored process", Some(&e.to_string()));
+            CommandError::Internal(format!("Failed to remove ignored process: {}", e))
+        })?
+    };
+    if !removed {
+        return Err(CommandError::NotFound(format!("Ignored process {} not found", id)));
+    }
+    crate::auto_backup::invalidate_detection_cache(None).await;
+    Ok(())
+}
+
+/// Steam accounts signed in on this machine, the profile `{{steam-userdata-current}}`
+/// resolves to first
+#[tauri::command]
+pub async fn list_steam_users() -> CommandResult<Vec<SteamUser>> {
+    tokio::task::spawn_blocking(crate::manifest::ManifestResolver::discover_steam_users)
+        .await
+        .map_err(|e| CommandError::Internal(format!("Steam user lookup failed: {}", e)))
+}
+
+/// Pick the Steam profile whose saves `{{steam-userdata-current}}` resolves to, or go back
+/// to the most recently signed-in account with `None`
+#[tauri::command]
+pub async fn set_active_steam_user(steam_id: Option<String>) -> CommandResult<serde_json::Value> {
+    let steam_id = steam_id.filter(|id| !id.trim().is_empty());
+    if let Some(steam_id) = &steam_id {
+        let known = tokio::task::spawn_blocking(crate::manifest::ManifestResolver::discover_steam_users)
+            .await
+            .map_err(|e| CommandError::Internal(format!("Steam user lookup failed: {}", e)))?;
+        if !known.iter().any(|user| &user.steam_id == steam_id) {
+            return Err(CommandError::NotFound(format!("Steam user {} not found", steam_id)));
+        }
+    }
+
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("SYSTEM_COMMAND", "Failed to ensure database ready for set_active_steam_user", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    {
+        let db_guard = db_conn.lock().await;
+        let conn = db_guard.get_connection().await;
+        let value = steam_id.clone().unwrap_or_default();
+        if let Err(e) = crate::database::settings::AppSettings::set(&conn, steam_users::ACTIVE_STEAM_USER_SETTING, &value) {
+            crate::logger::error("SYSTEM_COMMAND", "Failed to save active_steam_user setting", Some(&e.to_string()));
+            return Err(CommandError::Internal(format!("Failed to save setting: {}", e)));
+        }
+    }
+    steam_users::set_active_steam_id(steam_id.clone());
+
+    crate::logger::info("SYSTEM_COMMAND", &format!("Active Steam user set to {}", steam_id.as_deref().unwrap_or("the most recent account")), None);
+    Ok(serde_json::json!({ "steam_id": steam_id }))
+}
+
+#[tauri::command]
+pub async fn get_database_status() -> CommandResult<serde_json::Value> {
+    let error = crate::database::connection::database_startup_error();
+
+    Ok(serde_json::json!({
+        "ready": error.is_none(),
+        "error": error,
+        "database_path": crate::database::connection::DatabasePaths::database_file().to_string_lossy(),
+    }))
+}
+
+#[tauri::command]
+pub async fn retry_database_initialization() -> CommandResult<serde_json::Value> {
+    crate::logger::info("APP", "Retrying database initialization", None);
+
+    if let Err(e) = crate::database::connection::initialize_database_at_startup().await {
+        crate::logger::error("APP", "Database initialization retry failed", Some(&e));
+    }
+
+    get_database_status().await
+}
+
+/// Zip the log files and a snapshot of the app's state into one file to attach to bug
+/// reports, returning its path. `dest` may be a file or an existing folder.
+#[tauri::command]
+pub async fn export_diagnostics(dest: String) -> CommandResult<String> {
+    let mut dest = PathBuf::from(&dest);
+    if dest.is_dir() {
+        dest = dest.join(format!("save-steward-diagnostics-{}.zip", chrono::Utc::now().format("%Y%m%d-%H%M%S")));
+    }
+
+    let error = crate::database::connection::database_startup_error();
+    let schema = match crate::database::connection::ensure_database_ready().await {
+        Ok(db_conn) => {
+            let db_guard = db_conn.lock().await;
+            db_guard.with_connection(|conn| DatabaseMaintenance::schema_summary(conn)).await
+                .and_then(|r| r)
+                .map(|s| serde_json::json!(s))
+                .unwrap_or_else(|e| serde_json::json!({ "error": e }))
+        }
+        Err(e) => serde_json::json!({ "error": e }),
+    };
+
+    let log_files = crate::logger::log_files();
+    let snapshot = serde_json::json!({
+        "generated_at": chrono::Utc::now().to_rfc3339(),
+        "app_version": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "database": {
+            "ready": error.is_none(),
+            "error": error,
+            "database_path": crate::database::connection::DatabasePaths::database_file().to_string_lossy(),
+            "schema": schema,
+        },
+        "backup_service_started": crate::auto_backup::backup_service_if_started().is_some(),
+        "log_files": log_files.iter().map(|p| p.to_string_lossy().to_string()).collect::<Vec<_>>(),
+    });
+
+    let bundle_path = dest.clone();
+    let included = tokio::task::spawn_blocking(move || crate::diagnostics::write_bundle(&bundle_path, &snapshot, &log_files))
+        .await
+        .map_err(|e| CommandError::Internal(format!("Diagnostics export task failed: {}", e)))?
+        .map_err(|e| {
+            crate::logger::error("SYSTEM_COMMAND", "Failed to export diagnostics", Some(&e));
+            CommandError::from(e)
+        })?;
+
+    let path = dest.to_string_lossy().to_string();
+    crate::logger::info("SYSTEM_COMMAND", &format!("Exported diagnostics with {} log files", included), Some(&path));
+    Ok(path)
+}
+
 #[tauri::command]
-pub async fn launch_game(executable_path: String, installation_path: Option<String>) -> Result<String, String> {
+pub async fn backup_database(dest: String) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("SYSTEM_COMMAND", "Failed to ensure database ready for backup_database", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let result = match DatabaseMaintenance::backup_to(&db_conn, &PathBuf::from(&dest)).await {
+        Ok(r) => r,
+        Err(e) => {
+            crate::logger::error("SYSTEM_COMMAND", &format!("Failed to back up database to {}", dest), Some(&e));
+            return Err(e.into());
+        }
+    };
+
+    Ok(serde_json::to_value(result)?)
+}
+
+#[tauri::command]
+pub async fn restore_database(src: String) -> CommandResult<serde_json::Value> {
+    let db_conn = match crate::database::connection::ensure_database_ready().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            crate::logger::error("SYSTEM_COMMAND", "Failed to ensure database ready for restore_database", Some(&e));
+            return Err(CommandError::DatabaseUnavailable(e));
+        }
+    };
+
+    let result = match DatabaseMaintenance::restore_from(&db_conn, &PathBuf::from(&src)).await {
+        Ok(r) => r,
+        Err(e) => {
+            crate::logger::error("SYSTEM_COMMAND", &format!("Failed to restore database from {}", src), Some(&e));
+            return Err(e.into());
+        }
+    };
+
+    Ok(serde_json::to_value(result)?)
+}
+
+/// Launch a game. With `game_id`, Steam and Epic games that have an app id are started
+/// through their store client (unless `use_launcher` is false) so DRM-wrapped games work;
+/// everything else runs the executable directly.
+#[tauri::command]
+pub async fn launch_game(
+    executable_path: String,
+    installation_path: Option<String>,
+    game_id: Option<i64>,
+    use_launcher: Option<bool>,
+) -> CommandResult<LaunchResult> {
+    if let (Some(game_id), true) = (game_id, use_launcher.unwrap_or(true)) {
+        let db_conn = crate::database::connection::ensure_database_ready().await.map_err(CommandError::DatabaseUnavailable)?;
+        let game = {
+            let db_guard = db_conn.lock().await;
+            let conn = db_guard.get_connection().await;
+            super::game::require_game(&conn, game_id)?
+        };
+
+        if let Some(url) = launcher_url(&game.platform, game.platform_app_id.as_deref()) {
+            crate::logger::info("SYSTEM_COMMAND", &format!("Launching {} through its launcher", game.name), Some(&url));
+            return Ok(launch_via_url(&url, game.installation_path.as_deref()).await?);
+        }
+    }
+
+    if executable_path.trim().is_empty() {
+        return Err(CommandError::InvalidInput("No executable to launch".to_string()));
+    }
+
     // For Unity games and other complex launch scenarios, we need to use the installation directory
     // Try to get the installation directory from the parameter or parse from executable path
     
@@ -53,22 +632,18 @@ pub async fn launch_game(executable_path: String, installation_path: Option<Stri
             use std::process::Command;
             
             #[cfg(target_os = "windows")]
-            {
-                Command::new(&executable_path)
-                    .spawn()
-                    .map_err(|err| format!("Failed to launch game: {}", err))?;
-            }
+            let result = crate::launch_utils::spawn_and_track(Command::new(&executable_path), &executable_path, &install_dir).await
+                .map_err(|err| format!("Failed to launch game: {}", err))?;
             
             #[cfg(not(target_os = "windows"))]
-            {
-                Command::new("sh")
-                    .arg("-c")
-                    .arg(&executable_path)
-                    .spawn()
-                    .map_err(|err| format!("Failed to launch game: {}. Make sure the file has executable permissions (chmod +x)", err))?;
-            }
+            let result = {
+                let mut command = Command::new("sh");
+                command.arg("-c").arg(&executable_path);
+                crate::launch_utils::spawn_and_track(command, &executable_path, &install_dir).await
+                    .map_err(|err| format!("Failed to launch game: {}. Make sure the file has executable permissions (chmod +x)", err))?
+            };
             
-            Ok(format!("Launched: {}", executable_path))
+            Ok(result)
         }
     }
 }
@@ -62,6 +62,27 @@ impl Database {
         self.conn.lock().await
     }
 
+    /// Run `f` against the connection on the blocking thread pool.
+    ///
+    /// Use this for long queries and bulk work so the async runtime isn't stalled while
+    /// SQLite runs. The connection stays locked until `f` returns, so `f` must not try to
+    /// reach the database through another path.
+    pub async fn with_connection<F, T>(&self, f: F) -> Result<T, String>
+    where
+        F: FnOnce(&mut Connection) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let mut conn = self.conn.clone().lock_owned().await;
+        tokio::task::spawn_blocking(move || f(&mut conn))
+            .await
+            .map_err(|e| format!("Database task failed: {}", e))
+    }
+
+    /// Shared handle to the underlying connection, for components built on `DatabaseConnection`
+    pub fn shared_connection(&self) -> DatabaseConnection {
+        self.conn.clone()
+    }
+
     pub fn get_path(&self) -> &Path {
         &self.path
     }
@@ -79,6 +100,52 @@ impl Database {
     }
 }
 
+/// Error from the startup database initialization, if it failed
+static STARTUP_ERROR: once_cell::sync::Lazy<std::sync::Mutex<Option<String>>> =
+    once_cell::sync::Lazy::new(|| std::sync::Mutex::new(None));
+
+/// Connect to the database and create the schema at application startup.
+///
+/// Failures are logged and remembered instead of panicking, so the app can start
+/// in a degraded state and let the user retry.
+pub async fn initialize_database_at_startup() -> Result<(), String> {
+    let db_path = DatabasePaths::database_file();
+
+    crate::logger::database::connection_attempt(&db_path);
+    let result = match Database::new(&db_path).await {
+        Ok(db) => {
+            crate::logger::database::connection_success(&db_path);
+            crate::logger::database::schema_creation_start();
+
+            match db.initialize_database().await {
+                Ok(()) => {
+                    crate::logger::database::schema_creation_success();
+                    Ok(())
+                }
+                Err(e) => {
+                    crate::logger::database::schema_creation_error(&e.to_string());
+                    Err(format!("Failed to initialize database schema: {}", e))
+                }
+            }
+        }
+        Err(e) => {
+            crate::logger::database::connection_error(&db_path, &e.to_string());
+            Err(format!("Failed to connect to database at {}: {}", db_path.display(), e))
+        }
+    };
+
+    if let Ok(mut startup_error) = STARTUP_ERROR.lock() {
+        *startup_error = result.as_ref().err().cloned();
+    }
+
+    result
+}
+
+/// The error from the last startup initialization attempt, or `None` if the database is usable
+pub fn database_startup_error() -> Option<String> {
+    STARTUP_ERROR.lock().ok().and_then(|e| e.clone())
+}
+
 // Simple database initialization - no flags, no versions
 pub async fn ensure_database_ready() -> Result<Arc<tokio::sync::Mutex<Database>>, String> {
     let db_path = DatabasePaths::database_file();
@@ -174,6 +241,22 @@ mod tests {
     use super::*;
     use std::fs;
 
+    #[tokio::test]
+    async fn test_with_connection_runs_off_the_runtime() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = Database::new(dir.path().join("test.db")).await.unwrap();
+
+        db.with_connection(|conn| conn.execute_batch("CREATE TABLE t (v INTEGER); INSERT INTO t VALUES (1), (2);"))
+            .await
+            .unwrap()
+            .unwrap();
+
+        // The connection is released once the closure returns
+        let conn = db.get_connection().await;
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM t", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 2);
+    }
+
     #[tokio::test]
     async fn test_ensure_database_ready_initializes_correctly() {
         // Setup: Clean up existing files
@@ -188,6 +271,7 @@ mod tests {
             log_file_path: log_path.clone(),
             max_file_size_bytes: 1024 * 1024,
             max_log_files: 1,
+            max_total_log_bytes: 10 * 1024 * 1024,
             enable_console_output: true,
         };
         // We ignore the error here as it might be already initialized
@@ -0,0 +1,241 @@
+use chrono::Utc;
+use rusqlite::{Connection, DatabaseName, OpenFlags};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::database::connection::{Database, DatabasePaths};
+use crate::database::schema::DatabaseSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseBackupResult {
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseRestoreResult {
+    pub restored_from: String,
+    /// Copy of the database as it was before the restore
+    pub previous_database_backup: String,
+}
+
+/// Rows removed from the on-disk cache tables
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachePurgeResult {
+    pub pcgw_rows: usize,
+    pub steam_store_rows: usize,
+    /// Size of the deleted cached responses
+    pub bytes_freed: u64,
+}
+
+/// Shape of the database, for diagnostics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaSummary {
+    /// `PRAGMA user_version`
+    pub schema_version: i64,
+    /// Row count per table
+    pub tables: std::collections::BTreeMap<String, i64>,
+}
+
+/// Backup and restore of the application's own SQLite database
+pub struct DatabaseMaintenance;
+
+impl DatabaseMaintenance {
+    /// Copy the live database to `dest` using SQLite's online backup API
+    pub async fn backup_to(db: &Arc<tokio::sync::Mutex<Database>>, dest: &Path) -> Result<DatabaseBackupResult, String> {
+        if let Some(parent) = dest.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+
+        let db_guard = db.lock().await;
+        if Self::same_file(db_guard.get_path(), dest) {
+            return Err("Destination is the live database file".to_string());
+        }
+
+        let backup_dest = dest.to_path_buf();
+        db_guard.with_connection(move |conn| {
+            Self::checkpoint(conn);
+            conn.backup(DatabaseName::Main, &backup_dest, None)
+                .map_err(|e| format!("Database backup failed: {}", e))
+        }).await??;
+
+        let size_bytes = std::fs::metadata(dest).map(|m| m.len()).unwrap_or(0);
+        crate::logger::info("DATABASE", "Database backed up", Some(&format!("{} ({} bytes)", dest.display(), size_bytes)));
+
+        Ok(DatabaseBackupResult {
+            path: dest.to_string_lossy().to_string(),
+            size_bytes,
+        })
+    }
+
+    /// Replace the live database with the contents of `src`.
+    ///
+    /// `src` must pass an integrity check and contain a `games` table. The current database
+    /// is backed up first, and the restored copy is brought up to the current schema.
+    pub async fn restore_from(db: &Arc<tokio::sync::Mutex<Database>>, src: &Path) -> Result<DatabaseRestoreResult, String> {
+        let candidate = src.to_path_buf();
+        tokio::task::spawn_blocking(move || Self::validate_database_file(&candidate))
+            .await
+            .map_err(|e| format!("Database validation task failed: {}", e))??;
+
+        let previous = DatabasePaths::backup_directory()
+            .join(format!("save_steward_pre_restore_{}.db", Utc::now().format("%Y%m%d-%H%M%S")));
+        Self::backup_to(db, &previous).await?;
+
+        let db_guard = db.lock().await;
+        if Self::same_file(db_guard.get_path(), src) {
+            return Err("Source is the live database file".to_string());
+        }
+
+        let restore_src = src.to_path_buf();
+        db_guard.with_connection(move |conn| {
+            conn.restore(DatabaseName::Main, &restore_src, None::<fn(rusqlite::backup::Progress)>)
+                .map_err(|e| format!("Database restore failed: {}", e))?;
+            Self::checkpoint(conn);
+
+            DatabaseSchema::create_tables(conn)
+                .map_err(|e| format!("Restored database could not be upgraded: {}", e))
+        }).await??;
+
+        crate::logger::info("DATABASE", "Database restored", Some(&format!("from {}, previous copy at {}", src.display(), previous.display())));
+
+        Ok(DatabaseRestoreResult {
+            restored_from: src.to_string_lossy().to_string(),
+            previous_database_backup: previous.to_string_lossy().to_string(),
+        })
+    }
+
+    /// Open `path` read-only and check it is an intact Save Steward database
+    pub fn validate_database_file(path: &Path) -> Result<(), String> {
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+
+        let integrity: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))
+            .map_err(|e| format!("{} is not a valid database: {}", path.display(), e))?;
+        if integrity != "ok" {
+            return Err(format!("{} failed the integrity check: {}", path.display(), integrity));
+        }
+
+        let has_games: i64 = conn.query_row(
+            "SELECT count(name) FROM sqlite_master WHERE type='table' AND name='games'",
+            [],
+            |row| row.get(0),
+        ).map_err(|e| format!("Failed to read schema of {}: {}", path.display(), e))?;
+        if has_games == 0 {
+            return Err(format!("{} is not a Save Steward database", path.display()));
+        }
+
+        Ok(())
+    }
+
+    /// Empty `pcgw_cache` and drop expired `steam_store_cache` rows
+    pub fn purge_caches(conn: &Connection) -> Result<CachePurgeResult, String> {
+        let now = Utc::now().to_rfc3339();
+        let tx = conn.unchecked_transaction()
+            .map_err(|e| format!("Failed to start cache purge: {}", e))?;
+
+        let pcgw_bytes: i64 = tx.query_row(
+            "SELECT COALESCE(SUM(LENGTH(query_key) + LENGTH(response_json)), 0) FROM pcgw_cache",
+            [],
+            |row| row.get(0),
+        ).map_err(|e| format!("Failed to measure PCGamingWiki cache: {}", e))?;
+        let steam_bytes: i64 = tx.query_row(
+            "SELECT COALESCE(SUM(LENGTH(app_id) + LENGTH(response_json)), 0) FROM steam_store_cache WHERE expires_at <= ?",
+            [&now],
+            |row| row.get(0),
+        ).map_err(|e| format!("Failed to measure Steam store cache: {}", e))?;
+
+        let pcgw_rows = crate::pcgaming_wiki::cache::PcgwCache::clear(&tx)
+            .map_err(|e| format!("Failed to clear PCGamingWiki cache: {}", e))?;
+        let steam_store_rows = tx.execute("DELETE FROM steam_store_cache WHERE expires_at <= ?", [&now])
+            .map_err(|e| format!("Failed to purge Steam store cache: {}", e))?;
+
+        tx.commit().map_err(|e| format!("Failed to commit cache purge: {}", e))?;
+
+        Ok(CachePurgeResult {
+            pcgw_rows,
+            steam_store_rows,
+            bytes_freed: (pcgw_bytes + steam_bytes).max(0) as u64,
+        })
+    }
+
+    /// Schema version and row count of every table
+    pub fn schema_summary(conn: &Connection) -> Result<SchemaSummary, String> {
+        let schema_version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(|e| format!("Failed to read schema version: {}", e))?;
+
+        let mut stmt = conn.prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name")
+            .map_err(|e| format!("Failed to list tables: {}", e))?;
+        let names = stmt.query_map([], |row| row.get::<_, String>(0))
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to list tables: {}", e))?;
+
+        let mut tables = std::collections::BTreeMap::new();
+        for name in names {
+            let count: i64 = conn.query_row(&format!("SELECT COUNT(*) FROM \"{}\"", name), [], |row| row.get(0))
+                .map_err(|e| format!("Failed to count rows of {}: {}", name, e))?;
+            tables.insert(name, count);
+        }
+
+        Ok(SchemaSummary { schema_version, tables })
+    }
+
+    /// Fold the WAL back into the main file
+    pub fn checkpoint(conn: &Connection) {
+        if let Err(e) = conn.query_row("PRAGMA wal_checkpoint(TRUNCATE);", [], |_| Ok(())) {
+            crate::logger::warn("DATABASE", "WAL checkpoint failed", Some(&e.to_string()));
+        }
+    }
+
+    fn same_file(a: &Path, b: &Path) -> bool {
+        let canonical = |p: &Path| p.canonicalize().unwrap_or_else(|_| PathBuf::from(p));
+        canonical(a) == canonical(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_database_file() {
+        let dir = TempDir::new().unwrap();
+
+        let valid = dir.path().join("valid.db");
+        let conn = Connection::open(&valid).unwrap();
+        conn.execute("CREATE TABLE games (id INTEGER PRIMARY KEY)", []).unwrap();
+        drop(conn);
+        assert!(DatabaseMaintenance::validate_database_file(&valid).is_ok());
+
+        let unrelated = dir.path().join("other.db");
+        let conn = Connection::open(&unrelated).unwrap();
+        conn.execute("CREATE TABLE notes (id INTEGER PRIMARY KEY)", []).unwrap();
+        drop(conn);
+        assert!(DatabaseMaintenance::validate_database_file(&unrelated).is_err());
+
+        let garbage = dir.path().join("garbage.db");
+        std::fs::write(&garbage, b"definitely not sqlite, just some bytes padding it out").unwrap();
+        assert!(DatabaseMaintenance::validate_database_file(&garbage).is_err());
+    }
+
+    #[test]
+    fn test_purge_caches_keeps_fresh_steam_rows() {
+        let conn = Connection::open_in_memory().unwrap();
+        DatabaseSchema::create_tables(&conn).unwrap();
+
+        crate::pcgaming_wiki::cache::PcgwCache::set(&conn, "search:portal", "{}", 1).unwrap();
+        crate::detection::platform::SteamStoreCache::set(&conn, "400", "{\"fresh\":1}", 7).unwrap();
+        crate::detection::platform::SteamStoreCache::set(&conn, "620", "{\"stale\":1}", -1).unwrap();
+
+        let result = DatabaseMaintenance::purge_caches(&conn).unwrap();
+        assert_eq!(result.pcgw_rows, 1);
+        assert_eq!(result.steam_store_rows, 1);
+        assert_eq!(result.bytes_freed, ("search:portal".len() + 2 + 3 + "{\"stale\":1}".len()) as u64);
+
+        let remaining: i64 = conn.query_row("SELECT COUNT(*) FROM steam_store_cache", [], |row| row.get(0)).unwrap();
+        assert_eq!(remaining, 1);
+    }
+}
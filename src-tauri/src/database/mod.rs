@@ -1,5 +1,7 @@
 pub mod schema;
 pub mod connection;
 pub mod models;
+pub mod settings;
+pub mod maintenance;
 
 pub use connection::*;
@@ -11,7 +11,7 @@ pub struct Game {
     pub platform_app_id: Option<String>,
     pub executable_path: Option<String>, // Legacy single executable path
     pub installation_path: Option<String>,
-    pub platform_executables: Option<String>, // JSON: {"linux": "run.sh", "windows": "Game.exe", "macos": "Game.app"}
+    pub platform_executables: Option<String>, // JSON: {"linux": ["run.sh"], "windows": ["Game.exe", "Launcher.exe"], "macos": ["Game.app"]}
     pub genre: Option<String>,
     pub release_date: Option<String>,
     pub cover_image_url: Option<String>,
@@ -94,10 +94,24 @@ pub struct UserGame {
     pub auto_backup_interval: i32, // seconds
     pub max_versions: i32,
     pub compression_level: i32, // 1-22 for zstd
+    pub real_time_backup_enabled: Option<bool>, // None follows the global setting
+    pub backup_debounce_seconds: Option<i64>, // None follows the global real-time delay
+    pub event_coalesce_ms: Option<i64>, // None uses the default coalesce window
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameBranch {
+    pub id: i64,
+    pub game_id: i64,
+    pub branch_name: String,
+    pub description: Option<String>,
+    pub is_active: bool,
+    pub last_commit_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 // Response types for frontend
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GameWithSaves {
@@ -107,6 +121,203 @@ pub struct GameWithSaves {
     pub user_config: Option<UserGame>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GameBackupStats {
+    pub version_count: i64,
+    pub total_size_bytes: i64,
+    pub last_backup: Option<DateTime<Utc>>,
+    pub checkpoint_count: i64,
+    pub last_checkpoint: Option<DateTime<Utc>>,
+}
+
+/// A game as listed in the library, with its favorite flag from `user_games`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GameListEntry {
+    #[serde(flatten)]
+    pub game: Game,
+    pub is_favorite: bool,
+}
+
+/// Backups made on one day, for the calendar view
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupTimelineDay {
+    /// `YYYY-MM-DD`, UTC
+    pub date: String,
+    pub count: i64,
+    pub total_bytes: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GameDetail {
+    pub game: Game,
+    pub save_locations: Vec<SaveLocation>,
+    pub identifiers: Vec<GameIdentifier>,
+    pub branches: Vec<GameBranch>,
+    pub backup_stats: GameBackupStats,
+}
+
+/// Per-game file-event timing; `None` fields fall back to the global configuration
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct GameMonitorSettings {
+    pub debounce_seconds: Option<u64>,
+    pub coalesce_window_ms: Option<u64>,
+}
+
+/// Per-game backup retention; `None` fields fall back to the global retention config
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct GameRetentionSettings {
+    pub max_backups: Option<usize>,
+    pub max_bytes: Option<u64>,
+    pub min_age_hours: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SaveScanSummary {
+    pub game_id: i64,
+    pub paths_scanned: usize,
+    pub files_scanned: usize,
+    pub total_bytes: u64,
+    pub limit_reached: bool,
+    pub elapsed_ms: u64,
+    pub saves: Vec<DetectedSave>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IconRefreshResult {
+    pub game_id: i64,
+    pub refreshed: bool,
+    pub icon_base64: Option<String>,
+    pub source_path: Option<String>,
+    pub message: String,
+}
+
+/// A possible save location offered while setting up a game
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SuggestedLocation {
+    pub path_pattern: String,
+    pub platform: Option<String>,
+    /// `pcgamingwiki` or `heuristic`
+    pub source: String,
+    pub resolved_paths: Vec<String>,
+    /// Whether any resolved path exists on this machine
+    pub exists: bool,
+    /// Higher is more likely; suggestions are sorted by it
+    pub likelihood: u32,
+    /// Set when the location is inside a folder a cloud client syncs
+    pub cloud_sync: Option<CloudSyncInfo>,
+}
+
+/// A cloud-synced folder that contains a save location
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloudSyncInfo {
+    /// `OneDrive`, `Dropbox`, `Google Drive` or `iCloud Drive`
+    pub provider: String,
+    pub sync_root: String,
+    /// Message to show the user, naming the provider
+    pub warning: String,
+}
+
+/// A directory that may hold a game's saves after they moved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftCandidate {
+    pub path: String,
+    /// `sibling` (next to the configured path) or `suggestion` (a common save directory)
+    pub source: String,
+    pub file_count: usize,
+    pub newest_modified: Option<DateTime<Utc>>,
+}
+
+/// A save location from PCGW compared with the game's configured ones
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PcgwLocationDiff {
+    pub path_pattern: String,
+    pub platform: Option<String>,
+    /// `unchanged` when configured as listed, `new` when only PCGW lists it, `removed` when
+    /// a location added from PCGW is no longer listed there
+    pub status: String,
+    /// The matching configured location, if any
+    pub location_id: Option<i64>,
+}
+
+/// Save locations re-fetched from a game's PCGW page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PcgwLocationRefresh {
+    pub game_id: i64,
+    pub pcgw_page_name: String,
+    /// Any location is new or removed
+    pub changed: bool,
+    pub locations: Vec<PcgwLocationDiff>,
+}
+
+/// Descriptive fields of a game as listed on its PCGW infobox
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameMetadata {
+    pub developer: Option<String>,
+    pub publisher: Option<String>,
+    pub genre: Option<String>,
+    pub release_date: Option<String>,
+    pub cover_image_url: Option<String>,
+}
+
+/// Metadata applied to a game from its PCGW page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataEnrichment {
+    pub game_id: i64,
+    pub pcgw_page_name: String,
+    /// Columns that were written, e.g. `["developer", "genre"]`
+    pub updated_fields: Vec<String>,
+    pub game: Game,
+}
+
+/// Whether a save location still holds the game's saves, with likely new homes if not
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocationDrift {
+    pub location_id: i64,
+    pub path_pattern: String,
+    pub resolved_paths: Vec<String>,
+    pub file_count: usize,
+    /// The configured path is empty while a candidate has recent files
+    pub drifted: bool,
+    /// Most recently written first
+    pub candidates: Vec<DriftCandidate>,
+}
+
+/// How a save location resolves on this machine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SaveLocationAuditStatus {
+    /// At least one resolved path exists
+    Resolved,
+    /// A `{{placeholder}}` or `%VAR%` has no value here
+    UnresolvedPlaceholder,
+    /// The pattern resolves, but to nothing on disk
+    PathMissing,
+    /// The game has no save location that applies to this machine
+    NoLocations,
+}
+
+/// One row of the library-wide save location audit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveLocationAudit {
+    pub game_id: i64,
+    pub game_name: String,
+    /// `None` for `NoLocations`
+    pub location_id: Option<i64>,
+    pub path_pattern: Option<String>,
+    pub status: SaveLocationAuditStatus,
+    pub resolved_paths: Vec<String>,
+    pub unresolved: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SaveLocationStatus {
+    pub location: SaveLocation,
+    pub resolved_paths: Vec<String>,
+    pub exists: bool,
+    pub matching_file_count: usize,
+    /// Set when a resolved path is inside a folder a cloud client syncs
+    pub cloud_sync: Option<CloudSyncInfo>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SaveBackupInfo {
     pub detected_save: DetectedSave,
@@ -136,7 +347,7 @@ pub struct AddGameRequest {
     pub platform_app_id: Option<String>,
     pub executable_path: Option<String>,
     pub installation_path: Option<String>,
-    pub platform_executables: Option<String>, // JSON: {"linux": "run.sh", "windows": "Game.exe", "macos": "Game.app"}
+    pub platform_executables: Option<String>, // JSON: {"linux": ["run.sh"], "windows": ["Game.exe", "Launcher.exe"], "macos": ["Game.app"]}
     pub icon_base64: Option<String>, // Base64 encoded icon
     pub icon_path: Option<String>, // Original exe path for icon extraction
 }
@@ -8,7 +8,7 @@ impl DatabaseSchema {
     pub fn create_tables(conn: &Connection) -> DatabaseResult<()> {
         logger::info("DATABASE", "Starting database table creation", None);
         
-        let tables: [(&str, fn(&Connection) -> DatabaseResult<()>); 13] = [
+        let tables: [(&str, fn(&Connection) -> DatabaseResult<()>); 18] = [
             ("games", Self::create_games_table),
             ("save_locations", Self::create_save_locations_table),
             ("detected_saves", Self::create_detected_saves_table),
@@ -22,6 +22,11 @@ impl DatabaseSchema {
             ("git_save_snapshots", Self::create_git_save_snapshots_table),
             ("pcgw_cache", Self::create_pcgw_cache_table),
             ("game_pcgw_mapping", Self::create_game_pcgw_mapping_table),
+            ("backups", Self::create_backups_table),
+            ("app_settings", Self::create_app_settings_table),
+            ("steam_store_cache", Self::create_steam_store_cache_table),
+            ("detection_history", Self::create_detection_history_table),
+            ("ignored_processes", Self::create_ignored_processes_table),
         ];
         
         let mut created_tables = Vec::new();
@@ -62,6 +67,9 @@ impl DatabaseSchema {
             }
         }
         
+        // Bring tables created by older versions up to date
+        Self::add_missing_columns(conn)?;
+
         // Create indexes (only if we have some tables)
         match Self::create_indexes(conn) {
             Ok(_) => {
@@ -96,7 +104,7 @@ impl DatabaseSchema {
                 platform_app_id TEXT,   -- Steam AppID, Epic Game ID, etc.
                 executable_path TEXT,   -- Legacy single executable path
                 installation_path TEXT,
-                platform_executables TEXT, -- JSON: {"linux": "run.sh", "windows": "Game.exe", "macos": "Game.app"}
+                platform_executables TEXT, -- JSON: {"linux": ["run.sh"], "windows": ["Game.exe", "Launcher.exe"], "macos": ["Game.app"]}
                 genre TEXT,
                 release_date DATE,
                 cover_image_url TEXT,
@@ -178,6 +186,7 @@ impl DatabaseSchema {
                 version_number INTEGER NOT NULL,
                 backup_path TEXT NOT NULL,
                 compressed_size INTEGER,
+                original_size INTEGER,                 -- total size of the saved files before compression
                 original_hash TEXT NOT NULL,
                 compressed_hash TEXT NOT NULL,
                 compression_method TEXT DEFAULT 'zstd',
@@ -228,8 +237,16 @@ impl DatabaseSchema {
                 is_favorite INTEGER DEFAULT 0,
                 backup_enabled INTEGER DEFAULT 1,
                 auto_backup_interval INTEGER DEFAULT 3600, -- seconds
-                max_versions INTEGER DEFAULT 10,
+                max_versions INTEGER DEFAULT 10,          -- NULL follows the global retention limit
                 compression_level INTEGER DEFAULT 3,      -- 1-22 for zstd
+                real_time_backup_enabled INTEGER,         -- NULL follows the global setting
+                backup_debounce_seconds INTEGER,          -- NULL follows the global real-time delay
+                event_coalesce_ms INTEGER,                -- NULL uses the default coalesce window
+                naming_template TEXT,                     -- NULL keeps the default branch and backup names
+                archive_format TEXT,                      -- 'tar.zst', 'tar' or 'zip'; NULL means 'tar.zst'
+                cold_storage_path TEXT,                   -- set while backups and branches are in cold storage
+                retention_max_bytes INTEGER,              -- NULL means no size limit
+                retention_min_age_hours INTEGER,          -- NULL follows the global minimum age
                 created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
                 updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
                 FOREIGN KEY (game_id) REFERENCES games(id)
@@ -281,6 +298,7 @@ impl DatabaseSchema {
                 cloud_sync_url TEXT,
                 created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
                 is_current INTEGER DEFAULT 0,
+                save_modified_at TIMESTAMP,              -- newest save file mtime when committed
                 FOREIGN KEY (game_id) REFERENCES games(id)
             )
             "#,
@@ -394,6 +412,146 @@ impl DatabaseSchema {
         }).map(|_| ())
     }
 
+    fn create_backups_table(conn: &Connection) -> DatabaseResult<()> {
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS backups (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                backup_id TEXT NOT NULL UNIQUE,
+                game_id INTEGER NOT NULL,
+                backup_type TEXT NOT NULL,           -- JSON encoded retention::BackupType
+                created_at TEXT NOT NULL,            -- RFC 3339
+                file_path TEXT NOT NULL,
+                compression_level TEXT NOT NULL,     -- JSON encoded retention::CompressionLevel
+                size_bytes INTEGER,                  -- archive size on disk
+                original_bytes INTEGER,              -- total size of the archived files
+                FOREIGN KEY (game_id) REFERENCES games(id)
+            )
+            "#,
+            [],
+        ).map_err(|e| {
+            logger::error("DATABASE", "Error creating backups table", Some(&e.to_string()));
+            e.into()
+        }).map(|_| ())
+    }
+
+    fn create_app_settings_table(conn: &Connection) -> DatabaseResult<()> {
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS app_settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            )
+            "#,
+            [],
+        ).map_err(|e| {
+            logger::error("DATABASE", "Error creating app_settings table", Some(&e.to_string()));
+            e.into()
+        }).map(|_| ())
+    }
+
+    fn create_steam_store_cache_table(conn: &Connection) -> DatabaseResult<()> {
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS steam_store_cache (
+                app_id TEXT PRIMARY KEY,
+                response_json TEXT NOT NULL,
+                fetched_at TEXT NOT NULL,
+                expires_at TEXT NOT NULL
+            )
+            "#,
+            [],
+        ).map_err(|e| {
+            logger::error("DATABASE", "Error creating steam_store_cache table", Some(&e.to_string()));
+            e.into()
+        }).map(|_| ())
+    }
+
+    fn create_detection_history_table(conn: &Connection) -> DatabaseResult<()> {
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS detection_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                game_id INTEGER,
+                process_name TEXT,
+                confidence_score REAL NOT NULL,
+                confidence_level TEXT NOT NULL,
+                identification_methods TEXT NOT NULL,
+                requires_manual_confirmation BOOLEAN NOT NULL,
+                detected_at TIMESTAMP NOT NULL,
+                FOREIGN KEY (game_id) REFERENCES games (id) ON DELETE CASCADE
+            )
+            "#,
+            [],
+        ).map_err(|e| {
+            logger::error("DATABASE", "Error creating detection_history table", Some(&e.to_string()));
+            e.into()
+        }).map(|_| ())
+    }
+
+    fn create_ignored_processes_table(conn: &Connection) -> DatabaseResult<()> {
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS ignored_processes (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                process_name TEXT UNIQUE,
+                executable_hash TEXT UNIQUE,
+                reason TEXT,
+                created_at TIMESTAMP NOT NULL,
+                CHECK (process_name IS NOT NULL OR executable_hash IS NOT NULL)
+            )
+            "#,
+            [],
+        ).map_err(|e| {
+            logger::error("DATABASE", "Error creating ignored_processes table", Some(&e.to_string()));
+            e.into()
+        }).map(|_| ())
+    }
+
+    /// Add columns introduced after a table was first created. `CREATE TABLE IF NOT EXISTS`
+    /// leaves existing tables untouched, so new columns are added here instead.
+    fn add_missing_columns(conn: &Connection) -> DatabaseResult<()> {
+        let columns = [
+            ("user_games", "real_time_backup_enabled", "INTEGER"),
+            ("user_games", "backup_debounce_seconds", "INTEGER"),
+            ("user_games", "event_coalesce_ms", "INTEGER"),
+            ("user_games", "naming_template", "TEXT"),
+            ("user_games", "archive_format", "TEXT"),
+            ("user_games", "cold_storage_path", "TEXT"),
+            ("user_games", "retention_max_bytes", "INTEGER"),
+            ("user_games", "retention_min_age_hours", "INTEGER"),
+            ("git_save_commits", "save_modified_at", "TIMESTAMP"),
+            ("backups", "size_bytes", "INTEGER"),
+            ("backups", "original_bytes", "INTEGER"),
+            ("save_versions", "original_size", "INTEGER"),
+        ];
+
+        for (table, column, definition) in &columns {
+            let exists: i64 = conn.query_row(
+                &format!("SELECT count(*) FROM pragma_table_info('{}') WHERE name = ?", table),
+                [column],
+                |row| row.get(0),
+            ).unwrap_or(0);
+
+            if exists > 0 {
+                continue;
+            }
+
+            match conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), []) {
+                Ok(_) => {
+                    logger::info("DATABASE", &format!("Added column {}.{}", table, column), None);
+                }
+                Err(e) => {
+                    logger::error("DATABASE", &format!("Failed to add column {}.{}", table, column), Some(&e.to_string()));
+                    return Err(e.into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     fn create_indexes(conn: &Connection) -> DatabaseResult<()> {
         logger::debug("DATABASE", "Creating database indexes", None);
         
@@ -406,6 +564,8 @@ impl DatabaseSchema {
             ("idx_save_versions_created_at", "CREATE INDEX IF NOT EXISTS idx_save_versions_created_at ON save_versions(created_at)"),
             ("idx_game_identifiers_type_value", "CREATE INDEX IF NOT EXISTS idx_game_identifiers_type_value ON game_identifiers(identifier_type, identifier_value)"),
             ("idx_game_identifiers_game_id", "CREATE INDEX IF NOT EXISTS idx_game_identifiers_game_id ON game_identifiers(game_id)"),
+            ("idx_backups_game_id", "CREATE INDEX IF NOT EXISTS idx_backups_game_id ON backups(game_id, created_at)"),
+            ("idx_detection_history_game_id", "CREATE INDEX IF NOT EXISTS idx_detection_history_game_id ON detection_history(game_id, detected_at)"),
         ];
         
         for (index_name, sql) in &indexes {
@@ -421,6 +581,16 @@ impl DatabaseSchema {
             }
         }
         
+        // One game per store id. Databases that already hold duplicates keep working without
+        // the index; the insert-time check in `add_manual_game` still applies to them.
+        if let Err(e) = conn.execute(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_games_platform_app_id_unique ON games(platform, platform_app_id)
+             WHERE platform_app_id IS NOT NULL AND platform_app_id != ''",
+            [],
+        ) {
+            logger::warn("DATABASE", "Could not create unique index on games(platform, platform_app_id); duplicate games exist", Some(&e.to_string()));
+        }
+
         crate::logger::database::index_creation("all_indexes", true);
         Ok(())
     }
@@ -435,6 +605,12 @@ impl DatabaseSchema {
             "git_save_commits",
             "git_branches",
             "git_repositories",
+            // Backup bookkeeping
+            "backups",
+            "app_settings",
+            "steam_store_cache",
+            "detection_history",
+            "ignored_processes",
             // Save management tables
             "save_versions",
             "detected_saves",
@@ -484,6 +660,11 @@ impl DatabaseSchema {
             "git_save_snapshots",
             "pcgw_cache",
             "game_pcgw_mapping",
+            "backups",
+            "app_settings",
+            "steam_store_cache",
+            "detection_history",
+            "ignored_processes",
         ];
 
         for table in &required_tables {
@@ -0,0 +1,28 @@
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Key/value application settings stored in the `app_settings` table
+pub struct AppSettings;
+
+impl AppSettings {
+    pub fn get(conn: &Connection, key: &str) -> rusqlite::Result<Option<String>> {
+        conn.query_row(
+            "SELECT value FROM app_settings WHERE key = ?",
+            params![key],
+            |row| row.get(0),
+        ).optional()
+    }
+
+    pub fn set(conn: &Connection, key: &str, value: &str) -> rusqlite::Result<()> {
+        conn.execute(
+            "INSERT INTO app_settings (key, value, updated_at) VALUES (?, ?, CURRENT_TIMESTAMP)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = CURRENT_TIMESTAMP",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    pub fn remove(conn: &Connection, key: &str) -> rusqlite::Result<()> {
+        conn.execute("DELETE FROM app_settings WHERE key = ?", params![key])?;
+        Ok(())
+    }
+}
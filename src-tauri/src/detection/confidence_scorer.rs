@@ -1,4 +1,38 @@
 use crate::detection::{GameCandidate, DetectionError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// `app_settings` key holding `ScorerWeights` as JSON
+pub const CONFIDENCE_WEIGHTS_SETTING: &str = "confidence_weights";
+
+/// Weights stored under `CONFIDENCE_WEIGHTS_SETTING`. Platforms listed override the
+/// default boost for that platform only; missing evidence fields keep their defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScorerWeights {
+    pub platform: HashMap<String, f32>,
+    pub evidence: EvidenceWeights,
+}
+
+/// A scorer using the weights stored in the settings table, or the defaults
+pub fn configured_scorer(conn: &rusqlite::Connection) -> ConfidenceScorer {
+    let stored = match crate::database::settings::AppSettings::get(conn, CONFIDENCE_WEIGHTS_SETTING) {
+        Ok(Some(json)) => json,
+        _ => return ConfidenceScorer::new(),
+    };
+
+    match serde_json::from_str::<ScorerWeights>(&stored) {
+        Ok(weights) => {
+            let mut platform = PlatformWeights::default();
+            platform.weights.extend(weights.platform);
+            ConfidenceScorer::new().with_weights(platform, weights.evidence)
+        }
+        Err(e) => {
+            crate::logger::warn("DETECTION", "Ignoring invalid confidence weights setting", Some(&e.to_string()));
+            ConfidenceScorer::new()
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct ConfidenceScore {
@@ -15,6 +49,7 @@ pub struct ConfidenceScore {
 pub struct ConfidenceScorer {
     platform_weights: PlatformWeights,
     evidence_weights: EvidenceWeights,
+    runtime_weights: RuntimeWeights,
 }
 
 impl ConfidenceScorer {
@@ -22,21 +57,55 @@ impl ConfidenceScorer {
         Self {
             platform_weights: PlatformWeights::default(),
             evidence_weights: EvidenceWeights::default(),
+            runtime_weights: RuntimeWeights::default(),
         }
     }
 
+    /// Replace the platform boosts and evidence categories
+    pub fn with_weights(mut self, platform_weights: PlatformWeights, evidence_weights: EvidenceWeights) -> Self {
+        self.platform_weights = platform_weights;
+        self.evidence_weights = evidence_weights;
+        self
+    }
+
+    /// Tune how much runtime behaviour contributes. Runtime scores at or below
+    /// `neutral_score` add nothing; above it the boost grows linearly up to
+    /// `max_boost` points for a perfect runtime score of 100.
+    pub fn with_runtime_weighting(mut self, neutral_score: f32, max_boost: f32) -> Self {
+        self.runtime_weights = RuntimeWeights {
+            neutral_score: neutral_score.clamp(0.0, 99.0),
+            max_boost: max_boost.max(0.0),
+        };
+        self
+    }
+
     pub fn calculate_overall_confidence(&self, candidates: &[GameCandidate]) -> (f32, bool, Option<String>) {
+        self.calculate_overall_confidence_with_runtime(candidates, None)
+    }
+
+    /// Same as `calculate_overall_confidence`, folding in the process's runtime
+    /// score (`RuntimeDetectionResult::overall_confidence`, 0-100) as extra evidence
+    pub fn calculate_overall_confidence_with_runtime(
+        &self,
+        candidates: &[GameCandidate],
+        runtime_confidence: Option<f32>,
+    ) -> (f32, bool, Option<String>) {
         if candidates.is_empty() {
             return (0.0, false, Some("No candidates found".to_string()));
         }
 
+        let runtime_boost = runtime_confidence
+            .map(|score| self.runtime_weights.get_boost(score))
+            .unwrap_or(0.0);
+
         if candidates.len() == 1 {
             let candidate = &candidates[0];
             let confidence = self.calculate_single_candidate_confidence(candidate);
-            let needs_confirmation = confidence.total_score < 70.0;
-            (confidence.total_score, needs_confirmation, None)
+            let total_score = (confidence.total_score + runtime_boost).min(100.0);
+            let needs_confirmation = total_score < 70.0;
+            (total_score, needs_confirmation, None)
         } else {
-            self.calculate_multiple_candidates_confidence(candidates)
+            self.calculate_multiple_candidates_confidence(candidates, runtime_boost)
         }
     }
 
@@ -64,18 +133,18 @@ impl ConfidenceScorer {
         }
 
         // Boost score based on number of matching identifiers
-        let identifier_boost = (candidate.matched_identifiers.len() as f32) * 5.0;
-        total_score += identifier_boost.min(25.0); // Cap at 25 points
+        let identifier_boost = (candidate.matched_identifiers.len() as f32) * self.evidence_weights.identifier_boost;
+        total_score += identifier_boost.min(self.evidence_weights.max_identifier_boost);
         evidence_count += candidate.matched_identifiers.len() as u32;
 
         // Categorize evidence based on matched identifiers
         for identifier in &candidate.matched_identifiers {
-            match identifier.as_str() {
-                id if id.contains("executable_hash") => high_confidence_evidence += 1,
-                id if id.contains("steam") || id.contains("epic") || id.contains("gog") => high_confidence_evidence += 1,
-                id if id.contains("process_name") => medium_confidence_evidence += 1,
-                id if id.contains("window_title") => medium_confidence_evidence += 1,
-                _ => low_confidence_evidence += 1,
+            if self.evidence_weights.is_high(identifier) {
+                high_confidence_evidence += 1;
+            } else if self.evidence_weights.is_medium(identifier) {
+                medium_confidence_evidence += 1;
+            } else {
+                low_confidence_evidence += 1;
             }
         }
 
@@ -108,7 +177,7 @@ impl ConfidenceScorer {
         }
     }
 
-    fn calculate_multiple_candidates_confidence(&self, candidates: &[GameCandidate]) -> (f32, bool, Option<String>) {
+    fn calculate_multiple_candidates_confidence(&self, candidates: &[GameCandidate], runtime_boost: f32) -> (f32, bool, Option<String>) {
         // Find the best candidate
         let best_candidate = candidates.iter()
             .max_by(|a, b| {
@@ -119,6 +188,9 @@ impl ConfidenceScorer {
 
         if let Some(best) = best_candidate {
             let best_confidence = self.calculate_single_candidate_confidence(best);
+            // Runtime behaviour says the process is a game, not which one, so it
+            // raises the final score without changing the candidate ranking
+            let best_score = (best_confidence.total_score + runtime_boost).min(100.0);
 
             // Check for close competition
             let second_best = candidates.iter()
@@ -140,12 +212,12 @@ impl ConfidenceScorer {
                         best.name, best_confidence.total_score,
                         second.name, second_confidence.total_score
                     );
-                    return (best_confidence.total_score, true, Some(conflict_reason));
+                    return (best_score, true, Some(conflict_reason));
                 }
             }
 
             // Clear winner found
-            (best_confidence.total_score, best_confidence.total_score < 75.0, None)
+            (best_score, best_score < 75.0, None)
         } else {
             (0.0, false, Some("No valid candidates found".to_string()))
         }
@@ -235,14 +307,16 @@ impl ConfidenceScorer {
     }
 }
 
-#[derive(Debug, Clone)]
-struct PlatformWeights {
-    weights: std::collections::HashMap<String, f32>,
+/// Score added for a candidate's store platform
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PlatformWeights {
+    pub weights: HashMap<String, f32>,
 }
 
-impl PlatformWeights {
+impl Default for PlatformWeights {
     fn default() -> Self {
-        let mut weights = std::collections::HashMap::new();
+        let mut weights = HashMap::new();
         weights.insert("steam".to_string(), 15.0);    // Very reliable
         weights.insert("epic".to_string(), 12.0);     // Reliable
         weights.insert("gog".to_string(), 12.0);      // Reliable
@@ -252,44 +326,80 @@ impl PlatformWeights {
 
         Self { weights }
     }
+}
 
+impl PlatformWeights {
     fn get_boost(&self, platform: &str) -> f32 {
         self.weights.get(platform).copied().unwrap_or(0.0)
     }
 }
 
+/// Weighting for runtime behaviour evidence (process name, memory, CPU and
+/// parent process scores combined by `RuntimeDetectionResult::overall_confidence`).
+/// By default a runtime score of 50 is neutral and a score of 100 adds 10 points.
 #[derive(Debug, Clone)]
-struct EvidenceWeights {
-    high_confidence: Vec<String>,
-    medium_confidence: Vec<String>,
-    low_confidence: Vec<String>,
+struct RuntimeWeights {
+    neutral_score: f32,
+    max_boost: f32,
 }
 
-impl EvidenceWeights {
+impl RuntimeWeights {
+    fn default() -> Self {
+        Self {
+            neutral_score: 50.0,
+            max_boost: 10.0,
+        }
+    }
+
+    fn get_boost(&self, runtime_confidence: f32) -> f32 {
+        let above_neutral = (runtime_confidence.clamp(0.0, 100.0) - self.neutral_score).max(0.0);
+        above_neutral / (100.0 - self.neutral_score) * self.max_boost
+    }
+}
+
+/// How matched identifiers count as evidence. An identifier containing one of the
+/// `high_confidence` keywords is strong evidence, one containing a `medium_confidence`
+/// keyword moderate evidence, and anything else weak evidence.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EvidenceWeights {
+    pub high_confidence: Vec<String>,
+    pub medium_confidence: Vec<String>,
+    /// Points per matched identifier
+    pub identifier_boost: f32,
+    /// Cap on the points all matched identifiers add together
+    pub max_identifier_boost: f32,
+}
+
+impl Default for EvidenceWeights {
     fn default() -> Self {
         Self {
             high_confidence: vec![
                 "executable_hash".to_string(),
-                "steam_appid".to_string(),
-                "epic_id".to_string(),
-                "gog_id".to_string(),
-                "digital_signature".to_string(),
+                "steam".to_string(),
+                "epic".to_string(),
+                "gog".to_string(),
             ],
             medium_confidence: vec![
                 "process_name".to_string(),
                 "window_title".to_string(),
-                "file_size".to_string(),
-                "company_name".to_string(),
-            ],
-            low_confidence: vec![
-                "directory_name".to_string(),
-                "file_pattern".to_string(),
-                "partial_match".to_string(),
             ],
+            identifier_boost: 5.0,
+            max_identifier_boost: 25.0,
         }
     }
 }
 
+impl EvidenceWeights {
+    fn is_high(&self, identifier: &str) -> bool {
+        self.high_confidence.iter().any(|keyword| identifier.contains(keyword.as_str()))
+    }
+
+    fn is_medium(&self, identifier: &str) -> bool {
+        self.medium_confidence.iter().any(|keyword| identifier.contains(keyword.as_str()))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ValidationResult {
     pub is_valid: bool,
@@ -360,6 +470,60 @@ mod tests {
         assert!(conflict.is_some());
     }
 
+    #[test]
+    fn test_runtime_confidence_boosts_score() {
+        let scorer = ConfidenceScorer::new();
+        let candidates = vec![create_test_candidate(1, "Test Game", "standalone", 50.0)];
+
+        let (base, _, _) = scorer.calculate_overall_confidence(&candidates);
+        let (weak, _, _) = scorer.calculate_overall_confidence_with_runtime(&candidates, Some(40.0));
+        let (strong, _, _) = scorer.calculate_overall_confidence_with_runtime(&candidates, Some(100.0));
+
+        assert_eq!(weak, base); // Below neutral adds nothing
+        assert!((strong - base - 10.0).abs() < f32::EPSILON);
+
+        let tuned = ConfidenceScorer::new().with_runtime_weighting(0.0, 20.0);
+        let (boosted, _, _) = tuned.calculate_overall_confidence_with_runtime(&candidates, Some(50.0));
+        assert!((boosted - base - 10.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_custom_weights_change_borderline_outcome() {
+        let mut candidate = create_test_candidate(1, "Epic Game", "epic", 55.0);
+        candidate.matched_identifiers = vec!["process_name".to_string()];
+        let candidates = vec![candidate.clone()];
+
+        // 55 + 12 (epic) + 5 (one identifier) clears the confirmation threshold of 70
+        let (score, needs_confirmation, _) = ConfidenceScorer::new().calculate_overall_confidence(&candidates);
+        assert!((score - 72.0).abs() < f32::EPSILON);
+        assert!(!needs_confirmation);
+
+        let mut platform = PlatformWeights::default();
+        platform.weights.insert("epic".to_string(), 5.0);
+        let evidence = EvidenceWeights {
+            high_confidence: vec!["process_name".to_string()],
+            ..EvidenceWeights::default()
+        };
+        let tuned = ConfidenceScorer::new().with_weights(platform, evidence);
+
+        let (score, needs_confirmation, _) = tuned.calculate_overall_confidence(&candidates);
+        assert!((score - 65.0).abs() < f32::EPSILON);
+        assert!(needs_confirmation);
+        assert_eq!(tuned.calculate_single_candidate_confidence(&candidate).high_confidence_evidence, 1);
+    }
+
+    #[test]
+    fn test_configured_scorer_merges_stored_weights() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute("CREATE TABLE app_settings (key TEXT PRIMARY KEY, value TEXT NOT NULL, updated_at TIMESTAMP)", []).unwrap();
+        crate::database::settings::AppSettings::set(&conn, CONFIDENCE_WEIGHTS_SETTING, r#"{"platform": {"epic": 5.0}}"#).unwrap();
+
+        let scorer = configured_scorer(&conn);
+        assert_eq!(scorer.platform_weights.get_boost("epic"), 5.0);
+        assert_eq!(scorer.platform_weights.get_boost("steam"), 15.0);
+        assert_eq!(scorer.evidence_weights.max_identifier_boost, 25.0);
+    }
+
     #[test]
     fn test_validation_red_flags() {
         let scorer = ConfidenceScorer::new();
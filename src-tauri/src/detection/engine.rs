@@ -1,41 +1,166 @@
 use crate::detection::{
     ProcessInfo, IdentificationEvidence, GameIdentification, GameCandidate,
-    DetectionError, process_monitor::ProcessMonitor,
-    executable_analysis::ExecutableAnalyzer, platform::PlatformApiClient,
-    runtime_detection::RuntimeDetector, confidence_scorer::ConfidenceScorer
+    ManualIdentification, DetectionError, process_monitor::ProcessMonitor,
+    executable_analysis::{self, ExecutableAnalyzer, MIN_EXECUTABLE_SIZE_SETTING}, platform::PlatformApiClient,
+    runtime_detection::RuntimeDetector, confidence_scorer::{self, ConfidenceScorer},
+    history::DetectionHistory, ignored::{IgnoreList, IgnoredProcesses},
 };
 use crate::database::DatabaseConnection;
 use crate::manifest::ManifestResolver;
 use rusqlite::{params, Connection};
 use std::collections::{HashMap, HashSet};
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
 use tokio::sync::RwLock;
 
+/// Number of entries removed from each in-memory cache
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct CacheClearReport {
+    pub identifications: usize,
+    pub platform: usize,
+    pub executable: usize,
+    pub runtime: usize,
+    /// Rows removed from the on-disk PCGamingWiki response cache
+    pub pcgw: usize,
+}
+
+/// Outcome of `GameIdentificationEngine::warm_cache`
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct CacheWarmReport {
+    /// Games with a stored executable hash
+    pub games: usize,
+    /// Executables found on disk and hashed
+    pub hashed: usize,
+    /// Executables whose hash matched a single game and are now cached
+    pub warmed: usize,
+}
+
+/// Executable paths of every game with an `executable_hash` identifier
+fn known_executables(conn: &Connection) -> Result<Vec<(i64, String)>, DetectionError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, executable_path, installation_path, platform_executables FROM games
+         WHERE id IN (SELECT game_id FROM game_identifiers WHERE identifier_type = 'executable_hash')"
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, Option<String>>(3)?,
+        ))
+    })?;
+
+    let platform = crate::game_manager::GameManager::get_current_platform();
+    let mut executables = Vec::new();
+    for row in rows {
+        let (game_id, executable_path, installation_path, platform_executables) = row?;
+        let mut paths: Vec<String> = executable_path.into_iter().filter(|p| !p.is_empty()).collect();
+
+        let platform_files = platform_executables
+            .and_then(|json| serde_json::from_str::<HashMap<String, Vec<String>>>(&json).ok())
+            .and_then(|mut executables| executables.remove(platform))
+            .unwrap_or_default();
+        if let Some(installation_path) = installation_path {
+            paths.extend(platform_files.iter().map(|file| {
+                std::path::Path::new(&installation_path).join(file).to_string_lossy().to_string()
+            }));
+        }
+
+        paths.sort();
+        paths.dedup();
+        executables.extend(paths.into_iter().map(|path| (game_id, path)));
+    }
+    Ok(executables)
+}
+
 pub struct GameIdentificationEngine {
     db_conn: DatabaseConnection,
     process_monitor: ProcessMonitor,
     executable_analyzer: ExecutableAnalyzer,
     platform_client: PlatformApiClient,
     runtime_detector: RuntimeDetector,
-    confidence_scorer: ConfidenceScorer,
+    /// Built from the stored confidence weights on first use
+    confidence_scorer: RwLock<Option<ConfidenceScorer>>,
     manifest_resolver: ManifestResolver,
     cache: RwLock<HashMap<String, GameIdentification>>,
     monitored_processes: RwLock<HashMap<u32, ProcessInfo>>,
+    /// Smallest executable treated as a game; loaded from settings on first use
+    min_executable_size: RwLock<Option<u64>>,
+    /// Results of the last `scan_running_games` and when it finished
+    last_scan: RwLock<Option<(DateTime<Utc>, Vec<GameIdentification>)>>,
+    /// Held while a scan requested through `recent_scan` runs, so concurrent pollers share it
+    scan_lock: tokio::sync::Mutex<()>,
 }
 
 impl GameIdentificationEngine {
     pub fn new(db_conn: DatabaseConnection, manifest_resolver: ManifestResolver) -> Self {
         Self {
+            platform_client: PlatformApiClient::new().with_database(db_conn.clone()),
             db_conn,
             process_monitor: ProcessMonitor::new(),
-            executable_analyzer: ExecutableAnalyzer::new(),
-            platform_client: PlatformApiClient::new(),
+            executable_analyzer: ExecutableAnalyzer::new().with_progress(crate::progress::ProgressSink::frontend()),
             runtime_detector: RuntimeDetector::new(),
-            confidence_scorer: ConfidenceScorer::new(),
+            confidence_scorer: RwLock::new(None),
             manifest_resolver,
             cache: RwLock::new(HashMap::new()),
             monitored_processes: RwLock::new(HashMap::new()),
+            min_executable_size: RwLock::new(None),
+            last_scan: RwLock::new(None),
+            scan_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Use a fixed minimum executable size instead of the stored setting
+    pub fn with_min_executable_size(self, bytes: u64) -> Self {
+        self.executable_analyzer.set_min_hash_size(bytes);
+        Self {
+            min_executable_size: RwLock::new(Some(bytes)),
+            ..self
+        }
+    }
+
+    /// Smallest executable, in bytes, that is considered a game
+    pub async fn min_executable_size(&self) -> u64 {
+        if let Some(bytes) = *self.min_executable_size.read().await {
+            return bytes;
+        }
+
+        let bytes = {
+            let conn = self.db_conn.lock().await;
+            executable_analysis::configured_min_executable_size(&conn)
+        };
+        self.executable_analyzer.set_min_hash_size(bytes);
+        *self.min_executable_size.write().await = Some(bytes);
+        bytes
+    }
+
+    /// Persist a new minimum executable size and apply it to this engine
+    pub async fn set_min_executable_size(&self, bytes: u64) -> Result<(), DetectionError> {
+        {
+            let conn = self.db_conn.lock().await;
+            crate::database::settings::AppSettings::set(&conn, MIN_EXECUTABLE_SIZE_SETTING, &bytes.to_string())?;
+        }
+
+        self.executable_analyzer.set_min_hash_size(bytes);
+        *self.min_executable_size.write().await = Some(bytes);
+
+        // Earlier results may have skipped executables that now qualify
+        self.executable_analyzer.clear_cache().await;
+        self.clear_cache().await;
+        Ok(())
+    }
+
+    /// The confidence scorer, loading its weights from settings the first time
+    async fn confidence_scorer(&self) -> ConfidenceScorer {
+        if let Some(scorer) = self.confidence_scorer.read().await.as_ref() {
+            return scorer.clone();
         }
+
+        let scorer = {
+            let conn = self.db_conn.lock().await;
+            confidence_scorer::configured_scorer(&conn)
+        };
+        *self.confidence_scorer.write().await = Some(scorer.clone());
+        scorer
     }
 
     pub async fn start_monitoring(&self) -> Result<(), DetectionError> {
@@ -43,6 +168,10 @@ impl GameIdentificationEngine {
         Ok(())
     }
 
+    pub async fn stop_monitoring(&self) {
+        self.process_monitor.stop_monitoring().await;
+    }
+
     pub async fn identify_game_by_process(&self, pid: u32) -> Result<GameIdentification, DetectionError> {
         // Check cache first
         let process_key = format!("process_{}", pid);
@@ -86,7 +215,7 @@ impl GameIdentificationEngine {
 
         // Build evidence
         let evidence = IdentificationEvidence {
-            executable_hash: Some(signature.file_hash),
+            executable_hash: (!signature.file_hash.is_empty()).then_some(signature.file_hash),
             window_title_patterns: runtime_signatures.window_title_patterns,
             process_name: std::path::Path::new(executable_path)
                 .file_name()
@@ -97,6 +226,7 @@ impl GameIdentificationEngine {
             file_signature: Some(signature.product_name),
             installation_path: self.get_installation_path(executable_path),
             game_features: Vec::new(), // Will be populated from manifest
+            runtime_confidence: None,
         };
 
         let result = self.identify_from_evidence(&evidence).await?;
@@ -114,8 +244,20 @@ impl GameIdentificationEngine {
         let running_processes = self.process_monitor.get_running_processes().await?;
         let mut identifications = Vec::new();
 
+        // Processes the user dismissed as not being games
+        let ignored = {
+            let conn = self.db_conn.lock().await;
+            IgnoredProcesses::load(&conn)
+        };
+
         for process in running_processes {
-            if self.is_game_process(&process).await {
+            if self.is_game_process(&process, &ignored).await {
+                // Known executables resolve from their stored hash without a full analysis
+                if let Some(identification) = self.identify_known_process(&process).await {
+                    identifications.push(identification);
+                    continue;
+                }
+
                 match self.identify_game_from_process(&process).await {
                     Ok(identification) => identifications.push(identification),
                     Err(e) => eprintln!("Failed to identify process {}: {}", process.pid, e),
@@ -123,10 +265,192 @@ impl GameIdentificationEngine {
             }
         }
 
+        *self.last_scan.write().await = Some((Utc::now(), identifications.clone()));
         Ok(identifications)
     }
 
+    /// The last scan's results if they are at most `max_age` old
+    pub async fn last_scan(&self, max_age: Duration) -> Option<Vec<GameIdentification>> {
+        let last_scan = self.last_scan.read().await;
+        let (scanned_at, identifications) = last_scan.as_ref()?;
+        (Utc::now() - *scanned_at <= max_age).then(|| identifications.clone())
+    }
+
+    /// The last scan's results, scanning again first when they are older than `max_age`.
+    ///
+    /// When the new scan fails the previous results are returned, however old; `None`
+    /// means no scan has succeeded yet.
+    pub async fn recent_scan(&self, max_age: Duration) -> Option<Vec<GameIdentification>> {
+        if let Some(identifications) = self.last_scan(max_age).await {
+            return Some(identifications);
+        }
+
+        let _scanning = self.scan_lock.lock().await;
+        // Another caller may have scanned while this one waited
+        if let Some(identifications) = self.last_scan(max_age).await {
+            return Some(identifications);
+        }
+
+        match self.scan_running_games().await {
+            Ok(identifications) => Some(identifications),
+            Err(e) => {
+                crate::logger::warn("DETECTION", "Failed to scan running games, keeping the previous results", Some(&e.to_string()));
+                self.last_scan.read().await.as_ref().map(|(_, identifications)| identifications.clone())
+            }
+        }
+    }
+
+    /// Match a process against stored `executable_hash` identifiers only.
+    ///
+    /// Returns a definitive identification when exactly one game owns the hash,
+    /// and `None` when the process needs the full analysis.
+    async fn identify_known_process(&self, process_info: &ProcessInfo) -> Option<GameIdentification> {
+        let hash = self.executable_analyzer.executable_hash(&process_info.executable_path).await.ok()??;
+
+        let conn = self.db_conn.lock().await;
+        let identification = self.identify_hash(&conn, hash, Some(process_info))?;
+        DetectionHistory::record_if_enabled(&conn, &identification, Some(&process_info.name));
+        Some(identification)
+    }
+
+    /// Definitive identification for an executable hash owned by exactly one game
+    fn identify_hash(&self, conn: &Connection, hash: String, process_info: Option<&ProcessInfo>) -> Option<GameIdentification> {
+        let game_ids = self.find_games_by_hash(conn, &hash).ok()?;
+        let [game_id] = game_ids.as_slice() else {
+            return None;
+        };
+
+        let candidate = self.build_candidate(conn, *game_id, hash, 100.0).ok()?;
+        Some(GameIdentification {
+            game_id: Some(candidate.game_id),
+            candidate_games: vec![candidate],
+            confidence_score: 100.0,
+            confidence_level: 100.0_f32.into(),
+            identification_methods: vec!["executable_hash".to_string()],
+            process_info: process_info.cloned(),
+            requires_manual_confirmation: false,
+            identified_at: Utc::now(),
+            conflict_reason: None,
+        })
+    }
+
+    /// Hash the executables of games with stored `executable_hash` identifiers and cache
+    /// their identifications, so the first scan recognises them without hashing.
+    ///
+    /// Executables are the game's `executable_path` and its current platform's executables
+    /// under `installation_path`; only those whose hash still matches are cached.
+    pub async fn warm_cache(&self) -> Result<CacheWarmReport, DetectionError> {
+        let executables = {
+            let conn = self.db_conn.lock().await;
+            known_executables(&conn)?
+        };
+
+        let mut report = CacheWarmReport {
+            games: executables.iter().map(|(game_id, _)| *game_id).collect::<HashSet<_>>().len(),
+            ..CacheWarmReport::default()
+        };
+
+        for (_, path) in executables {
+            if !std::path::Path::new(&path).is_file() {
+                continue;
+            }
+            let hash = match self.executable_analyzer.executable_hash(&path).await {
+                Ok(Some(hash)) => hash,
+                Ok(None) => continue,
+                Err(e) => {
+                    crate::logger::warn("DETECTION", &format!("Failed to hash {}", path), Some(&e.to_string()));
+                    continue;
+                }
+            };
+            report.hashed += 1;
+
+            let identification = {
+                let conn = self.db_conn.lock().await;
+                self.identify_hash(&conn, hash, None)
+            };
+            if let Some(identification) = identification {
+                self.cache.write().await.insert(path, identification);
+                report.warmed += 1;
+            }
+        }
+
+        Ok(report)
+    }
+
     async fn identify_game_from_process(&self, process_info: &ProcessInfo) -> Result<GameIdentification, DetectionError> {
+        let evidence = self.build_process_evidence(process_info).await?;
+        self.identify_from_evidence(&evidence).await
+    }
+
+    /// Return every candidate game for a running process, ranked by confidence.
+    ///
+    /// Unlike `identify_game_by_process` no game is auto-selected, so a confirmation
+    /// UI can leave the choice to the user.
+    pub async fn get_identification_candidates(&self, pid: u32) -> Result<Vec<GameCandidate>, DetectionError> {
+        let process_info = self.process_monitor.get_process_info(pid).await?;
+        let evidence = self.build_process_evidence(&process_info).await?;
+
+        let conn = self.db_conn.lock().await;
+        let (mut candidates, _) = self.collect_candidates(&conn, &evidence)?;
+
+        candidates.sort_by(|a, b| b.confidence_score.total_cmp(&a.confidence_score));
+        Ok(candidates)
+    }
+
+    /// Record a running process as belonging to `game_id`.
+    ///
+    /// Stores the executable hash and process name as manual identifiers, replacing any
+    /// previous owner, so future scans recognise the process without help.
+    pub async fn record_manual_identification(&self, pid: u32, game_id: i64) -> Result<ManualIdentification, DetectionError> {
+        let process_info = self.process_monitor.get_live_process_info(pid).await?;
+
+        let hash = if process_info.executable_path.is_empty() {
+            None
+        } else {
+            self.executable_analyzer.executable_hash(&process_info.executable_path).await?
+        };
+
+        let mut identifiers = vec![("process_name", process_info.name.to_lowercase())];
+        if let Some(hash) = hash {
+            identifiers.push(("executable_hash", hash));
+        }
+
+        {
+            let conn = self.db_conn.lock().await;
+            let game_exists: bool = conn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM games WHERE id = ?)",
+                params![game_id],
+                |row| row.get(0),
+            )?;
+            if !game_exists {
+                return Err(DetectionError::ConflictError(format!("Game {} not found", game_id)));
+            }
+
+            let tx = conn.unchecked_transaction()?;
+            for (identifier_type, identifier_value) in &identifiers {
+                tx.execute(
+                    "INSERT INTO game_identifiers (game_id, identifier_type, identifier_value, confidence_score, detection_context)
+                     VALUES (?, ?, ?, 1.0, 'manual')
+                     ON CONFLICT(identifier_type, identifier_value) DO UPDATE SET
+                        game_id = excluded.game_id,
+                        confidence_score = excluded.confidence_score,
+                        detection_context = excluded.detection_context",
+                    params![game_id, identifier_type, identifier_value],
+                )?;
+            }
+            tx.commit()?;
+        }
+
+        self.cache.write().await.remove(&format!("process_{}", pid));
+
+        Ok(ManualIdentification {
+            game_id,
+            process_info,
+            recorded_identifiers: identifiers.into_iter().map(|(t, _)| t.to_string()).collect(),
+        })
+    }
+
+    async fn build_process_evidence(&self, process_info: &ProcessInfo) -> Result<IdentificationEvidence, DetectionError> {
         // Analyze executable
         let signature = self.executable_analyzer.analyze_executable(&process_info.executable_path).await?;
 
@@ -136,9 +460,11 @@ impl GameIdentificationEngine {
         // Get runtime detection
         let runtime_data = self.runtime_detector.detect_from_process(process_info).await?;
 
+        let runtime_confidence = runtime_data.overall_confidence();
+
         // Build evidence
-        let evidence = IdentificationEvidence {
-            executable_hash: Some(signature.file_hash),
+        Ok(IdentificationEvidence {
+            executable_hash: (!signature.file_hash.is_empty()).then_some(signature.file_hash),
             window_title_patterns: vec![
                 runtime_data.current_window_title.unwrap_or_default()
             ],
@@ -147,53 +473,20 @@ impl GameIdentificationEngine {
             file_signature: Some(signature.product_name),
             installation_path: self.get_installation_path(&process_info.executable_path),
             game_features: Vec::new(),
-        };
-
-        self.identify_from_evidence(&evidence).await
+            runtime_confidence: Some(runtime_confidence),
+        })
     }
 
     async fn identify_from_evidence(&self, evidence: &IdentificationEvidence) -> Result<GameIdentification, DetectionError> {
+        let scorer = self.confidence_scorer().await;
         let conn = self.db_conn.lock().await;
 
         // Search database for matches
-        let mut candidate_games = Vec::new();
-        let mut identification_methods = Vec::new();
-
-        // Search by executable hash
-        if let Some(hash) = &evidence.executable_hash {
-            if let Ok(game_ids) = self.find_games_by_hash(&conn, hash) {
-                for game_id in game_ids {
-                    candidate_games.push(self.build_candidate(&conn, game_id, hash.clone(), 95.0).await?);
-                }
-                identification_methods.push("executable_hash".to_string());
-            }
-        }
-
-        // Search by platform IDs
-        for platform_id in &evidence.platform_ids {
-            if let Ok(game_ids) = self.find_games_by_platform_id(&conn, &platform_id.platform, &platform_id.app_id) {
-                for game_id in game_ids {
-                    if !candidate_games.iter().any(|c| c.game_id == game_id) {
-                        candidate_games.push(self.build_candidate(&conn, game_id, format!("{}_{}", platform_id.platform, platform_id.app_id), platform_id.confidence_weight).await?);
-                    }
-                }
-                identification_methods.push(platform_id.platform.clone());
-            }
-        }
+        let (candidate_games, identification_methods) = self.collect_candidates(&conn, evidence)?;
 
-        // Search by process name patterns
-        if let Ok(game_ids) = self.find_games_by_process_name(&conn, &evidence.process_name) {
-            for game_id in game_ids {
-                if !candidate_games.iter().any(|c| c.game_id == game_id) {
-                    candidate_games.push(self.build_candidate(&conn, game_id, format!("process_{}", evidence.process_name), 60.0).await?);
-                }
-            }
-            identification_methods.push("process_name".to_string());
-        }
-
-        // Calculate overall confidence
+        // Calculate overall confidence, letting strong runtime behaviour boost the result
         let (confidence_score, requires_confirmation, conflict_reason) =
-            self.confidence_scorer.calculate_overall_confidence(&candidate_games);
+            scorer.calculate_overall_confidence_with_runtime(&candidate_games, evidence.runtime_confidence);
 
         let selected_game = if candidate_games.len() == 1 {
             Some(candidate_games[0].game_id)
@@ -204,7 +497,7 @@ impl GameIdentificationEngine {
             None
         };
 
-        Ok(GameIdentification {
+        let identification = GameIdentification {
             game_id: selected_game,
             candidate_games,
             confidence_score,
@@ -214,7 +507,60 @@ impl GameIdentificationEngine {
             requires_manual_confirmation: requires_confirmation,
             identified_at: Utc::now(),
             conflict_reason,
-        })
+        };
+        DetectionHistory::record_if_enabled(&conn, &identification, Some(&evidence.process_name));
+        Ok(identification)
+    }
+
+    /// Search the database for every game matching the evidence.
+    ///
+    /// A game matched several ways appears once, with all matched identifiers and its best score.
+    fn collect_candidates(&self, conn: &Connection, evidence: &IdentificationEvidence) -> Result<(Vec<GameCandidate>, Vec<String>), DetectionError> {
+        let mut candidate_games: Vec<GameCandidate> = Vec::new();
+        let mut identification_methods = Vec::new();
+        let mut matches: Vec<(i64, String, f32)> = Vec::new();
+
+        // Search by executable hash
+        if let Some(hash) = &evidence.executable_hash {
+            if let Ok(game_ids) = self.find_games_by_hash(conn, hash) {
+                matches.extend(game_ids.into_iter().map(|id| (id, hash.clone(), 95.0)));
+                identification_methods.push("executable_hash".to_string());
+            }
+        }
+
+        // Search by platform IDs
+        for platform_id in &evidence.platform_ids {
+            if let Ok(game_ids) = self.find_games_by_platform_id(conn, &platform_id.platform, &platform_id.app_id) {
+                let identifier = format!("{}_{}", platform_id.platform, platform_id.app_id);
+                matches.extend(game_ids.into_iter().map(|id| (id, identifier.clone(), platform_id.confidence_weight)));
+                identification_methods.push(platform_id.platform.clone());
+            }
+        }
+
+        // Search by process name patterns
+        if let Ok(game_ids) = self.find_games_by_process_name(conn, &evidence.process_name) {
+            let identifier = format!("process_{}", evidence.process_name);
+            matches.extend(game_ids.into_iter().map(|id| (id, identifier.clone(), 60.0)));
+            identification_methods.push("process_name".to_string());
+        }
+
+        for (game_id, identifier, confidence) in matches {
+            match candidate_games.iter_mut().find(|c| c.game_id == game_id) {
+                Some(existing) => {
+                    if !existing.matched_identifiers.contains(&identifier) {
+                        existing.matched_identifiers.push(identifier);
+                    }
+                    existing.confidence_score = existing.confidence_score.max(confidence);
+                }
+                None => candidate_games.push(self.build_candidate(conn, game_id, identifier, confidence)?),
+            }
+        }
+
+        if evidence.runtime_confidence.is_some() && !candidate_games.is_empty() {
+            identification_methods.push("runtime_behavior".to_string());
+        }
+
+        Ok((candidate_games, identification_methods))
     }
 
     fn resolve_conflict(&self, candidates: &[GameCandidate]) -> Option<i64> {
@@ -237,7 +583,7 @@ impl GameIdentificationEngine {
             .map(|c| c.game_id)
     }
 
-    async fn build_candidate(&self, conn: &Connection, game_id: i64, matched_identifier: String, confidence: f32) -> Result<GameCandidate, DetectionError> {
+    fn build_candidate(&self, conn: &Connection, game_id: i64, matched_identifier: String, confidence: f32) -> Result<GameCandidate, DetectionError> {
         let mut stmt = conn.prepare(
             "SELECT name, platform, platform_app_id FROM games WHERE id = ?"
         )?;
@@ -309,7 +655,7 @@ impl GameIdentificationEngine {
             .map(|p| p.to_string_lossy().to_string())
     }
 
-    async fn is_game_process(&self, process_info: &ProcessInfo) -> bool {
+    async fn is_game_process(&self, process_info: &ProcessInfo, ignored: &IgnoreList) -> bool {
         // Simple heuristic: check if process name doesn't contain system processes
         let system_processes = HashSet::from([
             "explorer.exe", "svchost.exe", "winlogon.exe", "csrss.exe", "smss.exe",
@@ -317,18 +663,145 @@ impl GameIdentificationEngine {
             "launchd", "kernel_task", "WindowServer", "Dock"
         ]);
 
-        // Check file size (games are typically larger than 10MB)
+        if ignored.matches_name(&process_info.name, &process_info.executable_path) {
+            return false;
+        }
+
+        // Check file size (games are typically larger than the configured minimum, 10MB by default)
         if let Ok(metadata) = tokio::fs::metadata(&process_info.executable_path).await {
-            if metadata.len() < 10_000_000 { // 10MB
+            if metadata.len() < self.min_executable_size().await {
                 return false;
             }
         }
 
-        !system_processes.contains(&process_info.name.to_lowercase().as_str())
+        if system_processes.contains(&process_info.name.to_lowercase().as_str()) {
+            return false;
+        }
+
+        if ignored.has_hashes() {
+            if let Ok(Some(hash)) = self.executable_analyzer.executable_hash(&process_info.executable_path).await {
+                return !ignored.matches_hash(&hash);
+            }
+        }
+        true
     }
 
-    pub async fn clear_cache(&self) {
+    /// Drop every cached identification, returning how many there were
+    pub async fn clear_cache(&self) -> usize {
+        *self.last_scan.write().await = None;
         let mut cache = self.cache.write().await;
+        let cleared = cache.len();
         cache.clear();
+        cleared
+    }
+
+    /// Clear this engine's identification cache and the caches of its detectors.
+    /// The confidence weights are reloaded from settings on the next identification.
+    pub async fn clear_all_caches(&self) -> CacheClearReport {
+        *self.confidence_scorer.write().await = None;
+        CacheClearReport {
+            identifications: self.clear_cache().await,
+            platform: self.platform_client.clear_cache().await,
+            executable: self.executable_analyzer.clear_cache().await,
+            runtime: self.runtime_detector.clear_caches().await,
+            pcgw: 0,
+        }
+    }
+
+    /// Drop cached identifications that selected or considered `game_id`
+    pub async fn invalidate_game(&self, game_id: i64) {
+        *self.last_scan.write().await = None;
+        let mut cache = self.cache.write().await;
+        cache.retain(|_, identification| {
+            identification.game_id != Some(game_id)
+                && !identification.candidate_games.iter().any(|c| c.game_id == game_id)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn process_for(path: &std::path::Path) -> ProcessInfo {
+        ProcessInfo {
+            pid: 1,
+            name: "tinygame".to_string(),
+            executable_path: path.to_string_lossy().to_string(),
+            command_line: Vec::new(),
+            window_title: None,
+            parent_pid: None,
+            creation_time: None,
+            memory_usage: 0,
+            cpu_usage: 0.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_min_executable_size_boundary() {
+        let dir = TempDir::new().unwrap();
+        let below = dir.path().join("below.exe");
+        let at = dir.path().join("at.exe");
+        std::fs::write(&below, vec![0u8; 1023]).unwrap();
+        std::fs::write(&at, vec![0u8; 1024]).unwrap();
+
+        let conn = Arc::new(tokio::sync::Mutex::new(Connection::open_in_memory().unwrap()));
+        let resolver = ManifestResolver::new().unwrap();
+
+        let default_engine = GameIdentificationEngine::new(conn.clone(), resolver.clone());
+        assert!(!default_engine.is_game_process(&process_for(&at), &IgnoreList::default()).await);
+
+        let engine = GameIdentificationEngine::new(conn, resolver).with_min_executable_size(1024);
+        assert!(!engine.is_game_process(&process_for(&below), &IgnoreList::default()).await);
+        assert!(engine.is_game_process(&process_for(&at), &IgnoreList::default()).await);
+    }
+
+    #[tokio::test]
+    async fn test_last_scan_respects_max_age() {
+        let conn = Arc::new(tokio::sync::Mutex::new(Connection::open_in_memory().unwrap()));
+        let engine = GameIdentificationEngine::new(conn, ManifestResolver::new().unwrap());
+        assert!(engine.last_scan(Duration::seconds(60)).await.is_none());
+
+        *engine.last_scan.write().await = Some((Utc::now() - Duration::seconds(120), Vec::new()));
+        assert!(engine.last_scan(Duration::seconds(60)).await.is_none());
+        assert!(engine.last_scan(Duration::seconds(300)).await.is_some());
+
+        engine.clear_cache().await;
+        assert!(engine.last_scan(Duration::seconds(300)).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_warm_cache_caches_matching_executables() {
+        let dir = TempDir::new().unwrap();
+        let exe = dir.path().join("game.exe");
+        let changed = dir.path().join("changed.exe");
+        std::fs::write(&exe, vec![1u8; 2048]).unwrap();
+        std::fs::write(&changed, vec![2u8; 2048]).unwrap();
+
+        let db = Connection::open_in_memory().unwrap();
+        crate::database::schema::DatabaseSchema::create_tables(&db).unwrap();
+        let engine = GameIdentificationEngine::new(Arc::new(tokio::sync::Mutex::new(db)), ManifestResolver::new().unwrap())
+            .with_min_executable_size(1024);
+        let hash = engine.executable_analyzer.executable_hash(&exe.to_string_lossy()).await.unwrap().unwrap();
+
+        {
+            let conn = engine.db_conn.lock().await;
+            for (name, path, identifier) in [("Game", &exe, hash.as_str()), ("Changed", &changed, "stale"), ("Gone", &dir.path().join("gone.exe"), "other")] {
+                conn.execute("INSERT INTO games (name, platform, executable_path) VALUES (?, 'other', ?)", params![name, path.to_string_lossy()]).unwrap();
+                conn.execute(
+                    "INSERT INTO game_identifiers (game_id, identifier_type, identifier_value) VALUES (?, 'executable_hash', ?)",
+                    params![conn.last_insert_rowid(), identifier],
+                ).unwrap();
+            }
+        }
+
+        let report = engine.warm_cache().await.unwrap();
+        assert_eq!((report.games, report.hashed, report.warmed), (3, 2, 1));
+
+        let cached = engine.identify_game_from_path(&exe.to_string_lossy()).await.unwrap();
+        assert_eq!(cached.game_id, Some(1));
+        assert_eq!(cached.identification_methods, vec!["executable_hash".to_string()]);
     }
 }
@@ -1,7 +1,12 @@
 use crate::detection::DetectionError;
+use crate::progress::{ProgressReporter, ProgressSink};
 use sha2::{Sha256, Digest};
 use std::fs;
+use std::io::Read;
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
 use tokio::task;
 use chrono::{DateTime, Utc};
 
@@ -18,18 +23,75 @@ pub struct ExecutableSignature {
     pub analyzed_at: DateTime<Utc>,
 }
 
+/// Executables smaller than this are not considered games (typically launchers or tools)
+pub const MIN_GAME_EXECUTABLE_SIZE: u64 = 10_000_000; // 10MB
+
+/// `app_settings` key overriding `MIN_GAME_EXECUTABLE_SIZE`
+pub const MIN_EXECUTABLE_SIZE_SETTING: &str = "min_game_executable_size";
+
+/// The configured minimum game executable size, falling back to `MIN_GAME_EXECUTABLE_SIZE`
+pub fn configured_min_executable_size(conn: &rusqlite::Connection) -> u64 {
+    crate::database::settings::AppSettings::get(conn, MIN_EXECUTABLE_SIZE_SETTING)
+        .ok()
+        .flatten()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(MIN_GAME_EXECUTABLE_SIZE)
+}
+
+const HASH_BUFFER_SIZE: usize = 1024 * 1024;
+
 #[derive(Debug, Clone)]
 pub struct ExecutableAnalyzer {
     cache: std::sync::Arc<tokio::sync::RwLock<std::collections::HashMap<String, ExecutableSignature>>>,
+    /// path -> (size, modified, hash), reused while the file is unchanged
+    hash_cache: Arc<tokio::sync::RwLock<std::collections::HashMap<String, (u64, Option<std::time::SystemTime>, String)>>>,
+    /// Limits how many executables are hashed at once; shared between clones
+    hash_permits: Arc<Semaphore>,
+    /// Shared between clones so a settings change reaches every copy
+    min_hash_size: Arc<AtomicU64>,
+    /// Receives hashing progress for large executables
+    progress: Option<ProgressSink>,
 }
 
 impl ExecutableAnalyzer {
     pub fn new() -> Self {
+        Self::with_max_concurrent_hashes(Self::default_max_concurrent_hashes())
+    }
+
+    /// Create an analyzer that hashes at most `max_concurrent_hashes` files at a time
+    pub fn with_max_concurrent_hashes(max_concurrent_hashes: usize) -> Self {
         Self {
             cache: std::sync::Arc::new(tokio::sync::RwLock::new(std::collections::HashMap::new())),
+            hash_cache: Arc::new(tokio::sync::RwLock::new(std::collections::HashMap::new())),
+            hash_permits: Arc::new(Semaphore::new(max_concurrent_hashes.max(1))),
+            min_hash_size: Arc::new(AtomicU64::new(MIN_GAME_EXECUTABLE_SIZE)),
+            progress: None,
         }
     }
 
+    /// Skip hashing executables smaller than `min_hash_size` bytes
+    pub fn with_min_hash_size(self, min_hash_size: u64) -> Self {
+        self.set_min_hash_size(min_hash_size);
+        self
+    }
+
+    /// Report hashing progress to `sink`
+    pub fn with_progress(mut self, sink: ProgressSink) -> Self {
+        self.progress = Some(sink);
+        self
+    }
+
+    pub fn set_min_hash_size(&self, min_hash_size: u64) {
+        self.min_hash_size.store(min_hash_size, Ordering::Relaxed);
+    }
+
+    /// One hash job per physical core, falling back to logical parallelism
+    pub fn default_max_concurrent_hashes() -> usize {
+        sysinfo::System::new().physical_core_count()
+            .or_else(|| std::thread::available_parallelism().ok().map(|n| n.get()))
+            .unwrap_or(1)
+    }
+
     pub async fn analyze_executable(&self, file_path: &str) -> Result<ExecutableSignature, DetectionError> {
         // Check cache first
         {
@@ -51,6 +113,32 @@ impl ExecutableAnalyzer {
         Ok(signature)
     }
 
+    /// SHA-256 of an executable without the rest of the analysis.
+    ///
+    /// Returns `None` for files below the hash size threshold. Hashes are cached by path
+    /// and reused until the file's size or modification time changes.
+    pub async fn executable_hash(&self, file_path: &str) -> Result<Option<String>, DetectionError> {
+        let metadata = tokio::fs::metadata(file_path).await?;
+        if metadata.len() < self.min_hash_size.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+        let modified = metadata.modified().ok();
+
+        {
+            let cache = self.hash_cache.read().await;
+            if let Some((size, cached_modified, hash)) = cache.get(file_path) {
+                if *size == metadata.len() && *cached_modified == modified {
+                    return Ok(Some(hash.clone()));
+                }
+            }
+        }
+
+        let hash = self.calculate_sha256(file_path).await?;
+        self.hash_cache.write().await
+            .insert(file_path.to_string(), (metadata.len(), modified, hash.clone()));
+        Ok(Some(hash))
+    }
+
     async fn analyze_executable_impl(&self, file_path: &str) -> Result<ExecutableSignature, DetectionError> {
         let path = Path::new(file_path);
 
@@ -66,8 +154,12 @@ impl ExecutableAnalyzer {
         let metadata = fs::metadata(path)?;
         let file_size = metadata.len();
 
-        // Calculate SHA-256 hash
-        let file_hash = self.calculate_sha256(file_path).await?;
+        // Calculate SHA-256 hash, unless the size already rules this out as a game
+        let file_hash = if file_size < self.min_hash_size.load(Ordering::Relaxed) {
+            String::new()
+        } else {
+            self.calculate_sha256(file_path).await?
+        };
 
         // Analyze file metadata (version info, etc.)
         let (product_name, company_name, file_version, product_version, original_filename, digital_signature) =
@@ -87,13 +179,36 @@ impl ExecutableAnalyzer {
     }
 
     async fn calculate_sha256(&self, file_path: &str) -> Result<String, DetectionError> {
+        // Hold a permit for the whole job so a library scan can't saturate I/O
+        let _permit = self.hash_permits.acquire().await
+            .map_err(|e| DetectionError::ExecutableAnalysisError(format!("Hash limiter closed: {}", e)))?;
+
         let file_path = file_path.to_string();
+        let sink = self.progress.clone();
         task::spawn_blocking(move || {
             let mut file = fs::File::open(&file_path)?;
+            let progress = sink.map(|sink| {
+                let progress = ProgressReporter::new("executable_hash", None, sink);
+                progress.add_total(1, file.metadata().map(|m| m.len()).unwrap_or(0));
+                progress
+            });
             let mut hasher = Sha256::new();
-            std::io::copy(&mut file, &mut hasher)?;
-            let hash = hasher.finalize();
-            Ok(hex::encode(hash))
+            let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+            loop {
+                let read = file.read(&mut buffer)?;
+                if read == 0 {
+                    break;
+                }
+                hasher.update(&buffer[..read]);
+                if let Some(progress) = &progress {
+                    progress.add_bytes(read as u64);
+                }
+            }
+            if let Some(progress) = &progress {
+                progress.file_done();
+                progress.finish();
+            }
+            Ok(hex::encode(hasher.finalize()))
         }).await.map_err(|e| DetectionError::ExecutableAnalysisError(format!("Hash calculation failed: {}", e)))?
     }
 
@@ -279,9 +394,13 @@ impl ExecutableAnalyzer {
         score.min(95.0) // Cap at 95% unless it's an exact hash match
     }
 
-    pub async fn clear_cache(&self) {
+    pub async fn clear_cache(&self) -> usize {
         let mut cache = self.cache.write().await;
+        let mut hash_cache = self.hash_cache.write().await;
+        let cleared = cache.len() + hash_cache.len();
         cache.clear();
+        hash_cache.clear();
+        cleared
     }
 
     pub async fn get_cached_signature(&self, file_path: &str) -> Option<ExecutableSignature> {
@@ -310,6 +429,20 @@ mod tests {
         assert_eq!(hash_result, expected);
     }
 
+    #[tokio::test]
+    async fn test_small_executable_skips_hash() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(b"tiny").unwrap();
+
+        let analyzer = ExecutableAnalyzer::with_max_concurrent_hashes(1);
+        let signature = analyzer.analyze_executable(temp_file.path().to_str().unwrap()).await.unwrap();
+        assert!(signature.file_hash.is_empty());
+
+        let analyzer = ExecutableAnalyzer::with_max_concurrent_hashes(1).with_min_hash_size(0);
+        let signature = analyzer.analyze_executable(temp_file.path().to_str().unwrap()).await.unwrap();
+        assert!(!signature.file_hash.is_empty());
+    }
+
     #[tokio::test]
     async fn test_nonexistent_file() {
         let analyzer = ExecutableAnalyzer::new();
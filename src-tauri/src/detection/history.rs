@@ -0,0 +1,223 @@
+use crate::detection::{GameIdentification, IdentificationConfidence};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// `app_settings` key controlling whether identifications are logged to `detection_history`
+pub const DETECTION_HISTORY_SETTING: &str = "log_detection_history";
+/// Newest rows kept in `detection_history`; older ones are pruned as new ones are logged
+pub const MAX_DETECTION_HISTORY_ROWS: i64 = 10_000;
+/// Scores below this fall in the uncertain band (`Low` and `Uncertain`)
+pub const UNCERTAIN_BELOW: f32 = IdentificationConfidence::Medium as i32 as f32;
+
+/// The `log_detection_history` setting, defaulting to off
+pub fn is_enabled(conn: &Connection) -> bool {
+    crate::database::settings::AppSettings::get(conn, DETECTION_HISTORY_SETTING)
+        .ok()
+        .flatten()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(false)
+}
+
+/// One logged identification
+#[derive(Debug, Clone, Serialize)]
+pub struct DetectionHistoryEntry {
+    pub game_id: Option<i64>,
+    pub process_name: Option<String>,
+    pub confidence_score: f32,
+    pub confidence_level: IdentificationConfidence,
+    pub identification_methods: Vec<String>,
+    pub requires_manual_confirmation: bool,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// How confidently a game has been identified across its logged detections
+#[derive(Debug, Clone, Serialize)]
+pub struct DetectionHistorySummary {
+    /// `None` groups detections that matched no game
+    pub game_id: Option<i64>,
+    pub detections: usize,
+    pub average_confidence: f32,
+    pub min_confidence: f32,
+    pub max_confidence: f32,
+    /// Detections scoring below `UNCERTAIN_BELOW`
+    pub uncertain_count: usize,
+    pub manual_confirmation_count: usize,
+    pub methods: Vec<String>,
+    pub last_detected_at: DateTime<Utc>,
+}
+
+impl DetectionHistorySummary {
+    /// Share of detections in the uncertain band
+    pub fn uncertain_ratio(&self) -> f32 {
+        self.uncertain_count as f32 / self.detections as f32
+    }
+}
+
+pub struct DetectionHistory;
+
+impl DetectionHistory {
+    /// Log an identification if history logging is enabled. Failures are logged, never returned,
+    /// so detection itself is unaffected.
+    pub fn record_if_enabled(conn: &Connection, identification: &GameIdentification, process_name: Option<&str>) {
+        if !is_enabled(conn) {
+            return;
+        }
+        if let Err(e) = Self::record(conn, identification, process_name)
+            .and_then(|_| Self::prune(conn, MAX_DETECTION_HISTORY_ROWS))
+        {
+            crate::logger::warn("DETECTION", "Failed to record detection history", Some(&e.to_string()));
+        }
+    }
+
+    /// Delete all but the `keep` newest rows, returning how many were deleted
+    pub fn prune(conn: &Connection, keep: i64) -> rusqlite::Result<usize> {
+        conn.execute(
+            "DELETE FROM detection_history WHERE id <= (SELECT id FROM detection_history ORDER BY id DESC LIMIT 1 OFFSET ?)",
+            [keep],
+        )
+    }
+
+    pub fn record(conn: &Connection, identification: &GameIdentification, process_name: Option<&str>) -> rusqlite::Result<()> {
+        let level = serde_json::to_string(&identification.confidence_level)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let methods = serde_json::to_string(&identification.identification_methods)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+
+        conn.execute(
+            "INSERT INTO detection_history
+                (game_id, process_name, confidence_score, confidence_level, identification_methods, requires_manual_confirmation, detected_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            params![
+                identification.game_id,
+                process_name,
+                identification.confidence_score,
+                level,
+                methods,
+                identification.requires_manual_confirmation,
+                identification.identified_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Logged detections, newest first, optionally for one game and since a point in time
+    pub fn entries(conn: &Connection, game_id: Option<i64>, since: Option<DateTime<Utc>>, limit: Option<usize>) -> rusqlite::Result<Vec<DetectionHistoryEntry>> {
+        let mut stmt = conn.prepare(
+            "SELECT game_id, process_name, confidence_score, confidence_level, identification_methods, requires_manual_confirmation, detected_at
+             FROM detection_history
+             WHERE (?1 IS NULL OR game_id = ?1) AND (?2 IS NULL OR detected_at >= ?2)
+             ORDER BY detected_at DESC
+             LIMIT ?3"
+        )?;
+
+        let rows = stmt.query_map(
+            params![game_id, since.map(|t| t.to_rfc3339()), limit.map_or(-1, |limit| limit as i64)],
+            |row| {
+                let level: String = row.get(3)?;
+                let methods: String = row.get(4)?;
+                let detected_at: String = row.get(6)?;
+                Ok(DetectionHistoryEntry {
+                    game_id: row.get(0)?,
+                    process_name: row.get(1)?,
+                    confidence_score: row.get(2)?,
+                    confidence_level: serde_json::from_str(&level)
+                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e)))?,
+                    identification_methods: serde_json::from_str(&methods)
+                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(4, rusqlite::types::Type::Text, Box::new(e)))?,
+                    requires_manual_confirmation: row.get(5)?,
+                    detected_at: DateTime::parse_from_rfc3339(&detected_at)
+                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(6, rusqlite::types::Type::Text, Box::new(e)))?
+                        .into(),
+                })
+            },
+        )?;
+        rows.collect()
+    }
+
+    /// Per-game confidence statistics since a point in time, least certain games first
+    pub fn summary(conn: &Connection, since: Option<DateTime<Utc>>) -> rusqlite::Result<Vec<DetectionHistorySummary>> {
+        let entries = Self::entries(conn, None, since, None)?;
+
+        let mut by_game: BTreeMap<Option<i64>, Vec<DetectionHistoryEntry>> = BTreeMap::new();
+        for entry in entries {
+            by_game.entry(entry.game_id).or_default().push(entry);
+        }
+
+        let mut summaries: Vec<DetectionHistorySummary> = by_game.into_iter().map(|(game_id, entries)| {
+            let scores = entries.iter().map(|e| e.confidence_score);
+            let methods: BTreeSet<&String> = entries.iter().flat_map(|e| &e.identification_methods).collect();
+            DetectionHistorySummary {
+                game_id,
+                detections: entries.len(),
+                average_confidence: scores.clone().sum::<f32>() / entries.len() as f32,
+                min_confidence: scores.clone().fold(f32::INFINITY, f32::min),
+                max_confidence: scores.fold(f32::NEG_INFINITY, f32::max),
+                uncertain_count: entries.iter().filter(|e| e.confidence_score < UNCERTAIN_BELOW).count(),
+                manual_confirmation_count: entries.iter().filter(|e| e.requires_manual_confirmation).count(),
+                methods: methods.into_iter().cloned().collect(),
+                // Entries come newest first
+                last_detected_at: entries[0].detected_at,
+            }
+        }).collect();
+
+        summaries.sort_by(|a, b| b.uncertain_ratio().total_cmp(&a.uncertain_ratio()).then(b.detections.cmp(&a.detections)));
+        Ok(summaries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn identification(game_id: Option<i64>, score: f32, confirm: bool, at: DateTime<Utc>) -> GameIdentification {
+        GameIdentification {
+            game_id,
+            candidate_games: Vec::new(),
+            confidence_score: score,
+            confidence_level: score.into(),
+            identification_methods: vec!["process_name".to_string()],
+            process_info: None,
+            requires_manual_confirmation: confirm,
+            identified_at: at,
+            conflict_reason: None,
+        }
+    }
+
+    #[test]
+    fn test_logging_is_opt_in_and_summary_ranks_uncertain_games_first() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::DatabaseSchema::create_tables(&conn).unwrap();
+        let now = Utc::now();
+
+        DetectionHistory::record_if_enabled(&conn, &identification(Some(1), 40.0, true, now), Some("game.exe"));
+        assert!(DetectionHistory::entries(&conn, None, None, Some(10)).unwrap().is_empty());
+
+        crate::database::settings::AppSettings::set(&conn, DETECTION_HISTORY_SETTING, "true").unwrap();
+        DetectionHistory::record_if_enabled(&conn, &identification(Some(1), 95.0, false, now - Duration::days(10)), None);
+        DetectionHistory::record_if_enabled(&conn, &identification(Some(1), 90.0, false, now), Some("game.exe"));
+        DetectionHistory::record_if_enabled(&conn, &identification(Some(2), 40.0, true, now - Duration::hours(1)), None);
+        DetectionHistory::record_if_enabled(&conn, &identification(Some(2), 70.0, false, now), Some("game.exe"));
+
+        let entries = DetectionHistory::entries(&conn, Some(2), None, Some(10)).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].confidence_level, IdentificationConfidence::Medium);
+        assert_eq!(entries[1].identification_methods, vec!["process_name".to_string()]);
+
+        let summary = DetectionHistory::summary(&conn, Some(now - Duration::days(1))).unwrap();
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].game_id, Some(2));
+        assert_eq!((summary[0].uncertain_count, summary[0].manual_confirmation_count), (1, 1));
+        assert_eq!(summary[0].average_confidence, 55.0);
+        assert_eq!(summary[1].detections, 1);
+        assert_eq!(summary[1].min_confidence, 90.0);
+
+        assert_eq!(DetectionHistory::prune(&conn, 2).unwrap(), 2);
+        assert_eq!(DetectionHistory::prune(&conn, 2).unwrap(), 0);
+        let kept = DetectionHistory::entries(&conn, None, None, Some(10)).unwrap();
+        assert_eq!(kept.len(), 2);
+        assert!(kept.iter().all(|entry| entry.game_id == Some(2)));
+    }
+}
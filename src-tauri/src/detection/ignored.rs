@@ -0,0 +1,141 @@
+//! Processes the user dismissed as not being games
+//!
+//! Entries match a process by name (case-insensitive, against either the process name or
+//! its executable's file name) or by the hash of its executable, and survive restarts so a
+//! dismissed false positive such as Discord or OBS stays dismissed.
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IgnoredProcess {
+    pub id: i64,
+    pub process_name: Option<String>,
+    pub executable_hash: Option<String>,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The ignored names and hashes, lowercased for matching
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreList {
+    names: HashSet<String>,
+    hashes: HashSet<String>,
+}
+
+impl IgnoreList {
+    /// Whether the process name or executable file name is ignored
+    pub fn matches_name(&self, process_name: &str, executable_path: &str) -> bool {
+        let file_name = Path::new(executable_path).file_name().map(|n| n.to_string_lossy().to_lowercase());
+        self.names.contains(&process_name.to_lowercase())
+            || file_name.is_some_and(|name| self.names.contains(&name))
+    }
+
+    pub fn matches_hash(&self, executable_hash: &str) -> bool {
+        self.hashes.contains(&executable_hash.to_lowercase())
+    }
+
+    /// Hashing is only worth it when some entry is a hash
+    pub fn has_hashes(&self) -> bool {
+        !self.hashes.is_empty()
+    }
+}
+
+pub struct IgnoredProcesses;
+
+impl IgnoredProcesses {
+    pub fn list(conn: &Connection) -> rusqlite::Result<Vec<IgnoredProcess>> {
+        let mut stmt = conn.prepare(
+            "SELECT id, process_name, executable_hash, reason, created_at FROM ignored_processes ORDER BY id"
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let created_at: String = row.get(4)?;
+            Ok(IgnoredProcess {
+                id: row.get(0)?,
+                process_name: row.get(1)?,
+                executable_hash: row.get(2)?,
+                reason: row.get(3)?,
+                created_at: DateTime::parse_from_rfc3339(&created_at)
+                    .map_err(|e| rusqlite::Error::FromSqlConversionFailure(4, rusqlite::types::Type::Text, Box::new(e)))?
+                    .into(),
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Names and hashes to skip; an unreadable table ignores nothing
+    pub fn load(conn: &Connection) -> IgnoreList {
+        let entries = Self::list(conn).unwrap_or_else(|e| {
+            crate::logger::warn("DETECTION", "Failed to load ignored processes", Some(&e.to_string()));
+            Vec::new()
+        });
+
+        let mut list = IgnoreList::default();
+        for entry in entries {
+            list.names.extend(entry.process_name.map(|name| name.to_lowercase()));
+            list.hashes.extend(entry.executable_hash.map(|hash| hash.to_lowercase()));
+        }
+        list
+    }
+
+    /// Ignore a process by name, executable hash or both. Adding an entry that is already
+    /// ignored returns the existing one.
+    pub fn add(conn: &Connection, process_name: Option<&str>, executable_hash: Option<&str>, reason: Option<&str>) -> rusqlite::Result<IgnoredProcess> {
+        let process_name = process_name.map(str::trim).filter(|n| !n.is_empty()).map(str::to_lowercase);
+        let executable_hash = executable_hash.map(str::trim).filter(|h| !h.is_empty()).map(str::to_lowercase);
+
+        // An entry with neither fails the table's CHECK constraint
+        conn.execute(
+            "INSERT INTO ignored_processes (process_name, executable_hash, reason, created_at) VALUES (?, ?, ?, ?)
+             ON CONFLICT DO NOTHING",
+            params![process_name, executable_hash, reason, Utc::now().to_rfc3339()],
+        )?;
+
+        Self::list(conn)?
+            .into_iter()
+            .find(|entry| {
+                (process_name.is_some() && entry.process_name == process_name)
+                    || (executable_hash.is_some() && entry.executable_hash == executable_hash)
+            })
+            .ok_or(rusqlite::Error::QueryReturnedNoRows)
+    }
+
+    /// Stop ignoring an entry; returns whether it existed
+    pub fn remove(conn: &Connection, id: i64) -> rusqlite::Result<bool> {
+        Ok(conn.execute("DELETE FROM ignored_processes WHERE id = ?", [id])? > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ignored_entries_match_by_name_and_hash() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::database::schema::DatabaseSchema::create_tables(&conn).unwrap();
+
+        let discord = IgnoredProcesses::add(&conn, Some("Discord"), None, Some("chat")).unwrap();
+        let again = IgnoredProcesses::add(&conn, Some("discord"), None, None).unwrap();
+        assert_eq!(discord.id, again.id);
+        IgnoredProcesses::add(&conn, None, Some("ABC123"), None).unwrap();
+        assert!(IgnoredProcesses::add(&conn, Some("  "), None, None).is_err());
+
+        let list = IgnoredProcesses::load(&conn);
+        assert!(list.matches_name("DISCORD", "/usr/bin/whatever"));
+        assert!(list.matches_name("electron", "/opt/discord/Discord"));
+        assert!(!list.matches_name("obs", "/usr/bin/obs"));
+        assert!(list.matches_hash("abc123"));
+
+        assert!(IgnoredProcesses::remove(&conn, discord.id).unwrap());
+        assert!(!IgnoredProcesses::remove(&conn, discord.id).unwrap());
+        assert!(!IgnoredProcesses::load(&conn).matches_name("discord", ""));
+
+        // Without the table nothing is ignored
+        let empty = Connection::open_in_memory().unwrap();
+        assert!(!IgnoredProcesses::load(&empty).has_hashes());
+    }
+}
@@ -4,6 +4,9 @@ pub mod process_monitor;
 pub mod executable_analysis;
 pub mod runtime_detection;
 pub mod confidence_scorer;
+pub mod history;
+pub mod proton;
+pub mod ignored;
 
 pub use engine::GameIdentificationEngine;
 
@@ -59,6 +62,9 @@ pub struct ProcessInfo {
     pub pid: u32,
     pub name: String,
     pub executable_path: String,
+    /// Arguments the process was started with, the executable first
+    #[serde(default)]
+    pub command_line: Vec<String>,
     pub window_title: Option<String>,
     pub parent_pid: Option<u32>,
     pub creation_time: Option<DateTime<Utc>>,
@@ -66,6 +72,14 @@ pub struct ProcessInfo {
     pub cpu_usage: f32,
 }
 
+/// Identifiers recorded when the user links a running process to a game by hand
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManualIdentification {
+    pub game_id: i64,
+    pub process_info: ProcessInfo,
+    pub recorded_identifiers: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IdentificationEvidence {
     pub executable_hash: Option<String>,
@@ -75,6 +89,8 @@ pub struct IdentificationEvidence {
     pub file_signature: Option<String>,
     pub installation_path: Option<String>,
     pub game_features: Vec<GameFeature>,
+    /// Weighted runtime behaviour score (0-100) when identifying a live process
+    pub runtime_confidence: Option<f32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
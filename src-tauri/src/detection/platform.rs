@@ -1,9 +1,12 @@
+use crate::database::DatabaseConnection;
 use crate::detection::{DetectionError, PlatformIdentifier};
-use reqwest::Client;
+use chrono::Utc;
+use reqwest::{Client, StatusCode};
+use rusqlite::{Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
-use tokio::time::{timeout, Duration};
+use tokio::time::{timeout, Duration, Instant};
 use regex::Regex;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,10 +18,20 @@ pub struct PlatformGameInfo {
     pub install_location: Option<String>,
 }
 
+/// How long Steam store responses stay cached
+const STEAM_CACHE_TTL_DAYS: i64 = 7;
+/// Back-off used when a 429 response carries no usable `Retry-After`
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(60);
+
 #[derive(Debug, Clone)]
 pub struct PlatformApiClient {
     http_client: Client,
     cache: std::sync::Arc<tokio::sync::RwLock<HashMap<String, PlatformGameInfo>>>,
+    db_conn: Option<DatabaseConnection>,
+    /// Set after an HTTP 429; Steam requests fail fast until this passes
+    steam_retry_at: std::sync::Arc<tokio::sync::RwLock<Option<Instant>>>,
+    /// app id -> store name, `None` when the store has no page for the app
+    steam_names: std::sync::Arc<tokio::sync::RwLock<HashMap<String, Option<String>>>>,
 }
 
 impl PlatformApiClient {
@@ -30,9 +43,18 @@ impl PlatformApiClient {
                 .build()
                 .unwrap_or_else(|_| Client::new()),
             cache: std::sync::Arc::new(tokio::sync::RwLock::new(HashMap::new())),
+            db_conn: None,
+            steam_retry_at: std::sync::Arc::new(tokio::sync::RwLock::new(None)),
+            steam_names: std::sync::Arc::new(tokio::sync::RwLock::new(HashMap::new())),
         }
     }
 
+    /// Cache Steam store responses in the database
+    pub fn with_database(mut self, db_conn: DatabaseConnection) -> Self {
+        self.db_conn = Some(db_conn);
+        self
+    }
+
     pub async fn get_platform_info(&self, executable_path: &str) -> Result<PlatformGameInfo, DetectionError> {
         // Check cache first
         {
@@ -291,10 +313,31 @@ impl PlatformApiClient {
         Ok(info)
     }
 
+    /// Fetch a game's store details from Steam's `appdetails` endpoint.
+    ///
+    /// Responses are cached for a week when a database is attached. After an HTTP 429
+    /// further requests fail immediately until the `Retry-After` period has passed.
     pub async fn fetch_steam_game_info(&self, app_id: &str) -> Result<serde_json::Value, DetectionError> {
-        // Fetch game info from Steam API (if needed)
-        // This would be useful for getting game names and metadata
-        // but requires Steam API key and is rate-limited
+        if let Some(conn) = &self.db_conn {
+            let cached = {
+                let guard = conn.lock().await;
+                SteamStoreCache::get(&guard, app_id)
+            };
+            if let Ok(Some(cached)) = cached {
+                if let Ok(json) = serde_json::from_str(&cached) {
+                    return Ok(json);
+                }
+            }
+        }
+
+        if let Some(retry_at) = *self.steam_retry_at.read().await {
+            let now = Instant::now();
+            if retry_at > now {
+                return Err(DetectionError::PlatformApiError(
+                    format!("Steam API rate limited, retry in {}s", (retry_at - now).as_secs().max(1))
+                ));
+            }
+        }
 
         let url = format!("https://store.steampowered.com/api/appdetails?appids={}&cc=us&l=english", app_id);
 
@@ -304,6 +347,21 @@ impl PlatformApiClient {
         ).await.map_err(|_| DetectionError::PlatformApiError("Steam API timeout".to_string()))?
             .map_err(|e| DetectionError::PlatformApiError(format!("Steam API error: {}", e)))?;
 
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response.headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(parse_retry_after)
+                .unwrap_or(DEFAULT_RETRY_AFTER);
+
+            *self.steam_retry_at.write().await = Some(Instant::now() + retry_after);
+            crate::logger::warn("PLATFORM_API", "Steam API rate limited", Some(&format!("Retrying after {}s", retry_after.as_secs())));
+
+            return Err(DetectionError::PlatformApiError(
+                format!("Steam API rate limited, retry in {}s", retry_after.as_secs())
+            ));
+        }
+
         if !response.status().is_success() {
             return Err(DetectionError::PlatformApiError(
                 format!("Steam API returned status: {}", response.status())
@@ -313,12 +371,35 @@ impl PlatformApiClient {
         let json: serde_json::Value = response.json().await
             .map_err(|e| DetectionError::PlatformApiError(format!("JSON parse error: {}", e)))?;
 
+        if let Some(conn) = &self.db_conn {
+            let guard = conn.lock().await;
+            if let Err(e) = SteamStoreCache::set(&guard, app_id, &json.to_string(), STEAM_CACHE_TTL_DAYS) {
+                crate::logger::warn("PLATFORM_API", &format!("Failed to cache Steam app {}", app_id), Some(&e.to_string()));
+            }
+        }
+
         Ok(json)
     }
 
-    pub async fn clear_cache(&self) {
+    /// Canonical store name of a Steam app, or `None` when the store doesn't know the app
+    pub async fn resolve_steam_game_name(&self, app_id: &str) -> Result<Option<String>, DetectionError> {
+        if let Some(name) = self.steam_names.read().await.get(app_id) {
+            return Ok(name.clone());
+        }
+
+        let json = self.fetch_steam_game_info(app_id).await?;
+        let name = steam_name_from_appdetails(&json, app_id);
+        self.steam_names.write().await.insert(app_id.to_string(), name.clone());
+        Ok(name)
+    }
+
+    pub async fn clear_cache(&self) -> usize {
+        let mut steam_names = self.steam_names.write().await;
         let mut cache = self.cache.write().await;
+        let cleared = cache.len() + steam_names.len();
         cache.clear();
+        steam_names.clear();
+        cleared
     }
 
     pub async fn get_cached_info(&self, executable_path: &str) -> Option<PlatformGameInfo> {
@@ -327,6 +408,56 @@ impl PlatformApiClient {
     }
 }
 
+/// Parse a `Retry-After` header given either as seconds or as an HTTP date
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let seconds = (date.with_timezone(&Utc) - Utc::now()).num_seconds().max(0);
+    Some(Duration::from_secs(seconds as u64))
+}
+
+/// The `name` of a successful `appdetails` response
+fn steam_name_from_appdetails(json: &serde_json::Value, app_id: &str) -> Option<String> {
+    let details = json.get(app_id)?;
+    if !details.get("success").and_then(|v| v.as_bool()).unwrap_or(false) {
+        return None;
+    }
+    details.get("data")?
+        .get("name")?
+        .as_str()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
+
+/// Steam store responses stored in `steam_store_cache`, in the style of `PcgwCache`
+pub struct SteamStoreCache;
+
+impl SteamStoreCache {
+    pub fn get(conn: &Connection, app_id: &str) -> rusqlite::Result<Option<String>> {
+        conn.query_row(
+            "SELECT response_json FROM steam_store_cache WHERE app_id = ? AND expires_at > ?",
+            [app_id, &Utc::now().to_rfc3339()],
+            |row| row.get(0),
+        ).optional()
+    }
+
+    pub fn set(conn: &Connection, app_id: &str, value: &str, ttl_days: i64) -> rusqlite::Result<()> {
+        let now = Utc::now();
+        let expires_at = now + chrono::Duration::days(ttl_days);
+
+        conn.execute(
+            "INSERT OR REPLACE INTO steam_store_cache (app_id, response_json, fetched_at, expires_at) VALUES (?, ?, ?, ?)",
+            (app_id, value, now.to_rfc3339(), expires_at.to_rfc3339()),
+        )?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -338,6 +469,23 @@ mod tests {
         assert!(client.cache.read().await.is_empty());
     }
 
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), Some(Duration::from_secs(0)));
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+
+    #[test]
+    fn test_steam_name_from_appdetails() {
+        let json = serde_json::json!({ "570": { "success": true, "data": { "name": " Dota 2 " } } });
+        assert_eq!(steam_name_from_appdetails(&json, "570").as_deref(), Some("Dota 2"));
+        assert_eq!(steam_name_from_appdetails(&json, "440"), None);
+
+        let missing = serde_json::json!({ "1": { "success": false } });
+        assert_eq!(steam_name_from_appdetails(&missing, "1"), None);
+    }
+
     #[test]
     fn test_game_name_extraction() {
         let client = PlatformApiClient::new();
@@ -1,7 +1,9 @@
-use crate::detection::{ProcessInfo, DetectionError};
+use crate::detection::{ProcessInfo, DetectionError, proton};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 use tokio::time::{self, Duration};
 use chrono::{DateTime, Utc};
 use sysinfo::{System, ProcessRefreshKind, ProcessesToUpdate};
@@ -9,22 +11,38 @@ use sysinfo::{System, ProcessRefreshKind, ProcessesToUpdate};
 #[cfg(target_os = "windows")]
 use winapi::um::winuser::{FindWindowA, GetWindowTextA};
 
+/// How often the monitor loop refreshes the process list
+const UPDATE_INTERVAL: Duration = Duration::from_millis(1000);
+/// How often the watchdog checks on the monitor loop
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(5);
+/// A loop that hasn't completed an update for this long is considered hung
+const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(30);
+
 #[derive(Debug, Clone)]
 pub struct ProcessMonitor {
     system: Arc<RwLock<System>>,
     monitored_processes: Arc<RwLock<HashMap<u32, ProcessInfo>>>,
     is_monitoring: Arc<RwLock<bool>>,
+    /// The running monitor loop, kept so the watchdog can tell when it has died
+    task: Arc<tokio::sync::Mutex<Option<JoinHandle<()>>>>,
+    /// When the monitor loop last completed an update
+    heartbeat: Arc<RwLock<Option<DateTime<Utc>>>>,
+    restarts: Arc<AtomicUsize>,
 }
 
 impl ProcessMonitor {
     pub fn new() -> Self {
         let mut system = System::new();
-        system.refresh_processes(ProcessesToUpdate::All, false);
+        // Command lines and environments are needed to resolve Proton games
+        system.refresh_processes_specifics(ProcessesToUpdate::All, false, ProcessRefreshKind::everything());
 
         Self {
             system: Arc::new(RwLock::new(system)),
             monitored_processes: Arc::new(RwLock::new(HashMap::new())),
             is_monitoring: Arc::new(RwLock::new(false)),
+            task: Arc::new(tokio::sync::Mutex::new(None)),
+            heartbeat: Arc::new(RwLock::new(None)),
+            restarts: Arc::new(AtomicUsize::new(0)),
         }
     }
 
@@ -34,20 +52,77 @@ impl ProcessMonitor {
             return Ok(());
         }
         *is_monitoring = true;
+        drop(is_monitoring);
+
+        // Start background monitoring task, and a watchdog restarting it if it dies
+        *self.task.lock().await = Some(self.spawn_monitor_loop());
+
+        let watchdog = self.clone();
+        tokio::spawn(async move {
+            loop {
+                time::sleep(WATCHDOG_INTERVAL).await;
+                if !*watchdog.is_monitoring.read().await {
+                    break;
+                }
+                watchdog.restart_if_stopped().await;
+            }
+        });
 
-        // Start background monitoring task
+        Ok(())
+    }
+
+    fn spawn_monitor_loop(&self) -> JoinHandle<()> {
         let system = Arc::clone(&self.system);
         let monitored_processes = Arc::clone(&self.monitored_processes);
         let is_monitoring = Arc::clone(&self.is_monitoring);
+        let heartbeat = Arc::clone(&self.heartbeat);
 
         tokio::spawn(async move {
             while *is_monitoring.read().await {
                 Self::update_process_info(&system, &monitored_processes).await;
-                time::sleep(Duration::from_millis(1000)).await; // Update every second
+                *heartbeat.write().await = Some(Utc::now());
+                time::sleep(UPDATE_INTERVAL).await; // Update every second
             }
-        });
+        })
+    }
 
-        Ok(())
+    /// Restart the monitor loop if it has exited (e.g. after a panic) or stopped reporting
+    /// a heartbeat while monitoring is still on. Returns whether it was restarted.
+    pub async fn restart_if_stopped(&self) -> bool {
+        if !*self.is_monitoring.read().await {
+            return false;
+        }
+
+        let mut task = self.task.lock().await;
+        let reason = match task.as_ref() {
+            None => "is not running".to_string(),
+            Some(handle) if handle.is_finished() => "exited".to_string(),
+            Some(_) => match *self.heartbeat.read().await {
+                Some(beat) if (Utc::now() - beat).to_std().unwrap_or_default() > HEARTBEAT_TIMEOUT => {
+                    format!("has not updated since {}", beat.to_rfc3339())
+                }
+                _ => return false,
+            },
+        };
+
+        if let Some(handle) = task.take() {
+            handle.abort();
+        }
+        *task = Some(self.spawn_monitor_loop());
+        let restarts = self.restarts.fetch_add(1, Ordering::Relaxed) + 1;
+
+        crate::logger::warn("PROCESS_MONITOR", &format!("Process monitor {}, restarted it (restart #{})", reason, restarts), None);
+        true
+    }
+
+    /// When the monitor loop last completed an update
+    pub async fn last_heartbeat(&self) -> Option<DateTime<Utc>> {
+        *self.heartbeat.read().await
+    }
+
+    /// How many times the watchdog has restarted the monitor loop
+    pub fn restart_count(&self) -> usize {
+        self.restarts.load(Ordering::Relaxed)
     }
 
     pub async fn stop_monitoring(&self) {
@@ -60,17 +135,7 @@ impl ProcessMonitor {
 
         if let Some(process) = system.process(sysinfo::Pid::from_u32(pid)) {
             let window_title = self.get_window_title(pid).await;
-
-            Ok(ProcessInfo {
-                pid,
-                name: process.name().to_string_lossy().to_string(),
-                executable_path: process.exe().map(|p| p.display().to_string()).unwrap_or_default(),
-                window_title,
-                parent_pid: process.parent().map(|p| p.as_u32()),
-                creation_time: Some(DateTime::from_timestamp(process.start_time() as i64, 0).unwrap_or_else(Utc::now)),
-                memory_usage: process.memory(),
-                cpu_usage: process.cpu_usage(),
-            })
+            Ok(Self::process_info(pid, process, window_title))
         } else {
             Err(DetectionError::ProcessMonitoringError(format!("Process {} not found", pid)))
         }
@@ -82,22 +147,83 @@ impl ProcessMonitor {
 
         for (pid, process) in system.processes() {
             let window_title = self.get_window_title(pid.as_u32()).await;
-
-            processes.push(ProcessInfo {
-                pid: pid.as_u32(),
-                name: process.name().to_string_lossy().to_string(),
-                executable_path: process.exe().map(|p| p.display().to_string()).unwrap_or_default(),
-                window_title,
-                parent_pid: process.parent().map(|p| p.as_u32()),
-                creation_time: Some(DateTime::from_timestamp(process.start_time() as i64, 0).unwrap_or_else(Utc::now)),
-                memory_usage: process.memory(),
-                cpu_usage: process.cpu_usage(),
-            });
+            processes.push(Self::process_info(pid.as_u32(), process, window_title));
         }
 
         Ok(processes)
     }
 
+    /// Build a `ProcessInfo`, naming Proton/Wine wrappers after the Windows game they run
+    fn process_info(pid: u32, process: &sysinfo::Process, window_title: Option<String>) -> ProcessInfo {
+        let lossy = |args: &[std::ffi::OsString]| args.iter().map(|a| a.to_string_lossy().to_string()).collect::<Vec<_>>();
+        let mut name = process.name().to_string_lossy().to_string();
+        let mut executable_path = process.exe().map(|p| p.display().to_string()).unwrap_or_default();
+        let command_line = lossy(process.cmd());
+
+        if proton::is_wrapper(&name, &executable_path) {
+            if let Some(game_exe) = proton::game_executable(&command_line, &lossy(process.environ())) {
+                // Only a path the host can open is usable for hashing and platform lookups
+                name = game_exe.to_string_lossy().rsplit(['\\', '/']).next().unwrap_or_default().to_string();
+                if game_exe.is_absolute() {
+                    executable_path = game_exe.display().to_string();
+                }
+            }
+        }
+
+        ProcessInfo {
+            pid,
+            name,
+            executable_path,
+            command_line,
+            window_title,
+            parent_pid: process.parent().map(|p| p.as_u32()),
+            creation_time: Some(DateTime::from_timestamp(process.start_time() as i64, 0).unwrap_or_else(Utc::now)),
+            memory_usage: process.memory(),
+            cpu_usage: process.cpu_usage(),
+        }
+    }
+
+    /// Refresh a single process before reading it, so the result doesn't depend on the monitor loop
+    pub async fn get_live_process_info(&self, pid: u32) -> Result<ProcessInfo, DetectionError> {
+        {
+            let mut system = self.system.write().await;
+            system.refresh_processes_specifics(ProcessesToUpdate::Some(&[sysinfo::Pid::from_u32(pid)]), true, ProcessRefreshKind::everything());
+        }
+        self.get_process_info(pid).await
+    }
+
+    /// Take a fresh process snapshot and return the first process whose name (or executable
+    /// file name) matches one of `names` case-insensitively, or whose executable is one of
+    /// `executable_paths`. Proton/Wine wrappers match on the game they run. Blocking; call
+    /// from `spawn_blocking`.
+    pub fn find_matching_process(names: &[String], executable_paths: &[String]) -> Option<u32> {
+        let names: Vec<String> = names.iter().map(|n| n.to_lowercase()).collect();
+        let mut system = System::new();
+        system.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::everything());
+
+        system.processes().iter()
+            .find(|(pid, process)| {
+                // Proton/Wine games are matched by the Windows executable they run
+                let info = Self::process_info(pid.as_u32(), process, None);
+                let exe = std::path::Path::new(&info.executable_path);
+                let exe_name = exe.file_name().map(|n| n.to_string_lossy().to_lowercase());
+
+                names.contains(&info.name.to_lowercase())
+                    || exe_name.is_some_and(|n| names.contains(&n))
+                    || executable_paths.iter().any(|e| std::path::Path::new(e) == exe)
+            })
+            .map(|(pid, _)| pid.as_u32())
+    }
+
+    /// Whether a process with `pid` exists right now, from a fresh look at that process only
+    /// rather than the monitor's snapshot. Blocking; call from `spawn_blocking`.
+    pub fn is_pid_alive(pid: u32) -> bool {
+        let pid = sysinfo::Pid::from_u32(pid);
+        let mut system = System::new();
+        system.refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), true, ProcessRefreshKind::new());
+        system.process(pid).is_some()
+    }
+
     pub async fn is_process_running(&self, pid: u32) -> bool {
         let system = self.system.read().await;
         system.process(sysinfo::Pid::from_u32(pid)).is_some()
@@ -294,3 +420,29 @@ pub struct ProcessStatusSummary {
     pub total_memory_mb: f64,
     pub total_cpu_percent: f32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_restarts_dead_monitor_loop() {
+        let monitor = ProcessMonitor::new();
+        monitor.start_monitoring().await.unwrap();
+        assert!(!monitor.restart_if_stopped().await, "A healthy loop should be left alone");
+
+        // Simulate the loop dying
+        monitor.task.lock().await.as_ref().unwrap().abort();
+        tokio::task::yield_now().await;
+        while !monitor.task.lock().await.as_ref().unwrap().is_finished() {
+            tokio::task::yield_now().await;
+        }
+
+        assert!(monitor.restart_if_stopped().await);
+        assert_eq!(monitor.restart_count(), 1);
+        assert!(!monitor.task.lock().await.as_ref().unwrap().is_finished());
+
+        monitor.stop_monitoring().await;
+        assert!(!monitor.restart_if_stopped().await, "Nothing to restart once stopped");
+    }
+}
@@ -0,0 +1,111 @@
+//! Resolve Windows games running under Proton or Wine to their `.exe`
+//!
+//! On Linux such a game shows up as `wine64-preloader`, `wine`, or a `pv-bwrap` / `reaper`
+//! wrapper, so its process name and executable say nothing about the game. The real
+//! executable is on the command line, as a Unix path or a Wine drive path.
+
+use std::path::{Path, PathBuf};
+
+/// Executables of the processes Proton and Wine run games under
+const WRAPPER_NAMES: &[&str] = &[
+    "wine", "wine64", "wine-preloader", "wine64-preloader", "pv-bwrap", "reaper", "proton",
+];
+
+/// Helpers Proton starts the game through; never the game itself
+const HELPER_EXECUTABLES: &[&str] = &["steam.exe", "explorer.exe", "services.exe", "winedevice.exe", "start.exe"];
+
+/// Whether a process is a Proton/Wine wrapper, judging by its name or executable
+pub fn is_wrapper(name: &str, executable_path: &str) -> bool {
+    let executable_name = Path::new(executable_path).file_name().map(|n| n.to_string_lossy().to_lowercase());
+    let name = name.to_lowercase();
+
+    WRAPPER_NAMES.iter().any(|wrapper| name == *wrapper || executable_name.as_deref() == Some(*wrapper))
+}
+
+/// Windows game executable started by a wrapper process, as a path on this machine.
+///
+/// `C:` paths need the Wine prefix, taken from `WINEPREFIX` or Proton's `STEAM_COMPAT_DATA_PATH`
+/// in `environ`; without it only the Windows path is known and that is returned unchanged.
+pub fn game_executable(command_line: &[String], environ: &[String]) -> Option<PathBuf> {
+    let argument = command_line.iter().find(|arg| is_game_executable(arg))?;
+    Some(to_host_path(argument, wine_prefix(environ).as_deref()).unwrap_or_else(|| PathBuf::from(argument)))
+}
+
+fn is_game_executable(argument: &str) -> bool {
+    let lower = argument.to_lowercase();
+    if !lower.ends_with(".exe") {
+        return false;
+    }
+
+    let normalized = lower.replace('\\', "/");
+    let file_name = normalized.rsplit('/').next().unwrap_or(&normalized);
+    !HELPER_EXECUTABLES.contains(&file_name)
+        && !normalized.contains("/windows/system32/")
+        && !normalized.contains("/windows/syswow64/")
+}
+
+fn wine_prefix(environ: &[String]) -> Option<PathBuf> {
+    let variable = |key: &str| {
+        environ.iter().find_map(|entry| entry.strip_prefix(key)?.strip_prefix('=').map(str::to_string))
+    };
+
+    variable("WINEPREFIX")
+        .map(PathBuf::from)
+        .or_else(|| variable("STEAM_COMPAT_DATA_PATH").map(|data| Path::new(&data).join("pfx")))
+}
+
+/// Map a command line path to the host filesystem: Unix paths stay as they are, `Z:` is the
+/// host root and `C:` is the prefix's `drive_c`
+fn to_host_path(argument: &str, prefix: Option<&Path>) -> Option<PathBuf> {
+    if argument.starts_with('/') {
+        return Some(PathBuf::from(argument));
+    }
+
+    let (drive, rest) = argument.split_once(':')?;
+    let components = rest.split(['\\', '/']).filter(|c| !c.is_empty());
+    let root = match drive.to_ascii_lowercase().as_str() {
+        "z" => PathBuf::from("/"),
+        "c" => prefix?.join("drive_c"),
+        _ => return None,
+    };
+    Some(components.fold(root, |path, component| path.join(component)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_resolves_proton_and_wine_command_lines() {
+        assert!(is_wrapper("wine64-preloader", "/opt/proton/files/bin/wine64-preloader"));
+        assert!(is_wrapper("Game.exe", "/opt/proton/files/bin/wine64-preloader"));
+        assert!(!is_wrapper("game", "/usr/games/game"));
+
+        // The game process itself, under a Steam Linux Runtime container
+        let game = args(&["Z:\\home\\deck\\.local\\share\\Steam\\steamapps\\common\\Game\\Game.exe", "-windowed"]);
+        assert_eq!(game_executable(&game, &[]), Some(PathBuf::from("/home/deck/.local/share/Steam/steamapps/common/Game/Game.exe")));
+
+        // The reaper parent, whose command line runs Proton's steam.exe helper first
+        let reaper = args(&[
+            "/home/deck/.local/share/Steam/ubuntu12_32/reaper", "SteamLaunch", "AppId=1234", "--",
+            "/proton", "waitforexitandrun", "C:\\windows\\system32\\steam.exe",
+            "/games/Game/Game.exe",
+        ]);
+        assert_eq!(game_executable(&reaper, &[]), Some(PathBuf::from("/games/Game/Game.exe")));
+
+        // Plain `wine Game.exe` with a game installed inside the prefix
+        let wine = args(&["wine", "C:\\Games\\Game\\Game.EXE"]);
+        let environ = args(&["HOME=/home/user", "WINEPREFIX=/home/user/.wine"]);
+        assert_eq!(game_executable(&wine, &environ), Some(PathBuf::from("/home/user/.wine/drive_c/Games/Game/Game.EXE")));
+        assert_eq!(game_executable(&wine, &[]), Some(PathBuf::from("C:\\Games\\Game\\Game.EXE")));
+
+        let proton_env = args(&["STEAM_COMPAT_DATA_PATH=/steam/compatdata/1234"]);
+        assert_eq!(wine_prefix(&proton_env), Some(PathBuf::from("/steam/compatdata/1234/pfx")));
+
+        assert_eq!(game_executable(&args(&["wine", "--version"]), &[]), None);
+    }
+}
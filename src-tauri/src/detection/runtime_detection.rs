@@ -39,17 +39,68 @@ impl RuntimeDetector {
         let window_title = self.get_window_title(process_info.pid).await;
         let current_time = chrono::Utc::now();
 
+        // Best match among the known signatures that constrain the command line
+        let mut command_line_match: Option<f32> = None;
+        for signature in self.load_known_signatures(&process_info.name).await? {
+            if let Some(score) = self.command_line_score(process_info, &signature).await {
+                command_line_match = Some(command_line_match.map_or(score, |best| best.max(score)));
+            }
+        }
+
         Ok(RuntimeDetectionResult {
             current_window_title: window_title,
             process_name_match_score: self.calculate_process_name_score(&process_info.name).await,
             memory_pattern_match: self.check_memory_pattern(process_info),
             cpu_pattern_match: self.check_cpu_pattern(process_info),
             parent_process_match: self.check_parent_process(&process_info),
+            command_line_match,
             detected_at: current_time,
             confidence_score: 0.0, // Will be calculated by caller
         })
     }
 
+    /// Share of the signature's `command_line_patterns` matching the process's command line,
+    /// as a 0-100 score. `None` when the signature has no command line patterns.
+    ///
+    /// Each pattern is tried against every argument and against the whole command line,
+    /// so both `^-dx12$` and `-profile \w+` work.
+    pub async fn command_line_score(&self, process_info: &ProcessInfo, signature: &RuntimeSignature) -> Option<f32> {
+        if signature.command_line_patterns.is_empty() {
+            return None;
+        }
+
+        let joined = process_info.command_line.join(" ");
+        let mut matched = 0;
+        for pattern in &signature.command_line_patterns {
+            let Some(regex) = self.compiled_pattern(pattern).await else {
+                continue;
+            };
+            if regex.is_match(&joined) || process_info.command_line.iter().any(|arg| regex.is_match(arg)) {
+                matched += 1;
+            }
+        }
+
+        Some(matched as f32 / signature.command_line_patterns.len() as f32 * 100.0)
+    }
+
+    /// Compile a signature pattern once; invalid patterns are logged and never match
+    async fn compiled_pattern(&self, pattern: &str) -> Option<Regex> {
+        if let Some(regex) = self.compiled_patterns.read().await.get(pattern) {
+            return Some(regex.clone());
+        }
+
+        match Regex::new(pattern) {
+            Ok(regex) => {
+                self.compiled_patterns.write().await.insert(pattern.to_string(), regex.clone());
+                Some(regex)
+            }
+            Err(e) => {
+                crate::logger::warn("RUNTIME_DETECTION", &format!("Invalid runtime signature pattern {}", pattern), Some(&e.to_string()));
+                None
+            }
+        }
+    }
+
     pub async fn detect_runtime_signatures(&self, executable_path: &str) -> Result<RuntimeSignature, DetectionError> {
         // This would analyze an executable to determine what runtime signatures
         // it might exhibit. In practice, this would require running the executable
@@ -321,12 +372,15 @@ impl RuntimeDetector {
         Ok(signatures)
     }
 
-    pub async fn clear_caches(&self) {
+    pub async fn clear_caches(&self) -> usize {
         let mut title_cache = self.window_title_cache.write().await;
+        let mut cleared = title_cache.len();
         title_cache.clear();
 
         let mut pattern_cache = self.compiled_patterns.write().await;
+        cleared += pattern_cache.len();
         pattern_cache.clear();
+        cleared
     }
 }
 
@@ -355,6 +409,8 @@ pub struct RuntimeDetectionResult {
     pub memory_pattern_match: f32,
     pub cpu_pattern_match: f32,
     pub parent_process_match: f32,
+    /// Match against the command line patterns of known signatures, when any apply
+    pub command_line_match: Option<f32>,
     pub detected_at: chrono::DateTime<chrono::Utc>,
     pub confidence_score: f32,
 }
@@ -362,12 +418,15 @@ pub struct RuntimeDetectionResult {
 impl RuntimeDetectionResult {
     pub fn overall_confidence(&self) -> f32 {
         // Weighted combination of different detection methods
-        let weights = [
+        let mut weights = vec![
             (self.process_name_match_score, 0.4),
             (self.memory_pattern_match, 0.3),
             (self.cpu_pattern_match, 0.2),
             (self.parent_process_match, 0.1),
         ];
+        if let Some(command_line_match) = self.command_line_match {
+            weights.push((command_line_match, 0.3));
+        }
 
         let weighted_sum: f32 = weights.iter().map(|(score, weight)| score * weight).sum();
         let total_weight: f32 = weights.iter().map(|(_, weight)| weight).sum();
@@ -400,6 +459,37 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_command_line_patterns_match_arguments() {
+        let detector = RuntimeDetector::new();
+        let process = ProcessInfo {
+            pid: 1,
+            name: "Game.exe".to_string(),
+            executable_path: "/games/Game/Game.exe".to_string(),
+            command_line: vec!["/games/Game/Game.exe".to_string(), "-dx12".to_string(), "-profile".to_string(), "main".to_string()],
+            window_title: None,
+            parent_pid: None,
+            creation_time: None,
+            memory_usage: 0,
+            cpu_usage: 0.0,
+        };
+
+        assert_eq!(detector.command_line_score(&process, &RuntimeSignature::default()).await, None);
+
+        let signature = RuntimeSignature {
+            command_line_patterns: vec!["^-dx12$".to_string(), r"-profile \w+".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(detector.command_line_score(&process, &signature).await, Some(100.0));
+
+        let signature = RuntimeSignature {
+            command_line_patterns: vec!["^-vulkan$".to_string(), "(?i)game\\.exe$".to_string(), "(".to_string()],
+            ..Default::default()
+        };
+        let score = detector.command_line_score(&process, &signature).await.unwrap();
+        assert!((score - 100.0 / 3.0).abs() < 0.01);
+    }
+
     #[test]
     fn test_game_name_extraction() {
         let test_cases = vec![
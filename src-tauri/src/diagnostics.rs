@@ -0,0 +1,125 @@
+//! Diagnostics bundles for bug reports
+//!
+//! Zips the log files together with a JSON snapshot of the app's state, with the
+//! user's home directory replaced by `~` wherever it appears.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Name of the state snapshot inside the bundle
+pub const SNAPSHOT_ENTRY: &str = "snapshot.json";
+
+/// The current user's home directory, if it can be determined
+pub fn home_dir() -> Option<String> {
+    std::env::var("HOME").ok()
+        .or_else(|| std::env::var("USERPROFILE").ok())
+        .map(|home| home.trim_end_matches(['/', '\\']).to_string())
+        .filter(|home| !home.is_empty())
+}
+
+/// Replace every spelling of `home` (either slash style, or JSON-escaped backslashes)
+/// with `~`
+pub fn scrub_home_paths(text: &str, home: Option<&str>) -> String {
+    let Some(home) = home.filter(|h| h.len() > 1) else {
+        return text.to_string();
+    };
+
+    let forward = home.replace('\\', "/");
+    let backward = home.replace('/', "\\");
+    let escaped = backward.replace('\\', "\\\\");
+
+    // Longest first, so the escaped form isn't half-replaced by the plain one
+    let mut spellings = vec![escaped, backward, forward];
+    spellings.sort_by_key(|s| std::cmp::Reverse(s.len()));
+    spellings.dedup();
+
+    spellings.iter().fold(text.to_string(), |text, spelling| text.replace(spelling.as_str(), "~"))
+}
+
+/// Write `snapshot` and the given log files into a zip at `dest`, returning how many
+/// log files were included. Logs that can't be read are noted in the bundle instead.
+pub fn write_bundle(dest: &Path, snapshot: &serde_json::Value, log_files: &[PathBuf]) -> Result<usize, String> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+
+    let home = home_dir();
+    let file = std::fs::File::create(dest)
+        .map_err(|e| format!("Failed to create diagnostics bundle: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let snapshot_json = serde_json::to_string_pretty(snapshot)
+        .map_err(|e| format!("Failed to serialize diagnostics snapshot: {}", e))?;
+    add_entry(&mut zip, SNAPSHOT_ENTRY, &scrub_home_paths(&snapshot_json, home.as_deref()), options)?;
+
+    let mut included = 0;
+    for path in log_files {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let contents = match std::fs::read(path) {
+            Ok(bytes) => {
+                included += 1;
+                String::from_utf8_lossy(&bytes).into_owned()
+            }
+            Err(e) => format!("Could not read {}: {}\n", name, e),
+        };
+
+        add_entry(&mut zip, &format!("logs/{}", name), &scrub_home_paths(&contents, home.as_deref()), options)?;
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finish diagnostics bundle: {}", e))?;
+    Ok(included)
+}
+
+fn add_entry(
+    zip: &mut zip::ZipWriter<std::fs::File>,
+    name: &str,
+    contents: &str,
+    options: zip::write::SimpleFileOptions,
+) -> Result<(), String> {
+    zip.start_file(name, options)
+        .map_err(|e| format!("Failed to add {} to diagnostics bundle: {}", name, e))?;
+    zip.write_all(contents.as_bytes())
+        .map_err(|e| format!("Failed to add {} to diagnostics bundle: {}", name, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scrubs_every_spelling_of_home() {
+        let home = Some(r"C:\Users\alex");
+        let text = r#"C:\Users\alex\saves, C:/Users/alex/saves, "C:\\Users\\alex\\saves""#;
+        assert_eq!(scrub_home_paths(text, home), r#"~\saves, ~/saves, "~\\saves""#);
+        assert_eq!(scrub_home_paths("/home/alex/.config", Some("/home/alex")), "~/.config");
+        assert_eq!(scrub_home_paths("/home/alex", None), "/home/alex");
+    }
+
+    #[test]
+    fn test_bundle_contains_snapshot_and_logs() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = temp_dir.path().join("save-steward.log");
+        std::fs::write(&log, "[INFO] started\n").unwrap();
+        let missing = temp_dir.path().join("save-steward.log.1");
+
+        let dest = temp_dir.path().join("out").join("diagnostics.zip");
+        let snapshot = serde_json::json!({ "app_version": "1.0.0" });
+        assert_eq!(write_bundle(&dest, &snapshot, &[log, missing]).unwrap(), 1);
+
+        let mut zip = zip::ZipArchive::new(std::fs::File::open(&dest).unwrap()).unwrap();
+        let mut contents = String::new();
+        zip.by_name("logs/save-steward.log").unwrap().read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "[INFO] started\n");
+
+        contents.clear();
+        zip.by_name(SNAPSHOT_ENTRY).unwrap().read_to_string(&mut contents).unwrap();
+        assert!(contents.contains("1.0.0"));
+        assert!(zip.by_name("logs/save-steward.log.1").is_ok());
+    }
+}
@@ -2,15 +2,34 @@ pub mod persistence;
 pub mod detection;
 pub mod pcgw;
 pub mod utils;
+pub mod icon;
+pub mod scanner;
+pub mod naming;
+pub mod suggestions;
+pub mod validation;
+pub mod drift;
+pub mod audit;
+pub mod cloud_sync;
 
 use crate::database::models::*;
 use std::sync::Arc;
 use crate::pcgaming_wiki::PcgwClient;
+use crate::progress::{ProgressReporter, ProgressSink};
 
 use self::persistence::Persistence;
 use self::detection::Detection;
 use self::pcgw::PcgwIntegration;
 use self::utils::Utils;
+use self::icon::IconExtractor;
+use self::suggestions::SaveLocationSuggestions;
+use self::validation::{FieldError, GameRequestValidation};
+use self::drift::LocationDriftDetector;
+use self::audit::SaveLocationAuditor;
+
+/// Upper bound for a game's `max_backups` retention override
+const MAX_RETAINED_BACKUPS: usize = 1000;
+/// Upper bound for a game's `min_age_hours` retention override (one year)
+const MAX_RETENTION_MIN_AGE_HOURS: u64 = 24 * 365;
 
 pub struct GameManager;
 
@@ -18,8 +37,20 @@ impl GameManager {
     /// Add a game manually with automatic save location detection
     pub async fn add_manual_game(
         db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
-        request: AddGameRequest,
+        mut request: AddGameRequest,
+        return_existing: bool,
     ) -> Result<GameWithSaves, String> {
+        Self::validate_game_request(&mut request).map_err(|errors| GameRequestValidation::describe(&errors))?;
+
+        // A game with the same store id is either handed back or refused
+        {
+            let conn_guard = db.lock().await;
+            let conn = conn_guard.get_connection().await;
+            if let Some(existing) = Self::resolve_duplicate(&conn, &request, return_existing)? {
+                return Ok(existing);
+            }
+        }
+
         // 1. Pre-fetch PCGamingWiki data (outside transaction)
         let mut pcgw_save_locations: Option<Vec<SaveLocation>> = None;
         let mut pcgw_response_text: Option<String> = None;
@@ -49,46 +80,103 @@ impl GameManager {
             }
         }
 
-        let conn_guard = db.lock().await;
-        let mut conn = conn_guard.get_connection().await;
+        let (game_id, save_locations, limits) = {
+            let conn_guard = db.lock().await;
+            let mut conn = conn_guard.get_connection().await;
 
-        // Start transaction
-        let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+            // Start transaction
+            let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
 
-        // Update cache if we fetched new data
-        if let Some(text) = pcgw_response_text {
-            let _ = crate::pcgaming_wiki::cache::PcgwCache::set(&tx, &cache_key, &text, 7);
-        }
+            // Update cache if we fetched new data
+            if let Some(text) = pcgw_response_text {
+                let _ = crate::pcgaming_wiki::cache::PcgwCache::set(&tx, &cache_key, &text, 7);
+            }
+
+            // Check again under the transaction in case the game was added meanwhile
+            if let Some(existing) = Self::resolve_duplicate(&tx, &request, return_existing)? {
+                return Ok(existing);
+            }
 
-        // Insert game
-        let game_id = Persistence::insert_game(&tx, &request)?;
+            // Insert game
+            let game_id = Persistence::insert_game(&tx, &request)?;
 
-        // Update game with PCGW data if available (including executables)
-        if pcgw_save_locations.is_some() {
-            // Fetch PCGW executables and update game record
-            if let Some(page_name) = PcgwIntegration::extract_pcgw_page_name(&request.name) {
-                if let Some(executables_json) = PcgwIntegration::fetch_pcgw_executables(&page_name) {
-                    Persistence::update_game_platform_executables(&tx, game_id, &executables_json)?;
+            // Update game with PCGW data if available (including executables)
+            if pcgw_save_locations.is_some() {
+                // Fetch PCGW executables and update game record
+                if let Some(page_name) = PcgwIntegration::extract_pcgw_page_name(&request.name) {
+                    if let Some(executables_json) = PcgwIntegration::fetch_pcgw_executables(&page_name) {
+                        Persistence::update_game_platform_executables(&tx, game_id, &executables_json)?;
+                    }
                 }
             }
-        }
 
-        // Detect and insert save locations (passing pre-fetched data)
-        let save_locations = Detection::detect_save_locations(&tx, game_id, &request, pcgw_save_locations)?;
+            // Detect and insert save locations (passing pre-fetched data)
+            let save_locations = Detection::detect_save_locations(&tx, game_id, &request, pcgw_save_locations)?;
+            let limits = scanner::ScanLimits::from_settings(&tx);
 
-        // Scan for existing saves
-        let detected_saves = Detection::scan_existing_saves(&tx, game_id, &save_locations)?;
+            tx.commit().map_err(|e| format!("Commit error: {}", e))?;
+            (game_id, save_locations, limits)
+        };
 
-        tx.commit().map_err(|e| format!("Commit error: {}", e))?;
+        // Walk and hash existing saves without holding the database lock
+        let locations = save_locations.clone();
+        let scans = tokio::task::spawn_blocking(move || {
+            Detection::scan_locations(&locations, &limits, None)
+                .into_iter()
+                .map(|(location, path, scan)| (location.id, path, scan))
+                .collect::<Vec<_>>()
+        })
+        .await
+        .map_err(|e| format!("Save scan failed: {}", e))?;
+
+        let (game, detected_saves) = {
+            let conn_guard = db.lock().await;
+            conn_guard.with_connection(move |conn| {
+                let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+                let mut saves = Vec::with_capacity(scans.len());
+                for (location_id, path, scan) in &scans {
+                    saves.push(Persistence::upsert_detected_save(&tx, game_id, *location_id, path, scan)?);
+                }
+                tx.commit().map_err(|e| format!("Failed to commit save scan: {}", e))?;
+                Ok::<_, String>((Persistence::get_game_by_id(conn, game_id)?, saves))
+            }).await??
+        };
 
         Ok(GameWithSaves {
-            game: Persistence::get_game_by_id(&*conn, game_id)?,
-            save_locations: save_locations,
+            game,
+            save_locations,
             detected_saves,
             user_config: None,
         })
     }
 
+    /// The game already added with the request's platform and app id: returned as is with
+    /// `return_existing`, otherwise an "already exists" error
+    fn resolve_duplicate(conn: &rusqlite::Connection, request: &AddGameRequest, return_existing: bool) -> Result<Option<GameWithSaves>, String> {
+        let Some(existing_id) = Persistence::find_game_by_platform_id(conn, &request.platform, request.platform_app_id.as_deref())? else {
+            return Ok(None);
+        };
+
+        if !return_existing {
+            crate::logger::warn("GAME_MANAGER", &format!("Refusing to add '{}': already added as game {}", request.name, existing_id), None);
+            return Err(Persistence::duplicate_game_error(existing_id));
+        }
+
+        crate::logger::info("GAME_MANAGER", &format!("'{}' is already added as game {}, returning it", request.name, existing_id), None);
+        Ok(Some(GameWithSaves {
+            game: Persistence::get_game_by_id(conn, existing_id)?,
+            save_locations: Persistence::get_save_locations(conn, existing_id)?,
+            detected_saves: Vec::new(),
+            user_config: None,
+        }))
+    }
+
+    /// Normalize a game request in place and check it, returning every problem found
+    pub fn validate_game_request(request: &mut AddGameRequest) -> Result<(), Vec<FieldError>> {
+        GameRequestValidation::normalize(request);
+        GameRequestValidation::validate(request)
+    }
+
     // Delegate methods to submodules
 
     pub fn insert_game(tx: &rusqlite::Transaction, request: &AddGameRequest) -> Result<i64, String> {
@@ -133,17 +221,368 @@ impl GameManager {
         Persistence::get_game_by_id(conn, game_id)
     }
 
+    pub fn find_game_by_id(conn: &rusqlite::Connection, game_id: i64) -> Result<Option<Game>, String> {
+        Persistence::find_game_by_id(conn, game_id)
+    }
+
+    pub fn get_game_identifiers(conn: &rusqlite::Connection, game_id: i64) -> Result<Vec<GameIdentifier>, String> {
+        Persistence::get_game_identifiers(conn, game_id)
+    }
+
+    pub fn delete_game_identifiers(conn: &rusqlite::Connection, game_id: i64) -> Result<usize, String> {
+        Persistence::delete_game_identifiers(conn, game_id)
+    }
+
     pub async fn get_all_games(
         db: &std::sync::Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
     ) -> Result<Vec<Game>, String> {
         Persistence::get_all_games(db).await
     }
 
+    /// Every active game with its favorite flag
+    pub async fn get_game_list(
+        db: &std::sync::Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+    ) -> Result<Vec<GameListEntry>, String> {
+        let games = Persistence::get_all_games(db).await?;
+        let favorites = {
+            let conn_guard = db.lock().await;
+            let conn = conn_guard.get_connection().await;
+            Persistence::get_favorite_game_ids(&conn)?
+        };
+
+        Ok(games.into_iter()
+            .map(|game| GameListEntry { is_favorite: favorites.contains(&game.id), game })
+            .collect())
+    }
+
+    pub async fn set_favorite(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        favorite: bool,
+    ) -> Result<(), String> {
+        Persistence::set_favorite(db, game_id, favorite).await
+    }
+
+    pub fn get_archived_games(conn: &rusqlite::Connection) -> Result<Vec<Game>, String> {
+        Persistence::get_archived_games(conn)
+    }
+
+    /// Hide a game from the library and stop backing it up, keeping its backups and history
+    pub async fn archive_game(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+    ) -> Result<(), String> {
+        Persistence::set_game_active(db, game_id, false).await
+    }
+
+    pub async fn unarchive_game(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+    ) -> Result<(), String> {
+        Persistence::set_game_active(db, game_id, true).await
+    }
+
+    /// Active games with no configured save locations
+    pub async fn games_without_save_locations(
+        db: &std::sync::Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+    ) -> Result<Vec<Game>, String> {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+        Persistence::get_games_without_save_locations(&conn)
+    }
+
+    pub fn get_save_locations(conn: &rusqlite::Connection, game_id: i64) -> Result<Vec<SaveLocation>, String> {
+        Persistence::get_save_locations(conn, game_id)
+    }
+
+    /// Set how strongly a save location is preferred (1-10, higher wins) and return the
+    /// game's locations in their new order
+    pub async fn set_save_location_priority(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        location_id: i64,
+        priority: i32,
+    ) -> Result<Vec<SaveLocation>, String> {
+        Self::validate_priority(priority)?;
+
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+        let game_id = Persistence::set_save_location_priority(&conn, location_id, priority)?;
+        Persistence::get_save_locations(&conn, game_id)
+    }
+
+    /// Get a game together with its save locations, identifiers, branches and backup stats
+    pub async fn get_game_detail(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+    ) -> Result<GameDetail, String> {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+
+        Ok(GameDetail {
+            game: Persistence::get_game_by_id(&conn, game_id)?,
+            save_locations: Persistence::get_save_locations(&conn, game_id)?,
+            identifiers: Persistence::get_game_identifiers(&conn, game_id)?,
+            branches: Persistence::get_git_branches(&conn, game_id)?,
+            backup_stats: Persistence::get_backup_stats(&conn, game_id)?,
+        })
+    }
+
+    pub fn available_placeholders() -> Vec<crate::manifest::resolver::PlaceholderInfo> {
+        Detection::available_placeholders()
+    }
+
+    pub fn resolve_save_paths(location: &SaveLocation) -> Result<Vec<String>, String> {
+        Detection::resolve_save_paths(location)
+    }
+
+    /// Check whether the game's saves moved away from its configured locations, e.g. after
+    /// an update, and suggest where they went
+    pub async fn detect_location_drift(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+    ) -> Result<Vec<LocationDrift>, String> {
+        let (game, locations) = {
+            let conn_guard = db.lock().await;
+            let conn = conn_guard.get_connection().await;
+            (Persistence::get_game_by_id(&conn, game_id)?, Persistence::get_save_locations(&conn, game_id)?)
+        };
+
+        tokio::task::spawn_blocking(move || LocationDriftDetector::detect(&game.name, &locations))
+            .await
+            .map_err(|e| format!("Location drift check failed: {}", e))
+    }
+
+    /// Check how every active game's save locations resolve on this machine
+    pub async fn audit_save_locations(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+    ) -> Result<Vec<SaveLocationAudit>, String> {
+        let games = Persistence::get_all_games(db).await?;
+        let games = {
+            let conn_guard = db.lock().await;
+            let conn = conn_guard.get_connection().await;
+            games.into_iter()
+                .map(|game| Persistence::get_save_locations(&conn, game.id).map(|locations| (game, locations)))
+                .collect::<Result<Vec<_>, String>>()?
+        };
+
+        tokio::task::spawn_blocking(move || SaveLocationAuditor::audit(&games))
+            .await
+            .map_err(|e| format!("Save location audit failed: {}", e))
+    }
+
+    /// Get each save location with its resolved path, existence, matching file count and
+    /// any cloud-synced folder it sits in
+    pub async fn get_save_locations_detailed(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+    ) -> Result<Vec<SaveLocationStatus>, String> {
+        let locations = {
+            let conn_guard = db.lock().await;
+            let conn = conn_guard.get_connection().await;
+            Persistence::get_save_locations(&conn, game_id)?
+        };
+
+        tokio::task::spawn_blocking(move || {
+            locations.iter().map(Detection::get_save_location_status).collect()
+        })
+        .await
+        .map_err(|e| format!("Save location scan failed: {}", e))
+    }
+
+    /// Re-scan every save location of a game, hashing files in parallel, and refresh `detected_saves`
+    pub async fn rescan_saves(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+    ) -> Result<SaveScanSummary, String> {
+        let started = std::time::Instant::now();
+
+        let (locations, limits) = {
+            let conn_guard = db.lock().await;
+            let conn = conn_guard.get_connection().await;
+            (Persistence::get_save_locations(&conn, game_id)?, scanner::ScanLimits::from_settings(&conn))
+        };
+
+        // Walk and hash without holding the database lock
+        let scans = tokio::task::spawn_blocking(move || {
+            let progress = ProgressReporter::new("save_scan", Some(game_id), ProgressSink::frontend());
+            Detection::scan_locations(&locations, &limits, Some(&progress))
+                .into_iter()
+                .map(|(location, path, scan)| (location.id, path, scan))
+                .collect::<Vec<_>>()
+        })
+        .await
+        .map_err(|e| format!("Save scan failed: {}", e))?;
+
+        // One transaction for all rows, off the async runtime
+        let (scans, saves) = {
+            let conn_guard = db.lock().await;
+            conn_guard.with_connection(move |conn| {
+                let tx = conn.transaction().map_err(|e| format!("Transaction error: {}", e))?;
+                let mut saves = Vec::with_capacity(scans.len());
+                for (location_id, path, scan) in &scans {
+                    saves.push(Persistence::upsert_detected_save(&tx, game_id, *location_id, path, scan)?);
+                }
+                tx.commit().map_err(|e| format!("Failed to commit save scan: {}", e))?;
+                Ok::<_, String>((scans, saves))
+            }).await??
+        };
+
+        Ok(SaveScanSummary {
+            game_id,
+            paths_scanned: scans.len(),
+            files_scanned: scans.iter().map(|(_, _, s)| s.files.len()).sum(),
+            total_bytes: scans.iter().map(|(_, _, s)| s.total_bytes).sum(),
+            limit_reached: scans.iter().any(|(_, _, s)| s.limit_reached),
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            saves,
+        })
+    }
+
+    pub fn get_real_time_backup_override(conn: &rusqlite::Connection, game_id: i64) -> Result<Option<bool>, String> {
+        Persistence::get_real_time_backup_override(conn, game_id)
+    }
+
+    /// Override real-time backups for one game; `None` falls back to the global setting
+    pub async fn set_real_time_backup_override(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        enabled: Option<bool>,
+    ) -> Result<(), String> {
+        Persistence::set_real_time_backup_override(db, game_id, enabled).await
+    }
+
+    pub fn is_backup_enabled(conn: &rusqlite::Connection, game_id: i64) -> Result<bool, String> {
+        Persistence::is_backup_enabled(conn, game_id)
+    }
+
+    pub fn get_backup_enabled_game_ids(conn: &rusqlite::Connection) -> Result<Vec<i64>, String> {
+        Persistence::get_backup_enabled_game_ids(conn)
+    }
+
+    /// Stop or resume automatic protection of a game without touching its backups
+    pub async fn set_backup_enabled(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        enabled: bool,
+    ) -> Result<(), String> {
+        Persistence::set_backup_enabled(db, game_id, enabled).await
+    }
+
+    pub fn get_compression_level(conn: &rusqlite::Connection, game_id: i64) -> Result<Option<i32>, String> {
+        Persistence::get_compression_level(conn, game_id)
+    }
+
+    pub async fn set_compression_level(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        level: i32,
+    ) -> Result<(), String> {
+        Persistence::set_compression_level(db, game_id, level).await
+    }
+
+    pub fn get_naming_template(conn: &rusqlite::Connection, game_id: i64) -> Result<Option<String>, String> {
+        Persistence::get_naming_template(conn, game_id)
+    }
+
+    pub async fn set_naming_template(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        template: Option<&str>,
+    ) -> Result<(), String> {
+        Persistence::set_naming_template(db, game_id, template).await
+    }
+
+    pub fn get_backup_timeline(conn: &rusqlite::Connection, game_id: i64, from: &str, to: &str) -> Result<Vec<BackupTimelineDay>, String> {
+        Persistence::get_backup_timeline(conn, game_id, from, to)
+    }
+
+    pub fn get_archive_format(conn: &rusqlite::Connection, game_id: i64) -> Result<Option<crate::backup::archive::ArchiveFormat>, String> {
+        Persistence::get_archive_format(conn, game_id)
+    }
+
+    pub async fn set_archive_format(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        format: Option<crate::backup::archive::ArchiveFormat>,
+    ) -> Result<(), String> {
+        Persistence::set_archive_format(db, game_id, format).await
+    }
+
+    pub fn get_pcgw_page_name(conn: &rusqlite::Connection, game_id: i64) -> Result<Option<String>, String> {
+        Persistence::get_pcgw_page_name(conn, game_id)
+    }
+
+    pub fn set_pcgw_mapping(conn: &rusqlite::Connection, game_id: i64, page_name: &str) -> Result<(), String> {
+        Persistence::set_pcgw_mapping(conn, game_id, page_name)
+    }
+
+    pub fn apply_game_metadata(conn: &rusqlite::Connection, game_id: i64, metadata: &GameMetadata, overwrite: bool) -> Result<Vec<String>, String> {
+        Persistence::apply_game_metadata(conn, game_id, metadata, overwrite)
+    }
+
+    pub fn get_cold_storage_path(conn: &rusqlite::Connection, game_id: i64) -> Result<Option<String>, String> {
+        Persistence::get_cold_storage_path(conn, game_id)
+    }
+
+    pub fn set_cold_storage_path(conn: &rusqlite::Connection, game_id: i64, path: Option<&str>) -> Result<(), String> {
+        Persistence::set_cold_storage_path(conn, game_id, path)
+    }
+
+    pub fn get_monitor_settings(conn: &rusqlite::Connection, game_id: i64) -> Result<GameMonitorSettings, String> {
+        Persistence::get_monitor_settings(conn, game_id)
+    }
+
+    /// Override the file-event debounce and coalesce window for one game
+    pub async fn set_monitor_settings(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        settings: GameMonitorSettings,
+    ) -> Result<(), String> {
+        Persistence::set_monitor_settings(db, game_id, &settings).await
+    }
+
+    pub fn get_retention_settings(conn: &rusqlite::Connection, game_id: i64) -> Result<GameRetentionSettings, String> {
+        Persistence::get_retention_settings(conn, game_id)
+    }
+
+    /// Override how many backups of one game are kept, how much space they may use and how
+    /// old a backup must be before it is pruned
+    pub async fn set_retention_settings(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        settings: GameRetentionSettings,
+    ) -> Result<(), String> {
+        Self::validate_retention_settings(&settings)?;
+        Persistence::set_retention_settings(db, game_id, &settings).await
+    }
+
+    /// Save location priorities run from 1 to 10
+    pub fn validate_priority(priority: i32) -> Result<(), String> {
+        if !(1..=10).contains(&priority) {
+            return Err(format!("Invalid priority {}: must be between 1 and 10", priority));
+        }
+        Ok(())
+    }
+
+    pub fn validate_retention_settings(settings: &GameRetentionSettings) -> Result<(), String> {
+        if settings.max_backups.is_some_and(|n| n == 0 || n > MAX_RETAINED_BACKUPS) {
+            return Err(format!("Invalid retention settings: max_backups must be between 1 and {}", MAX_RETAINED_BACKUPS));
+        }
+        if settings.max_bytes == Some(0) {
+            return Err("Invalid retention settings: max_bytes must be greater than 0".to_string());
+        }
+        if settings.min_age_hours.is_some_and(|h| h > MAX_RETENTION_MIN_AGE_HOURS) {
+            return Err(format!("Invalid retention settings: min_age_hours must be at most {}", MAX_RETENTION_MIN_AGE_HOURS));
+        }
+        Ok(())
+    }
+
     pub async fn update_game(
         db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
         game_id: i64,
-        request: AddGameRequest,
+        mut request: AddGameRequest,
     ) -> Result<Game, String> {
+        Self::validate_game_request(&mut request).map_err(|errors| GameRequestValidation::describe(&errors))?;
         Persistence::update_game(db, game_id, request).await
     }
 
@@ -166,15 +605,106 @@ impl GameManager {
         Persistence::update_game_platform_executables(tx, game_id, executables_json)
     }
 
+    /// Validate and store a game's per-OS launch executables
+    pub async fn set_platform_executables(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        executables: &std::collections::HashMap<String, String>,
+    ) -> Result<Game, String> {
+        let executables_json = Utils::build_platform_executables_json(executables)?;
+        Persistence::set_platform_executables(db, game_id, &executables_json).await
+    }
+
+    /// Re-extract a game's icon from `icon_path`, falling back to its launch executable.
+    ///
+    /// A missing source file leaves the stored icon untouched and is reported in the result.
+    pub async fn refresh_icon(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+    ) -> Result<IconRefreshResult, String> {
+        let game = {
+            let conn_guard = db.lock().await;
+            let conn = conn_guard.get_connection().await;
+            Persistence::get_game_by_id(&conn, game_id)?
+        };
+
+        let candidates: Vec<String> = [
+            game.icon_path.clone(),
+            Utils::get_platform_executable(&game),
+            game.executable_path.clone(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|p| !p.trim().is_empty())
+        .collect();
+
+        let Some(source) = candidates.iter().find(|p| std::path::Path::new(p).is_file()).cloned() else {
+            let message = match candidates.first() {
+                Some(path) => format!("Icon source no longer exists: {}", path),
+                None => "Game has no executable to extract an icon from".to_string(),
+            };
+            crate::logger::warn("GAME_MANAGER", &format!("Cannot refresh icon for game {}", game_id), Some(&message));
+            return Ok(IconRefreshResult {
+                game_id,
+                refreshed: false,
+                icon_base64: game.icon_base64,
+                source_path: candidates.first().cloned(),
+                message,
+            });
+        };
+
+        let source_path = std::path::PathBuf::from(&source);
+        let icon_base64 = tokio::task::spawn_blocking(move || IconExtractor::extract_icon_base64(&source_path))
+            .await
+            .map_err(|e| format!("Icon extraction failed: {}", e))??;
+
+        Persistence::update_game_icon(db, game_id, &icon_base64, &source).await?;
+
+        Ok(IconRefreshResult {
+            game_id,
+            refreshed: true,
+            icon_base64: Some(icon_base64),
+            source_path: Some(source.clone()),
+            message: format!("Icon refreshed from {}", source),
+        })
+    }
+
     pub fn get_current_platform() -> &'static str {
         Utils::get_current_platform()
     }
 
+    pub fn applies_to_current_platform(location: &SaveLocation) -> bool {
+        Utils::applies_to_current_platform(location)
+    }
+
     pub fn get_platform_executable(game: &Game) -> Option<String> {
         Utils::get_platform_executable(game)
     }
 
+    pub fn metadata_from_pcgw(info: &crate::pcgaming_wiki::models::PcgwGameMetadata) -> GameMetadata {
+        PcgwIntegration::metadata_from_pcgw(info)
+    }
+
     pub fn convert_pcgw_locations(result: &crate::pcgaming_wiki::models::SaveLocationResult) -> Vec<SaveLocation> {
         PcgwIntegration::convert_pcgw_locations(result)
     }
+
+    pub fn diff_pcgw_locations(pcgw: &[SaveLocation], configured: &[SaveLocation]) -> Vec<PcgwLocationDiff> {
+        PcgwIntegration::diff_locations(pcgw, configured)
+    }
+
+    pub fn suggest_save_locations(
+        game_name: &str,
+        pcgw: Option<&crate::pcgaming_wiki::models::SaveLocationResult>,
+    ) -> Vec<SuggestedLocation> {
+        SaveLocationSuggestions::suggest(game_name, pcgw)
+    }
+
+    pub async fn resolve_search_result_names(
+        results: &mut [crate::pcgaming_wiki::models::GameSearchResult],
+        query: &str,
+        client: &crate::detection::platform::PlatformApiClient,
+    ) {
+        PcgwIntegration::resolve_search_result_names(results, query, client).await
+    }
 }
@@ -0,0 +1,146 @@
+use crate::database::models::{Game, SaveLocation, SaveLocationAudit, SaveLocationAuditStatus};
+use std::path::Path;
+use super::detection::Detection;
+use super::utils::Utils;
+
+/// Checks every game's save locations at once, so games that will never back up show up
+/// in one list
+pub struct SaveLocationAuditor;
+
+impl SaveLocationAuditor {
+    /// One row per save location that applies to this machine, or a single `NoLocations`
+    /// row for a game without any. Registry locations are not file paths and are skipped.
+    pub fn audit(games: &[(Game, Vec<SaveLocation>)]) -> Vec<SaveLocationAudit> {
+        let mut rows = Vec::new();
+        for (game, locations) in games {
+            let applicable: Vec<&SaveLocation> = locations.iter()
+                .filter(|location| location.path_type != "registry" && Utils::applies_to_current_platform(location))
+                .collect();
+
+            if applicable.is_empty() {
+                rows.push(SaveLocationAudit {
+                    game_id: game.id,
+                    game_name: game.name.clone(),
+                    location_id: None,
+                    path_pattern: None,
+                    status: SaveLocationAuditStatus::NoLocations,
+                    resolved_paths: Vec::new(),
+                    unresolved: Vec::new(),
+                });
+                continue;
+            }
+
+            rows.extend(applicable.into_iter().map(|location| Self::check_location(game, location)));
+        }
+        rows
+    }
+
+    fn check_location(game: &Game, location: &SaveLocation) -> SaveLocationAudit {
+        let unresolved = Detection::unresolved_variables(&location.path_pattern);
+        let resolved_paths = if unresolved.is_empty() {
+            Detection::resolve_save_paths(location).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        let status = if !unresolved.is_empty() {
+            SaveLocationAuditStatus::UnresolvedPlaceholder
+        } else if resolved_paths.iter().any(|path| Path::new(path).exists()) {
+            SaveLocationAuditStatus::Resolved
+        } else {
+            SaveLocationAuditStatus::PathMissing
+        };
+
+        SaveLocationAudit {
+            game_id: game.id,
+            game_name: game.name.clone(),
+            location_id: Some(location.id),
+            path_pattern: Some(location.path_pattern.clone()),
+            status,
+            resolved_paths,
+            unresolved,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    fn game(id: i64) -> Game {
+        Game {
+            id,
+            name: format!("Game {}", id),
+            developer: None,
+            publisher: None,
+            platform: "other".to_string(),
+            platform_app_id: None,
+            executable_path: None,
+            installation_path: None,
+            platform_executables: None,
+            genre: None,
+            release_date: None,
+            cover_image_url: None,
+            icon_base64: None,
+            icon_path: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            is_active: true,
+        }
+    }
+
+    fn location(id: i64, path_pattern: &str, path_type: &str) -> SaveLocation {
+        SaveLocation {
+            id,
+            game_id: 1,
+            path_pattern: path_pattern.to_string(),
+            path_type: path_type.to_string(),
+            platform: None,
+            save_type: "auto".to_string(),
+            file_patterns: None,
+            exclude_patterns: None,
+            is_relative_to_user: false,
+            environment_variable: None,
+            priority: 5,
+            detection_method: None,
+            community_confirmed: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_audit_reports_each_status() {
+        let dir = TempDir::new().unwrap();
+        let existing = dir.path().to_string_lossy().to_string();
+        let missing = dir.path().join("missing").to_string_lossy().to_string();
+
+        let games = vec![
+            (game(1), vec![
+                location(1, &existing, "directory"),
+                location(2, &missing, "directory"),
+                location(3, "{{no-such-placeholder}}/saves", "directory"),
+                location(4, "%SAVE_STEWARD_UNSET_VAR%/saves", "directory"),
+            ]),
+            (game(2), Vec::new()),
+            (game(3), vec![location(5, "HKEY_CURRENT_USER\\Software\\Game", "registry")]),
+        ];
+
+        let rows = SaveLocationAuditor::audit(&games);
+        let statuses: Vec<(i64, Option<i64>, SaveLocationAuditStatus)> = rows.iter()
+            .map(|row| (row.game_id, row.location_id, row.status))
+            .collect();
+        assert_eq!(statuses, vec![
+            (1, Some(1), SaveLocationAuditStatus::Resolved),
+            (1, Some(2), SaveLocationAuditStatus::PathMissing),
+            (1, Some(3), SaveLocationAuditStatus::UnresolvedPlaceholder),
+            (1, Some(4), SaveLocationAuditStatus::UnresolvedPlaceholder),
+            (2, None, SaveLocationAuditStatus::NoLocations),
+            (3, None, SaveLocationAuditStatus::NoLocations),
+        ]);
+        assert_eq!(rows[2].unresolved, vec!["no-such-placeholder".to_string()]);
+        assert_eq!(rows[3].unresolved, vec!["%SAVE_STEWARD_UNSET_VAR%".to_string()]);
+    }
+}
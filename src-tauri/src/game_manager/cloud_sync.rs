@@ -0,0 +1,126 @@
+use crate::database::models::CloudSyncInfo;
+use std::path::{Path, PathBuf};
+
+pub const ONEDRIVE: &str = "OneDrive";
+pub const DROPBOX: &str = "Dropbox";
+pub const GOOGLE_DRIVE: &str = "Google Drive";
+pub const ICLOUD: &str = "iCloud Drive";
+
+/// Spots save folders that a cloud client already syncs, where backing up on every write
+/// races the client and can leave conflicted copies behind
+pub struct CloudSyncDetector;
+
+impl CloudSyncDetector {
+    /// First resolved path that lives in a synced folder
+    pub fn detect_any(paths: &[String]) -> Option<CloudSyncInfo> {
+        let roots = Self::known_roots();
+        paths.iter().find_map(|p| Self::detect_in(Path::new(p), &roots))
+    }
+
+    /// Check against the configured sync roots first, then fall back to recognising the
+    /// default folder names anywhere in the path
+    fn detect_in(path: &Path, roots: &[(&'static str, PathBuf)]) -> Option<CloudSyncInfo> {
+        let from_roots = roots.iter()
+            .filter(|(_, root)| path.starts_with(root))
+            .max_by_key(|(_, root)| root.components().count())
+            .map(|(provider, root)| (*provider, root.clone()));
+
+        let (provider, root) = from_roots.or_else(|| {
+            path.ancestors()
+                .find_map(|ancestor| {
+                    let name = ancestor.file_name()?.to_string_lossy().to_lowercase();
+                    Self::provider_for_folder(&name).map(|provider| (provider, ancestor.to_path_buf()))
+                })
+        })?;
+
+        Some(CloudSyncInfo {
+            provider: provider.to_string(),
+            sync_root: root.to_string_lossy().to_string(),
+            warning: format!(
+                "These saves are in a {} folder. Backing them up in real time can cause sync conflicts; consider turning off real-time backups for this game.",
+                provider
+            ),
+        })
+    }
+
+    /// Provider whose client creates a folder with this (lowercase) name
+    fn provider_for_folder(name: &str) -> Option<&'static str> {
+        if name == "onedrive" || name.starts_with("onedrive -") || name.starts_with("onedrive-") {
+            Some(ONEDRIVE)
+        } else if name == "dropbox" || name.starts_with("dropbox (") || name.starts_with("dropbox-") {
+            Some(DROPBOX)
+        } else if name == "google drive" || name == "my drive" || name.starts_with("googledrive-") {
+            Some(GOOGLE_DRIVE)
+        } else if name == "icloud drive" || name == "iclouddrive" || name == "com~apple~clouddocs" {
+            Some(ICLOUD)
+        } else {
+            None
+        }
+    }
+
+    /// Sync roots the clients advertise through the environment or their settings files
+    fn known_roots() -> Vec<(&'static str, PathBuf)> {
+        let mut roots: Vec<(&'static str, PathBuf)> = ["OneDrive", "OneDriveConsumer", "OneDriveCommercial"]
+            .iter()
+            .filter_map(|var| std::env::var_os(var))
+            .map(|root| (ONEDRIVE, PathBuf::from(root)))
+            .collect();
+
+        let dropbox_info = [
+            home::home_dir().map(|h| h.join(".dropbox").join("info.json")),
+            std::env::var_os("LOCALAPPDATA").map(|d| PathBuf::from(d).join("Dropbox").join("info.json")),
+            std::env::var_os("APPDATA").map(|d| PathBuf::from(d).join("Dropbox").join("info.json")),
+        ];
+        for info in dropbox_info.into_iter().flatten() {
+            roots.extend(Self::dropbox_roots(&info).into_iter().map(|root| (DROPBOX, root)));
+        }
+
+        if let Some(home) = home::home_dir() {
+            roots.push((ICLOUD, home.join("Library").join("Mobile Documents")));
+        }
+        roots
+    }
+
+    /// Account folders listed in Dropbox's `info.json`
+    fn dropbox_roots(info_path: &Path) -> Vec<PathBuf> {
+        let Ok(content) = std::fs::read_to_string(info_path) else {
+            return Vec::new();
+        };
+        let Ok(info) = serde_json::from_str::<serde_json::Value>(&content) else {
+            return Vec::new();
+        };
+
+        info.as_object()
+            .map(|accounts| accounts.values()
+                .filter_map(|account| account.get("path")?.as_str())
+                .map(PathBuf::from)
+                .collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_default_folder_names() {
+        let path = Path::new("/home/user/Dropbox (Personal)/Games/Saves");
+        let info = CloudSyncDetector::detect_in(path, &[]).unwrap();
+        assert_eq!(info.provider, DROPBOX);
+        assert_eq!(info.sync_root, "/home/user/Dropbox (Personal)");
+
+        let path = Path::new("/Users/user/Library/CloudStorage/OneDrive-Contoso/Documents/My Games");
+        assert_eq!(CloudSyncDetector::detect_in(path, &[]).unwrap().provider, ONEDRIVE);
+
+        assert!(CloudSyncDetector::detect_in(Path::new("/home/user/.local/share/Game"), &[]).is_none());
+    }
+
+    #[test]
+    fn test_configured_root_takes_precedence() {
+        let roots = vec![(ONEDRIVE, PathBuf::from("/data/work-sync"))];
+        let info = CloudSyncDetector::detect_in(Path::new("/data/work-sync/Documents/Game"), &roots).unwrap();
+        assert_eq!(info.provider, ONEDRIVE);
+        assert_eq!(info.sync_root, "/data/work-sync");
+    }
+}
@@ -1,7 +1,19 @@
 use crate::database::models::*;
 use chrono::Utc;
-use std::path::Path;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::{Path, PathBuf};
 use super::persistence::Persistence;
+use super::scanner::{PathScan, PatternFilter, SaveScanner, ScanLimits};
+use super::utils::Utils;
+use super::cloud_sync::CloudSyncDetector;
+use crate::progress::ProgressReporter;
+
+static PLACEHOLDER_RESOLVER: Lazy<Option<crate::manifest::ManifestResolver>> = Lazy::new(|| {
+    crate::manifest::ManifestResolver::new().ok()
+});
+
+static ENV_VAR_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"%([A-Za-z0-9_]+)%").unwrap());
 
 pub struct Detection;
 
@@ -110,49 +122,199 @@ impl Detection {
         Ok(locations)
     }
 
-    /// Scan for existing save files
+    /// Scan for existing save files, hashing their contents
     pub fn scan_existing_saves(
         tx: &rusqlite::Transaction,
         game_id: i64,
         save_locations: &[SaveLocation],
     ) -> Result<Vec<DetectedSave>, String> {
+        let limits = ScanLimits::from_settings(tx);
         let mut detected_saves = Vec::new();
 
-        for location in save_locations {
-            // Resolve the actual path (simplified - would need path resolution logic)
-            let resolved_paths = Self::resolve_save_paths(location)?;
-
-            for actual_path in resolved_paths {
-                if Path::new(&actual_path).exists() {
-                    let id = Persistence::insert_detected_save(tx, game_id, location.id, &actual_path)?;
-                    detected_saves.push(DetectedSave {
-                        id,
-                        game_id,
-                        save_location_id: location.id,
-                        actual_path,
-                        current_hash: None, // Would compute hash
-                        file_size: None,    // Would get file size
-                        last_modified: Some(Utc::now()),
-                        first_detected: Utc::now(),
-                        last_checked: Utc::now(),
-                        is_active: true,
-                        metadata_json: None,
-                    });
+        for (location, actual_path, scan) in Self::scan_locations(save_locations, &limits, None) {
+            detected_saves.push(Persistence::upsert_detected_save(tx, game_id, location.id, &actual_path, &scan)?);
+        }
+
+        Ok(detected_saves)
+    }
+
+    /// Resolve and scan every existing path of the given locations.
+    ///
+    /// Registry locations and locations for another OS are skipped; each resolved path is walked and hashed separately.
+    /// The returned path is the real directory that was scanned, with symlinks resolved when
+    /// they are followed, so two patterns pointing at the same folder are only scanned once.
+    ///
+    /// Every path is walked before any file is hashed, so `progress` knows the totals up front.
+    pub fn scan_locations<'a>(
+        save_locations: &'a [SaveLocation],
+        limits: &ScanLimits,
+        progress: Option<&ProgressReporter>,
+    ) -> Vec<(&'a SaveLocation, String, PathScan)> {
+        let mut results = Vec::new();
+        let mut scanned = std::collections::HashSet::new();
+
+        for location in save_locations.iter().filter(|l| l.path_type != "registry" && Utils::applies_to_current_platform(l)) {
+            let filter = PatternFilter::new(&location.file_patterns, &location.exclude_patterns);
+
+            for resolved in Self::resolve_save_paths(location).unwrap_or_default() {
+                let Some(root) = SaveScanner::resolve_scan_root(Path::new(&resolved), limits.follow_symlinks) else {
+                    continue;
+                };
+                if !root.exists() || !scanned.insert(root.clone()) {
+                    continue;
+                }
+                let scan = SaveScanner::collect_files(&root, &filter, limits);
+                if let Some(progress) = progress {
+                    progress.add_total(scan.files.len(), scan.total_bytes);
                 }
+                results.push((location, Utils::path_to_string(&root), scan));
             }
         }
 
-        Ok(detected_saves)
+        for (_, _, scan) in &mut results {
+            SaveScanner::hash_files(&mut scan.files, progress);
+        }
+        if let Some(progress) = progress {
+            progress.finish();
+        }
+
+        results
+    }
+
+    /// Resolve save paths from patterns, expanding placeholders, environment
+    /// variables and wildcard path segments
+    pub fn resolve_save_paths(location: &SaveLocation) -> Result<Vec<String>, String> {
+        Ok(Self::resolve_pattern(&location.path_pattern))
+    }
+
+    /// Resolve a single path pattern the way `resolve_save_paths` does. Patterns using
+    /// `{{steam-userdata-current}}` resolve for every Steam account, the current one first.
+    pub fn resolve_pattern(pattern: &str) -> Vec<String> {
+        let mut paths = Vec::new();
+        for expanded in Self::expand_path_variables(pattern) {
+            // Anything still relative (e.g. unknown variables) can't be located on disk
+            if !Path::new(&expanded).is_absolute() {
+                continue;
+            }
+
+            if expanded.contains('*') || expanded.contains('?') {
+                paths.extend(Self::expand_wildcards(&expanded));
+            } else {
+                paths.push(expanded);
+            }
+        }
+        paths
+    }
+
+    /// `{{placeholder}}` names and `%VAR%` environment variables in a pattern that have no
+    /// value on this machine
+    pub fn unresolved_variables(pattern: &str) -> Vec<String> {
+        let mut unresolved = PLACEHOLDER_RESOLVER.as_ref()
+            .map(|resolver| resolver.find_unresolved_placeholders(pattern))
+            .unwrap_or_default();
+        unresolved.extend(ENV_VAR_REGEX.captures_iter(pattern)
+            .filter(|caps| std::env::var(&caps[1]).is_err())
+            .map(|caps| caps[0].to_string()));
+        unresolved
     }
 
-    /// Resolve save paths from patterns (simplified)
-    fn resolve_save_paths(location: &SaveLocation) -> Result<Vec<String>, String> {
-        // This would implement path resolution logic
-        // For now, return the pattern as-is if it's an absolute path
-        if Path::new(&location.path_pattern).is_absolute() {
-            Ok(vec![location.path_pattern.clone()])
+    /// The `{{placeholder}}` names path templates can use here, with their values
+    pub fn available_placeholders() -> Vec<crate::manifest::resolver::PlaceholderInfo> {
+        PLACEHOLDER_RESOLVER.as_ref()
+            .map(|resolver| resolver.describe_placeholders())
+            .unwrap_or_default()
+    }
+
+    /// Expand `{{placeholder}}` templates, `%VAR%` environment variables and `~`, once
+    /// per Steam account for templates that depend on it
+    fn expand_path_variables(pattern: &str) -> Vec<String> {
+        let mut templates = vec![pattern.to_string()];
+
+        if pattern.contains("{{") {
+            if let Some(resolver) = PLACEHOLDER_RESOLVER.as_ref() {
+                if let Ok(resolved) = resolver.resolve_path_candidates(pattern) {
+                    templates = resolved;
+                }
+            }
+        }
+
+        templates.into_iter().map(|template| Self::expand_variables(&template)).collect()
+    }
+
+    fn expand_variables(template: &str) -> String {
+        let mut result = ENV_VAR_REGEX.replace_all(template, |caps: &regex::Captures| {
+            std::env::var(&caps[1]).unwrap_or_else(|_| caps[0].to_string())
+        }).to_string();
+
+        if result == "~" || result.starts_with("~/") {
+            if let Ok(home) = std::env::var("HOME") {
+                result = result.replacen('~', &home, 1);
+            }
+        }
+
+        result
+    }
+
+    /// Expand wildcard path segments against the directories that exist on disk
+    fn expand_wildcards(pattern: &str) -> Vec<String> {
+        let mut candidates = vec![PathBuf::new()];
+
+        for component in Path::new(pattern).components() {
+            let part = component.as_os_str().to_string_lossy().to_string();
+
+            if part.contains('*') || part.contains('?') {
+                let mut next = Vec::new();
+                for base in &candidates {
+                    if let Ok(entries) = std::fs::read_dir(base) {
+                        for entry in entries.flatten() {
+                            if Utils::matches_glob(&entry.file_name().to_string_lossy(), &part) {
+                                next.push(entry.path());
+                            }
+                        }
+                    }
+                }
+                candidates = next;
+            } else {
+                for candidate in candidates.iter_mut() {
+                    candidate.push(component.as_os_str());
+                }
+            }
+        }
+
+        candidates.into_iter()
+            .map(|p| Utils::path_to_string(&p))
+            .collect()
+    }
+
+    /// Describe how a save location resolves on this machine
+    pub fn get_save_location_status(location: &SaveLocation) -> SaveLocationStatus {
+        let resolved_paths = if location.path_type == "registry" {
+            Vec::new()
         } else {
-            Ok(vec![])
+            Self::resolve_save_paths(location).unwrap_or_default()
+        };
+
+        let filter = PatternFilter::new(&location.file_patterns, &location.exclude_patterns);
+        let limits = ScanLimits::default();
+
+        let mut exists = false;
+        let mut matching_file_count = 0;
+
+        for resolved in &resolved_paths {
+            let path = Path::new(resolved);
+            if !path.exists() {
+                continue;
+            }
+            exists = true;
+            matching_file_count += SaveScanner::collect_files(path, &filter, &limits).files.len();
+        }
+
+        SaveLocationStatus {
+            location: location.clone(),
+            cloud_sync: CloudSyncDetector::detect_any(&resolved_paths),
+            resolved_paths,
+            exists,
+            matching_file_count,
         }
     }
 }
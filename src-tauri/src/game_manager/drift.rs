@@ -0,0 +1,177 @@
+use crate::database::models::{DriftCandidate, LocationDrift, SaveLocation};
+use chrono::{Duration, Utc};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use super::detection::Detection;
+use super::scanner::{PatternFilter, SaveScanner, ScanLimits};
+use super::suggestions::SaveLocationSuggestions;
+use super::utils::Utils;
+
+/// Only files written this recently suggest the game is saving somewhere new
+const RECENT_DAYS: i64 = 30;
+/// Directory names that look like save folders even without sharing a name
+const SAVE_DIR_HINTS: [&str; 3] = ["save", "profile", "slot"];
+
+/// Notices when a game update moved its saves away from the configured locations
+pub struct LocationDriftDetector;
+
+impl LocationDriftDetector {
+    /// Check each location that applies to this machine. A location counts as drifted when
+    /// it has no files while a sibling directory or a common save directory for the game has
+    /// files written in the last `RECENT_DAYS` days.
+    pub fn detect(game_name: &str, locations: &[SaveLocation]) -> Vec<LocationDrift> {
+        locations.iter()
+            .filter(|location| location.path_type != "registry" && Utils::applies_to_current_platform(location))
+            .map(|location| Self::check_location(game_name, location))
+            .collect()
+    }
+
+    fn check_location(game_name: &str, location: &SaveLocation) -> LocationDrift {
+        let resolved_paths = Detection::resolve_save_paths(location).unwrap_or_default();
+        let filter = PatternFilter::new(&location.file_patterns, &location.exclude_patterns);
+        let limits = ScanLimits::default();
+
+        let file_count: usize = resolved_paths.iter()
+            .map(Path::new)
+            .filter(|p| p.exists())
+            .map(|p| SaveScanner::collect_files(p, &filter, &limits).files.len())
+            .sum();
+
+        let mut candidates = Vec::new();
+        if file_count == 0 {
+            let cutoff = Utc::now() - Duration::days(RECENT_DAYS);
+            let mut seen: HashSet<PathBuf> = resolved_paths.iter().map(PathBuf::from).collect();
+
+            let siblings = resolved_paths.iter()
+                .flat_map(|p| Self::sibling_dirs(Path::new(p), game_name))
+                .map(|p| (p, "sibling"));
+            let suggestions = SaveLocationSuggestions::suggest(game_name, None).into_iter()
+                .filter(|s| s.exists && s.platform.as_deref() == Some(Utils::get_current_platform()))
+                .flat_map(|s| s.resolved_paths)
+                .map(|p| (PathBuf::from(p), "suggestion"));
+
+            for (path, source) in siblings.chain(suggestions) {
+                if !path.is_dir() || !seen.insert(path.clone()) {
+                    continue;
+                }
+                let scan = SaveScanner::collect_files(&path, &filter, &limits);
+                let newest_modified = scan.last_modified();
+                if scan.files.is_empty() || newest_modified.is_none_or(|m| m < cutoff) {
+                    continue;
+                }
+                candidates.push(DriftCandidate {
+                    path: path.to_string_lossy().to_string(),
+                    source: source.to_string(),
+                    file_count: scan.files.len(),
+                    newest_modified,
+                });
+            }
+            candidates.sort_by(|a, b| b.newest_modified.cmp(&a.newest_modified));
+        }
+
+        LocationDrift {
+            location_id: location.id,
+            path_pattern: location.path_pattern.clone(),
+            resolved_paths,
+            file_count,
+            drifted: !candidates.is_empty(),
+            candidates,
+        }
+    }
+
+    /// Directories next to `path` (or next to its closest existing ancestor) whose name
+    /// resembles it, the game, or a save folder
+    fn sibling_dirs(path: &Path, game_name: &str) -> Vec<PathBuf> {
+        let Some(name) = path.file_name().map(|n| Self::normalize(&n.to_string_lossy())) else {
+            return Vec::new();
+        };
+        let game = Self::normalize(game_name);
+
+        let Some(parent) = path.ancestors().skip(1).find(|p| p.is_dir()) else {
+            return Vec::new();
+        };
+        let Ok(entries) = std::fs::read_dir(parent) else {
+            return Vec::new();
+        };
+
+        entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_dir() && p != path)
+            .filter(|p| {
+                let candidate = Self::normalize(&p.file_name().unwrap_or_default().to_string_lossy());
+                Self::resembles(&candidate, &name)
+                    || (!game.is_empty() && Self::resembles(&candidate, &game))
+                    || SAVE_DIR_HINTS.iter().any(|hint| candidate.contains(hint))
+            })
+            .collect()
+    }
+
+    fn resembles(a: &str, b: &str) -> bool {
+        !a.is_empty() && !b.is_empty() && (a.contains(b) || b.contains(a))
+    }
+
+    /// Lowercase with only letters and digits, so `Save Games` matches `savegames_v2`
+    fn normalize(name: &str) -> String {
+        name.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn location(path: &Path) -> SaveLocation {
+        SaveLocation {
+            id: 1,
+            game_id: 1,
+            path_pattern: path.to_string_lossy().to_string(),
+            path_type: "directory".to_string(),
+            platform: None,
+            save_type: "auto".to_string(),
+            file_patterns: None,
+            exclude_patterns: None,
+            is_relative_to_user: false,
+            environment_variable: None,
+            priority: 5,
+            detection_method: None,
+            community_confirmed: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_flags_empty_location_with_recent_sibling() {
+        let dir = TempDir::new().unwrap();
+        let configured = dir.path().join("Saves");
+        let moved = dir.path().join("Saves_v2");
+        std::fs::create_dir(&configured).unwrap();
+        std::fs::create_dir(&moved).unwrap();
+        std::fs::write(moved.join("slot1.sav"), b"data").unwrap();
+        std::fs::create_dir(dir.path().join("Shaders")).unwrap();
+
+        let reports = LocationDriftDetector::detect("Some Game", &[location(&configured)]);
+        assert_eq!(reports.len(), 1);
+        assert!(reports[0].drifted);
+        assert_eq!(reports[0].candidates.len(), 1);
+        assert_eq!(reports[0].candidates[0].path, moved.to_string_lossy());
+        assert_eq!(reports[0].candidates[0].source, "sibling");
+    }
+
+    #[test]
+    fn test_location_with_files_has_not_drifted() {
+        let dir = TempDir::new().unwrap();
+        let configured = dir.path().join("Saves");
+        std::fs::create_dir(&configured).unwrap();
+        std::fs::write(configured.join("slot1.sav"), b"data").unwrap();
+        std::fs::create_dir(dir.path().join("Saves_v2")).unwrap();
+        std::fs::write(dir.path().join("Saves_v2").join("slot1.sav"), b"data").unwrap();
+
+        let reports = LocationDriftDetector::detect("Some Game", &[location(&configured)]);
+        assert!(!reports[0].drifted);
+        assert_eq!(reports[0].file_count, 1);
+        assert!(reports[0].candidates.is_empty());
+    }
+}
@@ -0,0 +1,320 @@
+use base64::Engine;
+use std::path::Path;
+
+const RT_ICON: u32 = 3;
+const RT_GROUP_ICON: u32 = 14;
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+pub struct IconExtractor;
+
+impl IconExtractor {
+    /// Extract an icon from an executable or image file and return it base64 encoded.
+    ///
+    /// Image files are encoded as-is. Windows executables yield their largest embedded
+    /// icon, as PNG when the resource is PNG-compressed and as a single-image ICO otherwise.
+    pub fn extract_icon_base64(path: &Path) -> Result<String, String> {
+        let data = std::fs::read(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+        let extension = path.extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        let icon = if matches!(extension.as_str(), "png" | "ico" | "jpg" | "jpeg" | "bmp") {
+            data
+        } else if data.starts_with(b"MZ") {
+            Self::extract_pe_icon(&data)
+                .ok_or_else(|| format!("No icon resource found in {}", path.display()))?
+        } else {
+            return Err(format!("Unsupported icon source: {}", path.display()));
+        };
+
+        Ok(base64::engine::general_purpose::STANDARD.encode(icon))
+    }
+
+    /// Find the largest icon in a PE file's resource section
+    fn extract_pe_icon(data: &[u8]) -> Option<Vec<u8>> {
+        let pe = PeResources::parse(data)?;
+
+        let group = pe.find_first_resource(RT_GROUP_ICON)?;
+        let count = read_u16(group, 4)? as usize;
+
+        // GRPICONDIRENTRY: width, height, colors, reserved, planes, bit count, size, id
+        let (_, entry) = (0..count)
+            .filter_map(|i| {
+                let entry = group.get(6 + i * 14..6 + (i + 1) * 14)?;
+                let width = if entry[0] == 0 { 256 } else { entry[0] as u32 };
+                let bit_count = read_u16(entry, 6)? as u32;
+                Some((width * 1000 + bit_count, entry))
+            })
+            .max_by_key(|(score, _)| *score)?;
+
+        let icon_id = read_u16(entry, 12)? as u32;
+        let image = pe.find_resource(RT_ICON, icon_id)?;
+
+        if image.starts_with(&PNG_SIGNATURE) {
+            return Some(image.to_vec());
+        }
+
+        // Wrap the raw DIB in a single-image ICO container
+        let mut ico = Vec::with_capacity(22 + image.len());
+        ico.extend_from_slice(&[0, 0, 1, 0, 1, 0]);
+        ico.extend_from_slice(&entry[0..8]);
+        ico.extend_from_slice(&(image.len() as u32).to_le_bytes());
+        ico.extend_from_slice(&22u32.to_le_bytes());
+        ico.extend_from_slice(image);
+        Some(ico)
+    }
+}
+
+/// Minimal reader for the `.rsrc` directory tree of a PE image
+struct PeResources<'a> {
+    data: &'a [u8],
+    sections: Vec<(u32, u32, u32)>, // (virtual address, virtual size, raw offset)
+    rsrc_offset: usize,
+}
+
+impl<'a> PeResources<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let pe = pe_parser::pe::parse_portable_executable(data).ok()?;
+
+        let resource_table = match (pe.optional_header_32, pe.optional_header_64) {
+            (Some(header), _) => header.data_directories.resource_table,
+            (_, Some(header)) => header.data_directories.resource_table,
+            _ => return None,
+        };
+        if resource_table.virtual_address == 0 {
+            return None;
+        }
+
+        let sections = pe.section_table.iter()
+            .map(|section| (
+                section.virtual_address,
+                section.virtual_size.max(section.size_of_raw_data),
+                section.pointer_to_raw_data,
+            ))
+            .collect();
+
+        let mut resources = Self { data, sections, rsrc_offset: 0 };
+        resources.rsrc_offset = resources.rva_to_offset(resource_table.virtual_address)?;
+        Some(resources)
+    }
+
+    fn rva_to_offset(&self, rva: u32) -> Option<usize> {
+        self.sections.iter()
+            .find(|(address, size, _)| rva >= *address && (rva as u64) < *address as u64 + *size as u64)
+            .map(|(address, _, raw)| (rva - address) as usize + *raw as usize)
+    }
+
+    /// Entries of the resource directory at `offset` (relative to the resource section)
+    fn directory_entries(&self, offset: usize) -> Vec<(u32, u32)> {
+        let base = self.rsrc_offset + offset;
+        let named = read_u16(self.data, base + 12).unwrap_or(0) as usize;
+        let ids = read_u16(self.data, base + 14).unwrap_or(0) as usize;
+
+        (0..named + ids)
+            .filter_map(|i| {
+                let entry = base + 16 + i * 8;
+                Some((read_u32(self.data, entry)?, read_u32(self.data, entry + 4)?))
+            })
+            .collect()
+    }
+
+    /// Descend to the first leaf below a subdirectory entry and return its bytes
+    fn first_leaf(&self, mut target: u32) -> Option<&'a [u8]> {
+        // The tree is type/name/language, so never descend more than three levels
+        for _ in 0..3 {
+            if target & 0x8000_0000 == 0 {
+                break;
+            }
+            let (_, next) = *self.directory_entries((target & 0x7FFF_FFFF) as usize).first()?;
+            target = next;
+        }
+        if target & 0x8000_0000 != 0 {
+            return None;
+        }
+
+        let leaf = self.rsrc_offset + target as usize;
+        let offset = self.rva_to_offset(read_u32(self.data, leaf)?)?;
+        let size = read_u32(self.data, leaf + 4)? as usize;
+        self.data.get(offset..offset + size)
+    }
+
+    fn type_directory(&self, resource_type: u32) -> Option<u32> {
+        self.directory_entries(0).into_iter()
+            .find(|(id, target)| *id == resource_type && target & 0x8000_0000 != 0)
+            .map(|(_, target)| target)
+    }
+
+    fn find_first_resource(&self, resource_type: u32) -> Option<&'a [u8]> {
+        self.first_leaf(self.type_directory(resource_type)?)
+    }
+
+    fn find_resource(&self, resource_type: u32, resource_id: u32) -> Option<&'a [u8]> {
+        let directory = self.type_directory(resource_type)?;
+        let (_, target) = self.directory_entries((directory & 0x7FFF_FFFF) as usize).into_iter()
+            .find(|(id, _)| *id == resource_id)?;
+        self.first_leaf(target)
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSRC_RVA: u32 = 0x1000;
+    const RSRC_OFFSET: usize = 0x200;
+    const SUBDIRECTORY: u32 = 0x8000_0000;
+
+    /// Resources of one type as `(id, data)` pairs
+    type Resources = (u32, Vec<(u32, Vec<u8>)>);
+
+    fn directory(out: &mut Vec<u8>, entries: &[(u32, u32)]) {
+        out.extend_from_slice(&[0; 12]);
+        out.extend_from_slice(&0u16.to_le_bytes());
+        out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        for (id, target) in entries {
+            out.extend_from_slice(&id.to_le_bytes());
+            out.extend_from_slice(&target.to_le_bytes());
+        }
+    }
+
+    /// A `.rsrc` section with a type/name/language tree holding `resources`
+    fn resource_section(resources: &[Resources]) -> Vec<u8> {
+        let mut offset = 16 + 8 * resources.len();
+        let type_directories: Vec<usize> = resources.iter()
+            .map(|(_, items)| {
+                let start = offset;
+                offset += 16 + 8 * items.len();
+                start
+            })
+            .collect();
+        let leaves: Vec<&Vec<u8>> = resources.iter().flat_map(|(_, items)| items.iter().map(|(_, data)| data)).collect();
+        let language_directories = offset;
+        let data_entries = language_directories + 24 * leaves.len();
+        let mut data_offset = data_entries + 16 * leaves.len();
+
+        let mut out = Vec::new();
+        let types: Vec<(u32, u32)> = resources.iter().zip(&type_directories)
+            .map(|((resource_type, _), start)| (*resource_type, SUBDIRECTORY | *start as u32))
+            .collect();
+        directory(&mut out, &types);
+
+        let mut leaf = 0;
+        for (_, items) in resources {
+            let names: Vec<(u32, u32)> = items.iter().enumerate()
+                .map(|(i, (id, _))| (*id, SUBDIRECTORY | (language_directories + 24 * (leaf + i)) as u32))
+                .collect();
+            directory(&mut out, &names);
+            leaf += items.len();
+        }
+        for i in 0..leaves.len() {
+            directory(&mut out, &[(0x409, (data_entries + 16 * i) as u32)]);
+        }
+        for data in &leaves {
+            out.extend_from_slice(&(RSRC_RVA + data_offset as u32).to_le_bytes());
+            out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            out.extend_from_slice(&[0; 8]);
+            data_offset += data.len();
+        }
+        for data in &leaves {
+            out.extend_from_slice(data);
+        }
+        out
+    }
+
+    /// A PE image with `rsrc` as its only section
+    fn pe_image(rsrc: &[u8], pe32_plus: bool) -> Vec<u8> {
+        let (magic, optional_size, data_directories): (u16, usize, usize) = if pe32_plus {
+            (0x20B, 240, 112)
+        } else {
+            (0x10B, 224, 96)
+        };
+
+        let mut image = vec![0u8; RSRC_OFFSET];
+        image[0..2].copy_from_slice(b"MZ");
+        image[0x3C..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+        image[0x40..0x44].copy_from_slice(b"PE\0\0");
+
+        let coff = 0x44;
+        image[coff + 2..coff + 4].copy_from_slice(&1u16.to_le_bytes());
+        image[coff + 16..coff + 18].copy_from_slice(&(optional_size as u16).to_le_bytes());
+
+        let optional = coff + 20;
+        image[optional..optional + 2].copy_from_slice(&magic.to_le_bytes());
+        image[optional + data_directories - 4..optional + data_directories].copy_from_slice(&16u32.to_le_bytes());
+        let resource_directory = optional + data_directories + 16;
+        image[resource_directory..resource_directory + 4].copy_from_slice(&RSRC_RVA.to_le_bytes());
+        image[resource_directory + 4..resource_directory + 8].copy_from_slice(&(rsrc.len() as u32).to_le_bytes());
+
+        let section = optional + optional_size;
+        image[section..section + 5].copy_from_slice(b".rsrc");
+        image[section + 8..section + 12].copy_from_slice(&(rsrc.len() as u32).to_le_bytes());
+        image[section + 12..section + 16].copy_from_slice(&RSRC_RVA.to_le_bytes());
+        image[section + 16..section + 20].copy_from_slice(&(rsrc.len() as u32).to_le_bytes());
+        image[section + 20..section + 24].copy_from_slice(&(RSRC_OFFSET as u32).to_le_bytes());
+
+        image.extend_from_slice(rsrc);
+        image
+    }
+
+    /// A GRPICONDIR listing `(width, bit count, icon id)` entries
+    fn icon_group(entries: &[(u8, u16, u16)]) -> Vec<u8> {
+        let mut group = vec![0, 0, 1, 0];
+        group.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        for (width, bit_count, id) in entries {
+            group.extend_from_slice(&[*width, *width, 0, 0]);
+            group.extend_from_slice(&1u16.to_le_bytes());
+            group.extend_from_slice(&bit_count.to_le_bytes());
+            group.extend_from_slice(&0u32.to_le_bytes());
+            group.extend_from_slice(&id.to_le_bytes());
+        }
+        group
+    }
+
+    #[test]
+    fn test_extract_pe_icon_wraps_largest_dib_in_ico() {
+        let small = vec![0x11; 40];
+        let large = vec![0x22; 64];
+        let rsrc = resource_section(&[
+            (RT_ICON, vec![(1, small), (2, large.clone())]),
+            (RT_GROUP_ICON, vec![(1, icon_group(&[(16, 32, 1), (48, 32, 2)]))]),
+        ]);
+
+        for pe32_plus in [false, true] {
+            let ico = IconExtractor::extract_pe_icon(&pe_image(&rsrc, pe32_plus)).unwrap();
+            assert_eq!(&ico[0..6], &[0, 0, 1, 0, 1, 0]);
+            assert_eq!(ico[6], 48);
+            assert_eq!(read_u32(&ico, 14), Some(large.len() as u32));
+            assert_eq!(read_u32(&ico, 18), Some(22));
+            assert_eq!(&ico[22..], &large[..]);
+        }
+    }
+
+    #[test]
+    fn test_extract_pe_icon_returns_png_as_is() {
+        let mut png = PNG_SIGNATURE.to_vec();
+        png.extend_from_slice(b"image data");
+        let rsrc = resource_section(&[
+            (RT_ICON, vec![(1, vec![0x11; 40]), (7, png.clone())]),
+            (RT_GROUP_ICON, vec![(1, icon_group(&[(32, 32, 1), (0, 32, 7)]))]),
+        ]);
+
+        assert_eq!(IconExtractor::extract_pe_icon(&pe_image(&rsrc, false)), Some(png));
+    }
+
+    #[test]
+    fn test_extract_pe_icon_without_icons() {
+        let rsrc = resource_section(&[(RT_ICON, vec![(1, vec![0x11; 40])])]);
+        assert_eq!(IconExtractor::extract_pe_icon(&pe_image(&rsrc, false)), None);
+        assert_eq!(IconExtractor::extract_pe_icon(b"MZ not a PE image"), None);
+    }
+}
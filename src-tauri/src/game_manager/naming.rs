@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+
+/// Placeholders a naming template may use
+pub const NAMING_PLACEHOLDERS: [&str; 4] = ["{game}", "{save}", "{date}", "{counter}"];
+
+/// Values substituted into a naming template
+pub struct NamingContext<'a> {
+    pub game: &'a str,
+    /// Checkpoint name, or the backup kind (`manual`, `session_end`, ...) for backups
+    pub save: &'a str,
+    pub date: DateTime<Utc>,
+    /// One more than the number of existing branches or backups for the game
+    pub counter: usize,
+}
+
+/// Per-game template for checkpoint branch names and backup ids, e.g. `{game}-{save}-{counter}`
+pub struct NamingTemplate;
+
+impl NamingTemplate {
+    /// Reject empty templates and unknown `{...}` placeholders
+    pub fn validate(template: &str) -> Result<(), String> {
+        if template.trim().is_empty() {
+            return Err("Invalid naming template: template is empty".to_string());
+        }
+
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..].find('}')
+                .ok_or_else(|| format!("Invalid naming template: unclosed placeholder in '{}'", template))?;
+            let placeholder = &rest[start..start + end + 1];
+            if !NAMING_PLACEHOLDERS.contains(&placeholder) {
+                return Err(format!("Invalid naming template: unknown placeholder {} (expected one of {})",
+                    placeholder, NAMING_PLACEHOLDERS.join(", ")));
+            }
+            rest = &rest[start + end + 1..];
+        }
+
+        Ok(())
+    }
+
+    /// Substitute the placeholders. The result still has to be sanitized for its use.
+    pub fn render(template: &str, context: &NamingContext) -> String {
+        template
+            .replace("{game}", context.game)
+            .replace("{save}", context.save)
+            .replace("{date}", &context.date.format("%Y%m%d-%H%M%S").to_string())
+            .replace("{counter}", &context.counter.to_string())
+    }
+
+    /// Render a template into something usable as a file name: anything other than
+    /// letters, digits, `.`, `_`, `+` and `-` becomes `-`
+    pub fn render_file_safe(template: &str, context: &NamingContext) -> String {
+        Self::render(template, context)
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '+' | '-') { c } else { '-' })
+            .collect::<String>()
+            .split('-')
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<&str>>()
+            .join("-")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_and_validate() {
+        let context = NamingContext {
+            game: "Dark Souls",
+            save: "NG+3 boss",
+            date: DateTime::from_timestamp(0, 0).unwrap(),
+            counter: 4,
+        };
+
+        assert_eq!(NamingTemplate::render("{game}-{save}-{counter}", &context), "Dark Souls-NG+3 boss-4");
+        assert_eq!(NamingTemplate::render_file_safe("{game}/{save}_{date}", &context), "Dark-Souls-NG+3-boss_19700101-000000");
+
+        assert!(NamingTemplate::validate("{game}-{save}").is_ok());
+        assert!(NamingTemplate::validate("{game}-{slot}").is_err());
+        assert!(NamingTemplate::validate("{game").is_err());
+        assert!(NamingTemplate::validate("  ").is_err());
+    }
+}
@@ -1,4 +1,6 @@
 use crate::database::models::*;
+use crate::detection::platform::PlatformApiClient;
+use crate::pcgaming_wiki::models::{GameSearchResult, PcgwGameMetadata};
 use chrono::Utc;
 use regex::Regex;
 use std::collections::HashMap;
@@ -92,6 +94,99 @@ impl PcgwIntegration {
         serde_json::to_string(&executables).ok()
     }
 
+    /// Whether a search result's name is just a placeholder or the echoed query
+    fn has_unreliable_name(result: &GameSearchResult, query: &str) -> bool {
+        let name = result.name.trim();
+        name.is_empty() || name.eq_ignore_ascii_case("unknown") || name.eq_ignore_ascii_case(query.trim())
+    }
+
+    /// Replace unreliable result names with the Steam store name of their app id.
+    /// Lookups that fail leave the name as it was.
+    pub async fn resolve_search_result_names(
+        results: &mut [GameSearchResult],
+        query: &str,
+        client: &PlatformApiClient,
+    ) {
+        for result in results.iter_mut().filter(|r| Self::has_unreliable_name(r, query)) {
+            let Some(app_id) = result.steam_id.clone() else { continue };
+            match client.resolve_steam_game_name(&app_id).await {
+                Ok(Some(name)) => result.name = name,
+                Ok(None) => {}
+                Err(e) => crate::logger::warn("PCGW", &format!("Could not resolve name of Steam app {}", app_id), Some(&e.to_string())),
+            }
+        }
+    }
+
+    /// Compare PCGW's locations with the configured ones by platform and path. Configured
+    /// locations that came from PCGW and are no longer listed are reported as removed;
+    /// locations added by hand are left out.
+    pub fn diff_locations(pcgw: &[SaveLocation], configured: &[SaveLocation]) -> Vec<PcgwLocationDiff> {
+        let key = |location: &SaveLocation| {
+            let path = location.path_pattern.trim().trim_end_matches(['/', '\\']);
+            let path = if location.platform.as_deref() == Some("windows") { path.to_lowercase() } else { path.to_string() };
+            (location.platform.clone(), path)
+        };
+
+        let mut matched = std::collections::HashSet::new();
+        let mut diffs: Vec<PcgwLocationDiff> = pcgw.iter()
+            .map(|location| {
+                let existing = configured.iter().find(|c| key(c) == key(location));
+                if let Some(existing) = existing {
+                    matched.insert(existing.id);
+                }
+                PcgwLocationDiff {
+                    path_pattern: location.path_pattern.clone(),
+                    platform: location.platform.clone(),
+                    status: if existing.is_some() { "unchanged" } else { "new" }.to_string(),
+                    location_id: existing.map(|c| c.id),
+                }
+            })
+            .collect();
+
+        diffs.extend(configured.iter()
+            .filter(|c| c.detection_method.as_deref() == Some("pcgamingwiki") && !matched.contains(&c.id))
+            .map(|c| PcgwLocationDiff {
+                path_pattern: c.path_pattern.clone(),
+                platform: c.platform.clone(),
+                status: "removed".to_string(),
+                location_id: Some(c.id),
+            }));
+        diffs
+    }
+
+    /// Clean up PCGW infobox values: list fields drop their `Company:` style prefixes and are
+    /// joined with ", ", and the release date is the earliest of the per-platform dates.
+    pub fn metadata_from_pcgw(info: &PcgwGameMetadata) -> GameMetadata {
+        let list = |value: &Option<String>| {
+            let items: Vec<&str> = value.as_deref()?
+                .split(',')
+                .map(|item| item.split_once(':').map_or(item, |(_, name)| name).trim())
+                .filter(|item| !item.is_empty())
+                .collect();
+            (!items.is_empty()).then(|| items.join(", "))
+        };
+
+        let release_date = info.released.as_deref().and_then(|released| {
+            let dates: Vec<&str> = released.split(';').map(str::trim).filter(|d| !d.is_empty()).collect();
+            dates.iter()
+                .filter(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").is_ok())
+                .min()
+                .or(dates.first())
+                .map(|d| d.to_string())
+        });
+
+        GameMetadata {
+            developer: list(&info.developers),
+            publisher: list(&info.publishers),
+            genre: list(&info.genres),
+            release_date,
+            cover_image_url: info.cover_url.as_deref()
+                .map(str::trim)
+                .filter(|url| url.starts_with("http"))
+                .map(str::to_string),
+        }
+    }
+
     pub fn convert_pcgw_locations(result: &crate::pcgaming_wiki::models::SaveLocationResult) -> Vec<SaveLocation> {
         let mut locations = Vec::new();
 
@@ -140,3 +235,56 @@ impl PcgwIntegration {
         locations
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pcgaming_wiki::models::SaveLocationResult;
+
+    #[test]
+    fn test_diff_locations_flags_new_and_removed() {
+        let result = SaveLocationResult {
+            windows: vec!["C:\\Users\\me\\Saved Games\\Game".to_string()],
+            linux: vec!["/home/me/.local/share/game-v2".to_string()],
+            macos: Vec::new(),
+            steam_play: Vec::new(),
+        };
+        let pcgw = PcgwIntegration::convert_pcgw_locations(&result);
+
+        let mut configured = PcgwIntegration::convert_pcgw_locations(&SaveLocationResult {
+            windows: vec!["c:\\users\\me\\saved games\\game\\".to_string()],
+            linux: vec!["/home/me/.local/share/game".to_string()],
+            macos: Vec::new(),
+            steam_play: Vec::new(),
+        });
+        configured[0].id = 1;
+        configured[1].id = 2;
+
+        let diffs = PcgwIntegration::diff_locations(&pcgw, &configured);
+        let status: Vec<(&str, Option<i64>)> = diffs.iter().map(|d| (d.status.as_str(), d.location_id)).collect();
+        assert_eq!(status, vec![("unchanged", Some(1)), ("new", None), ("removed", Some(2))]);
+    }
+
+    #[test]
+    fn test_metadata_from_pcgw_cleans_infobox_values() {
+        let info = PcgwGameMetadata {
+            developers: Some("Company:Valve,Company:Hidden Path Entertainment".to_string()),
+            publishers: Some("Company:Valve".to_string()),
+            released: Some("2014-09-23;2012-08-21".to_string()),
+            genres: Some("Action,FPS".to_string()),
+            cover_url: Some("".to_string()),
+        };
+
+        let metadata = PcgwIntegration::metadata_from_pcgw(&info);
+        assert_eq!(metadata.developer.as_deref(), Some("Valve, Hidden Path Entertainment"));
+        assert_eq!(metadata.publisher.as_deref(), Some("Valve"));
+        assert_eq!(metadata.genre.as_deref(), Some("Action, FPS"));
+        assert_eq!(metadata.release_date.as_deref(), Some("2012-08-21"));
+        assert_eq!(metadata.cover_image_url, None);
+
+        let undated = PcgwGameMetadata { released: Some("TBA".to_string()), developers: None, ..info };
+        let metadata = PcgwIntegration::metadata_from_pcgw(&undated);
+        assert_eq!(metadata.release_date.as_deref(), Some("TBA"));
+        assert_eq!(metadata.developer, None);
+    }
+}
@@ -1,8 +1,16 @@
 use crate::database::models::*;
+use crate::backup::archive::ArchiveFormat;
 use chrono::{DateTime, Utc};
-use rusqlite::params;
+use rusqlite::{params, OptionalExtension};
+use super::scanner::PathScan;
 use std::sync::Arc;
 
+/// Column list matching `Persistence::game_from_row`
+const GAME_COLUMNS: &str = "id, name, developer, publisher, platform, platform_app_id,
+    executable_path, installation_path, platform_executables,
+    genre, release_date, cover_image_url, icon_base64, icon_path,
+    created_at, updated_at, is_active";
+
 pub struct Persistence;
 
 impl Persistence {
@@ -32,6 +40,10 @@ impl Persistence {
                 crate::logger::info("DATABASE", &format!("Successfully inserted game '{}' into database", request.name), None);
                 Ok(rowid)
             }
+            Err(rusqlite::Error::SqliteFailure(err, _)) if err.code == rusqlite::ErrorCode::ConstraintViolation => {
+                let existing = Self::find_game_by_platform_id(tx, &request.platform, request.platform_app_id.as_deref())?;
+                Err(Self::duplicate_game_error(existing.unwrap_or_default()))
+            }
             Err(e) => {
                 crate::logger::error("DATABASE", &format!("Failed to insert game '{}': {}", request.name, e), None);
                 Err(format!("Insert game error: {}", e))
@@ -39,6 +51,25 @@ impl Persistence {
         }
     }
 
+    /// The game already added with this store id, if any. Games without an app id never match.
+    pub fn find_game_by_platform_id(conn: &rusqlite::Connection, platform: &str, platform_app_id: Option<&str>) -> Result<Option<i64>, String> {
+        let Some(app_id) = platform_app_id.map(str::trim).filter(|id| !id.is_empty()) else {
+            return Ok(None);
+        };
+
+        conn.query_row(
+            "SELECT id FROM games WHERE platform = ? AND platform_app_id = ? ORDER BY id LIMIT 1",
+            params![platform, app_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Query duplicate game error: {}", e))
+    }
+
+    pub fn duplicate_game_error(existing_id: i64) -> String {
+        format!("Invalid game request: game already exists (id {})", existing_id)
+    }
+
     /// Insert save location into database
     pub fn insert_save_location(
         tx: &rusqlite::Transaction,
@@ -96,6 +127,77 @@ impl Persistence {
         Ok(tx.last_insert_rowid())
     }
 
+    /// Insert or refresh the detected save for a path with the stats of its latest scan
+    pub fn upsert_detected_save(
+        conn: &rusqlite::Connection,
+        game_id: i64,
+        save_location_id: i64,
+        actual_path: &str,
+        scan: &PathScan,
+    ) -> Result<DetectedSave, String> {
+        let now = Utc::now();
+        let current_hash = scan.combined_hash();
+        let file_size = scan.total_bytes as i64;
+        let last_modified = scan.last_modified();
+
+        let existing: Option<(i64, String)> = conn.query_row(
+            "SELECT id, first_detected FROM detected_saves
+             WHERE game_id = ? AND save_location_id = ? AND actual_path = ?",
+            params![game_id, save_location_id, actual_path],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).optional().map_err(|e| format!("Query detected save error: {}", e))?;
+
+        let (id, first_detected) = match existing {
+            Some((id, first_detected)) => {
+                conn.execute(
+                    "UPDATE detected_saves SET current_hash = ?, file_size = ?, last_modified = ?,
+                                               last_checked = ?, is_active = 1
+                     WHERE id = ?",
+                    params![
+                        current_hash,
+                        file_size,
+                        last_modified.map(|t| t.to_rfc3339()),
+                        now.to_rfc3339(),
+                        id,
+                    ],
+                ).map_err(|e| format!("Update detected save error: {}", e))?;
+                (id, Self::parse_timestamp(&first_detected).unwrap_or(now))
+            }
+            None => {
+                conn.execute(
+                    "INSERT INTO detected_saves (game_id, save_location_id, actual_path, current_hash, file_size,
+                                                last_modified, first_detected, last_checked, is_active)
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, 1)",
+                    params![
+                        game_id,
+                        save_location_id,
+                        actual_path,
+                        current_hash,
+                        file_size,
+                        last_modified.map(|t| t.to_rfc3339()),
+                        now.to_rfc3339(),
+                        now.to_rfc3339(),
+                    ],
+                ).map_err(|e| format!("Insert detected save error: {}", e))?;
+                (conn.last_insert_rowid(), now)
+            }
+        };
+
+        Ok(DetectedSave {
+            id,
+            game_id,
+            save_location_id,
+            actual_path: actual_path.to_string(),
+            current_hash,
+            file_size: Some(file_size),
+            last_modified,
+            first_detected,
+            last_checked: now,
+            is_active: true,
+            metadata_json: None,
+        })
+    }
+
     /// Parse timestamp string from database to DateTime
     pub fn parse_timestamp(timestamp_str: &str) -> Result<DateTime<Utc>, String> {
         DateTime::parse_from_rfc3339(timestamp_str)
@@ -105,98 +207,734 @@ impl Persistence {
 
     /// Get game by ID
     pub fn get_game_by_id(conn: &rusqlite::Connection, game_id: i64) -> Result<Game, String> {
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM games WHERE id = ?", GAME_COLUMNS))
+            .map_err(|e| format!("Prepare statement error: {}", e))?;
+
+        let game = stmt.query_row([game_id], Self::game_from_row)
+            .map_err(|e| format!("Query game error: {}", e))?;
+
+        Ok(game)
+    }
+
+    /// Get game by ID, `None` when no game has it
+    pub fn find_game_by_id(conn: &rusqlite::Connection, game_id: i64) -> Result<Option<Game>, String> {
+        conn.query_row(&format!("SELECT {} FROM games WHERE id = ?", GAME_COLUMNS), [game_id], Self::game_from_row)
+            .optional()
+            .map_err(|e| format!("Query game error: {}", e))
+    }
+
+    /// Map a row selected with `GAME_COLUMNS` to a `Game`
+    fn game_from_row(row: &rusqlite::Row) -> rusqlite::Result<Game> {
+        let created_at_str: String = row.get(14)?;
+        let updated_at_str: String = row.get(15)?;
+
+        let created_at = Self::parse_timestamp(&created_at_str)
+            .unwrap_or_else(|_| Utc::now());
+        let updated_at = Self::parse_timestamp(&updated_at_str)
+            .unwrap_or_else(|_| Utc::now());
+
+        Ok(Game {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            developer: row.get(2)?,
+            publisher: row.get(3)?,
+            platform: row.get(4)?,
+            platform_app_id: row.get(5)?,
+            executable_path: row.get(6)?,
+            installation_path: row.get(7)?,
+            platform_executables: row.get(8)?,
+            genre: row.get(9)?,
+            release_date: row.get(10)?,
+            cover_image_url: row.get(11)?,
+            icon_base64: row.get(12)?,
+            icon_path: row.get(13)?,
+            created_at,
+            updated_at,
+            is_active: row.get(16)?,
+        })
+    }
+
+    /// Active games that have no rows in `save_locations`, so they can never be backed up
+    pub fn get_games_without_save_locations(conn: &rusqlite::Connection) -> Result<Vec<Game>, String> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM games
+             WHERE is_active = TRUE
+               AND NOT EXISTS (SELECT 1 FROM save_locations WHERE save_locations.game_id = games.id)
+             ORDER BY name ASC",
+            GAME_COLUMNS
+        )).map_err(|e| format!("Prepare statement error: {}", e))?;
+
+        let games = stmt.query_map([], Self::game_from_row)
+            .map_err(|e| format!("Query games error: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Collect games error: {}", e))?;
+
+        Ok(games)
+    }
+
+    /// Get save locations for a game, highest priority first
+    pub fn get_save_locations(conn: &rusqlite::Connection, game_id: i64) -> Result<Vec<SaveLocation>, String> {
         let mut stmt = conn.prepare(
-            "SELECT id, name, developer, publisher, platform, platform_app_id,
-                    executable_path, installation_path, platform_executables,
-                    genre, release_date, cover_image_url, icon_base64, icon_path,
-                    created_at, updated_at, is_active
-             FROM games WHERE id = ?"
+            "SELECT id, game_id, path_pattern, path_type, platform, save_type,
+                    file_patterns, exclude_patterns, is_relative_to_user,
+                    environment_variable, priority, detection_method,
+                    community_confirmed, created_at, updated_at
+             FROM save_locations WHERE game_id = ? ORDER BY priority DESC, id ASC"
         ).map_err(|e| format!("Prepare statement error: {}", e))?;
 
-        let game = stmt.query_row([game_id], |row| {
-            let created_at_str: String = row.get(14)?;
-            let updated_at_str: String = row.get(15)?;
+        let locations = stmt.query_map([game_id], |row| {
+            let created_at_str: String = row.get(13)?;
+            let updated_at_str: String = row.get(14)?;
 
-            let created_at = Self::parse_timestamp(&created_at_str)
-                .unwrap_or_else(|_| Utc::now());
-            let updated_at = Self::parse_timestamp(&updated_at_str)
-                .unwrap_or_else(|_| Utc::now());
-
-            Ok(Game {
+            Ok(SaveLocation {
                 id: row.get(0)?,
-                name: row.get(1)?,
-                developer: row.get(2)?,
-                publisher: row.get(3)?,
+                game_id: row.get(1)?,
+                path_pattern: row.get(2)?,
+                path_type: row.get(3)?,
                 platform: row.get(4)?,
-                platform_app_id: row.get(5)?,
-                executable_path: row.get(6)?,
-                installation_path: row.get(7)?,
-                platform_executables: row.get(8)?,
-                genre: row.get(9)?,
-                release_date: row.get(10)?,
-                cover_image_url: row.get(11)?,
-                icon_base64: row.get(12)?,
-                icon_path: row.get(13)?,
-                created_at,
-                updated_at,
-                is_active: row.get(16)?,
+                save_type: row.get(5)?,
+                file_patterns: row.get(6)?,
+                exclude_patterns: row.get(7)?,
+                is_relative_to_user: row.get(8)?,
+                environment_variable: row.get(9)?,
+                priority: row.get(10)?,
+                detection_method: row.get(11)?,
+                community_confirmed: row.get(12)?,
+                created_at: Self::parse_timestamp(&created_at_str).unwrap_or_else(|_| Utc::now()),
+                updated_at: Self::parse_timestamp(&updated_at_str).unwrap_or_else(|_| Utc::now()),
             })
-        }).map_err(|e| format!("Query game error: {}", e))?;
+        })
+        .map_err(|e| format!("Query save locations error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Collect save locations error: {}", e))?;
 
-        Ok(game)
+        Ok(locations)
     }
 
-    /// Get all active games
-    pub async fn get_all_games(
-        db: &std::sync::Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
-    ) -> Result<Vec<Game>, String> {
-        let conn_guard = db.lock().await;
-        let conn = conn_guard.get_connection().await;
+    /// Change a save location's priority, returning the id of the game it belongs to
+    pub fn set_save_location_priority(conn: &rusqlite::Connection, location_id: i64, priority: i32) -> Result<i64, String> {
+        let game_id: i64 = conn.query_row(
+            "SELECT game_id FROM save_locations WHERE id = ?",
+            [location_id],
+            |row| row.get(0),
+        ).optional()
+            .map_err(|e| format!("Query save location error: {}", e))?
+            .ok_or_else(|| format!("Save location {} not found", location_id))?;
 
+        conn.execute(
+            "UPDATE save_locations SET priority = ?, updated_at = ? WHERE id = ?",
+            params![priority, Utc::now().to_rfc3339(), location_id],
+        ).map_err(|e| format!("Update save location priority error: {}", e))?;
+        Ok(game_id)
+    }
+
+    /// Delete every identifier recorded for a game, returning how many were removed
+    pub fn delete_game_identifiers(conn: &rusqlite::Connection, game_id: i64) -> Result<usize, String> {
+        Self::get_game_by_id(conn, game_id)?;
+        conn.execute("DELETE FROM game_identifiers WHERE game_id = ?", [game_id])
+            .map_err(|e| format!("Failed to delete identifiers: {}", e))
+    }
+
+    /// Get identifiers (executable hashes, process names, ...) recorded for a game
+    pub fn get_game_identifiers(conn: &rusqlite::Connection, game_id: i64) -> Result<Vec<GameIdentifier>, String> {
         let mut stmt = conn.prepare(
-            "SELECT id, name, developer, publisher, platform, platform_app_id,
-                    executable_path, installation_path, platform_executables,
-                    genre, release_date, cover_image_url, icon_base64, icon_path,
-                    created_at, updated_at, is_active
-             FROM games WHERE is_active = TRUE ORDER BY name ASC"
+            "SELECT id, game_id, identifier_type, identifier_value, confidence_score,
+                    detection_context, created_at
+             FROM game_identifiers WHERE game_id = ? ORDER BY identifier_type, id"
         ).map_err(|e| format!("Prepare statement error: {}", e))?;
 
-        let games = stmt.query_map([], |row| {
-            let created_at_str: String = row.get(14)?;
-            let updated_at_str: String = row.get(15)?;
+        let identifiers = stmt.query_map([game_id], |row| {
+            let created_at_str: Option<String> = row.get(6)?;
+
+            Ok(GameIdentifier {
+                id: row.get(0)?,
+                game_id: row.get(1)?,
+                identifier_type: row.get(2)?,
+                identifier_value: row.get(3)?,
+                confidence_score: row.get::<_, f64>(4)? as f32,
+                detection_context: row.get(5)?,
+                created_at: created_at_str
+                    .and_then(|s| Self::parse_timestamp(&s).ok())
+                    .unwrap_or_else(Utc::now),
+            })
+        })
+        .map_err(|e| format!("Query game identifiers error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Collect game identifiers error: {}", e))?;
+
+        Ok(identifiers)
+    }
 
-            let created_at = Self::parse_timestamp(&created_at_str)
-                .unwrap_or_else(|_| Utc::now());
-            let updated_at = Self::parse_timestamp(&updated_at_str)
-                .unwrap_or_else(|_| Utc::now());
+    /// Get git branches recorded for a game
+    pub fn get_git_branches(conn: &rusqlite::Connection, game_id: i64) -> Result<Vec<GameBranch>, String> {
+        let mut stmt = conn.prepare(
+            "SELECT id, game_id, branch_name, description, is_active, last_commit_hash, created_at
+             FROM git_branches WHERE game_id = ? ORDER BY id"
+        ).map_err(|e| format!("Prepare statement error: {}", e))?;
 
-            Ok(Game {
+        let branches = stmt.query_map([game_id], |row| {
+            let created_at_str: Option<String> = row.get(6)?;
+
+            Ok(GameBranch {
                 id: row.get(0)?,
-                name: row.get(1)?,
-                developer: row.get(2)?,
-                publisher: row.get(3)?,
-                platform: row.get(4)?,
-                platform_app_id: row.get(5)?,
-                executable_path: row.get(6)?,
-                installation_path: row.get(7)?,
-                platform_executables: row.get(8)?,
-                genre: row.get(9)?,
-                release_date: row.get(10)?,
-                cover_image_url: row.get(11)?,
-                icon_base64: row.get(12)?,
-                icon_path: row.get(13)?,
-                created_at,
-                updated_at,
-                is_active: row.get(16)?,
+                game_id: row.get(1)?,
+                branch_name: row.get(2)?,
+                description: row.get(3)?,
+                is_active: row.get(4)?,
+                last_commit_hash: row.get(5)?,
+                created_at: created_at_str
+                    .and_then(|s| Self::parse_timestamp(&s).ok())
+                    .unwrap_or_else(Utc::now),
+            })
+        })
+        .map_err(|e| format!("Query git branches error: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Collect git branches error: {}", e))?;
+
+        Ok(branches)
+    }
+
+    /// Summarize backed-up save versions and git checkpoints for a game
+    pub fn get_backup_stats(conn: &rusqlite::Connection, game_id: i64) -> Result<GameBackupStats, String> {
+        let (version_count, total_size_bytes, last_backup): (i64, i64, Option<String>) = conn.query_row(
+            "SELECT COUNT(sv.id), COALESCE(SUM(sv.compressed_size), 0), MAX(sv.created_at)
+             FROM save_versions sv
+             JOIN detected_saves ds ON sv.detected_save_id = ds.id
+             WHERE ds.game_id = ?",
+            [game_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        ).map_err(|e| format!("Query save versions error: {}", e))?;
+
+        let (checkpoint_count, last_checkpoint): (i64, Option<String>) = conn.query_row(
+            "SELECT COUNT(*), MAX(timestamp) FROM git_save_commits WHERE game_id = ?",
+            [game_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).map_err(|e| format!("Query git commits error: {}", e))?;
+
+        Ok(GameBackupStats {
+            version_count,
+            total_size_bytes,
+            last_backup: last_backup.and_then(|s| Self::parse_timestamp(&s).ok()),
+            checkpoint_count,
+            last_checkpoint: last_checkpoint.and_then(|s| Self::parse_timestamp(&s).ok()),
+        })
+    }
+
+    /// Count save versions and git checkpoints per UTC day between `from` and `to`
+    /// (inclusive, `YYYY-MM-DD`). Days without backups are left out.
+    pub fn get_backup_timeline(conn: &rusqlite::Connection, game_id: i64, from: &str, to: &str) -> Result<Vec<BackupTimelineDay>, String> {
+        let mut stmt = conn.prepare(
+            "SELECT day, COUNT(*), COALESCE(SUM(bytes), 0) FROM (
+                 SELECT date(sv.created_at) AS day, COALESCE(sv.compressed_size, 0) AS bytes
+                 FROM save_versions sv
+                 JOIN detected_saves ds ON sv.detected_save_id = ds.id
+                 WHERE ds.game_id = ?1
+                 UNION ALL
+                 SELECT date(timestamp) AS day, 0 AS bytes
+                 FROM git_save_commits WHERE game_id = ?1
+             )
+             WHERE day BETWEEN ?2 AND ?3
+             GROUP BY day ORDER BY day"
+        ).map_err(|e| format!("Prepare statement error: {}", e))?;
+
+        let days = stmt.query_map(params![game_id, from, to], |row| {
+            Ok(BackupTimelineDay {
+                date: row.get(0)?,
+                count: row.get(1)?,
+                total_bytes: row.get(2)?,
             })
         })
-        .map_err(|e| format!("Query games error: {}", e))?
+        .map_err(|e| format!("Query backup timeline error: {}", e))?
         .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Collect games error: {}", e))?;
+        .map_err(|e| format!("Collect backup timeline error: {}", e))?;
+
+        Ok(days)
+    }
+
+    /// Make sure a user_games row exists for the game so per-game settings can be updated
+    pub fn ensure_user_game(conn: &rusqlite::Connection, game_id: i64) -> Result<(), String> {
+        let exists: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM user_games WHERE game_id = ?",
+            [game_id],
+            |row| row.get(0),
+        ).map_err(|e| format!("Query user game error: {}", e))?;
+
+        if exists == 0 {
+            let now = Utc::now().to_rfc3339();
+            conn.execute(
+                "INSERT INTO user_games (game_id, created_at, updated_at) VALUES (?, ?, ?)",
+                params![game_id, now, now],
+            ).map_err(|e| format!("Insert user game error: {}", e))?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether the game is protected by automatic backups; games without a user_games row are
+    pub fn is_backup_enabled(conn: &rusqlite::Connection, game_id: i64) -> Result<bool, String> {
+        // Archived games are never backed up automatically
+        let archived = conn.query_row("SELECT is_active FROM games WHERE id = ?", [game_id], |row| row.get::<_, bool>(0))
+            .map(|active| !active)
+            .unwrap_or(false);
+        if archived {
+            return Ok(false);
+        }
+
+        let result = conn.query_row(
+            "SELECT backup_enabled FROM user_games WHERE game_id = ? ORDER BY id LIMIT 1",
+            [game_id],
+            |row| row.get::<_, Option<bool>>(0),
+        );
+
+        match result {
+            Ok(value) => Ok(value.unwrap_or(true)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(true),
+            Err(e) => Err(format!("Query backup enabled error: {}", e)),
+        }
+    }
+
+    /// Ids of the active games whose backups are enabled, following `is_backup_enabled`
+    pub fn get_backup_enabled_game_ids(conn: &rusqlite::Connection) -> Result<Vec<i64>, String> {
+        let mut stmt = conn.prepare(
+            "SELECT g.id FROM games g
+             WHERE g.is_active = TRUE
+               AND COALESCE((SELECT u.backup_enabled FROM user_games u WHERE u.game_id = g.id ORDER BY u.id LIMIT 1), 1) = 1
+             ORDER BY g.id"
+        ).map_err(|e| format!("Prepare statement error: {}", e))?;
+
+        let ids = stmt.query_map([], |row| row.get(0))
+            .map_err(|e| format!("Query backup enabled games error: {}", e))?
+            .collect::<Result<Vec<i64>, _>>()
+            .map_err(|e| format!("Read backup enabled games error: {}", e))?;
+        Ok(ids)
+    }
+
+    /// Turn automatic backups for a game on or off, keeping its existing backups
+    pub async fn set_backup_enabled(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        enabled: bool,
+    ) -> Result<(), String> {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+
+        // Make sure the game exists
+        Self::get_game_by_id(&conn, game_id)?;
+        Self::ensure_user_game(&conn, game_id)?;
+
+        conn.execute(
+            "UPDATE user_games SET backup_enabled = ?, updated_at = ? WHERE game_id = ?",
+            params![enabled, Utc::now().to_rfc3339(), game_id],
+        ).map_err(|e| format!("Update backup enabled error: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Get the per-game real-time backup override (None follows the global setting)
+    pub fn get_real_time_backup_override(conn: &rusqlite::Connection, game_id: i64) -> Result<Option<bool>, String> {
+        let result = conn.query_row(
+            "SELECT real_time_backup_enabled FROM user_games WHERE game_id = ? ORDER BY id LIMIT 1",
+            [game_id],
+            |row| row.get::<_, Option<bool>>(0),
+        );
+
+        match result {
+            Ok(value) => Ok(value),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(format!("Query real-time backup setting error: {}", e)),
+        }
+    }
+
+    /// Set or clear the per-game real-time backup override
+    pub async fn set_real_time_backup_override(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        enabled: Option<bool>,
+    ) -> Result<(), String> {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+
+        // Make sure the game exists
+        Self::get_game_by_id(&conn, game_id)?;
+        Self::ensure_user_game(&conn, game_id)?;
+
+        conn.execute(
+            "UPDATE user_games SET real_time_backup_enabled = ?, updated_at = ? WHERE game_id = ?",
+            params![enabled, Utc::now().to_rfc3339(), game_id],
+        ).map_err(|e| format!("Update real-time backup setting error: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Get the per-game zstd level for save archives, if one was chosen
+    pub fn get_compression_level(conn: &rusqlite::Connection, game_id: i64) -> Result<Option<i32>, String> {
+        let result = conn.query_row(
+            "SELECT compression_level FROM user_games WHERE game_id = ? ORDER BY id LIMIT 1",
+            [game_id],
+            |row| row.get::<_, Option<i32>>(0),
+        );
+
+        match result {
+            Ok(value) => Ok(value),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(format!("Query compression level error: {}", e)),
+        }
+    }
+
+    /// Set the per-game zstd level for save archives
+    pub async fn set_compression_level(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        level: i32,
+    ) -> Result<(), String> {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+
+        // Make sure the game exists
+        Self::get_game_by_id(&conn, game_id)?;
+        Self::ensure_user_game(&conn, game_id)?;
+
+        conn.execute(
+            "UPDATE user_games SET compression_level = ?, updated_at = ? WHERE game_id = ?",
+            params![level, Utc::now().to_rfc3339(), game_id],
+        ).map_err(|e| format!("Update compression level error: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Get the per-game naming template for checkpoints and backups, if one was set
+    pub fn get_naming_template(conn: &rusqlite::Connection, game_id: i64) -> Result<Option<String>, String> {
+        let result = conn.query_row(
+            "SELECT naming_template FROM user_games WHERE game_id = ? ORDER BY id LIMIT 1",
+            [game_id],
+            |row| row.get::<_, Option<String>>(0),
+        );
+
+        match result {
+            Ok(value) => Ok(value),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(format!("Query naming template error: {}", e)),
+        }
+    }
+
+    /// Set (or with `None`, clear) the per-game naming template
+    pub async fn set_naming_template(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        template: Option<&str>,
+    ) -> Result<(), String> {
+        if let Some(template) = template {
+            super::naming::NamingTemplate::validate(template)?;
+        }
+
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+
+        // Make sure the game exists
+        Self::get_game_by_id(&conn, game_id)?;
+        Self::ensure_user_game(&conn, game_id)?;
+
+        conn.execute(
+            "UPDATE user_games SET naming_template = ?, updated_at = ? WHERE game_id = ?",
+            params![template, Utc::now().to_rfc3339(), game_id],
+        ).map_err(|e| format!("Update naming template error: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Get the per-game archive format for exported and safety backups, if one was chosen
+    pub fn get_archive_format(conn: &rusqlite::Connection, game_id: i64) -> Result<Option<ArchiveFormat>, String> {
+        let result = conn.query_row(
+            "SELECT archive_format FROM user_games WHERE game_id = ? ORDER BY id LIMIT 1",
+            [game_id],
+            |row| row.get::<_, Option<String>>(0),
+        );
+
+        match result {
+            Ok(value) => value.map(|v| ArchiveFormat::parse(&v)).transpose(),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(format!("Query archive format error: {}", e)),
+        }
+    }
+
+    /// Set (or with `None`, reset to the default) the per-game archive format
+    pub async fn set_archive_format(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        format: Option<ArchiveFormat>,
+    ) -> Result<(), String> {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+
+        // Make sure the game exists
+        Self::get_game_by_id(&conn, game_id)?;
+        Self::ensure_user_game(&conn, game_id)?;
+
+        conn.execute(
+            "UPDATE user_games SET archive_format = ?, updated_at = ? WHERE game_id = ?",
+            params![format.map(|f| f.as_str()), Utc::now().to_rfc3339(), game_id],
+        ).map_err(|e| format!("Update archive format error: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Folder holding the game's backups and branches while they are in cold storage
+    pub fn get_cold_storage_path(conn: &rusqlite::Connection, game_id: i64) -> Result<Option<String>, String> {
+        conn.query_row(
+            "SELECT cold_storage_path FROM user_games WHERE game_id = ? ORDER BY id LIMIT 1",
+            [game_id],
+            |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()
+        .map(|path| path.flatten())
+        .map_err(|e| format!("Query cold storage path error: {}", e))
+    }
+
+    pub fn set_cold_storage_path(conn: &rusqlite::Connection, game_id: i64, path: Option<&str>) -> Result<(), String> {
+        Self::ensure_user_game(conn, game_id)?;
+        conn.execute(
+            "UPDATE user_games SET cold_storage_path = ?, updated_at = ? WHERE game_id = ?",
+            params![path, Utc::now().to_rfc3339(), game_id],
+        ).map_err(|e| format!("Update cold storage path error: {}", e))?;
+        Ok(())
+    }
+
+    /// The PCGW page a game was mapped to
+    pub fn get_pcgw_page_name(conn: &rusqlite::Connection, game_id: i64) -> Result<Option<String>, String> {
+        conn.query_row(
+            "SELECT pcgw_page_name FROM game_pcgw_mapping WHERE game_id = ?",
+            [game_id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|e| format!("Query PCGW mapping error: {}", e))
+    }
+
+    /// Map a game to a PCGW page and mark it as synced now
+    pub fn set_pcgw_mapping(conn: &rusqlite::Connection, game_id: i64, page_name: &str) -> Result<(), String> {
+        conn.execute(
+            "INSERT INTO game_pcgw_mapping (game_id, pcgw_page_name, last_synced_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(game_id) DO UPDATE SET pcgw_page_name = excluded.pcgw_page_name, last_synced_at = excluded.last_synced_at",
+            params![game_id, page_name, Utc::now().to_rfc3339()],
+        ).map_err(|e| format!("Update PCGW mapping error: {}", e))?;
+        Ok(())
+    }
+
+    /// Write PCGW metadata to a game's columns. Values the game already has are kept unless
+    /// `overwrite` is set; returns the columns that changed.
+    pub fn apply_game_metadata(
+        conn: &rusqlite::Connection,
+        game_id: i64,
+        metadata: &GameMetadata,
+        overwrite: bool,
+    ) -> Result<Vec<String>, String> {
+        let game = Self::get_game_by_id(conn, game_id)?;
+        let fields = [
+            ("developer", &game.developer, &metadata.developer),
+            ("publisher", &game.publisher, &metadata.publisher),
+            ("genre", &game.genre, &metadata.genre),
+            ("release_date", &game.release_date, &metadata.release_date),
+            ("cover_image_url", &game.cover_image_url, &metadata.cover_image_url),
+        ];
+
+        let mut updated = Vec::new();
+        for (column, current, new) in fields {
+            let Some(new) = new else { continue };
+            let is_set = current.as_deref().is_some_and(|value| !value.trim().is_empty());
+            if current.as_ref() == Some(new) || (is_set && !overwrite) {
+                continue;
+            }
+            conn.execute(
+                &format!("UPDATE games SET {} = ? WHERE id = ?", column),
+                params![new, game_id],
+            ).map_err(|e| format!("Update game metadata error: {}", e))?;
+            updated.push(column.to_string());
+        }
+
+        if !updated.is_empty() {
+            conn.execute(
+                "UPDATE games SET updated_at = ? WHERE id = ?",
+                params![Utc::now().to_rfc3339(), game_id],
+            ).map_err(|e| format!("Update game metadata error: {}", e))?;
+        }
+        Ok(updated)
+    }
+
+    /// Get the per-game debounce and coalesce settings
+    pub fn get_monitor_settings(conn: &rusqlite::Connection, game_id: i64) -> Result<GameMonitorSettings, String> {
+        let result = conn.query_row(
+            "SELECT backup_debounce_seconds, event_coalesce_ms FROM user_games WHERE game_id = ? ORDER BY id LIMIT 1",
+            [game_id],
+            |row| Ok(GameMonitorSettings {
+                debounce_seconds: row.get::<_, Option<i64>>(0)?.map(|v| v.max(0) as u64),
+                coalesce_window_ms: row.get::<_, Option<i64>>(1)?.map(|v| v.max(0) as u64),
+            }),
+        );
+
+        match result {
+            Ok(settings) => Ok(settings),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(GameMonitorSettings::default()),
+            Err(e) => Err(format!("Query monitor settings error: {}", e)),
+        }
+    }
+
+    /// Set or clear the per-game debounce and coalesce settings
+    pub async fn set_monitor_settings(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        settings: &GameMonitorSettings,
+    ) -> Result<(), String> {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+
+        // Make sure the game exists
+        Self::get_game_by_id(&conn, game_id)?;
+        Self::ensure_user_game(&conn, game_id)?;
+
+        conn.execute(
+            "UPDATE user_games SET backup_debounce_seconds = ?, event_coalesce_ms = ?, updated_at = ? WHERE game_id = ?",
+            params![
+                settings.debounce_seconds.map(|v| v as i64),
+                settings.coalesce_window_ms.map(|v| v as i64),
+                Utc::now().to_rfc3339(),
+                game_id,
+            ],
+        ).map_err(|e| format!("Update monitor settings error: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Get the per-game retention overrides
+    pub fn get_retention_settings(conn: &rusqlite::Connection, game_id: i64) -> Result<GameRetentionSettings, String> {
+        let result = conn.query_row(
+            "SELECT max_versions, retention_max_bytes, retention_min_age_hours FROM user_games WHERE game_id = ? ORDER BY id LIMIT 1",
+            [game_id],
+            |row| Ok(GameRetentionSettings {
+                max_backups: row.get::<_, Option<i64>>(0)?.map(|v| v.max(0) as usize),
+                max_bytes: row.get::<_, Option<i64>>(1)?.map(|v| v.max(0) as u64),
+                min_age_hours: row.get::<_, Option<i64>>(2)?.map(|v| v.max(0) as u64),
+            }),
+        );
+
+        match result {
+            Ok(settings) => Ok(settings),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(GameRetentionSettings::default()),
+            Err(e) => Err(format!("Query retention settings error: {}", e)),
+        }
+    }
+
+    /// Set or clear the per-game retention overrides
+    pub async fn set_retention_settings(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        settings: &GameRetentionSettings,
+    ) -> Result<(), String> {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+
+        // Make sure the game exists
+        Self::get_game_by_id(&conn, game_id)?;
+        Self::ensure_user_game(&conn, game_id)?;
+
+        conn.execute(
+            "UPDATE user_games SET max_versions = ?, retention_max_bytes = ?, retention_min_age_hours = ?, updated_at = ? WHERE game_id = ?",
+            params![
+                settings.max_backups.map(|v| v as i64),
+                settings.max_bytes.map(|v| v as i64),
+                settings.min_age_hours.map(|v| v as i64),
+                Utc::now().to_rfc3339(),
+                game_id,
+            ],
+        ).map_err(|e| format!("Update retention settings error: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Get all active games
+    pub async fn get_all_games(
+        db: &std::sync::Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+    ) -> Result<Vec<Game>, String> {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM games WHERE is_active = TRUE ORDER BY name ASC", GAME_COLUMNS))
+            .map_err(|e| format!("Prepare statement error: {}", e))?;
+
+        let games = stmt.query_map([], Self::game_from_row)
+            .map_err(|e| format!("Query games error: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Collect games error: {}", e))?;
+
+        Ok(games)
+    }
+
+    /// Ids of the games marked as favorites
+    pub fn get_favorite_game_ids(conn: &rusqlite::Connection) -> Result<std::collections::HashSet<i64>, String> {
+        let mut stmt = conn.prepare("SELECT DISTINCT game_id FROM user_games WHERE is_favorite = 1")
+            .map_err(|e| format!("Prepare statement error: {}", e))?;
+
+        let ids = stmt.query_map([], |row| row.get(0))
+            .map_err(|e| format!("Query favorites error: {}", e))?
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("Collect favorites error: {}", e))?;
+
+        Ok(ids)
+    }
+
+    /// Archived games: hidden from the library but keeping their backups and history
+    pub fn get_archived_games(conn: &rusqlite::Connection) -> Result<Vec<Game>, String> {
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM games WHERE is_active = FALSE ORDER BY name ASC", GAME_COLUMNS))
+            .map_err(|e| format!("Prepare statement error: {}", e))?;
+
+        let games = stmt.query_map([], Self::game_from_row)
+            .map_err(|e| format!("Query games error: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Collect games error: {}", e))?;
 
         Ok(games)
     }
 
+    /// Archive (`active = false`) or restore a game without touching its saves or backups
+    pub async fn set_game_active(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        active: bool,
+    ) -> Result<(), String> {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+
+        // Make sure the game exists
+        Self::get_game_by_id(&conn, game_id)?;
+
+        conn.execute(
+            "UPDATE games SET is_active = ?, updated_at = ? WHERE id = ?",
+            params![active, Utc::now().to_rfc3339(), game_id],
+        ).map_err(|e| format!("Update game active state error: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Mark or unmark a game as a favorite
+    pub async fn set_favorite(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        favorite: bool,
+    ) -> Result<(), String> {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+
+        // Make sure the game exists
+        Self::get_game_by_id(&conn, game_id)?;
+        Self::ensure_user_game(&conn, game_id)?;
+
+        conn.execute(
+            "UPDATE user_games SET is_favorite = ?, updated_at = ? WHERE game_id = ?",
+            params![favorite, Utc::now().to_rfc3339(), game_id],
+        ).map_err(|e| format!("Update favorite error: {}", e))?;
+
+        Ok(())
+    }
+
     /// Update an existing game
     pub async fn update_game(
         db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
@@ -243,6 +981,49 @@ impl Persistence {
         Ok(())
     }
 
+    /// Replace a game's per-OS launch executables
+    pub async fn set_platform_executables(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        executables_json: &str,
+    ) -> Result<Game, String> {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+
+        let rows_affected = conn.execute(
+            "UPDATE games SET platform_executables = ?, updated_at = ? WHERE id = ?",
+            params![executables_json, Utc::now().to_rfc3339(), game_id],
+        ).map_err(|e| format!("Update game executables error: {}", e))?;
+
+        if rows_affected == 0 {
+            return Err(format!("Game with id {} not found", game_id));
+        }
+
+        Self::get_game_by_id(&conn, game_id)
+    }
+
+    /// Store a freshly extracted icon and the file it came from
+    pub async fn update_game_icon(
+        db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+        game_id: i64,
+        icon_base64: &str,
+        icon_path: &str,
+    ) -> Result<(), String> {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+
+        let rows_affected = conn.execute(
+            "UPDATE games SET icon_base64 = ?, icon_path = ?, updated_at = ? WHERE id = ?",
+            params![icon_base64, icon_path, Utc::now().to_rfc3339(), game_id],
+        ).map_err(|e| format!("Update game icon error: {}", e))?;
+
+        if rows_affected == 0 {
+            return Err(format!("Game with id {} not found", game_id));
+        }
+
+        Ok(())
+    }
+
     /// Delete a game and all associated data
     pub async fn delete_game(
         db: &Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
@@ -291,6 +1072,7 @@ impl Persistence {
         safe_delete(&tx, "save_locations", game_id)?;
         safe_delete(&tx, "user_games", game_id)?;
         safe_delete(&tx, "game_identifiers", game_id)?;
+        safe_delete(&tx, "detection_history", game_id)?;
         
         // Finally delete the game itself
         let rows_affected = tx.execute("DELETE FROM games WHERE id = ?", [game_id])
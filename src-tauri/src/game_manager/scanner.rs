@@ -0,0 +1,308 @@
+use chrono::{DateTime, Utc};
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+use super::utils::Utils;
+use crate::progress::ProgressReporter;
+
+/// `app_settings` key controlling whether scans descend into symlinked files and folders
+pub const FOLLOW_SYMLINKS_SETTING: &str = "follow_symlinks";
+/// Read size while hashing; also how often hashing progress is counted
+const HASH_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Upper bounds for a single scan, so a misconfigured root like `C:\` can't run away
+#[derive(Debug, Clone, Copy)]
+pub struct ScanLimits {
+    pub max_files: usize,
+    pub max_total_bytes: u64,
+    /// Follow symlinks below (and at) the scan root; when off they are skipped entirely
+    pub follow_symlinks: bool,
+}
+
+impl Default for ScanLimits {
+    fn default() -> Self {
+        Self {
+            max_files: 50_000,
+            max_total_bytes: 10 * 1024 * 1024 * 1024, // 10 GB
+            follow_symlinks: true,
+        }
+    }
+}
+
+impl ScanLimits {
+    /// Default limits with `follow_symlinks` taken from the app settings
+    pub fn from_settings(conn: &rusqlite::Connection) -> Self {
+        Self {
+            follow_symlinks: configured_follow_symlinks(conn),
+            ..Self::default()
+        }
+    }
+}
+
+/// The `follow_symlinks` setting, defaulting to on
+pub fn configured_follow_symlinks(conn: &rusqlite::Connection) -> bool {
+    crate::database::settings::AppSettings::get(conn, FOLLOW_SYMLINKS_SETTING)
+        .ok()
+        .flatten()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(true)
+}
+
+#[derive(Debug, Clone)]
+pub struct ScannedFile {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: Option<DateTime<Utc>>,
+    pub hash: Option<String>,
+}
+
+/// Files found below one resolved save path
+#[derive(Debug, Clone, Default)]
+pub struct PathScan {
+    pub files: Vec<ScannedFile>,
+    pub total_bytes: u64,
+    pub limit_reached: bool,
+}
+
+impl PathScan {
+    /// Single hash over every file's path and content hash
+    pub fn combined_hash(&self) -> Option<String> {
+        if self.files.is_empty() {
+            return None;
+        }
+
+        let mut hasher = Sha256::new();
+        for file in &self.files {
+            hasher.update(file.path.as_os_str().as_encoded_bytes());
+            hasher.update(file.hash.as_deref().unwrap_or("").as_bytes());
+        }
+        Some(hex::encode(hasher.finalize()))
+    }
+
+    pub fn last_modified(&self) -> Option<DateTime<Utc>> {
+        self.files.iter().filter_map(|f| f.modified).max()
+    }
+}
+
+/// Compiled `file_patterns` / `exclude_patterns` of a save location
+pub struct PatternFilter {
+    include: Vec<regex::Regex>,
+    exclude: Vec<regex::Regex>,
+}
+
+impl PatternFilter {
+    pub fn new(file_patterns: &Option<String>, exclude_patterns: &Option<String>) -> Self {
+        let compile = |patterns: &Option<String>| {
+            Utils::parse_patterns(patterns).iter()
+                .filter_map(|p| Utils::glob_regex(p))
+                .collect::<Vec<_>>()
+        };
+
+        Self {
+            include: compile(file_patterns),
+            exclude: compile(exclude_patterns),
+        }
+    }
+
+    /// Filter that only excludes, from already-parsed glob patterns
+    pub fn excluding(patterns: &[String]) -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: patterns.iter().filter_map(|p| Utils::glob_regex(p)).collect(),
+        }
+    }
+
+    pub fn has_exclusions(&self) -> bool {
+        !self.exclude.is_empty()
+    }
+
+    pub fn is_excluded(&self, name: &str) -> bool {
+        self.exclude.iter().any(|re| re.is_match(name))
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|re| re.is_match(name)))
+            && !self.is_excluded(name)
+    }
+}
+
+pub struct SaveScanner;
+
+impl SaveScanner {
+    /// Resolve the directory a scan of `path` actually reads.
+    ///
+    /// With `follow_symlinks` the canonical target is returned; otherwise a root that is
+    /// itself a symlink yields `None` so it is skipped rather than silently followed.
+    pub fn resolve_scan_root(path: &Path, follow_symlinks: bool) -> Option<PathBuf> {
+        if follow_symlinks {
+            return path.canonicalize().ok();
+        }
+
+        match std::fs::symlink_metadata(path) {
+            Ok(metadata) if metadata.file_type().is_symlink() => None,
+            Ok(_) => Some(path.to_path_buf()),
+            Err(_) => None,
+        }
+    }
+
+    /// Walk `root` and collect matching files without hashing them.
+    ///
+    /// Excluded directory names are pruned. Collection stops once either limit is hit,
+    /// and the result is flagged with `limit_reached`. Symlinks are followed or skipped
+    /// according to `limits.follow_symlinks`; loops are reported and broken, and a file
+    /// reachable through several links is only collected once.
+    pub fn collect_files(root: &Path, filter: &PatternFilter, limits: &ScanLimits) -> PathScan {
+        let mut scan = PathScan::default();
+        let mut seen_targets = HashSet::new();
+
+        let walker = WalkDir::new(root)
+            .follow_links(limits.follow_symlinks)
+            .follow_root_links(limits.follow_symlinks)
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !e.file_type().is_dir() || !filter.is_excluded(&e.file_name().to_string_lossy()));
+
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    if let Some(ancestor) = e.loop_ancestor() {
+                        crate::logger::warn("SAVE_SCANNER", &format!("Skipping symlink loop under {}", root.display()),
+                            Some(&format!("{} links back to {}", e.path().map(|p| p.display().to_string()).unwrap_or_default(), ancestor.display())));
+                    }
+                    continue;
+                }
+            };
+
+            if !entry.file_type().is_file() || !filter.matches(&entry.file_name().to_string_lossy()) {
+                continue;
+            }
+
+            if limits.follow_symlinks {
+                if let Ok(target) = entry.path().canonicalize() {
+                    if !seen_targets.insert(target) {
+                        continue;
+                    }
+                }
+            }
+
+            let metadata = match entry.metadata() {
+                Ok(m) => m,
+                Err(_) => continue,
+            };
+
+            if scan.files.len() >= limits.max_files || scan.total_bytes + metadata.len() > limits.max_total_bytes {
+                crate::logger::warn("SAVE_SCANNER", &format!("Scan limit reached under {}", root.display()),
+                    Some(&format!("{} files, {} bytes", scan.files.len(), scan.total_bytes)));
+                scan.limit_reached = true;
+                break;
+            }
+
+            scan.total_bytes += metadata.len();
+            scan.files.push(ScannedFile {
+                path: entry.into_path(),
+                size: metadata.len(),
+                modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+                hash: None,
+            });
+        }
+
+        // Stable order keeps combined hashes comparable between scans
+        scan.files.sort_by(|a, b| a.path.cmp(&b.path));
+        scan
+    }
+
+    /// Hash collected files in parallel on the rayon pool
+    pub fn hash_files(files: &mut [ScannedFile], progress: Option<&ProgressReporter>) {
+        files.par_iter_mut().for_each(|file| {
+            file.hash = Self::hash_file(&file.path, progress).ok();
+            if let Some(progress) = progress {
+                progress.file_done();
+            }
+        });
+    }
+
+    /// Collect and hash the matching files below `root`
+    pub fn scan_path(root: &Path, filter: &PatternFilter, limits: &ScanLimits) -> PathScan {
+        let mut scan = Self::collect_files(root, filter, limits);
+        Self::hash_files(&mut scan.files, None);
+        scan
+    }
+
+    fn hash_file(path: &Path, progress: Option<&ProgressReporter>) -> Result<String, String> {
+        let mut file = std::fs::File::open(path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0u8; HASH_BUFFER_SIZE];
+        loop {
+            let read = file.read(&mut buffer)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+            if let Some(progress) = progress {
+                progress.add_bytes(read as u64);
+            }
+        }
+        Ok(hex::encode(hasher.finalize()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scan_respects_patterns_and_limits() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("Screenshots")).unwrap();
+        std::fs::write(dir.path().join("slot1.sav"), b"one").unwrap();
+        std::fs::write(dir.path().join("slot2.sav"), b"two").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), b"skip").unwrap();
+        std::fs::write(dir.path().join("Screenshots").join("shot.sav"), b"skip").unwrap();
+
+        let filter = PatternFilter::new(
+            &Some(r#"["*.sav"]"#.to_string()),
+            &Some(r#"["Screenshots"]"#.to_string()),
+        );
+
+        let scan = SaveScanner::scan_path(dir.path(), &filter, &ScanLimits::default());
+        assert_eq!(scan.files.len(), 2);
+        assert_eq!(scan.total_bytes, 6);
+        assert!(!scan.limit_reached);
+        assert!(scan.files.iter().all(|f| f.hash.is_some()));
+
+        let limited = SaveScanner::collect_files(dir.path(), &filter, &ScanLimits { max_files: 1, max_total_bytes: u64::MAX, follow_symlinks: true });
+        assert_eq!(limited.files.len(), 1);
+        assert!(limited.limit_reached);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_loops_and_follow_setting() {
+        let dir = TempDir::new().unwrap();
+        let saves = dir.path().join("saves");
+        std::fs::create_dir(&saves).unwrap();
+        std::fs::write(saves.join("slot1.sav"), b"one").unwrap();
+        std::os::unix::fs::symlink(&saves, saves.join("loop")).unwrap();
+        std::os::unix::fs::symlink(saves.join("slot1.sav"), saves.join("alias.sav")).unwrap();
+
+        let filter = PatternFilter::new(&None, &None);
+        let followed = SaveScanner::collect_files(&saves, &filter, &ScanLimits::default());
+        assert_eq!(followed.files.len(), 1);
+
+        let link = dir.path().join("linked");
+        std::os::unix::fs::symlink(&saves, &link).unwrap();
+        assert_eq!(SaveScanner::resolve_scan_root(&link, true), Some(saves.canonicalize().unwrap()));
+        assert_eq!(SaveScanner::resolve_scan_root(&link, false), None);
+
+        let no_follow = ScanLimits { follow_symlinks: false, ..ScanLimits::default() };
+        let skipped = SaveScanner::collect_files(&saves, &filter, &no_follow);
+        assert_eq!(skipped.files.len(), 1);
+        assert_eq!(skipped.files[0].path.file_name().unwrap(), "slot1.sav");
+    }
+}
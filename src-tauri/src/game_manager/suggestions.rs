@@ -0,0 +1,131 @@
+use crate::database::models::SuggestedLocation;
+use crate::pcgaming_wiki::models::SaveLocationResult;
+use std::collections::HashSet;
+use super::cloud_sync::CloudSyncDetector;
+use super::detection::Detection;
+use super::utils::Utils;
+
+/// PCGW documents where a game actually saves, so it ranks above any guess
+const PCGW_LIKELIHOOD: u32 = 60;
+const HEURISTIC_LIKELIHOOD: u32 = 30;
+/// A path that already exists is the strongest hint of all
+const EXISTS_BONUS: u32 = 100;
+const CURRENT_PLATFORM_BONUS: u32 = 10;
+
+/// Candidate save locations for a game that is being set up
+pub struct SaveLocationSuggestions;
+
+impl SaveLocationSuggestions {
+    /// Merge PCGW's documented locations with common save directories named after the
+    /// game, most likely first. A path suggested by both keeps the PCGW entry.
+    pub fn suggest(game_name: &str, pcgw: Option<&SaveLocationResult>) -> Vec<SuggestedLocation> {
+        let mut candidates: Vec<(String, &str, &str)> = Vec::new();
+        if let Some(pcgw) = pcgw {
+            for (paths, platform) in [(&pcgw.windows, "windows"), (&pcgw.linux, "linux"), (&pcgw.macos, "macos")] {
+                candidates.extend(paths.iter().map(|path| (path.clone(), platform, "pcgamingwiki")));
+            }
+        }
+        candidates.extend(Self::heuristic_patterns(game_name).into_iter().map(|(path, platform)| (path, platform, "heuristic")));
+
+        let current_platform = Utils::get_current_platform();
+        let mut seen = HashSet::new();
+        let mut suggestions: Vec<SuggestedLocation> = candidates.into_iter()
+            .filter(|(path, _, _)| seen.insert(path.replace('\\', "/").to_lowercase()))
+            .map(|(path_pattern, platform, source)| {
+                let resolved_paths = Detection::resolve_pattern(&path_pattern);
+                let exists = resolved_paths.iter().any(|p| std::path::Path::new(p).exists());
+
+                let mut likelihood = if source == "pcgamingwiki" { PCGW_LIKELIHOOD } else { HEURISTIC_LIKELIHOOD };
+                if exists {
+                    likelihood += EXISTS_BONUS;
+                }
+                if platform == current_platform {
+                    likelihood += CURRENT_PLATFORM_BONUS;
+                }
+
+                SuggestedLocation {
+                    path_pattern,
+                    platform: Some(platform.to_string()),
+                    source: source.to_string(),
+                    cloud_sync: CloudSyncDetector::detect_any(&resolved_paths),
+                    resolved_paths,
+                    exists,
+                    likelihood,
+                }
+            })
+            .collect();
+
+        // Stable, so equally likely suggestions keep PCGW's order
+        suggestions.sort_by(|a, b| b.likelihood.cmp(&a.likelihood));
+        suggestions
+    }
+
+    /// Directories games commonly save to, named after the game
+    fn heuristic_patterns(game_name: &str) -> Vec<(String, &'static str)> {
+        let name: String = game_name.trim()
+            .chars()
+            .filter(|c| !matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*'))
+            .collect();
+        if name.trim().is_empty() {
+            return Vec::new();
+        }
+        let compact = name.replace(' ', "");
+
+        let mut patterns = vec![
+            (format!("%USERPROFILE%/Documents/My Games/{}", name), "windows"),
+            (format!("%USERPROFILE%/Documents/{}", name), "windows"),
+            (format!("%USERPROFILE%/Saved Games/{}", name), "windows"),
+            (format!("%APPDATA%/{}", name), "windows"),
+            (format!("%LOCALAPPDATA%/{}", name), "windows"),
+            (format!("%LOCALAPPDATA%/{}/Saved/SaveGames", compact), "windows"),
+            (format!("~/.local/share/{}", name), "linux"),
+            (format!("~/.config/{}", name), "linux"),
+            (format!("~/.{}", compact.to_lowercase()), "linux"),
+            (format!("~/Library/Application Support/{}", name), "macos"),
+        ];
+        if compact != name {
+            patterns.push((format!("%APPDATA%/{}", compact), "windows"));
+            patterns.push((format!("~/.local/share/{}", compact), "linux"));
+        }
+        patterns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_existing_and_documented_locations_rank_first() {
+        let temp_dir = TempDir::new().unwrap();
+        let existing = temp_dir.path().join("saves").to_string_lossy().to_string();
+        let missing = temp_dir.path().join("missing").to_string_lossy().to_string();
+        std::fs::create_dir(&existing).unwrap();
+
+        let pcgw = SaveLocationResult {
+            windows: vec![missing.clone()],
+            linux: vec![existing.clone(), missing.clone()],
+            macos: vec![],
+            steam_play: vec![],
+        };
+
+        let suggestions = SaveLocationSuggestions::suggest("No Such Game 4711", Some(&pcgw));
+
+        assert_eq!(suggestions[0].path_pattern, existing);
+        assert!(suggestions[0].exists);
+        assert_eq!(suggestions[0].source, "pcgamingwiki");
+
+        // The duplicate is dropped and the missing PCGW path still beats every guess
+        assert_eq!(suggestions.iter().filter(|s| s.path_pattern == missing).count(), 1);
+        assert_eq!(suggestions[1].path_pattern, missing);
+        assert!(!suggestions[1].exists);
+        assert!(suggestions[2..].iter().all(|s| s.source == "heuristic" && !s.exists));
+        assert!(suggestions.iter().any(|s| s.path_pattern.ends_with("My Games/No Such Game 4711")));
+    }
+
+    #[test]
+    fn test_blank_name_has_no_heuristics() {
+        assert!(SaveLocationSuggestions::suggest("  ", None).is_empty());
+    }
+}
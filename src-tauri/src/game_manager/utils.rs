@@ -1,4 +1,8 @@
-use crate::database::models::Game;
+use crate::database::models::{Game, SaveLocation};
+use std::path::Path;
+
+/// Operating systems a game can have a launch executable for
+pub const SUPPORTED_PLATFORMS: [&str; 3] = ["linux", "windows", "macos"];
 
 pub struct Utils;
 
@@ -15,6 +19,85 @@ impl Utils {
         { "unknown" }
     }
 
+    /// `path` as a string, warning when it isn't valid UTF-8 and had characters replaced,
+    /// since the string then no longer names the same file
+    pub fn path_to_string(path: &Path) -> String {
+        if path.to_str().is_none() {
+            crate::logger::warn("GAME_MANAGER", "Path is not valid UTF-8 and was altered by conversion", Some(&path.to_string_lossy()));
+        }
+        path.to_string_lossy().to_string()
+    }
+
+    /// Match a file name against a simple glob pattern (`*` and `?` wildcards, case-insensitive)
+    pub fn matches_glob(name: &str, pattern: &str) -> bool {
+        Self::glob_regex(pattern)
+            .map(|re| re.is_match(name))
+            .unwrap_or(false)
+    }
+
+    /// Compile a simple glob pattern into a case-insensitive regex, for matching many names
+    pub fn glob_regex(pattern: &str) -> Option<regex::Regex> {
+        let mut regex_str = String::from("(?i)^");
+        for c in pattern.chars() {
+            match c {
+                '*' => regex_str.push_str(".*"),
+                '?' => regex_str.push('.'),
+                other => regex_str.push_str(&regex::escape(&other.to_string())),
+            }
+        }
+        regex_str.push('$');
+
+        regex::Regex::new(&regex_str).ok()
+    }
+
+    /// Parse a JSON array of glob patterns as stored in `save_locations`
+    pub fn parse_patterns(patterns_json: &Option<String>) -> Vec<String> {
+        patterns_json.as_ref()
+            .and_then(|json| serde_json::from_str::<Vec<String>>(json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Validate a per-OS executable map and convert it to the stored JSON format
+    /// (`{"windows": ["Game.exe"], ...}`)
+    pub fn build_platform_executables_json(
+        executables: &std::collections::HashMap<String, String>,
+    ) -> Result<String, String> {
+        let mut stored: std::collections::BTreeMap<String, Vec<String>> = std::collections::BTreeMap::new();
+
+        for (platform, path) in executables {
+            let platform = platform.trim().to_lowercase();
+            if !SUPPORTED_PLATFORMS.contains(&platform.as_str()) {
+                return Err(format!(
+                    "Unsupported platform '{}'; expected one of: {}",
+                    platform,
+                    SUPPORTED_PLATFORMS.join(", ")
+                ));
+            }
+
+            let path = path.trim();
+            if path.is_empty() {
+                return Err(format!("Executable path for '{}' is empty", platform));
+            }
+
+            stored.insert(platform, vec![path.to_string()]);
+        }
+
+        serde_json::to_string(&stored).map_err(|e| format!("Serialization error: {}", e))
+    }
+
+    /// Whether a save location is meant for the running OS. Locations without a
+    /// platform, or with one we don't recognize, apply everywhere.
+    pub fn applies_to_current_platform(location: &SaveLocation) -> bool {
+        Self::platform_matches(location.platform.as_deref(), Self::get_current_platform())
+    }
+
+    fn platform_matches(location_platform: Option<&str>, current: &str) -> bool {
+        match location_platform.map(|p| p.trim().to_lowercase()) {
+            Some(platform) if SUPPORTED_PLATFORMS.contains(&platform.as_str()) => platform == current,
+            _ => true,
+        }
+    }
+
     /// Get executable path for current platform from stored data
     pub fn get_platform_executable(game: &Game) -> Option<String> {
         let platform = Self::get_current_platform();
@@ -35,3 +118,16 @@ impl Utils {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_platform_matches() {
+        assert!(Utils::platform_matches(None, "linux"));
+        assert!(Utils::platform_matches(Some("Linux"), "linux"));
+        assert!(!Utils::platform_matches(Some("windows"), "linux"));
+        assert!(Utils::platform_matches(Some("steam_play"), "linux"));
+    }
+}
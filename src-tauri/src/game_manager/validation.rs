@@ -0,0 +1,226 @@
+use crate::database::models::AddGameRequest;
+use base64::Engine;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use super::utils::SUPPORTED_PLATFORMS;
+
+/// Store platforms a game can be added under, matching the add-game form
+pub const GAME_PLATFORMS: [&str; 7] = ["steam", "epic", "gog", "standalone", "origin", "uplay", "other"];
+
+/// A problem with one field of a game request, for the form to show next to that field
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    fn new(field: &str, message: impl Into<String>) -> Self {
+        Self { field: field.to_string(), message: message.into() }
+    }
+}
+
+pub struct GameRequestValidation;
+
+impl GameRequestValidation {
+    /// Trim every field, turn blank optional fields into `None`, lowercase the platform
+    /// and store `platform_executables` in the `{"linux": ["run.sh"]}` shape the rest of
+    /// the app reads. Values that can't be normalized are left for `validate` to report.
+    pub fn normalize(request: &mut AddGameRequest) {
+        fn clean(value: &mut Option<String>) {
+            *value = value.take()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty());
+        }
+
+        request.name = request.name.trim().to_string();
+        request.platform = request.platform.trim().to_lowercase();
+        clean(&mut request.platform_app_id);
+        clean(&mut request.executable_path);
+        clean(&mut request.installation_path);
+        clean(&mut request.platform_executables);
+        clean(&mut request.icon_base64);
+        clean(&mut request.icon_path);
+
+        if let Some(json) = &request.platform_executables {
+            if let Ok(executables) = Self::parse_platform_executables(json) {
+                if let Ok(normalized) = serde_json::to_string(&executables) {
+                    request.platform_executables = Some(normalized);
+                }
+            }
+        }
+    }
+
+    /// Check every field and return all problems at once
+    pub fn validate(request: &AddGameRequest) -> Result<(), Vec<FieldError>> {
+        let mut errors = Vec::new();
+
+        if request.name.trim().is_empty() {
+            errors.push(FieldError::new("name", "Name is required"));
+        }
+
+        let platform = request.platform.trim().to_lowercase();
+        if platform.is_empty() {
+            errors.push(FieldError::new("platform", "Platform is required"));
+        } else if !GAME_PLATFORMS.contains(&platform.as_str()) {
+            errors.push(FieldError::new("platform", format!(
+                "Unknown platform '{}'; expected one of: {}", request.platform, GAME_PLATFORMS.join(", ")
+            )));
+        }
+
+        if let Some(app_id) = request.platform_app_id.as_deref().map(str::trim).filter(|id| !id.is_empty()) {
+            if let Err(message) = Self::check_platform_app_id(&platform, app_id) {
+                errors.push(FieldError::new("platform_app_id", message));
+            }
+        }
+
+        if let Some(json) = request.platform_executables.as_deref().filter(|j| !j.trim().is_empty()) {
+            if let Err(message) = Self::parse_platform_executables(json) {
+                errors.push(FieldError::new("platform_executables", message));
+            }
+        }
+
+        for (field, value, want_dir) in [
+            ("executable_path", &request.executable_path, false),
+            ("installation_path", &request.installation_path, true),
+            ("icon_path", &request.icon_path, false),
+        ] {
+            let Some(path) = value.as_deref().map(str::trim).filter(|p| !p.is_empty()) else { continue };
+            let path = Path::new(path);
+            if !path.exists() {
+                errors.push(FieldError::new(field, format!("{} does not exist", path.display())));
+            } else if want_dir && !path.is_dir() {
+                errors.push(FieldError::new(field, format!("{} is not a folder", path.display())));
+            } else if !want_dir && path.is_dir() {
+                errors.push(FieldError::new(field, format!("{} is a folder, not a file", path.display())));
+            }
+        }
+
+        if let Some(icon) = request.icon_base64.as_deref().filter(|i| !i.trim().is_empty()) {
+            if base64::engine::general_purpose::STANDARD.decode(icon.trim()).is_err() {
+                errors.push(FieldError::new("icon_base64", "Icon is not valid base64"));
+            }
+        }
+
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+
+    /// Summarize field errors as a single `Invalid ...` message for command errors
+    pub fn describe(errors: &[FieldError]) -> String {
+        let details: Vec<String> = errors.iter().map(|e| format!("{}: {}", e.field, e.message)).collect();
+        format!("Invalid game request: {}", details.join("; "))
+    }
+
+    /// Store ids must look like the platform's own, or detection would never match them:
+    /// Steam and GOG ids are numbers, Epic ids are catalog ids or app names made of letters
+    /// and digits. Other platforms have no fixed format.
+    fn check_platform_app_id(platform: &str, app_id: &str) -> Result<(), String> {
+        let numeric = app_id.chars().all(|c| c.is_ascii_digit());
+        match platform {
+            "steam" if !numeric => Err(format!("Steam app ids are numbers, like 367520; got '{}'", app_id)),
+            "gog" if !numeric => Err(format!("GOG product ids are numbers, like 1308320804; got '{}'", app_id)),
+            "epic" if numeric => Err(format!("'{}' looks like a Steam or GOG id; Epic ids contain letters", app_id)),
+            "epic" if !app_id.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                Err(format!("Epic ids contain only letters and digits; got '{}'", app_id))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Accept `{"linux": "run.sh"}` as well as `{"linux": ["run.sh"]}`
+    fn parse_platform_executables(json: &str) -> Result<BTreeMap<String, Vec<String>>, String> {
+        let value: serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| format!("Not valid JSON: {}", e))?;
+        let object = value.as_object()
+            .ok_or_else(|| "Expected an object mapping platforms to executables".to_string())?;
+
+        let mut executables = BTreeMap::new();
+        for (platform, files) in object {
+            let platform = platform.trim().to_lowercase();
+            if !SUPPORTED_PLATFORMS.contains(&platform.as_str()) {
+                return Err(format!("Unsupported platform '{}'; expected one of: {}", platform, SUPPORTED_PLATFORMS.join(", ")));
+            }
+
+            let files: Vec<String> = match files {
+                serde_json::Value::String(file) => vec![file.clone()],
+                serde_json::Value::Array(items) => items.iter()
+                    .map(|item| item.as_str().map(str::to_string))
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| format!("Executables for '{}' must be strings", platform))?,
+                _ => return Err(format!("Executables for '{}' must be a string or a list of strings", platform)),
+            };
+            executables.insert(platform, files);
+        }
+
+        Ok(executables)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn request(name: &str, platform: &str) -> AddGameRequest {
+        AddGameRequest {
+            name: name.to_string(),
+            platform: platform.to_string(),
+            platform_app_id: None,
+            executable_path: None,
+            installation_path: None,
+            platform_executables: None,
+            icon_base64: None,
+            icon_path: None,
+        }
+    }
+
+    #[test]
+    fn test_reports_every_invalid_field() {
+        let mut bad = request("  ", "playstation");
+        bad.platform_executables = Some("{not json".to_string());
+        bad.installation_path = Some("/definitely/not/here".to_string());
+
+        let errors = GameRequestValidation::validate(&bad).unwrap_err();
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, ["name", "platform", "platform_executables", "installation_path"]);
+        assert!(GameRequestValidation::describe(&errors).starts_with("Invalid game request: name:"));
+    }
+
+    #[test]
+    fn test_normalizes_valid_request() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut good = request("  Hollow Knight ", " Steam ");
+        good.installation_path = Some(temp_dir.path().to_string_lossy().to_string());
+        good.platform_app_id = Some("   ".to_string());
+        good.platform_executables = Some(r#"{"Linux": "hollow_knight.x86_64"}"#.to_string());
+
+        GameRequestValidation::normalize(&mut good);
+        assert_eq!(good.name, "Hollow Knight");
+        assert_eq!(good.platform, "steam");
+        assert_eq!(good.platform_app_id, None);
+        assert_eq!(good.platform_executables.as_deref(), Some(r#"{"linux":["hollow_knight.x86_64"]}"#));
+        assert!(GameRequestValidation::validate(&good).is_ok());
+
+        good.installation_path = Some(temp_dir.path().join("missing").to_string_lossy().to_string());
+        assert_eq!(GameRequestValidation::validate(&good).unwrap_err()[0].field, "installation_path");
+    }
+
+    #[test]
+    fn test_platform_app_id_must_match_platform() {
+        let with_id = |platform: &str, id: &str| {
+            let mut req = request("Game", platform);
+            req.platform_app_id = Some(id.to_string());
+            GameRequestValidation::validate(&req).err().map(|errors| errors[0].field.clone())
+        };
+
+        assert_eq!(with_id("steam", "367520"), None);
+        assert_eq!(with_id("steam", "hollow-knight").as_deref(), Some("platform_app_id"));
+        assert_eq!(with_id("gog", "1308320804"), None);
+        assert_eq!(with_id("gog", "abc").as_deref(), Some("platform_app_id"));
+        assert_eq!(with_id("epic", "4fe75bbc5a674f4f9b356b5c90567da5"), None);
+        assert_eq!(with_id("epic", "367520").as_deref(), Some("platform_app_id"));
+        assert_eq!(with_id("epic", "Sugar:Game").as_deref(), Some("platform_app_id"));
+        assert_eq!(with_id("other", "anything-goes"), None);
+    }
+}
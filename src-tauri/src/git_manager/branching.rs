@@ -1,6 +1,46 @@
+use crate::backup::SaveArchiveManifest;
 use crate::database::connection::Database;
+use crate::game_manager::naming::{NamingContext, NamingTemplate};
+use super::operation::GitOperation;
+use super::types::SaveSlot;
 use git2::Repository;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Branch name prefix owned by a game: `g<game_id>-`
+pub fn branch_prefix(game_id: i64) -> String {
+    format!("g{}-", game_id)
+}
+
+/// Parse the owning game id out of a `g<game_id>-savename` branch name
+pub fn game_id_from_branch(branch_name: &str) -> Option<i64> {
+    let rest = branch_name.strip_prefix('g')?;
+    let (id, save) = rest.split_once('-')?;
+    if save.is_empty() {
+        return None;
+    }
+    id.parse().ok()
+}
+
+/// Sanitize a branch name: replace spaces and invalid characters with dashes
+/// Git branch names cannot contain spaces, ~, ^, :, ?, *, [, \, or ASCII control characters
+pub fn sanitize_branch_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_whitespace() || c.is_control() || c == '~' || c == '^' || c == ':' || c == '?' || c == '*' || c == '[' || c == '\\' {
+                '-'
+            } else {
+                c
+            }
+        })
+        .collect::<String>()
+        // Remove consecutive dashes
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-")
+}
 
 /// Create a save checkpoint with user-named branch
 pub async fn create_save_checkpoint(
@@ -8,108 +48,81 @@ pub async fn create_save_checkpoint(
     master_repo_path: &str,
     game_id: i64, 
     save_name: &str
+) -> Result<String, String> {
+    create_save_checkpoint_with_operation(db, master_repo_path, &GitOperation::start(game_id), save_name).await
+}
+
+/// `create_save_checkpoint` as part of an operation that can be cancelled and reports progress
+pub async fn create_save_checkpoint_with_operation(
+    db: &std::sync::Arc<tokio::sync::Mutex<Database>>,
+    master_repo_path: &str,
+    operation: &Arc<GitOperation>,
+    save_name: &str
+) -> Result<String, String> {
+    checkpoint_branch(db, master_repo_path, operation, operation.game_id(), save_name, None).await
+}
+
+/// Create or switch to the save's branch and commit the current saves onto it.
+/// `description` is stored for the branch; `None` keeps an existing one.
+async fn checkpoint_branch(
+    db: &std::sync::Arc<tokio::sync::Mutex<Database>>,
+    master_repo_path: &str,
+    operation: &Arc<GitOperation>,
+    game_id: i64,
+    save_name: &str,
+    description: Option<&str>,
 ) -> Result<String, String> {
     crate::logger::info("GIT_BRANCHING", &format!("Creating save checkpoint for game_id: {}, save_name: {}", game_id, save_name), None);
     
-    let game_name = {
+    // Make sure the game exists before touching the repository, and apply its naming template
+    let save_label = {
         let conn_guard = db.lock().await;
         let conn = conn_guard.get_connection().await;
         
-        crate::logger::debug("GIT_BRANCHING", &format!("Querying game name for game_id: {}", game_id), None);
+        crate::logger::debug("GIT_BRANCHING", &format!("Checking game exists for game_id: {}", game_id), None);
         
-        let mut stmt = conn.prepare("SELECT name FROM games WHERE id = ?")
-            .map_err(|e| {
-                crate::logger::error("GIT_BRANCHING", "Failed to prepare game query", Some(&e.to_string()));
-                format!("Failed to prepare game query: {}", e)
-            })?;
-        
-        stmt.query_row([game_id], |row| row.get::<_, String>(0))
+        let game_name = conn.query_row("SELECT name FROM games WHERE id = ?", [game_id], |row| row.get::<_, String>(0))
             .map_err(|e| {
                 crate::logger::error("GIT_BRANCHING", &format!("Failed to get game name for game_id: {}", game_id), Some(&e.to_string()));
                 format!("Failed to get game name: {}", e)
-            })?
-    };
+            })?;
 
-    // Sanitize the branch name: replace spaces and invalid characters with dashes
-    // Git branch names cannot contain spaces, ~, ^, :, ?, *, [, \, or ASCII control characters
-    let sanitize_branch_name = |name: &str| -> String {
-        name.chars()
-            .map(|c| {
-                if c.is_whitespace() || c == '~' || c == '^' || c == ':' || c == '?' || c == '*' || c == '[' || c == '\\' {
-                    '-'
-                } else {
-                    c
-                }
-            })
-            .collect::<String>()
-            // Remove consecutive dashes
-            .split('-')
-            .filter(|s| !s.is_empty())
-            .collect::<Vec<&str>>()
-            .join("-")
+        match crate::game_manager::GameManager::get_naming_template(&conn, game_id)? {
+            Some(template) => {
+                let existing: i64 = conn.query_row("SELECT COUNT(*) FROM git_branches WHERE game_id = ?", [game_id], |row| row.get(0))
+                    .unwrap_or(0);
+                NamingTemplate::render(&template, &NamingContext {
+                    game: &game_name,
+                    save: save_name,
+                    date: Utc::now(),
+                    counter: existing as usize + 1,
+                })
+            }
+            None => save_name.to_string(),
+        }
     };
 
-    // Create branch name: gamename-save-name (sanitized)
-    let branch_name = sanitize_branch_name(&format!("{}-{}", game_name, save_name));
+    // Create branch name: g<game_id>-save-name (sanitized), so games sharing a name never collide
+    let branch_name = format!("{}{}", branch_prefix(game_id), sanitize_branch_name(&save_label));
     crate::logger::info("GIT_BRANCHING", &format!("Branch name: {}", branch_name), None);
     
-    // Check if branch exists (outside git2 scope so we can use it later)
-    let branch_exists = {
-        let repo = Repository::open(master_repo_path)
-            .map_err(|e| {
-                crate::logger::error("GIT_BRANCHING", "Failed to open master repository", Some(&e.to_string()));
-                format!("Failed to open master repository: {}", e)
-            })?;
-        let exists = repo.find_branch(&branch_name, git2::BranchType::Local).is_ok();
-        exists
-    };
-    
-    // Perform git operations in a synchronous block or scope
-    // Since git2 is synchronous, we can just do it here.
-    // The issue is if we hold any git2 types across an await.
-    // We are not calling any async functions inside the git block below.
-    {
-        // Open master repository
-        let repo = Repository::open(master_repo_path)
+    // git2 is synchronous: check out on the blocking pool so a big save tree doesn't stall
+    // other commands
+    let repo_path = master_repo_path.to_string();
+    let target_branch = branch_name.clone();
+    let (branch_exists, commit_hash) = operation.run_blocking(move |operation| {
+        let branch_name = target_branch;
+        let repo = Repository::open(&repo_path)
             .map_err(|e| {
                 crate::logger::error("GIT_BRANCHING", "Failed to open master repository", Some(&e.to_string()));
                 format!("Failed to open master repository: {}", e)
             })?;
 
-        // Check if branch already exists
+        let branch_exists = repo.find_branch(&branch_name, git2::BranchType::Local).is_ok();
         if branch_exists {
             crate::logger::info("GIT_BRANCHING", &format!("Branch '{}' already exists, switching to it", branch_name), None);
-            
-            // Switch to existing branch
-            let existing_branch = repo.find_branch(&branch_name, git2::BranchType::Local)
-                .map_err(|e| {
-                    crate::logger::error("GIT_BRANCHING", "Failed to get branch", Some(&e.to_string()));
-                    format!("Failed to get branch: {}", e)
-                })?;
-            
-            let branch_commit = existing_branch.get().peel_to_commit()
-                .map_err(|e| {
-                    crate::logger::error("GIT_BRANCHING", "Failed to get branch commit", Some(&e.to_string()));
-                    format!("Failed to get branch commit: {}", e)
-                })?;
-
-            repo.checkout_tree(&branch_commit.into_object(), None)
-                .map_err(|e| {
-                    crate::logger::error("GIT_BRANCHING", &format!("Failed to checkout branch '{}'", branch_name), Some(&e.to_string()));
-                    format!("Failed to checkout branch '{}': {}", branch_name, e)
-                })?;
-
-            repo.set_head(&format!("refs/heads/{}", branch_name))
-                .map_err(|e| {
-                    crate::logger::error("GIT_BRANCHING", &format!("Failed to set HEAD to branch '{}'", branch_name), Some(&e.to_string()));
-                    format!("Failed to set HEAD to branch '{}': {}", branch_name, e)
-                })?;
-
-            crate::logger::info("GIT_BRANCHING", &format!("Successfully switched to existing branch: {}", branch_name), None);
-            
-            // Drop git2 types before await (end of scope)
         } else {
-            // Get current branch to fork from
+            // Fork the new branch from the current HEAD
             let current_commit = repo.head()
                 .map_err(|e| {
                     crate::logger::error("GIT_BRANCHING", "Failed to get HEAD", Some(&e.to_string()));
@@ -123,57 +136,39 @@ pub async fn create_save_checkpoint(
 
             crate::logger::debug("GIT_BRANCHING", &format!("Creating new branch '{}' from commit: {}", branch_name, current_commit.id()), None);
 
-            // Create new branch from current HEAD
             repo.branch(&branch_name, &current_commit, false)
                 .map_err(|e| {
                     crate::logger::error("GIT_BRANCHING", &format!("Failed to create branch '{}'", branch_name), Some(&e.to_string()));
                     format!("Failed to create branch '{}': {}", branch_name, e)
                 })?;
+        }
 
-            // Checkout the new branch
-            let branch_ref = repo.find_branch(&branch_name, git2::BranchType::Local)
-                .map_err(|e| {
-                    crate::logger::error("GIT_BRANCHING", &format!("Failed to find newly created branch '{}'", branch_name), Some(&e.to_string()));
-                    format!("Failed to find branch '{}': {}", branch_name, e)
-                })?;
-
-            let branch_commit = branch_ref.get().peel_to_commit()
-                .map_err(|e| {
-                    crate::logger::error("GIT_BRANCHING", "Failed to get branch commit", Some(&e.to_string()));
-                    format!("Failed to get branch commit: {}", e)
-                })?;
-
-            repo.checkout_tree(&branch_commit.into_object(), None)
-                .map_err(|e| {
-                    crate::logger::error("GIT_BRANCHING", &format!("Failed to checkout branch '{}'", branch_name), Some(&e.to_string()));
-                    format!("Failed to checkout branch '{}': {}", branch_name, e)
-                })?;
-
-            // Set HEAD to new branch
-            repo.set_head(&format!("refs/heads/{}", branch_name))
-                .map_err(|e| {
-                    crate::logger::error("GIT_BRANCHING", &format!("Failed to set HEAD to branch '{}'", branch_name), Some(&e.to_string()));
-                    format!("Failed to set HEAD to branch '{}': {}", branch_name, e)
-                })?;
-            
+        let commit_hash = checkout_branch(&repo, operation, &branch_name)?;
+        if branch_exists {
+            crate::logger::info("GIT_BRANCHING", &format!("Successfully switched to existing branch: {}", branch_name), None);
+        } else {
             crate::logger::info("GIT_BRANCHING", &format!("Successfully created and checked out branch: {}", branch_name), None);
-            
-            // Drop git2 types before await (end of scope)
         }
-    }
+        Ok((branch_exists, commit_hash))
+    }).await?;
     
-    // Now we're outside the git2 scope, safe to await
     // Save branch info to database (FIX: this was missing before)
     crate::logger::debug("GIT_BRANCHING", "Saving branch info to database", None);
-    save_branch_info(db, game_id, &branch_name, None).await?;
+    save_branch_info(db, game_id, &branch_name, description).await?;
     
     // Update active branch in database
     update_active_branch(db, game_id, &branch_name).await?;
+
+    // Snapshot the current save files onto the branch
+    let message = format!("Checkpoint: {}", save_label);
+    let snapshot = commit_save_snapshot(db, master_repo_path, operation, game_id, &message).await?;
+    let commit_hash = snapshot.commit_hash.unwrap_or(commit_hash);
+    record_save_commit(db, game_id, &commit_hash, &branch_name, &message, snapshot.save_modified_at).await?;
     
     let result = if branch_exists {
-        format!("Switched to existing save branch: {}", branch_name)
+        format!("Switched to existing save branch: {} ({} files committed)", branch_name, snapshot.files_committed)
     } else {
-        format!("Created save branch: {}", branch_name)
+        format!("Created save branch: {} ({} files committed)", branch_name, snapshot.files_committed)
     };
     
     crate::logger::info("GIT_BRANCHING", &result, None);
@@ -189,8 +184,18 @@ pub async fn create_save_branch(
     branch_name: &str, 
     description: Option<&str>
 ) -> Result<(), String> {
-    create_save_checkpoint(db, master_repo_path, game_id, branch_name).await?;
-    save_branch_info(db, game_id, branch_name, description).await?;
+    create_save_branch_with_operation(db, master_repo_path, &GitOperation::start(game_id), branch_name, description).await
+}
+
+/// `create_save_branch` as part of an operation that can be cancelled and reports progress
+pub async fn create_save_branch_with_operation(
+    db: &std::sync::Arc<tokio::sync::Mutex<Database>>,
+    master_repo_path: &str,
+    operation: &Arc<GitOperation>,
+    branch_name: &str,
+    description: Option<&str>
+) -> Result<(), String> {
+    checkpoint_branch(db, master_repo_path, operation, operation.game_id(), branch_name, description).await?;
     Ok(())
 }
 
@@ -201,40 +206,29 @@ pub async fn switch_save_branch(
     game_id: i64, 
     branch_name: &str
 ) -> Result<(), String> {
+    switch_save_branch_with_operation(db, master_repo_path, &GitOperation::start(game_id), branch_name).await
+}
+
+/// `switch_save_branch` as part of an operation that can be cancelled and reports progress
+pub async fn switch_save_branch_with_operation(
+    db: &std::sync::Arc<tokio::sync::Mutex<Database>>,
+    master_repo_path: &str,
+    operation: &Arc<GitOperation>,
+    branch_name: &str
+) -> Result<(), String> {
+    let game_id = operation.game_id();
     crate::logger::info("GIT_BRANCHING", &format!("Switching to branch '{}' for game_id: {}", branch_name, game_id), None);
     
-    {
-        let repo = Repository::open(master_repo_path)
+    let repo_path = master_repo_path.to_string();
+    let target_branch = branch_name.to_string();
+    operation.run_blocking(move |operation| {
+        let repo = Repository::open(&repo_path)
             .map_err(|e| {
                 crate::logger::error("GIT_BRANCHING", "Failed to open master repository", Some(&e.to_string()));
                 format!("Failed to open master repository: {}", e)
             })?;
-
-        // Find and checkout the branch
-        let branch_ref = repo.find_branch(branch_name, git2::BranchType::Local)
-            .map_err(|e| {
-                crate::logger::error("GIT_BRANCHING", &format!("Failed to find branch '{}'", branch_name), Some(&e.to_string()));
-                format!("Failed to find branch '{}': {}", branch_name, e)
-            })?;
-
-        let branch_commit = branch_ref.get().peel_to_commit()
-            .map_err(|e| {
-                crate::logger::error("GIT_BRANCHING", "Failed to get branch commit", Some(&e.to_string()));
-                format!("Failed to get branch commit: {}", e)
-            })?;
-
-        repo.checkout_tree(&branch_commit.into_object(), None)
-            .map_err(|e| {
-                crate::logger::error("GIT_BRANCHING", &format!("Failed to checkout branch '{}'", branch_name), Some(&e.to_string()));
-                format!("Failed to checkout branch '{}': {}", branch_name, e)
-            })?;
-
-        repo.set_head(&format!("refs/heads/{}", branch_name))
-            .map_err(|e| {
-                crate::logger::error("GIT_BRANCHING", &format!("Failed to set HEAD to branch '{}'", branch_name), Some(&e.to_string()));
-                format!("Failed to set HEAD to branch '{}': {}", branch_name, e)
-            })?;
-    }
+        checkout_branch(&repo, operation, &target_branch)
+    }).await?;
 
     update_active_branch(db, game_id, branch_name).await?;
     
@@ -243,6 +237,36 @@ pub async fn switch_save_branch(
     Ok(())
 }
 
+/// Check out a local branch and point HEAD at it. Returns the hash of its tip.
+fn checkout_branch(repo: &Repository, operation: &GitOperation, branch_name: &str) -> Result<String, String> {
+    let branch_ref = repo.find_branch(branch_name, git2::BranchType::Local)
+        .map_err(|e| {
+            crate::logger::error("GIT_BRANCHING", &format!("Failed to find branch '{}'", branch_name), Some(&e.to_string()));
+            format!("Failed to find branch '{}': {}", branch_name, e)
+        })?;
+
+    let branch_commit = branch_ref.get().peel_to_commit()
+        .map_err(|e| {
+            crate::logger::error("GIT_BRANCHING", "Failed to get branch commit", Some(&e.to_string()));
+            format!("Failed to get branch commit: {}", e)
+        })?;
+
+    let commit_hash = branch_commit.id().to_string();
+    operation.checkout_tree(repo, &branch_commit.into_object())
+        .map_err(|e| {
+            crate::logger::error("GIT_BRANCHING", &format!("Failed to checkout branch '{}'", branch_name), Some(&e));
+            format!("Failed to checkout branch '{}': {}", branch_name, e)
+        })?;
+
+    repo.set_head(&format!("refs/heads/{}", branch_name))
+        .map_err(|e| {
+            crate::logger::error("GIT_BRANCHING", &format!("Failed to set HEAD to branch '{}'", branch_name), Some(&e.to_string()));
+            format!("Failed to set HEAD to branch '{}': {}", branch_name, e)
+        })?;
+
+    Ok(commit_hash)
+}
+
 /// Get list of all branches
 pub async fn list_all_branches(master_repo_path: &str) -> Result<Vec<String>, String> {
     crate::logger::debug("GIT_BRANCHING", "Listing all branches", None);
@@ -268,22 +292,222 @@ pub async fn list_all_branches(master_repo_path: &str) -> Result<Vec<String>, St
 }
 
 /// Get branches for a specific game
-pub async fn get_game_branches(master_repo_path: &str, game_name: &str) -> Result<Vec<String>, String> {
-    crate::logger::debug("GIT_BRANCHING", &format!("Getting branches for game: {}", game_name), None);
+pub async fn get_game_branches(master_repo_path: &str, game_id: i64) -> Result<Vec<String>, String> {
+    crate::logger::debug("GIT_BRANCHING", &format!("Getting branches for game_id: {}", game_id), None);
     
     let all_branches = list_all_branches(master_repo_path).await?;
+    let prefix = branch_prefix(game_id);
     
-    // Filter branches that start with the game name followed by '-'
+    // Filter branches that start with the game's id prefix
     let game_branches: Vec<String> = all_branches
         .into_iter()
-        .filter(|branch| branch.starts_with(&format!("{}-", game_name)))
+        .filter(|branch| branch.starts_with(&prefix))
         .collect();
 
-    crate::logger::debug("GIT_BRANCHING", &format!("Found {} branches for game '{}'", game_branches.len(), game_name), None);
+    crate::logger::debug("GIT_BRANCHING", &format!("Found {} branches for game_id {}", game_branches.len(), game_id), None);
 
     Ok(game_branches)
 }
 
+/// Every save branch in the master repository, joined with its game and `git_branches` row.
+/// Branches that don't follow the `g<game_id>-` scheme are left out.
+pub async fn list_save_slots(
+    db: &std::sync::Arc<tokio::sync::Mutex<Database>>,
+    master_repo_path: &str
+) -> Result<Vec<SaveSlot>, String> {
+    crate::logger::debug("GIT_BRANCHING", "Listing save slots across all games", None);
+
+    let branches: Vec<(String, i64, Option<DateTime<Utc>>)> = {
+        let repo = Repository::open(master_repo_path)
+            .map_err(|e| {
+                crate::logger::error("GIT_BRANCHING", "Failed to open master repository", Some(&e.to_string()));
+                format!("Failed to open master repository: {}", e)
+            })?;
+
+        let branches = repo.branches(Some(git2::BranchType::Local))
+            .map_err(|e| {
+                crate::logger::error("GIT_BRANCHING", "Failed to get branches", Some(&e.to_string()));
+                format!("Failed to get branches: {}", e)
+            })?;
+
+        branches
+            .filter_map(|b| b.ok())
+            .filter_map(|(branch, _)| {
+                let name = branch.name().ok().flatten()?.to_string();
+                let game_id = game_id_from_branch(&name)?;
+                let last_commit_time = branch.get().peel_to_commit().ok()
+                    .and_then(|commit| DateTime::from_timestamp(commit.time().seconds(), 0));
+                Some((name, game_id, last_commit_time))
+            })
+            .collect()
+    };
+
+    let db_guard = db.lock().await;
+    let conn = db_guard.get_connection().await;
+
+    let mut stmt = conn.prepare("SELECT id, name FROM games")
+        .map_err(|e| format!("Failed to prepare games query: {}", e))?;
+    let game_names: HashMap<i64, String> = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))
+        .map_err(|e| format!("Failed to query games: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut stmt = conn.prepare("SELECT game_id, branch_name, description, is_active FROM git_branches")
+        .map_err(|e| format!("Failed to prepare branch query: {}", e))?;
+    let recorded: HashMap<(i64, String), (Option<String>, bool)> = stmt.query_map([], |row| {
+            Ok((
+                (row.get::<_, i64>(0)?, row.get::<_, String>(1)?),
+                (row.get::<_, Option<String>>(2)?, row.get::<_, Option<bool>>(3)?.unwrap_or(false)),
+            ))
+        })
+        .map_err(|e| format!("Failed to query branches: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut slots: Vec<SaveSlot> = branches.into_iter()
+        .map(|(branch_name, game_id, last_commit_time)| {
+            let (description, is_active) = recorded.get(&(game_id, branch_name.clone()))
+                .cloned()
+                .unwrap_or((None, false));
+            SaveSlot {
+                game_id,
+                game_name: game_names.get(&game_id).cloned(),
+                branch_name,
+                description: description.filter(|d| !d.is_empty()),
+                last_commit_time,
+                is_active,
+            }
+        })
+        .collect();
+
+    slots.sort_by(|a, b| a.game_name.cmp(&b.game_name)
+        .then(a.game_id.cmp(&b.game_id))
+        .then(b.last_commit_time.cmp(&a.last_commit_time)));
+
+    crate::logger::debug("GIT_BRANCHING", &format!("Found {} save slots", slots.len()), None);
+    Ok(slots)
+}
+
+/// Rename branches created with the old `gamename-savename` scheme to `g<game_id>-savename`.
+///
+/// Ownership is taken from `git_branches` first; branches without a row are matched by
+/// game name. Branches claimed by more than one game are left alone and logged, since
+/// their history may already mix both games' saves. Returns the number of renamed branches.
+pub async fn migrate_legacy_branch_names(
+    db: &std::sync::Arc<tokio::sync::Mutex<Database>>,
+    master_repo_path: &str
+) -> Result<usize, String> {
+    crate::logger::info("GIT_BRANCHING", "Migrating legacy branch names", None);
+
+    let (games, recorded) = {
+        let db_guard = db.lock().await;
+        let conn = db_guard.get_connection().await;
+
+        let mut stmt = conn.prepare("SELECT id, name FROM games")
+            .map_err(|e| format!("Failed to prepare games query: {}", e))?;
+        let games = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))
+            .map_err(|e| format!("Failed to query games: {}", e))?
+            .filter_map(|r| r.ok())
+            .collect::<Vec<(i64, String)>>();
+
+        let mut stmt = conn.prepare("SELECT game_id, branch_name FROM git_branches")
+            .map_err(|e| format!("Failed to prepare branch query: {}", e))?;
+        let recorded = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))
+            .map_err(|e| format!("Failed to query branches: {}", e))?
+            .filter_map(|r| r.ok())
+            .collect::<Vec<(i64, String)>>();
+
+        (games, recorded)
+    };
+
+    let legacy_prefixes: Vec<(i64, String)> = games.iter()
+        .map(|(id, name)| (*id, format!("{}-", sanitize_branch_name(name))))
+        .collect();
+    let is_id_prefixed = |game_id: i64, branch: &str| branch.starts_with(&branch_prefix(game_id));
+
+    // Collect every game that claims each legacy branch
+    let mut owners: HashMap<String, Vec<i64>> = HashMap::new();
+    for (game_id, branch) in &recorded {
+        if !is_id_prefixed(*game_id, branch) {
+            owners.entry(branch.clone()).or_default().push(*game_id);
+        }
+    }
+    for branch in list_all_branches(master_repo_path).await? {
+        if owners.contains_key(&branch) || recorded.iter().any(|(_, b)| b == &branch) {
+            continue;
+        }
+        let matching: Vec<i64> = legacy_prefixes.iter()
+            .filter(|(_, prefix)| branch.starts_with(prefix.as_str()))
+            .map(|(id, _)| *id)
+            .collect();
+        if !matching.is_empty() {
+            owners.insert(branch, matching);
+        }
+    }
+
+    let mut renames: Vec<(i64, String, String)> = Vec::new();
+    for (branch, mut game_ids) in owners {
+        game_ids.sort_unstable();
+        game_ids.dedup();
+        if game_ids.len() > 1 {
+            crate::logger::warn("GIT_BRANCHING", &format!("Branch '{}' is shared by games {:?}, leaving it for manual resolution", branch, game_ids), None);
+            continue;
+        }
+
+        let game_id = game_ids[0];
+        let save_part = legacy_prefixes.iter()
+            .find(|(id, _)| *id == game_id)
+            .and_then(|(_, prefix)| branch.strip_prefix(prefix.as_str()))
+            .unwrap_or(&branch);
+        let new_name = format!("{}{}", branch_prefix(game_id), save_part);
+        renames.push((game_id, branch, new_name));
+    }
+
+    // Rename in git first; rows whose branch no longer exists in the repository are just relabelled
+    let mut applied = Vec::new();
+    {
+        let repo = Repository::open(master_repo_path)
+            .map_err(|e| {
+                crate::logger::error("GIT_BRANCHING", "Failed to open master repository", Some(&e.to_string()));
+                format!("Failed to open master repository: {}", e)
+            })?;
+
+        for (game_id, old_name, new_name) in renames {
+            if repo.find_branch(&new_name, git2::BranchType::Local).is_ok() {
+                crate::logger::warn("GIT_BRANCHING", &format!("Cannot rename '{}': '{}' already exists", old_name, new_name), None);
+                continue;
+            }
+
+            if let Ok(mut branch) = repo.find_branch(&old_name, git2::BranchType::Local) {
+                if let Err(e) = branch.rename(&new_name, false) {
+                    crate::logger::warn("GIT_BRANCHING", &format!("Failed to rename branch '{}' to '{}'", old_name, new_name), Some(&e.to_string()));
+                    continue;
+                }
+            }
+
+            applied.push((game_id, old_name, new_name));
+        }
+    }
+
+    {
+        let db_guard = db.lock().await;
+        let conn = db_guard.get_connection().await;
+
+        for (game_id, old_name, new_name) in &applied {
+            for table in ["git_branches", "git_save_commits", "git_save_snapshots"] {
+                conn.execute(
+                    &format!("UPDATE {} SET branch_name = ? WHERE game_id = ? AND branch_name = ?", table),
+                    rusqlite::params![new_name, game_id, old_name]
+                ).map_err(|e| format!("Failed to update {} for branch '{}': {}", table, old_name, e))?;
+            }
+
+            crate::logger::info("GIT_BRANCHING", &format!("Renamed branch '{}' to '{}'", old_name, new_name), None);
+        }
+    }
+
+    Ok(applied.len())
+}
+
 /// Delete a save branch
 pub async fn delete_save_branch(master_repo_path: &str, branch_name: &str) -> Result<(), String> {
     crate::logger::info("GIT_BRANCHING", &format!("Deleting branch: {}", branch_name), None);
@@ -312,6 +536,99 @@ pub async fn delete_save_branch(master_repo_path: &str, branch_name: &str) -> Re
     Ok(())
 }
 
+/// Push every branch of a game into a bare repository at `dest`, creating it. Returns the
+/// pushed branch names; nothing is written when the game has no branches.
+pub async fn export_game_branches(master_repo_path: &str, game_id: i64, dest: &std::path::Path) -> Result<Vec<String>, String> {
+    if !std::path::Path::new(master_repo_path).join(".git").exists() {
+        return Ok(Vec::new());
+    }
+
+    let branches = get_game_branches(master_repo_path, game_id).await?;
+    if branches.is_empty() {
+        return Ok(branches);
+    }
+
+    crate::logger::info("GIT_BRANCHING", &format!("Exporting {} branches of game_id {}", branches.len(), game_id), Some(&dest.display().to_string()));
+
+    let repo = Repository::open(master_repo_path)
+        .map_err(|e| format!("Failed to open master repository: {}", e))?;
+    Repository::init_bare(dest)
+        .map_err(|e| format!("Failed to create branch bundle at {}: {}", dest.display(), e))?;
+
+    let mut remote = repo.remote_anonymous(&dest.to_string_lossy())
+        .map_err(|e| format!("Failed to open branch bundle: {}", e))?;
+    let refspecs: Vec<String> = branches.iter()
+        .map(|b| format!("+refs/heads/{0}:refs/heads/{0}", b))
+        .collect();
+    remote.push(&refspecs, None)
+        .map_err(|e| {
+            crate::logger::error("GIT_BRANCHING", "Failed to push branches to bundle", Some(&e.to_string()));
+            format!("Failed to export branches: {}", e)
+        })?;
+
+    Ok(branches)
+}
+
+/// Delete every branch of a game from the master repository. If one of them is checked
+/// out, HEAD is detached at its commit first so the working tree is left as it is.
+pub async fn remove_game_branches(master_repo_path: &str, game_id: i64) -> Result<usize, String> {
+    let branches = get_game_branches(master_repo_path, game_id).await?;
+    if branches.is_empty() {
+        return Ok(0);
+    }
+
+    {
+        let repo = Repository::open(master_repo_path)
+            .map_err(|e| format!("Failed to open master repository: {}", e))?;
+        let checked_out = repo.head().ok()
+            .filter(|h| h.is_branch() && h.shorthand().is_some_and(|name| branches.iter().any(|b| b == name)))
+            .map(|h| h.target());
+        if let Some(target) = checked_out {
+            let oid = target.ok_or_else(|| "HEAD has no commit".to_string())?;
+            repo.set_head_detached(oid)
+                .map_err(|e| format!("Failed to detach HEAD: {}", e))?;
+        }
+    }
+
+    for branch in &branches {
+        delete_save_branch(master_repo_path, branch).await?;
+    }
+    Ok(branches.len())
+}
+
+/// Fetch a game's branches back from a repository written by `export_game_branches`.
+/// Refuses to overwrite branches that exist in the master repository again.
+pub async fn import_game_branches(master_repo_path: &str, game_id: i64, src: &std::path::Path) -> Result<Vec<String>, String> {
+    let branches = get_game_branches(&src.to_string_lossy(), game_id).await?;
+    if branches.is_empty() {
+        return Ok(branches);
+    }
+
+    let existing = get_game_branches(master_repo_path, game_id).await?;
+    if let Some(clash) = branches.iter().find(|b| existing.contains(b)) {
+        return Err(format!("Branch '{}' already exists in the master repository", clash));
+    }
+
+    crate::logger::info("GIT_BRANCHING", &format!("Importing {} branches of game_id {}", branches.len(), game_id), Some(&src.display().to_string()));
+
+    let repo = Repository::open(master_repo_path)
+        .map_err(|e| format!("Failed to open master repository: {}", e))?;
+    let mut remote = repo.remote_anonymous(&src.to_string_lossy())
+        .map_err(|e| format!("Failed to open branch bundle: {}", e))?;
+    let refspecs: Vec<String> = branches.iter()
+        .map(|b| format!("refs/heads/{0}:refs/heads/{0}", b))
+        .collect();
+    remote.fetch(&refspecs, None, None)
+        .map_err(|e| {
+            crate::logger::error("GIT_BRANCHING", "Failed to fetch branches from bundle", Some(&e.to_string()));
+            format!("Failed to import branches: {}", e)
+        })?;
+
+    Ok(branches)
+}
+
+/// Record a branch, once per game and name. Checkpointing an existing branch again keeps
+/// its row, replacing the description only when a new one is given.
 async fn save_branch_info(
     db: &std::sync::Arc<tokio::sync::Mutex<Database>>,
     game_id: i64, 
@@ -323,11 +640,13 @@ async fn save_branch_info(
     
     conn.execute(
         "INSERT INTO git_branches (game_id, branch_name, description, created_at)
-            VALUES (?, ?, ?, ?)",
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(game_id, branch_name) DO UPDATE SET
+                description = COALESCE(excluded.description, git_branches.description)",
         rusqlite::params![
             game_id,
             branch_name,
-            description.unwrap_or(""),
+            description,
             Utc::now().to_rfc3339()
         ]
     ).map_err(|e| format!("Failed to save branch info: {}", e))?;
@@ -335,6 +654,82 @@ async fn save_branch_info(
     Ok(())
 }
 
+/// Save files committed for a checkpoint
+struct SaveSnapshot {
+    /// `None` when the files were unchanged since the branch tip
+    commit_hash: Option<String>,
+    files_committed: usize,
+    save_modified_at: Option<chrono::DateTime<Utc>>,
+}
+
+/// Copy the game's current save files into its directory of the working tree and commit
+/// them on the checked-out branch
+async fn commit_save_snapshot(
+    db: &std::sync::Arc<tokio::sync::Mutex<Database>>,
+    master_repo_path: &str,
+    operation: &Arc<GitOperation>,
+    game_id: i64,
+    message: &str,
+) -> Result<SaveSnapshot, String> {
+    let (game, locations) = crate::backup::PortableSaves::load_game_and_locations(db, game_id).await?;
+    let (author, follow_symlinks) = {
+        let db_guard = db.lock().await;
+        let conn = db_guard.get_connection().await;
+        (
+            super::repository::CommitAuthor::load(&conn),
+            crate::game_manager::scanner::configured_follow_symlinks(&conn),
+        )
+    };
+
+    let repo_path = master_repo_path.to_string();
+    let message = message.to_string();
+    operation.run_blocking(move |operation| {
+        let (archive_locations, sources) = crate::backup::PortableSaves::collect_save_sources(&locations, follow_symlinks);
+        if sources.is_empty() {
+            crate::logger::warn("GIT_BRANCHING", &format!("No save files found for game {}, checkpoint has no new snapshot", game_id), None);
+        }
+
+        let mut manifest = SaveArchiveManifest::new(&game.name, &game.platform, game.platform_app_id.clone());
+        manifest.locations = archive_locations;
+        manifest.files = sources.iter().map(|s| s.entry.clone()).collect();
+        manifest.save_modified_at = crate::backup::archive::newest_modified_at(&sources);
+
+        let files_committed = super::snapshot::write_game_tree(&repo_path, game_id, &manifest, &sources, operation)?;
+        let commit_hash = super::snapshot::commit_game_tree(&repo_path, game_id, &author, &message, operation)?;
+        crate::logger::info("GIT_BRANCHING", &format!("Committed {} save files for game {}", files_committed, game_id), commit_hash.as_deref());
+
+        Ok(SaveSnapshot { commit_hash, files_committed, save_modified_at: manifest.save_modified_at })
+    }).await
+}
+
+/// Record a checkpoint commit in `git_save_commits`, along with when the game last wrote
+/// its saves so restores can be matched against in-game save times
+async fn record_save_commit(
+    db: &std::sync::Arc<tokio::sync::Mutex<Database>>,
+    game_id: i64,
+    commit_hash: &str,
+    branch_name: &str,
+    message: &str,
+    save_modified_at: Option<chrono::DateTime<Utc>>,
+) -> Result<(), String> {
+    let db_guard = db.lock().await;
+    let conn = db_guard.get_connection().await;
+    conn.execute(
+        "INSERT INTO git_save_commits (game_id, git_commit_hash, branch_name, message, timestamp, save_modified_at)
+            VALUES (?, ?, ?, ?, ?, ?)",
+        rusqlite::params![
+            game_id,
+            commit_hash,
+            branch_name,
+            message,
+            Utc::now().to_rfc3339(),
+            save_modified_at.map(|t| t.to_rfc3339()),
+        ]
+    ).map_err(|e| format!("Failed to record save commit: {}", e))?;
+
+    Ok(())
+}
+
 async fn update_active_branch(
     db: &std::sync::Arc<tokio::sync::Mutex<Database>>,
     game_id: i64, 
@@ -3,12 +3,12 @@
 //! This module provides integration with GitHub, GitLab, and other Git hosting services
 //! for backing up and synchronizing game save repositories.
 
-use anyhow::Result;
+use git2::{BranchType, Cred, CredentialType, FetchOptions, PushOptions, RemoteCallbacks, Repository};
 use reqwest::{Client, header};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use crate::database::connection::Database;
 use crate::git_manager::types::*;
 
@@ -29,308 +29,403 @@ pub struct GitLabClient {
 /// Main cloud synchronization manager
 pub struct CloudSyncManager {
     db: Arc<Mutex<Database>>,
-    github: GitHubClient,
-    gitlab: GitLabClient,
+    master_repo_path: String,
 }
 
 impl CloudSyncManager {
     /// Create new cloud sync manager
-    pub fn new(db: Arc<Mutex<Database>>) -> Self {
+    pub fn new(db: Arc<Mutex<Database>>, master_repo_path: &str) -> Self {
         Self {
             db,
-            github: GitHubClient::new(),
-            gitlab: GitLabClient::new(),
+            master_repo_path: master_repo_path.to_string(),
         }
     }
 
-    /// Push repository to cloud storage
-    pub async fn push_to_cloud(&self, game_id: i64) -> Result<CloudSyncResult> {
-        // Get repository configuration
-        let repo_config = self.get_repo_config(game_id).await?;
-        
-        match repo_config.provider {
-            Some(ref provider) => {
-                match provider {
-                    CloudProvider::GitHub => self.push_to_github(game_id, &repo_config).await,
-                    CloudProvider::GitLab => self.push_to_gitlab(game_id, &repo_config).await,
-                    CloudProvider::Gitea => self.push_to_gitea(game_id, &repo_config).await,
-                    CloudProvider::SelfHosted => self.push_to_self_hosted(game_id, &repo_config).await,
-                }
-            }
-            None => Err(anyhow::anyhow!("No cloud provider configured"))
-        }
-    }
+    /// Push the game's save branches to its configured remote.
+    /// Authentication uses the system git credential helper or the SSH agent.
+    pub async fn push_to_cloud(&self, game_id: i64) -> Result<CloudSyncResult, String> {
+        let config = self.get_repo_config(game_id).await?;
+        let remote_url = config.remote_url.clone()
+            .ok_or_else(|| format!("No cloud remote configured for game {}", game_id))?;
+        let provider = config.provider.clone()
+            .unwrap_or_else(|| CloudProvider::from_remote_url(&remote_url));
+        let branches = self.get_game_branch_names(game_id).await?;
 
-    /// Push to GitHub
-    async fn push_to_github(&self, game_id: i64, config: &GitRepositoryConfig) -> Result<CloudSyncResult> {
-        let start_time = std::time::Instant::now();
-        
-        // Create repository name
-        let repo_name = format!("save-steward-game-{}", game_id);
-        
-        // Create or update repository
-        let repo_url = if let Some(remote_url) = &config.remote_url {
-            // Update existing repository
-            self.github.update_repository(&repo_name, remote_url, "game").await
-        } else {
-            // Create new repository
-            self.github.create_repository(&repo_name, "Game save repository for Save Steward").await
-        }?;
-        
-        // Add remote to local repository
-        self.add_remote_to_repo(game_id, &repo_url, "origin").await?;
-        
-        // Push to remote
-        self.push_repository(game_id, "origin", &config.default_branch).await?;
-        
-        // Update sync status in database
-        self.update_sync_status(game_id, CloudProvider::GitHub, SyncStatus::Success, Some(&repo_url)).await?;
-        
-        let execution_time = start_time.elapsed().as_millis() as u64;
-        
-        Ok(CloudSyncResult {
-            success: true,
-            provider: CloudProvider::GitHub,
-            repository_url: Some(repo_url.clone()),
-            sync_url: Some(repo_url),
-            message: format!("Successfully pushed to GitHub in {}ms", execution_time),
-            timestamp: Utc::now(),
-        })
-    }
+        crate::logger::info("GIT_CLOUD", &format!("Pushing {} branches for game {}", branches.len(), game_id), Some(&remote_url));
 
-    /// Push to GitLab
-    async fn push_to_gitlab(&self, game_id: i64, config: &GitRepositoryConfig) -> Result<CloudSyncResult> {
         let start_time = std::time::Instant::now();
-        
-        // Create project name
-        let project_name = format!("save-steward-game-{}", game_id);
-        
-        // Create or update project
-        let project_url = if let Some(remote_url) = &config.remote_url {
-            // Update existing project
-            self.gitlab.update_project(&project_name, remote_url).await
-        } else {
-            // Create new project
-            self.gitlab.create_project(&project_name, "Game save repository for Save Steward").await
-        }?;
-        
-        // Add remote to local repository
-        self.add_remote_to_repo(game_id, &project_url, "origin").await?;
-        
-        // Push to remote
-        self.push_repository(game_id, "origin", &config.default_branch).await?;
-        
-        // Update sync status in database
-        self.update_sync_status(game_id, CloudProvider::GitLab, SyncStatus::Success, Some(&project_url)).await?;
-        
-        let execution_time = start_time.elapsed().as_millis() as u64;
-        
-        Ok(CloudSyncResult {
-            success: true,
-            provider: CloudProvider::GitLab,
-            repository_url: Some(project_url.clone()),
-            sync_url: Some(project_url),
-            message: format!("Successfully pushed to GitLab in {}ms", execution_time),
-            timestamp: Utc::now(),
-        })
-    }
+        let repo_path = self.repo_path(&config);
+        let url = remote_url.clone();
+        let outcome = tokio::task::spawn_blocking(move || push_branches(&repo_path, &url, &branches))
+            .await
+            .map_err(|e| format!("Push task failed: {}", e))?
+            .map(|count| format!(
+                "Pushed {} branches to {} in {}ms",
+                count, provider.display_name(), start_time.elapsed().as_millis()
+            ));
 
-    /// Push to Gitea (self-hosted instance)
-    async fn push_to_gitea(&self, game_id: i64, config: &GitRepositoryConfig) -> Result<CloudSyncResult> {
-        // Similar implementation to GitLab but for Gitea
-        todo!("Gitea implementation")
+        self.finish_sync(game_id, "push", provider, remote_url, outcome).await
     }
 
-    /// Push to self-hosted Git server
-    async fn push_to_self_hosted(&self, game_id: i64, config: &GitRepositoryConfig) -> Result<CloudSyncResult> {
-        // Implementation for self-hosted Git server
-        todo!("Self-hosted implementation")
+    /// Fetch the game's save branches from its remote and fast-forward local branches
+    pub async fn pull_from_cloud(&self, game_id: i64) -> Result<CloudSyncResult, String> {
+        let config = self.get_repo_config(game_id).await?;
+        let remote_url = config.remote_url.clone()
+            .ok_or_else(|| format!("No cloud remote configured for game {}", game_id))?;
+        let provider = config.provider.clone()
+            .unwrap_or_else(|| CloudProvider::from_remote_url(&remote_url));
+        let branches = self.get_game_branch_names(game_id).await?;
+
+        crate::logger::info("GIT_CLOUD", &format!("Pulling {} branches for game {}", branches.len(), game_id), Some(&remote_url));
+
+        let repo_path = self.repo_path(&config);
+        let url = remote_url.clone();
+        let outcome = tokio::task::spawn_blocking(move || pull_branches(&repo_path, &url, &branches))
+            .await
+            .map_err(|e| format!("Pull task failed: {}", e))?
+            .map(|count| format!("Updated {} branches from {}", count, provider.display_name()));
+
+        self.finish_sync(game_id, "pull", provider, remote_url, outcome).await
     }
 
-    /// Pull from cloud storage
-    pub async fn pull_from_cloud(&self, game_id: i64) -> Result<CloudSyncResult> {
-        // Get repository configuration
-        let repo_config = self.get_repo_config(game_id).await?;
-        
-        // Pull latest changes
-        self.pull_repository(game_id, "origin", &repo_config.default_branch).await?;
-        
-        // Update sync status
-        self.update_sync_status(game_id,
-            repo_config.provider.clone().unwrap(),
-            SyncStatus::Success,
-            repo_config.remote_url.as_deref()
-        ).await?;
-        
+    /// Record the outcome of a sync and build the result returned to callers
+    async fn finish_sync(
+        &self,
+        game_id: i64,
+        sync_type: &str,
+        provider: CloudProvider,
+        remote_url: String,
+        outcome: Result<String, String>,
+    ) -> Result<CloudSyncResult, String> {
+        let (success, message, status) = match outcome {
+            Ok(message) => (true, message, SyncStatus::Success),
+            Err(e) => {
+                crate::logger::error("GIT_CLOUD", &format!("Cloud {} failed for game {}", sync_type, game_id), Some(&e));
+                (false, e, SyncStatus::Failed)
+            }
+        };
+
+        let error_message = (!success).then(|| message.clone());
+        self.log_sync(game_id, sync_type, &provider, &status, &remote_url, error_message.as_deref()).await?;
+
         Ok(CloudSyncResult {
-            success: true,
-            provider: repo_config.provider.unwrap(),
-            repository_url: repo_config.remote_url.clone(),
-            sync_url: repo_config.remote_url,
-            message: "Successfully pulled from cloud".to_string(),
+            success,
+            provider,
+            repository_url: Some(remote_url.clone()),
+            sync_url: Some(remote_url),
+            message,
             timestamp: Utc::now(),
         })
     }
 
     /// Get cloud sync status
-    pub async fn get_sync_status(&self, game_id: i64) -> Result<Vec<CloudSyncStatus>> {
+    pub async fn get_sync_status(&self, game_id: i64) -> Result<Vec<CloudSyncStatus>, String> {
         let db = self.db.lock().await;
         let conn = db.get_connection().await;
-        
+
         let mut stmt = conn.prepare(
-            "SELECT provider, last_sync_at, sync_status, remote_url, error_message
-             FROM cloud_sync_log 
-             WHERE game_id = ? 
-             ORDER BY created_at DESC 
+            "SELECT cloud_provider, created_at, sync_status, sync_url, error_message
+             FROM cloud_sync_log
+             WHERE game_id = ?
+             ORDER BY created_at DESC
              LIMIT 10"
-        )?;
-        
-        let status_iter = stmt.query_map([game_id], |row| {
+        ).map_err(|e| format!("Failed to prepare sync status query: {}", e))?;
+
+        let statuses = stmt.query_map([game_id], |row| {
+            let provider: String = row.get(0)?;
+            let created_at: Option<String> = row.get(1)?;
+            let status: String = row.get(2)?;
+
             Ok(CloudSyncStatus {
                 game_id,
-                provider: serde_json::from_str(row.get::<_, String>(0)?.as_str())
-                    .unwrap_or(CloudProvider::GitHub),
-                last_sync: row.get::<_, Option<String>>(1)?
-                    .and_then(|s| {
-                        chrono::DateTime::parse_from_rfc3339(s.as_str())
-                            .ok()
-                            .map(|dt| dt.with_timezone(&Utc))
-                    }),
-                sync_status: serde_json::from_str(row.get::<_, String>(2)?.as_str())
+                provider: CloudProvider::parse(&provider).unwrap_or(CloudProvider::SelfHosted),
+                last_sync: created_at.and_then(|s| parse_timestamp(&s)),
+                sync_status: serde_json::from_value(serde_json::Value::String(status))
                     .unwrap_or(SyncStatus::NotConfigured),
                 remote_url: row.get(3)?,
                 error_message: row.get(4)?,
             })
-        })?;
-        
-        let mut statuses = Vec::new();
-        for status in status_iter {
-            statuses.push(status?);
-        }
-        
+        })
+        .map_err(|e| format!("Failed to query sync status: {}", e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read sync status: {}", e))?;
+
         Ok(statuses)
     }
 
-    /// Configure cloud sync for a game
+    /// Configure cloud sync for a game. When no remote URL is given, a repository is
+    /// created with the provider's API using the supplied token.
     pub async fn configure_cloud_sync(
-        &self, 
-        game_id: i64, 
+        &self,
+        game_id: i64,
         provider: CloudProvider,
         credentials: CloudCredentials,
-        auto_sync: bool
-    ) -> Result<CloudSyncResult> {
-        // Store credentials securely (this should be encrypted)
-        self.store_cloud_credentials(game_id, &provider, &credentials).await?;
-        
-        // Update repository configuration
+        remote_url: Option<String>,
+    ) -> Result<CloudSyncResult, String> {
+        let repo_name = format!("save-steward-game-{}", game_id);
+        let description = "Game save repository for Save Steward";
+
+        let remote_url = match remote_url {
+            Some(url) => url,
+            None => match provider {
+                CloudProvider::GitHub => GitHubClient::new(credentials.token.clone())
+                    .create_repository(&repo_name, description).await?,
+                CloudProvider::GitLab => GitLabClient::new(credentials.token.clone(), credentials.url.clone())
+                    .create_project(&repo_name, description).await?,
+                CloudProvider::Gitea | CloudProvider::SelfHosted => {
+                    return Err(format!("A remote URL is required for {}", provider.display_name()));
+                }
+            },
+        };
+
         let db = self.db.lock().await;
         let conn = db.get_connection().await;
-        
-        conn.execute(
-            "UPDATE git_repositories
-             SET cloud_provider = ?, auto_sync = ?
-             WHERE game_id = ?",
-            [serde_json::to_string(&provider)?, auto_sync.to_string(), game_id.to_string()]
-        )?;
-        
+
+        let updated = conn.execute(
+            "UPDATE git_repositories SET cloud_provider = ?, remote_url = ? WHERE game_id = ?",
+            rusqlite::params![provider.as_str(), remote_url, game_id],
+        ).map_err(|e| format!("Failed to save cloud configuration: {}", e))?;
+
+        if updated == 0 {
+            return Err(format!("Git is not enabled for game {}", game_id));
+        }
+
         Ok(CloudSyncResult {
             success: true,
+            repository_url: Some(remote_url.clone()),
+            sync_url: Some(remote_url),
+            message: format!("Cloud sync configured for {}", provider.display_name()),
             provider,
-            repository_url: None,
-            sync_url: None,
-            message: "Cloud sync configured successfully".to_string(),
             timestamp: Utc::now(),
         })
     }
 
     /// Helper methods
-    async fn get_repo_config(&self, game_id: i64) -> Result<GitRepositoryConfig> {
+    async fn get_repo_config(&self, game_id: i64) -> Result<GitRepositoryConfig, String> {
         let db = self.db.lock().await;
         let conn = db.get_connection().await;
-        
-        let mut stmt = conn.prepare(
-            "SELECT local_path, remote_url, cloud_provider, default_branch, auto_sync, git_lfs_enabled
-             FROM git_repositories 
-             WHERE game_id = ?"
-        )?;
-        
-        let row = stmt.query_row([game_id], |row| {
-            Ok(GitRepositoryConfig {
-                id: uuid::Uuid::new_v4(), // TODO: Store actual ID in database
-                game_id,
-                local_path: row.get(0)?,
-                remote_url: row.get(1)?,
-                provider: row.get::<_, Option<String>>(2)?
-                    .and_then(|s| serde_json::from_str(&s).ok()),
-                default_branch: row.get(3)?,
-                auto_commit: true,
-                auto_branch: true,
-                git_lfs_enabled: row.get(5)?,
-                created_at: Utc::now(),
-                last_sync: row.get::<_, Option<String>>(1)?
-                    .and_then(|s| {
-                        chrono::DateTime::parse_from_rfc3339(s.as_str())
-                            .ok()
-                            .map(|dt| dt.with_timezone(&Utc))
-                    }),
-            })
-        })?;
-        
-        Ok(row)
-    }
 
-    async fn add_remote_to_repo(&self, game_id: i64, remote_url: &str, remote_name: &str) -> Result<()> {
-        // This would use git commands to add remote
-        // For now, just log the operation
-        println!("Adding remote {} to repository for game {}", remote_name, game_id);
-        Ok(())
+        let result = conn.query_row(
+            "SELECT local_path, remote_url, cloud_provider, default_branch, auto_commit,
+                    auto_branch, git_lfs_enabled, created_at, last_sync_at
+             FROM git_repositories
+             WHERE game_id = ?",
+            [game_id],
+            |row| {
+                let created_at: Option<String> = row.get(7)?;
+                let last_sync: Option<String> = row.get(8)?;
+
+                Ok(GitRepositoryConfig {
+                    id: uuid::Uuid::new_v4(), // TODO: Store actual ID in database
+                    game_id,
+                    local_path: row.get(0)?,
+                    remote_url: row.get::<_, Option<String>>(1)?.filter(|url| !url.is_empty()),
+                    provider: row.get::<_, Option<String>>(2)?
+                        .and_then(|s| CloudProvider::parse(&s)),
+                    default_branch: row.get::<_, Option<String>>(3)?.unwrap_or_else(|| "main".to_string()),
+                    auto_commit: row.get::<_, Option<bool>>(4)?.unwrap_or(true),
+                    auto_branch: row.get::<_, Option<bool>>(5)?.unwrap_or(true),
+                    git_lfs_enabled: row.get::<_, Option<bool>>(6)?.unwrap_or(false),
+                    created_at: created_at.and_then(|s| parse_timestamp(&s)).unwrap_or_else(Utc::now),
+                    last_sync: last_sync.and_then(|s| parse_timestamp(&s)),
+                })
+            },
+        );
+
+        match result {
+            Ok(config) => Ok(config),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Err(format!("Git is not enabled for game {}", game_id)),
+            Err(e) => Err(format!("Failed to load repository config: {}", e)),
+        }
     }
 
-    async fn push_repository(&self, game_id: i64, remote_name: &str, branch: &str) -> Result<()> {
-        // This would use git commands to push
-        // For now, just log the operation
-        println!("Pushing repository for game {} to {}/{}", game_id, remote_name, branch);
-        Ok(())
+    async fn get_game_branch_names(&self, game_id: i64) -> Result<Vec<String>, String> {
+        let db = self.db.lock().await;
+        let conn = db.get_connection().await;
+
+        let mut stmt = conn.prepare("SELECT branch_name FROM git_branches WHERE game_id = ? ORDER BY id")
+            .map_err(|e| format!("Failed to prepare branch query: {}", e))?;
+
+        let branches = stmt.query_map([game_id], |row| row.get::<_, String>(0))
+            .map_err(|e| format!("Failed to query branches: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Failed to read branches: {}", e))?;
+
+        if branches.is_empty() {
+            return Err(format!("No save branches recorded for game {}", game_id));
+        }
+
+        Ok(branches)
     }
 
-    async fn pull_repository(&self, game_id: i64, remote_name: &str, branch: &str) -> Result<()> {
-        // This would use git commands to pull
-        // For now, just log the operation
-        println!("Pulling repository for game {} from {}/{}", game_id, remote_name, branch);
-        Ok(())
+    fn repo_path(&self, config: &GitRepositoryConfig) -> String {
+        if config.local_path.is_empty() {
+            self.master_repo_path.clone()
+        } else {
+            config.local_path.clone()
+        }
     }
 
-    async fn update_sync_status(
+    async fn log_sync(
         &self,
         game_id: i64,
-        provider: CloudProvider,
-        status: SyncStatus,
-        remote_url: Option<&str>
-    ) -> Result<()> {
+        sync_type: &str,
+        provider: &CloudProvider,
+        status: &SyncStatus,
+        remote_url: &str,
+        error_message: Option<&str>,
+    ) -> Result<(), String> {
         let db = self.db.lock().await;
         let conn = db.get_connection().await;
-        
+        let now = Utc::now().to_rfc3339();
+
         conn.execute(
-            "INSERT INTO cloud_sync_log (game_id, provider, sync_status, remote_url, created_at)
-             VALUES (?, ?, ?, ?, ?)",
+            "INSERT INTO cloud_sync_log (game_id, sync_type, cloud_provider, sync_status, error_message, sync_url, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
             rusqlite::params![
                 game_id,
-                serde_json::to_string(&provider)?,
-                serde_json::to_string(&status)?,
-                remote_url.unwrap_or(""),
-                Utc::now().to_rfc3339()
+                sync_type,
+                provider.as_str(),
+                status.as_str(),
+                error_message,
+                remote_url,
+                now
             ]
-        )?;
-        
+        ).map_err(|e| format!("Failed to record cloud sync: {}", e))?;
+
+        if matches!(status, SyncStatus::Success) {
+            conn.execute(
+                "UPDATE git_repositories SET last_sync_at = ? WHERE game_id = ?",
+                rusqlite::params![now, game_id],
+            ).map_err(|e| format!("Failed to update last sync time: {}", e))?;
+        }
+
         Ok(())
     }
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Remote callbacks that authenticate through the SSH agent or git credential helpers
+fn remote_callbacks(config: &git2::Config) -> RemoteCallbacks<'_> {
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(move |url, username, allowed| {
+        if allowed.contains(CredentialType::SSH_KEY) {
+            Cred::ssh_key_from_agent(username.unwrap_or("git"))
+        } else if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+            Cred::credential_helper(config, url, username)
+        } else {
+            Cred::default()
+        }
+    });
+    callbacks
+}
+
+/// Push local save branches to `remote_url`, returning how many were pushed
+fn push_branches(repo_path: &str, remote_url: &str, branches: &[String]) -> Result<usize, String> {
+    let repo = Repository::open(repo_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+    let config = repo.config()
+        .map_err(|e| format!("Failed to read git config: {}", e))?;
+
+    let refspecs: Vec<String> = branches.iter()
+        .filter(|name| repo.find_branch(name, BranchType::Local).is_ok())
+        .map(|name| format!("refs/heads/{0}:refs/heads/{0}", name))
+        .collect();
+
+    if refspecs.is_empty() {
+        return Err("None of the game's save branches exist locally".to_string());
+    }
 
-    async fn store_cloud_credentials(&self, game_id: i64, provider: &CloudProvider, credentials: &CloudCredentials) -> Result<()> {
-        // This should store credentials securely (encrypted)
-        // For now, just log
-        println!("Storing cloud credentials for game {} on {:?}", game_id, provider);
+    let rejected = std::cell::RefCell::new(Vec::new());
+    let mut callbacks = remote_callbacks(&config);
+    callbacks.push_update_reference(|refname, status| {
+        if let Some(message) = status {
+            rejected.borrow_mut().push(format!("{}: {}", refname, message));
+        }
         Ok(())
+    });
+
+    let mut options = PushOptions::new();
+    options.remote_callbacks(callbacks);
+
+    let mut remote = repo.remote_anonymous(remote_url)
+        .map_err(|e| format!("Invalid remote '{}': {}", remote_url, e))?;
+    remote.push(&refspecs, Some(&mut options))
+        .map_err(|e| format!("Push failed: {}", e))?;
+    drop(options);
+
+    let rejected = rejected.into_inner();
+    if !rejected.is_empty() {
+        return Err(format!("Remote rejected: {}", rejected.join("; ")));
+    }
+
+    Ok(refspecs.len())
+}
+
+/// Fetch save branches from `remote_url` and fast-forward the local branches,
+/// returning how many branches changed
+fn pull_branches(repo_path: &str, remote_url: &str, branches: &[String]) -> Result<usize, String> {
+    let repo = Repository::open(repo_path)
+        .map_err(|e| format!("Failed to open repository: {}", e))?;
+    let config = repo.config()
+        .map_err(|e| format!("Failed to read git config: {}", e))?;
+
+    let refspecs: Vec<String> = branches.iter()
+        .map(|name| format!("+refs/heads/{0}:refs/remotes/cloud/{0}", name))
+        .collect();
+
+    let mut options = FetchOptions::new();
+    options.remote_callbacks(remote_callbacks(&config));
+
+    let mut remote = repo.remote_anonymous(remote_url)
+        .map_err(|e| format!("Invalid remote '{}': {}", remote_url, e))?;
+    remote.fetch(&refspecs, Some(&mut options), None)
+        .map_err(|e| format!("Fetch failed: {}", e))?;
+
+    let head_branch = repo.head().ok().and_then(|h| h.shorthand().map(|s| s.to_string()));
+    let mut updated = 0;
+
+    for name in branches {
+        let Ok(fetched) = repo.find_reference(&format!("refs/remotes/cloud/{}", name)) else { continue };
+        let Some(fetched_oid) = fetched.target() else { continue };
+
+        match repo.find_branch(name, BranchType::Local) {
+            Ok(branch) => {
+                let Some(local_oid) = branch.get().target() else { continue };
+                if local_oid == fetched_oid {
+                    continue;
+                }
+                let fast_forward = repo.graph_descendant_of(fetched_oid, local_oid)
+                    .map_err(|e| format!("Failed to compare {}: {}", name, e))?;
+                if !fast_forward {
+                    return Err(format!("Branch '{}' has diverged from the cloud copy", name));
+                }
+                branch.into_reference()
+                    .set_target(fetched_oid, "save-steward: fast-forward from cloud")
+                    .map_err(|e| format!("Failed to update {}: {}", name, e))?;
+            }
+            Err(_) => {
+                let commit = repo.find_commit(fetched_oid)
+                    .map_err(|e| format!("Failed to read fetched commit: {}", e))?;
+                repo.branch(name, &commit, false)
+                    .map_err(|e| format!("Failed to create {}: {}", name, e))?;
+            }
+        }
+        updated += 1;
+
+        // Keep the working tree in step with the checked-out branch
+        if head_branch.as_deref() == Some(name.as_str()) {
+            repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+                .map_err(|e| format!("Failed to check out {}: {}", name, e))?;
+        }
     }
+
+    Ok(updated)
 }
 
 /// Cloud credentials for different providers
@@ -343,62 +438,108 @@ pub struct CloudCredentials {
     pub api_key: Option<String>,
 }
 
+/// Check that a token is accepted by the provider's "authenticated user" endpoint,
+/// without creating or touching any repository
+pub async fn test_credentials(provider: CloudProvider, credentials: &CloudCredentials) -> Result<CredentialCheck, String> {
+    let user = match provider {
+        CloudProvider::GitHub => GitHubClient::new(credentials.token.clone()).authenticated_user().await,
+        CloudProvider::GitLab => GitLabClient::new(credentials.token.clone(), credentials.url.clone()).authenticated_user().await,
+        CloudProvider::Gitea | CloudProvider::SelfHosted => {
+            return Err(format!("Invalid provider: credential testing is not supported for {}", provider.display_name()));
+        }
+    };
+
+    Ok(match user {
+        Ok(username) => CredentialCheck {
+            valid: true,
+            message: format!("Authenticated with {} as {}", provider.display_name(), username),
+            username: Some(username),
+        },
+        Err(message) => CredentialCheck {
+            valid: false,
+            username: None,
+            message,
+        },
+    })
+}
+
 /// GitHub-specific client implementation
 impl GitHubClient {
-    fn new() -> Self {
+    fn new(token: Option<String>) -> Self {
         Self {
-            token: None,
+            token,
             client: Client::new(),
             base_url: "https://api.github.com".to_string(),
         }
     }
 
-    async fn create_repository(&self, name: &str, description: &str) -> Result<String> {
+    async fn create_repository(&self, name: &str, description: &str) -> Result<String, String> {
+        let token = self.token.as_ref().ok_or("A GitHub token is required to create a repository")?;
         let repo_data = serde_json::json!({
             "name": name,
             "description": description,
-            "private": false,
+            "private": true,
             "has_issues": false,
             "has_projects": false,
             "has_wiki": false
         });
 
         let response = self.client
-            .post(&format!("{}/user/repos", self.base_url))
-            .header(header::AUTHORIZATION, format!("token {}", self.token.as_ref().unwrap_or(&"".to_string())))
+            .post(format!("{}/user/repos", self.base_url))
+            .header(header::AUTHORIZATION, format!("token {}", token))
+            .header(header::USER_AGENT, "save-steward")
             .json(&repo_data)
             .send()
-            .await?;
+            .await
+            .map_err(|e| format!("GitHub request failed: {}", e))?;
 
         if response.status().is_success() {
-            let repo: serde_json::Value = response.json().await?;
+            let repo: serde_json::Value = response.json().await
+                .map_err(|e| format!("Invalid GitHub response: {}", e))?;
             Ok(repo["clone_url"].as_str().unwrap_or("").to_string())
         } else {
-            Err(anyhow::anyhow!("Failed to create GitHub repository"))
+            Err(format!("Failed to create GitHub repository: HTTP {}", response.status()))
         }
     }
 
-    async fn update_repository(&self, name: &str, remote_url: &str, _action: &str) -> Result<String> {
-        // For existing repositories, just return the clone URL
-        Ok(remote_url.to_string())
+    /// Login of the user the token belongs to
+    async fn authenticated_user(&self) -> Result<String, String> {
+        let token = self.token.as_ref().ok_or("A GitHub token is required")?;
+
+        let response = self.client
+            .get(format!("{}/user", self.base_url))
+            .header(header::AUTHORIZATION, format!("token {}", token))
+            .header(header::USER_AGENT, "save-steward")
+            .send()
+            .await
+            .map_err(|e| format!("GitHub request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("GitHub rejected the token: HTTP {}", response.status()));
+        }
+
+        let user: serde_json::Value = response.json().await
+            .map_err(|e| format!("Invalid GitHub response: {}", e))?;
+        Ok(user["login"].as_str().unwrap_or("").to_string())
     }
 }
 
 /// GitLab-specific client implementation
 impl GitLabClient {
-    fn new() -> Self {
+    fn new(token: Option<String>, base_url: Option<String>) -> Self {
         Self {
-            token: None,
+            token,
             client: Client::new(),
-            base_url: "https://gitlab.com/api/v4".to_string(),
+            base_url: base_url.unwrap_or_else(|| "https://gitlab.com/api/v4".to_string()),
         }
     }
 
-    async fn create_project(&self, name: &str, description: &str) -> Result<String> {
+    async fn create_project(&self, name: &str, description: &str) -> Result<String, String> {
+        let token = self.token.as_ref().ok_or("A GitLab token is required to create a project")?;
         let project_data = serde_json::json!({
             "name": name,
             "description": description,
-            "visibility": "public",
+            "visibility": "private",
             "issues_enabled": false,
             "merge_requests_enabled": false,
             "wiki_enabled": false,
@@ -406,22 +547,39 @@ impl GitLabClient {
         });
 
         let response = self.client
-            .post(&format!("{}/projects", self.base_url))
-            .header(header::AUTHORIZATION, format!("Bearer {}", self.token.as_ref().unwrap_or(&"".to_string())))
+            .post(format!("{}/projects", self.base_url))
+            .header(header::AUTHORIZATION, format!("Bearer {}", token))
             .json(&project_data)
             .send()
-            .await?;
+            .await
+            .map_err(|e| format!("GitLab request failed: {}", e))?;
 
         if response.status().is_success() {
-            let project: serde_json::Value = response.json().await?;
+            let project: serde_json::Value = response.json().await
+                .map_err(|e| format!("Invalid GitLab response: {}", e))?;
             Ok(project["http_url_to_repo"].as_str().unwrap_or("").to_string())
         } else {
-            Err(anyhow::anyhow!("Failed to create GitLab project"))
+            Err(format!("Failed to create GitLab project: HTTP {}", response.status()))
         }
     }
 
-    async fn update_project(&self, name: &str, remote_url: &str) -> Result<String> {
-        // For existing projects, just return the clone URL
-        Ok(remote_url.to_string())
+    /// Username of the user the token belongs to
+    async fn authenticated_user(&self) -> Result<String, String> {
+        let token = self.token.as_ref().ok_or("A GitLab token is required")?;
+
+        let response = self.client
+            .get(format!("{}/user", self.base_url))
+            .header(header::AUTHORIZATION, format!("Bearer {}", token))
+            .send()
+            .await
+            .map_err(|e| format!("GitLab request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("GitLab rejected the token: HTTP {}", response.status()));
+        }
+
+        let user: serde_json::Value = response.json().await
+            .map_err(|e| format!("Invalid GitLab response: {}", e))?;
+        Ok(user["username"].as_str().unwrap_or("").to_string())
     }
 }
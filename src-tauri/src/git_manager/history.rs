@@ -2,124 +2,159 @@ use git2::Repository;
 use chrono::{DateTime, Utc};
 use super::GitSaveCommit;
 use super::GitSaveHistory;
+use super::operation::GitOperation;
+use std::sync::Arc;
 
 /// Restore to a specific commit
-pub async fn restore_to_commit(master_repo_path: &str, _game_id: i64, commit_hash: &str) -> Result<(), String> {
+pub async fn restore_to_commit(master_repo_path: &str, game_id: i64, commit_hash: &str) -> Result<(), String> {
+    restore_to_commit_with_operation(master_repo_path, &GitOperation::start(game_id), commit_hash).await
+}
+
+/// `restore_to_commit` as part of an operation that can be cancelled and reports progress
+pub async fn restore_to_commit_with_operation(master_repo_path: &str, operation: &Arc<GitOperation>, commit_hash: &str) -> Result<(), String> {
     crate::logger::info("GIT_HISTORY", &format!("Restoring to commit: {}", commit_hash), None);
     
+    let repo_path = master_repo_path.to_string();
+    let commit_hash = commit_hash.to_string();
+    operation.run_blocking(move |operation| {
+        let repo = Repository::open(&repo_path)
+            .map_err(|e| {
+                crate::logger::error("GIT_HISTORY", "Failed to open master repository", Some(&e.to_string()));
+                format!("Failed to open master repository: {}", e)
+            })?;
+
+        // Find commit
+        let commit = repo.find_commit(git2::Oid::from_str(&commit_hash).map_err(|e| {
+            crate::logger::error("GIT_HISTORY","Invalid commit hash", Some(&e.to_string()));
+            format!("Invalid commit hash: {}", e)
+        })?)
+            .map_err(|e| {
+                crate::logger::error("GIT_HISTORY", &format!("Failed to find commit '{}'", commit_hash), Some(&e.to_string()));
+                format!("Failed to find commit '{}': {}", commit_hash, e)
+            })?;
+
+        // Create new branch for this commit (optional)
+        let timestamp = commit.time();
+        let branch_name = format!("restore-{}-{}",
+            chrono::DateTime::from_timestamp(timestamp.seconds(), 0).unwrap().format("%Y%m%d-%H%M%S"),
+            commit_hash.chars().take(8).collect::<String>()
+        );
+        
+        crate::logger::debug("GIT_HISTORY", &format!("Creating restore branch: {}", branch_name), None);
+
+        repo.branch(&branch_name, &commit, false)
+            .map_err(|e| {
+                crate::logger::error("GIT_HISTORY", "Failed to create restore branch", Some(&e.to_string()));
+                format!("Failed to create restore branch: {}", e)
+            })?;
+
+        // Checkout the commit
+        operation.checkout_tree(&repo, &commit.into_object())
+            .map_err(|e| {
+                crate::logger::error("GIT_HISTORY", &format!("Failed to checkout commit '{}'", commit_hash), Some(&e));
+                format!("Failed to checkout commit '{}': {}", commit_hash, e)
+            })?;
+
+        repo.set_head(&format!("refs/heads/{}", branch_name))
+            .map_err(|e| {
+                crate::logger::error("GIT_HISTORY", "Failed to set HEAD to restore branch", Some(&e.to_string()));
+                format!("Failed to set HEAD to restore branch: {}", e)
+            })?;
+
+        crate::logger::info("GIT_HISTORY", &format!("Successfully restored to commit: {}", commit_hash), None);
+        Ok(())
+    }).await
+}
+
+/// Get the commit time of a specific commit
+pub async fn get_commit_time(master_repo_path: &str, commit_hash: &str) -> Result<DateTime<Utc>, String> {
     let repo = Repository::open(master_repo_path)
         .map_err(|e| {
             crate::logger::error("GIT_HISTORY", "Failed to open master repository", Some(&e.to_string()));
             format!("Failed to open master repository: {}", e)
         })?;
 
-    // Find commit
-    let commit = repo.find_commit(git2::Oid::from_str(commit_hash).map_err(|e| {
-        crate::logger::error("GIT_HISTORY","Invalid commit hash", Some(&e.to_string()));
-        format!("Invalid commit hash: {}", e)
-    })?)
-        .map_err(|e| {
-            crate::logger::error("GIT_HISTORY", &format!("Failed to find commit '{}'", commit_hash), Some(&e.to_string()));
-            format!("Failed to find commit '{}': {}", commit_hash, e)
-        })?;
+    let oid = git2::Oid::from_str(commit_hash)
+        .map_err(|e| format!("Invalid commit hash: {}", e))?;
+    let commit = repo.find_commit(oid)
+        .map_err(|e| format!("Failed to find commit '{}': {}", commit_hash, e))?;
 
-    // Create new branch for this commit (optional)
-    let timestamp = commit.time();
-    let branch_name = format!("restore-{}-{}",
-        chrono::DateTime::from_timestamp(timestamp.seconds(), 0).unwrap().format("%Y%m%d-%H%M%S"),
-        commit_hash.chars().take(8).collect::<String>()
-    );
-    
-    crate::logger::debug("GIT_HISTORY", &format!("Creating restore branch: {}", branch_name), None);
+    DateTime::from_timestamp(commit.time().seconds(), 0)
+        .ok_or_else(|| "Invalid timestamp".to_string())
+}
 
-    repo.branch(&branch_name, &commit, false)
+/// Find the commit closest to a timestamp, returning its hash, message and time
+pub async fn find_closest_commit(master_repo_path: &str, target_time: DateTime<Utc>) -> Result<(String, String, DateTime<Utc>), String> {
+    let repo = Repository::open(master_repo_path)
         .map_err(|e| {
-            crate::logger::error("GIT_HISTORY", "Failed to create restore branch", Some(&e.to_string()));
-            format!("Failed to create restore branch: {}", e)
+            crate::logger::error("GIT_HISTORY", "Failed to open master repository", Some(&e.to_string()));
+            format!("Failed to open master repository: {}", e)
         })?;
 
-    // Checkout the commit
-    repo.checkout_tree(&commit.into_object(), None)
+    let mut revwalk = repo.revwalk()
         .map_err(|e| {
-            crate::logger::error("GIT_HISTORY", &format!("Failed to checkout commit '{}'", commit_hash), Some(&e.to_string()));
-            format!("Failed to checkout commit '{}': {}", commit_hash, e)
+            crate::logger::error("GIT_HISTORY", "Failed to create revision walker", Some(&e.to_string()));
+            format!("Failed to create revision walker: {}", e)
         })?;
 
-    repo.set_head(&format!("refs/heads/{}", branch_name))
+    revwalk.push_head()
         .map_err(|e| {
-            crate::logger::error("GIT_HISTORY", "Failed to set HEAD to restore branch", Some(&e.to_string()));
-            format!("Failed to set HEAD to restore branch: {}", e)
+            crate::logger::error("GIT_HISTORY", "Failed to push HEAD", Some(&e.to_string()));
+            format!("Failed to push HEAD: {}", e)
         })?;
 
-    crate::logger::info("GIT_HISTORY", &format!("Successfully restored to commit: {}", commit_hash), None);
-
-    Ok(())
-}
+    let mut closest_commit = None;
+    let mut closest_time_diff = i64::MAX;
 
-/// Restore to a timestamp (finds nearest commit)
-pub async fn restore_to_timestamp(master_repo_path: &str, game_id: i64, target_time: DateTime<Utc>) -> Result<String, String> {
-    crate::logger::info("GIT_HISTORY", &format!("Restoring to timestamp: {}", target_time), None);
-    
-    let (commit_hash, commit_msg) = {
-        let repo = Repository::open(master_repo_path)
+    for oid in revwalk {
+        let oid = oid.map_err(|e| {
+            crate::logger::error("GIT_HISTORY", "Failed to get revision", Some(&e.to_string()));
+            format!("Failed to get revision: {}", e)
+        })?;
+        let commit = repo.find_commit(oid)
             .map_err(|e| {
-                crate::logger::error("GIT_HISTORY", "Failed to open master repository", Some(&e.to_string()));
-                format!("Failed to open master repository: {}", e)
+                crate::logger::error("GIT_HISTORY", "Failed to find commit", Some(&e.to_string()));
+                format!("Failed to find commit: {}", e)
             })?;
 
-        let mut revwalk = repo.revwalk()
-            .map_err(|e| {
-                crate::logger::error("GIT_HISTORY", "Failed to create revision walker", Some(&e.to_string()));
-                format!("Failed to create revision walker: {}", e)
+        let commit_time = commit.time();
+        let commit_datetime = DateTime::from_timestamp(commit_time.seconds(), 0)
+            .ok_or_else(|| {
+                crate::logger::error("GIT_HISTORY", "Invalid timestamp", None);
+                "Invalid timestamp".to_string()
             })?;
 
-        revwalk.push_head()
-            .map_err(|e| {
-                crate::logger::error("GIT_HISTORY", "Failed to push HEAD", Some(&e.to_string()));
-                format!("Failed to push HEAD: {}", e)
-            })?;
+        let time_diff = (target_time.timestamp() - commit_datetime.timestamp()).abs();
 
-        let mut closest_commit = None;
-        let mut closest_time_diff = i64::MAX;
+        if time_diff < closest_time_diff {
+            closest_time_diff = time_diff;
+            closest_commit = Some((commit, commit_datetime));
+        }
+    }
 
-        for oid in revwalk {
-            let oid = oid.map_err(|e| {
-                crate::logger::error("GIT_HISTORY", "Failed to get revision", Some(&e.to_string()));
-                format!("Failed to get revision: {}", e)
-            })?;
-            let commit = repo.find_commit(oid)
-                .map_err(|e| {
-                    crate::logger::error("GIT_HISTORY", "Failed to find commit", Some(&e.to_string()));
-                    format!("Failed to find commit: {}", e)
-                })?;
+    if let Some((commit, commit_datetime)) = closest_commit {
+        let hash = commit.id().to_string();
+        let msg = commit.message().unwrap_or("Restore commit").to_string();
+        crate::logger::debug("GIT_HISTORY", &format!("Found closest commit: {} (time diff: {}s)", hash, closest_time_diff), None);
+        Ok((hash, msg, commit_datetime))
+    } else {
+        crate::logger::error("GIT_HISTORY", "No commits found", None);
+        Err("No commits found".to_string())
+    }
+}
 
-            let commit_time = commit.time();
-            let commit_datetime = DateTime::from_timestamp(commit_time.seconds(), 0)
-                .ok_or_else(|| {
-                    crate::logger::error("GIT_HISTORY", "Invalid timestamp", None);
-                    "Invalid timestamp".to_string()
-                })?;
+/// Restore to a timestamp (finds nearest commit)
+pub async fn restore_to_timestamp(master_repo_path: &str, game_id: i64, target_time: DateTime<Utc>) -> Result<String, String> {
+    restore_to_timestamp_with_operation(master_repo_path, &GitOperation::start(game_id), target_time).await
+}
 
-            let time_diff = (target_time.timestamp() - commit_datetime.timestamp()).abs();
-            
-            if time_diff < closest_time_diff {
-                closest_time_diff = time_diff;
-                closest_commit = Some(commit);
-            }
-        }
+/// `restore_to_timestamp` as part of an operation that can be cancelled and reports progress
+pub async fn restore_to_timestamp_with_operation(master_repo_path: &str, operation: &Arc<GitOperation>, target_time: DateTime<Utc>) -> Result<String, String> {
+    crate::logger::info("GIT_HISTORY", &format!("Restoring to timestamp: {}", target_time), None);
 
-        if let Some(commit) = closest_commit {
-            let hash = commit.id().to_string();
-            let msg = commit.message().unwrap_or("Restore commit").to_string();
-            crate::logger::debug("GIT_HISTORY", &format!("Found closest commit: {} (time diff: {}s)", hash, closest_time_diff), None);
-            (hash, msg)
-        } else {
-            crate::logger::error("GIT_HISTORY", "No commits found", None);
-            return Err("No commits found".to_string());
-        }
-    };
+    let (commit_hash, commit_msg, _) = find_closest_commit(master_repo_path, target_time).await?;
 
-    restore_to_commit(master_repo_path, game_id, &commit_hash).await?;
+    restore_to_commit_with_operation(master_repo_path, operation, &commit_hash).await?;
     
     let result = format!("Restored to nearest commit: {} ({})", commit_hash.chars().take(8).collect::<String>(), commit_msg);
     crate::logger::info("GIT_HISTORY", &result, None);
@@ -127,6 +162,53 @@ pub async fn restore_to_timestamp(master_repo_path: &str, game_id: i64, target_t
     Ok(result)
 }
 
+/// Find the recorded commit whose saves were written closest to a timestamp, returning
+/// its hash and that save time. Commits recorded without a save time are ignored.
+pub async fn find_closest_save_time_commit(
+    db: &std::sync::Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+    game_id: i64,
+    target_time: DateTime<Utc>,
+) -> Result<Option<(String, DateTime<Utc>)>, String> {
+    let db_guard = db.lock().await;
+    let conn = db_guard.get_connection().await;
+
+    let mut stmt = conn.prepare(
+        "SELECT git_commit_hash, save_modified_at FROM git_save_commits
+         WHERE game_id = ? AND save_modified_at IS NOT NULL"
+    ).map_err(|e| format!("Failed to query save commits: {}", e))?;
+    let rows = stmt.query_map([game_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| format!("Failed to query save commits: {}", e))?;
+
+    Ok(rows.into_iter()
+        .filter_map(|(hash, saved_at)| {
+            DateTime::parse_from_rfc3339(&saved_at).ok().map(|t| (hash, t.with_timezone(&Utc)))
+        })
+        .min_by_key(|(_, saved_at)| (target_time - *saved_at).num_seconds().abs()))
+}
+
+/// Restore the commit whose saves were written closest to a timestamp, falling back to
+/// the nearest commit time when no commit has a recorded save time
+pub async fn restore_to_save_time(
+    db: &std::sync::Arc<tokio::sync::Mutex<crate::database::connection::Database>>,
+    master_repo_path: &str,
+    operation: &Arc<GitOperation>,
+    game_id: i64,
+    target_time: DateTime<Utc>,
+) -> Result<String, String> {
+    let Some((commit_hash, saved_at)) = find_closest_save_time_commit(db, game_id, target_time).await? else {
+        crate::logger::warn("GIT_HISTORY", &format!("No save times recorded for game {}, matching commit time instead", game_id), None);
+        return restore_to_timestamp_with_operation(master_repo_path, operation, target_time).await;
+    };
+
+    restore_to_commit_with_operation(master_repo_path, operation, &commit_hash).await?;
+
+    let result = format!("Restored to commit {} (saves written {})", commit_hash.chars().take(8).collect::<String>(), saved_at.to_rfc3339());
+    crate::logger::info("GIT_HISTORY", &result, None);
+
+    Ok(result)
+}
+
 /// Get save history
 pub async fn get_save_history(master_repo_path: &str, _game_id: i64) -> Result<serde_json::Value, String> {
     crate::logger::debug("GIT_HISTORY", "Getting save history", None);
@@ -182,9 +264,10 @@ pub async fn get_save_history(master_repo_path: &str, _game_id: i64) -> Result<s
         let commit_datetime = DateTime::from_timestamp(commit_time.seconds(), 0)
             .unwrap_or_else(|| Utc::now());
 
-        // Extract game name from branch name using '-' separator (FIX: was using '+')
+        // Extract game name from legacy `gamename-savename` branches; id-prefixed
+        // branches no longer carry the name
         let branch_name = current_branch.clone(); // This should be extracted from commit branch
-        let game_name = if branch_name.contains('-') {
+        let game_name = if branch_name.contains('-') && super::branching::game_id_from_branch(&branch_name).is_none() {
             branch_name.split('-').next().unwrap_or("Unknown").to_string()
         } else {
             "Unknown".to_string()
@@ -212,3 +295,90 @@ pub async fn get_save_history(master_repo_path: &str, _game_id: i64) -> Result<s
         format!("Failed to serialize history: {}", e)
     })
 }
+
+/// Commits reachable from a branch (or HEAD) made within `[from, to]`, newest first.
+///
+/// Either bound may be omitted. At most `limit` commits are returned, so callers can page
+/// backwards by passing the oldest timestamp they have seen as the next `to`.
+pub async fn get_history_range(
+    master_repo_path: &str,
+    game_id: i64,
+    branch: Option<&str>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    limit: usize,
+) -> Result<Vec<GitSaveCommit>, String> {
+    crate::logger::debug("GIT_HISTORY", &format!("Getting history range for game_id: {} ({:?} to {:?}, limit {})", game_id, from, to, limit), None);
+
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err(format!("Invalid date range: {} is after {}", from, to));
+        }
+    }
+
+    let repo = Repository::open(master_repo_path)
+        .map_err(|e| {
+            crate::logger::error("GIT_HISTORY", "Failed to open master repository", Some(&e.to_string()));
+            format!("Failed to open master repository: {}", e)
+        })?;
+
+    let mut revwalk = repo.revwalk()
+        .map_err(|e| {
+            crate::logger::error("GIT_HISTORY", "Failed to create revision walker", Some(&e.to_string()));
+            format!("Failed to create revision walker: {}", e)
+        })?;
+    revwalk.set_sorting(git2::Sort::TIME)
+        .map_err(|e| format!("Failed to sort revisions: {}", e))?;
+
+    let branch_name = match branch {
+        Some(branch) => {
+            if super::branching::game_id_from_branch(branch) != Some(game_id) {
+                return Err(format!("Branch '{}' does not belong to game {}", branch, game_id));
+            }
+            revwalk.push_ref(&format!("refs/heads/{}", branch))
+                .map_err(|e| format!("Branch '{}' not found: {}", branch, e))?;
+            branch.to_string()
+        }
+        None => {
+            revwalk.push_head()
+                .map_err(|e| {
+                    crate::logger::error("GIT_HISTORY", "Failed to push HEAD", Some(&e.to_string()));
+                    format!("Failed to push HEAD: {}", e)
+                })?;
+            repo.head().ok()
+                .and_then(|r| r.shorthand().map(|s| s.to_string()))
+                .unwrap_or_else(|| "detached".to_string())
+        }
+    };
+
+    let mut commits = Vec::new();
+    for oid in revwalk {
+        if commits.len() >= limit {
+            break;
+        }
+
+        let oid = oid.map_err(|e| format!("Failed to get revision: {}", e))?;
+        let commit = repo.find_commit(oid)
+            .map_err(|e| format!("Failed to find commit: {}", e))?;
+        let Some(commit_datetime) = DateTime::from_timestamp(commit.time().seconds(), 0) else { continue };
+
+        if to.is_some_and(|to| commit_datetime > to) {
+            continue;
+        }
+        // The walk is time-ordered, so nothing older can be in range
+        if from.is_some_and(|from| commit_datetime < from) {
+            break;
+        }
+
+        commits.push(GitSaveCommit {
+            hash: commit.id().to_string(),
+            message: commit.message().unwrap_or("No message").to_string(),
+            timestamp: commit_datetime,
+            branch: branch_name.clone(),
+            game_name: "Unknown".to_string(),
+        });
+    }
+
+    crate::logger::debug("GIT_HISTORY", &format!("Retrieved {} commits in range", commits.len()), None);
+    Ok(commits)
+}
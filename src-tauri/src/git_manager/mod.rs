@@ -1,17 +1,24 @@
 //! Git integration for Save Steward
 //! 
 //! This module provides version control capabilities for game saves using Git,
-//! enabling session-based branching with the pattern: gamename+save-name
+//! enabling session-based branching with the pattern: g<game_id>-save-name
 
 pub mod types;
 pub mod repository;
 pub mod branching;
 pub mod history;
-// TODO: Fix cloud.rs compilation errors (42 errors) before enabling
-// pub mod cloud;
+pub mod cloud;
+pub mod snapshot;
+pub mod repair;
+pub mod operation;
 
 use crate::database::connection::{Database, DatabasePaths};
 use chrono::{DateTime, Utc};
+use self::cloud::CloudSyncManager;
+use self::operation::GitOperation;
+use self::repair::RepoHealthReport;
+use self::snapshot::BranchRestoreResult;
+use self::types::CloudSyncResult;
 
 pub struct GitSaveManager {
     db: std::sync::Arc<tokio::sync::Mutex<Database>>,
@@ -47,32 +54,89 @@ impl GitSaveManager {
 
     /// Initialize master repository for all game saves
     pub async fn initialize_master_repo(&self) -> Result<String, String> {
-        repository::initialize_master_repo(&self.db, &self.master_repo_path).await
+        let result = repository::initialize_master_repo(&self.db, &self.master_repo_path).await?;
+        self.migrate_legacy_branch_names().await?;
+        Ok(result)
+    }
+
+    /// Check the master repository for corruption and, when `rebuild` is set and problems
+    /// were found, reinitialize it from the working tree and recorded branches
+    pub async fn repair_master_repo(&self, rebuild: bool) -> Result<RepoHealthReport, String> {
+        let path = self.master_repo_path.clone();
+        let report = tokio::task::spawn_blocking(move || repair::check_repository(&path))
+            .await
+            .map_err(|e| format!("Repository check failed: {}", e))?;
+
+        if report.healthy || !rebuild {
+            return Ok(report);
+        }
+        repair::rebuild_repository(&self.db, &self.master_repo_path, report).await
+    }
+
+    /// Rename `gamename-savename` branches to the id-prefixed `g<game_id>-savename` scheme
+    pub async fn migrate_legacy_branch_names(&self) -> Result<usize, String> {
+        branching::migrate_legacy_branch_names(&self.db, &self.master_repo_path).await
     }
 
     /// Create a save checkpoint with user-named branch
-    pub async fn create_save_checkpoint(&self, game_id: i64, save_name: &str) -> Result<String, String> {
-        branching::create_save_checkpoint(&self.db, &self.master_repo_path, game_id, save_name).await
+    pub async fn create_save_checkpoint(&self, operation: &std::sync::Arc<GitOperation>, save_name: &str) -> Result<String, String> {
+        branching::create_save_checkpoint_with_operation(&self.db, &self.master_repo_path, operation, save_name).await
     }
 
     /// Create a new branch (alias for create_save_checkpoint)
-    pub async fn create_save_branch(&self, game_id: i64, branch_name: &str, description: Option<&str>) -> Result<(), String> {
-        branching::create_save_branch(&self.db, &self.master_repo_path, game_id, branch_name, description).await
+    pub async fn create_save_branch(&self, operation: &std::sync::Arc<GitOperation>, branch_name: &str, description: Option<&str>) -> Result<(), String> {
+        branching::create_save_branch_with_operation(&self.db, &self.master_repo_path, operation, branch_name, description).await
     }
 
     /// Switch to a branch
-    pub async fn switch_save_branch(&self, game_id: i64, branch_name: &str) -> Result<(), String> {
-        branching::switch_save_branch(&self.db, &self.master_repo_path, game_id, branch_name).await
+    pub async fn switch_save_branch(&self, operation: &std::sync::Arc<GitOperation>, branch_name: &str) -> Result<(), String> {
+        branching::switch_save_branch_with_operation(&self.db, &self.master_repo_path, operation, branch_name).await
     }
 
     /// Restore to a specific commit
-    pub async fn restore_to_commit(&self, game_id: i64, commit_hash: &str) -> Result<(), String> {
-        history::restore_to_commit(&self.master_repo_path, game_id, commit_hash).await
+    pub async fn restore_to_commit(&self, operation: &std::sync::Arc<GitOperation>, commit_hash: &str) -> Result<(), String> {
+        history::restore_to_commit_with_operation(&self.master_repo_path, operation, commit_hash).await
     }
 
     /// Restore to a timestamp (finds nearest commit)
-    pub async fn restore_to_timestamp(&self, game_id: i64, target_time: DateTime<Utc>) -> Result<String, String> {
-        history::restore_to_timestamp(&self.master_repo_path, game_id, target_time).await
+    pub async fn restore_to_timestamp(&self, operation: &std::sync::Arc<GitOperation>, target_time: DateTime<Utc>) -> Result<String, String> {
+        history::restore_to_timestamp_with_operation(&self.master_repo_path, operation, target_time).await
+    }
+
+    /// Restore the commit whose saves were written closest to a timestamp
+    pub async fn restore_to_save_time(&self, operation: &std::sync::Arc<GitOperation>, target_time: DateTime<Utc>) -> Result<String, String> {
+        history::restore_to_save_time(&self.db, &self.master_repo_path, operation, operation.game_id(), target_time).await
+    }
+
+    /// Find the commit whose saves were written closest to a timestamp
+    pub async fn find_closest_save_time_commit(&self, game_id: i64, target_time: DateTime<Utc>) -> Result<Option<(String, DateTime<Utc>)>, String> {
+        history::find_closest_save_time_commit(&self.db, game_id, target_time).await
+    }
+
+    /// Copy the latest commit of a branch into the game's live save locations
+    pub async fn restore_branch_tip(&self, operation: &std::sync::Arc<GitOperation>, branch_name: &str) -> Result<BranchRestoreResult, String> {
+        snapshot::restore_branch_tip(&self.db, &self.master_repo_path, operation, operation.game_id(), branch_name).await
+    }
+
+    /// Stop the game's running checkout, restore or checkpoint between files. Returns
+    /// whether one was running.
+    pub fn cancel_operation(game_id: i64) -> bool {
+        GitOperation::cancel(game_id)
+    }
+
+    /// Get the hash and time of the commit a branch points at
+    pub async fn get_branch_tip(&self, branch_name: &str) -> Result<(String, DateTime<Utc>), String> {
+        snapshot::branch_tip(&self.master_repo_path, branch_name).await
+    }
+
+    /// Get the time a commit was made
+    pub async fn get_commit_time(&self, commit_hash: &str) -> Result<DateTime<Utc>, String> {
+        history::get_commit_time(&self.master_repo_path, commit_hash).await
+    }
+
+    /// Find the commit nearest to a timestamp
+    pub async fn find_closest_commit(&self, target_time: DateTime<Utc>) -> Result<(String, String, DateTime<Utc>), String> {
+        history::find_closest_commit(&self.master_repo_path, target_time).await
     }
 
     /// Get save history
@@ -80,17 +144,23 @@ impl GitSaveManager {
         history::get_save_history(&self.master_repo_path, game_id).await
     }
 
-    /// Sync to cloud
-    pub async fn sync_to_cloud(&self, _game_id: i64) -> Result<serde_json::Value, String> {
-        // This would implement cloud synchronization
-        // For now, return a mock result
-        Ok(serde_json::json!({
-            "status": "success",
-            "message": "Cloud sync feature ready for implementation",
-            "timestamp": Utc::now().to_rfc3339(),
-            "sync_type": "master_repository",
-            "branches_synced": "all"
-        }))
+    /// Get commits made within a date window, newest first
+    pub async fn get_history_range(
+        &self,
+        game_id: i64,
+        branch: Option<&str>,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> Result<Vec<GitSaveCommit>, String> {
+        history::get_history_range(&self.master_repo_path, game_id, branch, from, to, limit).await
+    }
+
+    /// Push the game's save branches to its configured cloud remote
+    pub async fn sync_to_cloud(&self, game_id: i64) -> Result<CloudSyncResult, String> {
+        CloudSyncManager::new(self.db.clone(), &self.master_repo_path)
+            .push_to_cloud(game_id)
+            .await
     }
 
     /// Get list of all branches
@@ -98,9 +168,29 @@ impl GitSaveManager {
         branching::list_all_branches(&self.master_repo_path).await
     }
 
+    /// Save branches of every game with their metadata
+    pub async fn list_save_slots(&self) -> Result<Vec<types::SaveSlot>, String> {
+        branching::list_save_slots(&self.db, &self.master_repo_path).await
+    }
+
     /// Get branches for a specific game
-    pub async fn get_game_branches(&self, game_name: &str) -> Result<Vec<String>, String> {
-        branching::get_game_branches(&self.master_repo_path, game_name).await
+    pub async fn get_game_branches(&self, game_id: i64) -> Result<Vec<String>, String> {
+        branching::get_game_branches(&self.master_repo_path, game_id).await
+    }
+
+    /// Push a game's branches into a bare repository at `dest`
+    pub async fn export_game_branches(&self, game_id: i64, dest: &std::path::Path) -> Result<Vec<String>, String> {
+        branching::export_game_branches(&self.master_repo_path, game_id, dest).await
+    }
+
+    /// Delete every branch of a game from the master repository
+    pub async fn remove_game_branches(&self, game_id: i64) -> Result<usize, String> {
+        branching::remove_game_branches(&self.master_repo_path, game_id).await
+    }
+
+    /// Fetch a game's branches back from a repository written by `export_game_branches`
+    pub async fn import_game_branches(&self, game_id: i64, src: &std::path::Path) -> Result<Vec<String>, String> {
+        branching::import_game_branches(&self.master_repo_path, game_id, src).await
     }
 
     /// Delete a save branch
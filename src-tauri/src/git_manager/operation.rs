@@ -0,0 +1,138 @@
+use crate::progress::{ProgressReporter, ProgressSink};
+use git2::build::CheckoutBuilder;
+use git2::Repository;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Error returned by an operation stopped through `GitOperation::cancel`
+pub const CANCELLED: &str = "Git operation cancelled";
+
+/// Game and cancel flag of each git operation currently running, by operation id
+static RUNNING: Lazy<Mutex<HashMap<u64, (i64, Arc<AtomicBool>)>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// A checkout, restore or commit on a game's saves that may take long on a big save tree.
+///
+/// The git2 work runs on the blocking pool, checks the cancel flag between files and
+/// reports progress to the frontend. The operation can be cancelled by game id until it
+/// is dropped.
+pub struct GitOperation {
+    id: u64,
+    game_id: i64,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl GitOperation {
+    /// Register an operation on the game's saves
+    pub fn start(game_id: i64) -> Arc<Self> {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let cancelled = Arc::new(AtomicBool::new(false));
+        RUNNING.lock().unwrap_or_else(|e| e.into_inner()).insert(id, (game_id, cancelled.clone()));
+        Arc::new(Self { id, game_id, cancelled })
+    }
+
+    /// Ask the game's running operations to stop. Returns whether any was running.
+    pub fn cancel(game_id: i64) -> bool {
+        let running = RUNNING.lock().unwrap_or_else(|e| e.into_inner());
+        let mut found = false;
+        for (_, cancelled) in running.values().filter(|(game, _)| *game == game_id) {
+            cancelled.store(true, Ordering::Relaxed);
+            found = true;
+        }
+        if found {
+            crate::logger::info("GIT_OPERATION", &format!("Cancelling git operation for game {}", game_id), None);
+        }
+        found
+    }
+
+    pub fn game_id(&self) -> i64 {
+        self.game_id
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn check_cancelled(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            Err(CANCELLED.to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Progress reporter for one step of the operation, e.g. `git_checkout`
+    pub fn progress(&self, operation: &str) -> ProgressReporter {
+        ProgressReporter::new(operation, Some(self.game_id), ProgressSink::frontend())
+    }
+
+    /// Run git work on the blocking pool so other commands stay responsive meanwhile
+    pub async fn run_blocking<T, F>(self: &Arc<Self>, f: F) -> Result<T, String>
+    where
+        T: Send + 'static,
+        F: FnOnce(&GitOperation) -> Result<T, String> + Send + 'static,
+    {
+        let operation = self.clone();
+        tokio::task::spawn_blocking(move || {
+            operation.check_cancelled()?;
+            f(&operation)
+        })
+        .await
+        .map_err(|e| format!("Git task failed: {}", e))?
+    }
+
+    /// Check out `object` with progress reporting, stopping between files when cancelled.
+    ///
+    /// A cancelled checkout leaves the working tree half written, so it is reset to HEAD,
+    /// which the checkout hasn't moved yet.
+    pub fn checkout_tree(&self, repo: &Repository, object: &git2::Object<'_>) -> Result<(), String> {
+        let progress = self.progress("git_checkout");
+        let result = {
+            let mut checkout = CheckoutBuilder::new();
+            checkout
+                .progress(|_, completed, total| progress.set_files(completed, total))
+                .notify_on(git2::CheckoutNotificationType::UPDATED)
+                .notify(|_, _, _, _, _| !self.is_cancelled());
+            repo.checkout_tree(object, Some(&mut checkout))
+        };
+        progress.finish();
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(_) if self.is_cancelled() => {
+                if let Err(e) = repo.checkout_head(Some(CheckoutBuilder::new().force())) {
+                    crate::logger::error("GIT_OPERATION", "Failed to reset working tree after cancelled checkout", Some(&e.to_string()));
+                }
+                Err(CANCELLED.to_string())
+            }
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+impl Drop for GitOperation {
+    fn drop(&mut self) {
+        RUNNING.lock().unwrap_or_else(|e| e.into_inner()).remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_stops_only_the_games_operations() {
+        let operation = GitOperation::start(-101);
+        let other = GitOperation::start(-102);
+
+        assert!(GitOperation::cancel(-101));
+        assert_eq!(operation.check_cancelled().unwrap_err(), CANCELLED);
+        assert!(other.check_cancelled().is_ok());
+
+        drop(operation);
+        assert!(!GitOperation::cancel(-101));
+        assert!(!GitOperation::start(-101).is_cancelled());
+    }
+}
@@ -0,0 +1,180 @@
+use crate::database::connection::Database;
+use chrono::Utc;
+use git2::{ObjectType, Oid, Repository};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Outcome of checking (and optionally rebuilding) the master repository
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RepoHealthReport {
+    pub healthy: bool,
+    /// One line per problem found, worded for the user rather than as raw git2 errors
+    pub problems: Vec<String>,
+    pub branches_checked: usize,
+    pub objects_checked: usize,
+    pub repaired: bool,
+    /// Where the damaged `.git` directory was moved to before reinitializing
+    pub preserved_git_dir: Option<String>,
+    pub branches_restored: usize,
+}
+
+/// Check the master repository the way `git fsck` would: the repository opens, HEAD
+/// resolves, and every commit, tree and blob reachable from a local branch is readable.
+pub fn check_repository(master_repo_path: &str) -> RepoHealthReport {
+    let mut report = RepoHealthReport {
+        healthy: false,
+        problems: Vec::new(),
+        branches_checked: 0,
+        objects_checked: 0,
+        repaired: false,
+        preserved_git_dir: None,
+        branches_restored: 0,
+    };
+
+    if !Path::new(master_repo_path).join(".git").exists() {
+        report.problems.push("The save repository has not been initialized".to_string());
+        return report;
+    }
+
+    let repo = match Repository::open(master_repo_path) {
+        Ok(repo) => repo,
+        Err(e) => {
+            report.problems.push(format!("The save repository cannot be opened: {}", e.message()));
+            return report;
+        }
+    };
+
+    if let Err(e) = repo.head().and_then(|head| head.peel_to_commit()) {
+        report.problems.push(format!("HEAD does not point at a readable commit: {}", e.message()));
+    }
+
+    let branches = match repo.branches(Some(git2::BranchType::Local)) {
+        Ok(branches) => branches.filter_map(|b| b.ok()).collect::<Vec<_>>(),
+        Err(e) => {
+            report.problems.push(format!("Branches cannot be listed: {}", e.message()));
+            Vec::new()
+        }
+    };
+
+    let mut seen = HashSet::new();
+    for (branch, _) in branches {
+        report.branches_checked += 1;
+        let name = branch.name().ok().flatten().unwrap_or("<invalid name>").to_string();
+        match branch.get().peel_to_commit() {
+            Ok(commit) => {
+                if let Err(problem) = check_history(&repo, commit.id(), &mut seen) {
+                    report.problems.push(format!("Branch '{}': {}", name, problem));
+                }
+            }
+            Err(e) => report.problems.push(format!("Branch '{}' does not point at a readable commit: {}", name, e.message())),
+        }
+    }
+
+    report.objects_checked = seen.len();
+    report.healthy = report.problems.is_empty();
+    report
+}
+
+/// Walk every commit reachable from `tip`, reading each commit's tree and blobs once
+fn check_history(repo: &Repository, tip: Oid, seen: &mut HashSet<Oid>) -> Result<(), String> {
+    let mut revwalk = repo.revwalk().map_err(|e| e.message().to_string())?;
+    revwalk.push(tip).map_err(|e| e.message().to_string())?;
+
+    for oid in revwalk {
+        let oid = oid.map_err(|e| format!("history is broken: {}", e.message()))?;
+        if !seen.insert(oid) {
+            continue;
+        }
+        let commit = repo.find_commit(oid)
+            .map_err(|e| format!("commit {} is unreadable: {}", oid, e.message()))?;
+        check_tree(repo, commit.tree_id(), seen)?;
+    }
+    Ok(())
+}
+
+fn check_tree(repo: &Repository, tree_id: Oid, seen: &mut HashSet<Oid>) -> Result<(), String> {
+    if !seen.insert(tree_id) {
+        return Ok(());
+    }
+    let tree = repo.find_tree(tree_id)
+        .map_err(|e| format!("tree {} is unreadable: {}", tree_id, e.message()))?;
+
+    for entry in tree.iter() {
+        match entry.kind() {
+            Some(ObjectType::Tree) => check_tree(repo, entry.id(), seen)?,
+            Some(ObjectType::Blob) => {
+                if seen.insert(entry.id()) {
+                    repo.find_blob(entry.id())
+                        .map_err(|e| format!("file '{}' ({}) is unreadable: {}", entry.name().unwrap_or("?"), entry.id(), e.message()))?;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Move the damaged `.git` aside, reinitialize the repository from the current working
+/// tree and recreate the branches recorded in `git_branches` on the new initial commit.
+pub async fn rebuild_repository(
+    db: &std::sync::Arc<tokio::sync::Mutex<Database>>,
+    master_repo_path: &str,
+    mut report: RepoHealthReport,
+) -> Result<RepoHealthReport, String> {
+    crate::logger::warn("GIT_REPAIR", "Rebuilding master repository", Some(&report.problems.join("; ")));
+
+    let git_dir = Path::new(master_repo_path).join(".git");
+    if git_dir.exists() {
+        // Kept outside the working tree so the new initial commit doesn't pick it up
+        let preserved = std::path::PathBuf::from(format!("{}-corrupt-git-{}", master_repo_path.trim_end_matches(['/', '\\']), Utc::now().format("%Y%m%d-%H%M%S")));
+        std::fs::rename(&git_dir, &preserved)
+            .map_err(|e| format!("Failed to move the damaged repository aside: {}", e))?;
+        report.preserved_git_dir = Some(preserved.to_string_lossy().to_string());
+    }
+
+    let (author, branches) = {
+        let db_guard = db.lock().await;
+        let conn = db_guard.get_connection().await;
+        let mut stmt = conn.prepare("SELECT branch_name, is_active FROM git_branches ORDER BY id")
+            .map_err(|e| format!("Failed to read branch metadata: {}", e))?;
+        let branches = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, bool>(1)?)))
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to read branch metadata: {}", e))?;
+        (super::repository::CommitAuthor::load(&conn), branches)
+    };
+
+    let commit_hash = {
+        let repo = Repository::init(master_repo_path)
+            .map_err(|e| format!("Failed to reinitialize the save repository: {}", e))?;
+        super::repository::make_initial_commit(&repo, &author, "Rebuild game saves repository")?;
+        let commit = repo.head()
+            .and_then(|head| head.peel_to_commit())
+            .map_err(|e| format!("Failed to read the new initial commit: {}", e))?;
+
+        for (branch_name, _) in &branches {
+            match repo.branch(branch_name, &commit, true) {
+                Ok(_) => report.branches_restored += 1,
+                Err(e) => crate::logger::warn("GIT_REPAIR", &format!("Could not recreate branch '{}'", branch_name), Some(&e.to_string())),
+            }
+        }
+
+        // Check out whichever branch was last active, if any
+        if let Some((active, _)) = branches.iter().rev().find(|(_, active)| *active) {
+            repo.set_head(&format!("refs/heads/{}", active))
+                .map_err(|e| format!("Failed to check out branch '{}': {}", active, e))?;
+        }
+        commit.id().to_string()
+    };
+
+    {
+        let db_guard = db.lock().await;
+        let conn = db_guard.get_connection().await;
+        conn.execute("UPDATE git_branches SET last_commit_hash = ?", [&commit_hash])
+            .map_err(|e| format!("Failed to update branch metadata: {}", e))?;
+    }
+
+    report.repaired = true;
+    report.healthy = true;
+    crate::logger::info("GIT_REPAIR", &format!("Rebuilt master repository with {} branches", report.branches_restored), None);
+    Ok(report)
+}
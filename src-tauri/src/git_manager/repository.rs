@@ -2,6 +2,90 @@ use crate::database::connection::Database;
 use git2::{Repository, Signature};
 use tokio::fs;
 use chrono::Utc;
+use crate::database::settings::AppSettings;
+
+/// `app_settings` keys for the author recorded on save commits
+pub const GIT_AUTHOR_NAME_SETTING: &str = "git_author_name";
+pub const GIT_AUTHOR_EMAIL_SETTING: &str = "git_author_email";
+
+const DEFAULT_AUTHOR_NAME: &str = "Save Steward";
+const DEFAULT_AUTHOR_EMAIL: &str = "save-steward@localhost";
+
+/// Commit author configured in Save Steward. Unset fields fall back to the user's git
+/// config and then to the built-in Save Steward identity.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct CommitAuthor {
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+impl CommitAuthor {
+    pub fn load(conn: &rusqlite::Connection) -> Self {
+        let get = |key| AppSettings::get(conn, key).ok().flatten().filter(|v| !v.is_empty());
+        Self {
+            name: get(GIT_AUTHOR_NAME_SETTING),
+            email: get(GIT_AUTHOR_EMAIL_SETTING),
+        }
+    }
+
+    /// Check that git can record the name and email
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(name) = &self.name {
+            if name.trim().is_empty() || name.contains(['<', '>', '\n']) {
+                return Err(format!("Invalid author name: {:?}", name));
+            }
+        }
+        if let Some(email) = &self.email {
+            validate_email(email)?;
+        }
+        Ok(())
+    }
+
+    /// Validate and store the author; `None` fields are cleared
+    pub fn save(&self, conn: &rusqlite::Connection) -> Result<(), String> {
+        self.validate()?;
+
+        for (key, value) in [(GIT_AUTHOR_NAME_SETTING, &self.name), (GIT_AUTHOR_EMAIL_SETTING, &self.email)] {
+            match value {
+                Some(value) => AppSettings::set(conn, key, value.trim()),
+                None => AppSettings::remove(conn, key),
+            }.map_err(|e| format!("Failed to save git author: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Signature for a new commit in `repo`
+    pub fn signature(&self, repo: &Repository) -> Result<Signature<'static>, String> {
+        let git_config = repo.signature().ok();
+        let name = self.name.clone()
+            .or_else(|| git_config.as_ref().and_then(|s| s.name().map(str::to_string)))
+            .unwrap_or_else(|| DEFAULT_AUTHOR_NAME.to_string());
+        let email = self.email.clone()
+            .or_else(|| git_config.as_ref().and_then(|s| s.email().map(str::to_string)))
+            .unwrap_or_else(|| DEFAULT_AUTHOR_EMAIL.to_string());
+
+        Signature::now(&name, &email)
+            .map_err(|e| format!("Failed to create commit signature: {}", e))
+    }
+}
+
+/// Basic `local@domain.tld` check; git itself accepts almost anything, but hosts only
+/// attribute commits to well-formed addresses
+pub fn validate_email(email: &str) -> Result<(), String> {
+    let invalid = || format!("Invalid email address: {}", email);
+    if email.chars().any(|c| c.is_whitespace() || c == '<' || c == '>') {
+        return Err(invalid());
+    }
+
+    let (local, domain) = email.split_once('@').ok_or_else(invalid)?;
+    let domain_ok = !domain.contains('@')
+        && domain.split('.').count() >= 2
+        && domain.split('.').all(|part| !part.is_empty());
+    if local.is_empty() || !domain_ok {
+        return Err(invalid());
+    }
+    Ok(())
+}
 
 /// Initialize master repository for all game saves
 pub async fn initialize_master_repo(
@@ -35,8 +119,14 @@ pub async fn initialize_master_repo(
     // Setup .gitattributes for Git LFS
     setup_gitattributes(master_repo_path).await?;
 
+    let author = {
+        let db_guard = db.lock().await;
+        let conn = db_guard.get_connection().await;
+        CommitAuthor::load(&conn)
+    };
+
     // Make initial commit
-    make_initial_commit(&repo, "Initial game saves repository")
+    make_initial_commit(&repo, &author, "Initial game saves repository")
         .map_err(|e| {
             crate::logger::error("GIT_REPOSITORY", "Failed to create initial commit", Some(&e));
             e
@@ -54,7 +144,7 @@ async fn setup_master_gitignore(repo_path: &str) -> Result<(), String> {
     let gitignore_content = r#"
 # Game Saves Repository
 # Auto-generated by Save Steward
-# Branch naming: gamename-save-name
+# Branch naming: g<game_id>-save-name
 
 # Game executable files
 *.exe
@@ -133,7 +223,7 @@ async fn setup_gitattributes(repo_path: &str) -> Result<(), String> {
     Ok(())
 }
 
-fn make_initial_commit(repo: &Repository, message: &str) -> Result<(), String> {
+pub(super) fn make_initial_commit(repo: &Repository, author: &CommitAuthor, message: &str) -> Result<(), String> {
     let mut index = repo.index()
         .map_err(|e| format!("Failed to get index: {}", e))?;
 
@@ -146,8 +236,7 @@ fn make_initial_commit(repo: &Repository, message: &str) -> Result<(), String> {
     let tree_id = index.write_tree()
         .map_err(|e| format!("Failed to write tree: {}", e))?;
 
-    let signature = repo.signature()
-        .unwrap_or_else(|_| Signature::now("Save Steward", "save-steward@localhost").unwrap());
+    let signature = author.signature(repo)?;
 
     let tree = repo.find_tree(tree_id)
         .map_err(|e| format!("Failed to find tree: {}", e))?;
@@ -0,0 +1,235 @@
+use crate::backup::archive::{is_safe_relative_path, SaveArchiveManifest, SourceFile, MANIFEST_ENTRY};
+use crate::backup::PortableSaves;
+use crate::database::connection::Database;
+use chrono::{DateTime, Utc};
+use git2::Repository;
+use super::operation::{GitOperation, CANCELLED};
+use std::path::{Path, PathBuf};
+
+/// Result of copying a branch's saves back into the game's save locations
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BranchRestoreResult {
+    pub branch_name: String,
+    pub commit_hash: String,
+    pub files_restored: usize,
+    pub safety_backup_path: Option<String>,
+}
+
+/// Directory in the master repo's working tree holding one game's save files.
+///
+/// Files are stored under the archive paths of a `manifest.json` written next to them,
+/// the same layout as an exported save archive.
+pub fn game_tree_dir(master_repo_path: &str, game_id: i64) -> PathBuf {
+    Path::new(master_repo_path).join(format!("g{}", game_id))
+}
+
+/// Replace a game's directory in the working tree with its current save files and a
+/// manifest describing them. Returns how many files were copied.
+pub fn write_game_tree(
+    master_repo_path: &str,
+    game_id: i64,
+    manifest: &SaveArchiveManifest,
+    sources: &[SourceFile],
+    operation: &GitOperation,
+) -> Result<usize, String> {
+    let tree_dir = game_tree_dir(master_repo_path, game_id);
+    if tree_dir.exists() {
+        std::fs::remove_dir_all(&tree_dir)
+            .map_err(|e| format!("Failed to clear {}: {}", tree_dir.display(), e))?;
+    }
+    std::fs::create_dir_all(&tree_dir)
+        .map_err(|e| format!("Failed to create {}: {}", tree_dir.display(), e))?;
+
+    let progress = operation.progress("git_snapshot");
+    progress.add_total(sources.len(), sources.iter().map(|s| s.entry.size).sum());
+
+    let mut manifest = manifest.clone();
+    let mut copied = 0;
+    for source in sources {
+        if operation.is_cancelled() {
+            discard_game_tree_changes(master_repo_path, game_id);
+            return Err(CANCELLED.to_string());
+        }
+        let target = tree_dir.join(&source.entry.archive_path);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        match std::fs::copy(&source.absolute_path, &target) {
+            Ok(bytes) => {
+                copied += 1;
+                progress.add_bytes(bytes);
+                progress.file_done();
+            }
+            Err(e) => {
+                // A file the game is holding open shouldn't sink the whole checkpoint
+                crate::logger::warn("GIT_SNAPSHOT", &format!("Skipping unreadable save file {}", source.absolute_path.display()), Some(&e.to_string()));
+                manifest.files.retain(|entry| entry.archive_path != source.entry.archive_path);
+            }
+        }
+    }
+
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize snapshot manifest: {}", e))?;
+    std::fs::write(tree_dir.join(MANIFEST_ENTRY), manifest_json)
+        .map_err(|e| format!("Failed to write snapshot manifest: {}", e))?;
+
+    progress.finish();
+    Ok(copied)
+}
+
+/// Put a game's directory back the way the checked-out commit has it, after a snapshot
+/// was stopped halfway
+fn discard_game_tree_changes(master_repo_path: &str, game_id: i64) {
+    let result = Repository::open(master_repo_path).and_then(|repo| {
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new()
+            .force()
+            .remove_untracked(true)
+            .path(format!("g{}", game_id))))
+    });
+    if let Err(e) = result {
+        crate::logger::warn("GIT_SNAPSHOT", &format!("Failed to reset the snapshot of game {}", game_id), Some(&e.to_string()));
+    }
+}
+
+/// Stage a game's directory and commit it on the checked-out branch. Returns the new
+/// commit's hash, or `None` when the files are unchanged since the branch tip. Staging
+/// stops between files when the operation is cancelled, before anything is committed.
+pub fn commit_game_tree(
+    master_repo_path: &str,
+    game_id: i64,
+    author: &super::repository::CommitAuthor,
+    message: &str,
+    operation: &GitOperation,
+) -> Result<Option<String>, String> {
+    let repo = Repository::open(master_repo_path)
+        .map_err(|e| format!("Failed to open master repository: {}", e))?;
+    let mut index = repo.index()
+        .map_err(|e| format!("Failed to get index: {}", e))?;
+
+    // Drop the previous snapshot so deleted saves don't linger, then add the new one.
+    // Forced because the master .gitignore lists common save extensions.
+    let tree_name = format!("g{}", game_id);
+    index.remove_dir(Path::new(&tree_name), 0)
+        .map_err(|e| format!("Failed to unstage previous snapshot: {}", e))?;
+    let progress = operation.progress("git_stage");
+    let mut stage_file = |_: &Path, _: &[u8]| {
+        if operation.is_cancelled() {
+            return -1;
+        }
+        progress.file_done();
+        0
+    };
+    let staged = index.add_all([format!("{}/*", tree_name)].iter(), git2::IndexAddOption::FORCE, Some(&mut stage_file as &mut git2::IndexMatchedPath));
+    progress.finish();
+    if operation.is_cancelled() {
+        discard_game_tree_changes(master_repo_path, game_id);
+        return Err(CANCELLED.to_string());
+    }
+    staged.map_err(|e| format!("Failed to stage save files: {}", e))?;
+    index.write()
+        .map_err(|e| format!("Failed to write index: {}", e))?;
+
+    let tree_id = index.write_tree()
+        .map_err(|e| format!("Failed to write tree: {}", e))?;
+    let parent = repo.head()
+        .and_then(|head| head.peel_to_commit())
+        .map_err(|e| format!("Failed to get current commit: {}", e))?;
+    if parent.tree_id() == tree_id {
+        return Ok(None);
+    }
+
+    let tree = repo.find_tree(tree_id)
+        .map_err(|e| format!("Failed to find tree: {}", e))?;
+    let signature = author.signature(&repo)?;
+    let commit_id = repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &[&parent])
+        .map_err(|e| format!("Failed to commit save files: {}", e))?;
+
+    Ok(Some(commit_id.to_string()))
+}
+
+/// Hash and time of the commit a branch points at
+pub async fn branch_tip(master_repo_path: &str, branch_name: &str) -> Result<(String, DateTime<Utc>), String> {
+    let repo = Repository::open(master_repo_path)
+        .map_err(|e| {
+            crate::logger::error("GIT_SNAPSHOT", "Failed to open master repository", Some(&e.to_string()));
+            format!("Failed to open master repository: {}", e)
+        })?;
+
+    let commit = repo.find_branch(branch_name, git2::BranchType::Local)
+        .map_err(|e| format!("Failed to find branch '{}': {}", branch_name, e))?
+        .get()
+        .peel_to_commit()
+        .map_err(|e| format!("Failed to get branch commit: {}", e))?;
+
+    let time = DateTime::from_timestamp(commit.time().seconds(), 0)
+        .ok_or_else(|| "Invalid timestamp".to_string())?;
+    Ok((commit.id().to_string(), time))
+}
+
+/// Check out the tip of a game's branch and copy its files into the live save locations.
+///
+/// The saves currently on disk are archived first so the restore can be undone.
+pub async fn restore_branch_tip(
+    db: &std::sync::Arc<tokio::sync::Mutex<Database>>,
+    master_repo_path: &str,
+    operation: &std::sync::Arc<GitOperation>,
+    game_id: i64,
+    branch_name: &str,
+) -> Result<BranchRestoreResult, String> {
+    crate::logger::info("GIT_SNAPSHOT", &format!("Restoring tip of '{}' for game_id: {}", branch_name, game_id), None);
+
+    if super::branching::game_id_from_branch(branch_name) != Some(game_id) {
+        return Err(format!("Branch '{}' does not belong to game {}", branch_name, game_id));
+    }
+
+    let (commit_hash, _) = branch_tip(master_repo_path, branch_name).await?;
+    super::branching::switch_save_branch_with_operation(db, master_repo_path, operation, branch_name).await?;
+
+    let tree_dir = game_tree_dir(master_repo_path, game_id);
+    let manifest_path = tree_dir.join(MANIFEST_ENTRY);
+    let manifest: SaveArchiveManifest = std::fs::read(&manifest_path)
+        .map_err(|_| format!("Branch '{}' has no saved files for this game", branch_name))
+        .and_then(|data| serde_json::from_slice(&data).map_err(|e| format!("Invalid snapshot manifest: {}", e)))?;
+    manifest.validate()?;
+
+    let (game, locations) = PortableSaves::load_game_and_locations(db, game_id).await?;
+    let targets = PortableSaves::restore_targets(&game.name, &locations, &manifest)?;
+    let safety_backup_path = PortableSaves::create_safety_backup(db, &game, &locations).await?;
+
+    let files_restored = operation.run_blocking(move |operation| {
+        let progress = operation.progress("git_restore");
+        progress.add_total(manifest.files.len(), manifest.files.iter().map(|f| f.size).sum());
+
+        let mut restored = 0;
+        for entry in &manifest.files {
+            // Files already copied stay; the safety backup holds what they replaced
+            operation.check_cancelled()?;
+            let Some(target) = targets.get(&entry.archive_path) else { continue };
+            if !is_safe_relative_path(&entry.archive_path) {
+                continue;
+            }
+
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            let bytes = std::fs::copy(tree_dir.join(&entry.archive_path), target)
+                .map_err(|e| format!("Failed to restore {}: {}", entry.relative_path, e))?;
+            restored += 1;
+            progress.add_bytes(bytes);
+            progress.file_done();
+        }
+        progress.finish();
+        Ok(restored)
+    }).await?;
+
+    crate::logger::info("GIT_SNAPSHOT", &format!("Restored {} files from '{}' ({})", files_restored, branch_name, commit_hash), None);
+
+    Ok(BranchRestoreResult {
+        branch_name: branch_name.to_string(),
+        commit_hash,
+        files_restored,
+        safety_backup_path,
+    })
+}
@@ -31,6 +31,18 @@ pub struct GitBranchInfo {
     pub protected: bool,
 }
 
+/// A save branch with its game, for the cross-game overview
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveSlot {
+    pub game_id: i64,
+    /// `None` when the owning game has been deleted
+    pub game_name: Option<String>,
+    pub branch_name: String,
+    pub description: Option<String>,
+    pub last_commit_time: Option<DateTime<Utc>>,
+    pub is_active: bool,
+}
+
 /// Result of cloud synchronization
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CloudSyncResult {
@@ -42,6 +54,14 @@ pub struct CloudSyncResult {
     pub timestamp: DateTime<Utc>,
 }
 
+/// Outcome of checking a provider token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialCheck {
+    pub valid: bool,
+    pub username: Option<String>,
+    pub message: String,
+}
+
 /// Cloud storage providers
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CloudProvider {
@@ -51,6 +71,49 @@ pub enum CloudProvider {
     SelfHosted,
 }
 
+impl CloudProvider {
+    /// Name stored in the database (`git_repositories.cloud_provider`, `cloud_sync_log`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CloudProvider::GitHub => "github",
+            CloudProvider::GitLab => "gitlab",
+            CloudProvider::Gitea => "gitea",
+            CloudProvider::SelfHosted => "selfhosted",
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CloudProvider::GitHub => "GitHub",
+            CloudProvider::GitLab => "GitLab",
+            CloudProvider::Gitea => "Gitea",
+            CloudProvider::SelfHosted => "self-hosted remote",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "github" => Some(CloudProvider::GitHub),
+            "gitlab" => Some(CloudProvider::GitLab),
+            "gitea" => Some(CloudProvider::Gitea),
+            "selfhosted" | "self-hosted" | "self_hosted" => Some(CloudProvider::SelfHosted),
+            _ => None,
+        }
+    }
+
+    /// Guess the provider from a remote URL when none is configured
+    pub fn from_remote_url(url: &str) -> Self {
+        let url = url.to_lowercase();
+        if url.contains("github.com") {
+            CloudProvider::GitHub
+        } else if url.contains("gitlab.com") {
+            CloudProvider::GitLab
+        } else {
+            CloudProvider::SelfHosted
+        }
+    }
+}
+
 /// Cloud synchronization status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CloudSyncStatus {
@@ -64,6 +127,7 @@ pub struct CloudSyncStatus {
 
 /// Synchronization status
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SyncStatus {
     Pending,
     Syncing,
@@ -72,6 +136,18 @@ pub enum SyncStatus {
     NotConfigured,
 }
 
+impl SyncStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncStatus::Pending => "pending",
+            SyncStatus::Syncing => "syncing",
+            SyncStatus::Success => "success",
+            SyncStatus::Failed => "failed",
+            SyncStatus::NotConfigured => "not_configured",
+        }
+    }
+}
+
 /// Metadata for Git-saved files
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitSaveMetadata {
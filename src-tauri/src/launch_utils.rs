@@ -1,7 +1,175 @@
 // Utility functions for launching games with better support for Unity and other games
 
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::process::Command;
+use std::time::{Duration, Instant};
+use sysinfo::{ProcessesToUpdate, System};
+
+/// How long to watch for the real game process after launching
+const HANDOFF_WINDOW: Duration = Duration::from_secs(10);
+const HANDOFF_POLL: Duration = Duration::from_millis(500);
+/// Processes that only start the game: a shell script or a store client hands off to the
+/// real game process, so its PID is not the one to track
+const WRAPPER_PROCESSES: [&str; 8] = ["sh", "bash", "dash", "zsh", "cmd.exe", "steam", "steam.exe", "epicgameslauncher.exe"];
+
+/// What `launch_game` started
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchResult {
+    /// The game process. `None` when the game was handed to a store client and its process
+    /// did not show up within the hand-off window.
+    pub pid: Option<u32>,
+    /// Executable (or launcher URL) that was started
+    pub executable: String,
+    pub started_at: DateTime<Utc>,
+    /// Started through a store client's URL protocol
+    pub via_launcher: bool,
+}
+
+/// A running process as seen while looking for the game
+#[derive(Debug, Clone)]
+struct ProcessSnapshot {
+    pid: u32,
+    parent: Option<u32>,
+    name: String,
+    exe: Option<std::path::PathBuf>,
+    start_time: u64,
+}
+
+impl ProcessSnapshot {
+    fn is_wrapper(&self) -> bool {
+        WRAPPER_PROCESSES.contains(&self.name.to_lowercase().as_str())
+    }
+
+    fn is_under(&self, dir: &Path) -> bool {
+        self.exe.as_deref().is_some_and(|exe| exe.starts_with(dir))
+    }
+}
+
+/// Store launcher URL for games that have to be started through their launcher (for DRM),
+/// or `None` when the game should be launched from its executable
+pub fn launcher_url(platform: &str, app_id: Option<&str>) -> Option<String> {
+    let app_id = app_id.map(str::trim).filter(|id| !id.is_empty())?;
+    match platform.to_lowercase().as_str() {
+        "steam" if app_id.chars().all(|c| c.is_ascii_digit()) => Some(format!("steam://rungameid/{}", app_id)),
+        "epic" => Some(format!("com.epicgames.launcher://apps/{}?action=launch&silent=true", url::form_urlencoded::byte_serialize(app_id.as_bytes()).collect::<String>())),
+        _ => None,
+    }
+}
+
+/// Hand a launcher URL to the OS so the store client starts the game. With an install
+/// directory, the game process is looked for there once the client has started it.
+pub async fn launch_via_url(url: &str, install_dir: Option<&str>) -> Result<LaunchResult, String> {
+    let started_at = Utc::now();
+    tauri_plugin_opener::open_url(url, None::<&str>)
+        .map_err(|e| format!("Failed to open {}: {}", url, e))?;
+
+    let pid = match install_dir.filter(|dir| !dir.is_empty() && Path::new(dir).is_dir()) {
+        Some(dir) => {
+            let dir = dir.to_string();
+            tokio::task::spawn_blocking(move || find_game_process(None, Path::new(&dir), started_at))
+                .await
+                .unwrap_or(None)
+        }
+        None => None,
+    };
+
+    Ok(LaunchResult {
+        pid,
+        executable: url.to_string(),
+        started_at,
+        via_launcher: true,
+    })
+}
+
+/// Spawn `command` and follow any hand-off to the real game process
+pub async fn spawn_and_track(mut command: Command, executable: &str, install_dir: &str) -> Result<LaunchResult, std::io::Error> {
+    let started_at = Utc::now();
+    let child = command.spawn()?;
+    let spawned = child.id();
+
+    let dir = Path::new(install_dir).canonicalize().unwrap_or_else(|_| Path::new(install_dir).to_path_buf());
+    let pid = tokio::task::spawn_blocking(move || find_game_process(Some(spawned), &dir, started_at))
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or(spawned);
+
+    Ok(LaunchResult {
+        pid: Some(pid),
+        executable: executable.to_string(),
+        started_at,
+        via_launcher: false,
+    })
+}
+
+/// Poll the process list for up to `HANDOFF_WINDOW` for the game process: the spawned
+/// process itself once it is clearly the game, otherwise a process it started or one
+/// running from `install_dir`.
+fn find_game_process(spawned: Option<u32>, install_dir: &Path, started_at: DateTime<Utc>) -> Option<u32> {
+    let since = started_at.timestamp().max(1) as u64 - 1;
+    let deadline = Instant::now() + HANDOFF_WINDOW;
+    let mut system = System::new();
+    let mut best = None;
+
+    loop {
+        system.refresh_processes(ProcessesToUpdate::All, true);
+        let processes: Vec<ProcessSnapshot> = system.processes().iter()
+            .map(|(pid, process)| ProcessSnapshot {
+                pid: pid.as_u32(),
+                parent: process.parent().map(|p| p.as_u32()),
+                name: process.name().to_string_lossy().to_string(),
+                exe: process.exe().map(Path::to_path_buf),
+                start_time: process.start_time(),
+            })
+            .collect();
+
+        let (pid, settled) = pick_game_process(&processes, spawned, install_dir, since);
+        best = pid.or(best);
+        if settled || Instant::now() >= deadline {
+            return best;
+        }
+        std::thread::sleep(HANDOFF_POLL);
+    }
+}
+
+/// Choose the game process from a snapshot. The flag is true once the choice can't
+/// improve by waiting: the spawned process is running and isn't a wrapper, or a process
+/// from the install directory has taken over.
+fn pick_game_process(processes: &[ProcessSnapshot], spawned: Option<u32>, install_dir: &Path, since: u64) -> (Option<u32>, bool) {
+    if let Some(process) = spawned.and_then(|pid| processes.iter().find(|p| p.pid == pid)) {
+        if !process.is_wrapper() {
+            return (Some(process.pid), true);
+        }
+    }
+
+    let is_descendant = |process: &ProcessSnapshot| {
+        let Some(root) = spawned else { return false };
+        let mut parent = process.parent;
+        for _ in 0..16 {
+            match parent {
+                Some(pid) if pid == root => return true,
+                Some(pid) => parent = processes.iter().find(|p| p.pid == pid).and_then(|p| p.parent),
+                None => return false,
+            }
+        }
+        false
+    };
+
+    let mut candidates: Vec<&ProcessSnapshot> = processes.iter()
+        .filter(|p| !p.is_wrapper() && p.start_time >= since)
+        .filter(|p| is_descendant(p) || p.is_under(install_dir))
+        .collect();
+    // Prefer processes running from the install directory, then the newest
+    candidates.sort_by_key(|p| (!p.is_under(install_dir), std::cmp::Reverse(p.start_time)));
+
+    // A game process in the install directory is as good as it gets
+    match candidates.first() {
+        Some(p) => (Some(p.pid), p.is_under(install_dir)),
+        None => (None, false),
+    }
+}
 
 /// Find the best executable to launch for a given game directory
 pub fn find_game_launcher(install_dir: &str, executable_path: &str) -> Result<String, String> {
@@ -65,53 +233,94 @@ pub fn make_executable(path: &str) -> Result<(), String> {
 }
 
 /// Enhanced game launcher with Unity and Linux support
-pub async fn launch_game_enhanced(install_dir: &str, executable_path: &str) -> Result<String, String> {
+pub async fn launch_game_enhanced(install_dir: &str, executable_path: &str) -> Result<LaunchResult, String> {
     // Find the best launcher to use
     let launcher_path = find_game_launcher(install_dir, executable_path)?;
     
-    #[cfg(target_os = "windows")]
-    {
+    if cfg!(target_os = "windows") {
         // Windows: Direct execution
-        Command::new(&launcher_path)
-            .current_dir(install_dir)
-            .spawn()
-            .map_err(|e| format!("Failed to launch game on Windows: {}", e))?;
+        let mut command = Command::new(&launcher_path);
+        command.current_dir(install_dir);
+        return spawn_and_track(command, &launcher_path, install_dir).await
+            .map_err(|e| format!("Failed to launch game on Windows: {}", e));
     }
-    
-    #[cfg(not(target_os = "windows"))]
-    {
-        // Linux/Unix: Enhanced handling
-        
-        // First, try to make sure the launcher is executable
-        let _ = make_executable(&launcher_path);
-        
-        // Try different launch methods in order of preference
-        let launch_result = Command::new(&launcher_path)
-            .current_dir(install_dir)
-            .spawn();
-            
-        match launch_result {
-            Ok(_) => {
-                // Success!
-            }
-            Err(e) => {
-                // Try alternative methods if direct launch fails
-                
-                // Method 1: Try with sh -c
-                let shell_result = Command::new("sh")
-                    .arg("-c")
-                    .arg(format!("cd '{}' && ./{}", install_dir, Path::new(&launcher_path).file_name().unwrap_or_default().to_string_lossy()))
-                    .spawn();
-                    
-                if let Err(e2) = shell_result {
-                    return Err(format!(
-                        "Failed to launch game '{}'. Tried methods: 1) Direct: {}, 2) Shell: {}, 3) Shell with cd. Error: {}. Make sure the game is installed correctly and has executable permissions.",
-                        launcher_path, e, e2, e2
-                    ));
-                }
-            }
+
+    // Linux/Unix: Enhanced handling
+
+    // First, try to make sure the launcher is executable
+    let _ = make_executable(&launcher_path);
+
+    // Try different launch methods in order of preference
+    let mut command = Command::new(&launcher_path);
+    command.current_dir(install_dir);
+    let launch_result = spawn_and_track(command, &launcher_path, install_dir).await;
+
+    match launch_result {
+        Ok(result) => Ok(result),
+        Err(e) => {
+            // Try alternative methods if direct launch fails
+
+            // Method 1: Try with sh -c
+            let mut command = Command::new("sh");
+            command.arg("-c")
+                .arg(format!("cd '{}' && ./{}", install_dir, Path::new(&launcher_path).file_name().unwrap_or_default().to_string_lossy()));
+            spawn_and_track(command, &launcher_path, install_dir).await
+                .map_err(|e2| format!(
+                    "Failed to launch game '{}'. Tried methods: 1) Direct: {}, 2) Shell: {}, 3) Shell with cd. Error: {}. Make sure the game is installed correctly and has executable permissions.",
+                    launcher_path, e, e2, e2
+                ))
         }
     }
-    
-    Ok(format!("Launched game from: {} -> {}", install_dir, launcher_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launcher_url_by_platform() {
+        assert_eq!(launcher_url("steam", Some("620")).as_deref(), Some("steam://rungameid/620"));
+        assert_eq!(launcher_url("Epic", Some("Fortnite")).as_deref(), Some("com.epicgames.launcher://apps/Fortnite?action=launch&silent=true"));
+        assert_eq!(launcher_url("steam", Some("not-an-id")), None);
+        assert_eq!(launcher_url("steam", Some("  ")), None);
+        assert_eq!(launcher_url("gog", Some("1207658924")), None);
+        assert_eq!(launcher_url("steam", None), None);
+    }
+
+    fn process(pid: u32, parent: Option<u32>, name: &str, exe: &str, start_time: u64) -> ProcessSnapshot {
+        ProcessSnapshot {
+            pid,
+            parent,
+            name: name.to_string(),
+            exe: Some(std::path::PathBuf::from(exe)),
+            start_time,
+        }
+    }
+
+    #[test]
+    fn test_pick_game_process_follows_handoff() {
+        let dir = Path::new("/games/radio");
+
+        // A running game binary is tracked directly
+        let direct = [process(10, Some(1), "radio", "/games/radio/radio", 100)];
+        assert_eq!(pick_game_process(&direct, Some(10), dir, 100), (Some(10), true));
+
+        // A launcher script hands off to its child
+        let script = [
+            process(10, Some(1), "sh", "/usr/bin/sh", 100),
+            process(11, Some(10), "radio.x86_64", "/games/radio/radio.x86_64", 101),
+        ];
+        assert_eq!(pick_game_process(&script, Some(10), dir, 100), (Some(11), true));
+
+        // A reparented game is found by its install directory, older processes are ignored
+        let reparented = [
+            process(20, Some(1), "radio.x86_64", "/games/radio/radio.x86_64", 102),
+            process(5, Some(1), "old", "/games/radio/old", 50),
+        ];
+        assert_eq!(pick_game_process(&reparented, Some(10), dir, 100), (Some(20), true));
+        assert_eq!(pick_game_process(&reparented, None, dir, 100), (Some(20), true));
+
+        // Nothing yet: keep waiting
+        assert_eq!(pick_game_process(&script[..1], Some(10), dir, 100), (None, false));
+    }
 }
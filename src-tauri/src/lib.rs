@@ -2,14 +2,16 @@ mod database;
 mod manifest;
 mod detection;
 mod auto_backup;
+mod backup;
 mod game_manager;
 mod launch_utils;
 mod git_manager;
 mod pcgaming_wiki;
 mod commands;
 mod logger;
-
-use crate::database::connection::{Database, DatabasePaths};
+mod progress;
+mod diagnostics;
+mod shutdown;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -22,51 +24,127 @@ pub fn run() {
     // Initialize database at startup - app waits for this to complete
     crate::logger::info("APP", "Initializing database", None);
     let runtime = tokio::runtime::Runtime::new().expect("Failed to create Tokio runtime");
-    runtime.block_on(async {
-        let db_path = DatabasePaths::database_file();
-        
-        crate::logger::database::connection_attempt(&db_path);
-        let db = Database::new(&db_path)
-            .await
-            .expect("Failed to connect to database - cannot start application");
-
-        crate::logger::database::connection_success(&db_path);
-        crate::logger::database::schema_creation_start();
-        
-        db.initialize_database()
-            .await
-            .expect("Failed to initialize database schema - cannot start application");
-
-        crate::logger::database::schema_creation_success();
-        crate::logger::info("APP", "Database initialization complete", None);
-    });
+    match runtime.block_on(crate::database::connection::initialize_database_at_startup()) {
+        Ok(()) => {
+            crate::logger::info("APP", "Database initialization complete", None);
+            runtime.block_on(crate::manifest::steam_users::restore_active_steam_user());
+        }
+        // Keep starting up: the frontend shows the error and offers a retry
+        Err(e) => crate::logger::error("APP", "Database initialization failed, starting in degraded mode", Some(&e)),
+    }
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
+        .setup(|app| {
+            crate::progress::set_app_handle(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             commands::system::greet,
             commands::system::identify_game_by_pid,
             commands::system::scan_running_games,
+            commands::system::get_last_scan_results,
+            commands::system::get_detection_history,
+            commands::system::set_detection_history_enabled,
+            commands::system::list_ignored_processes,
+            commands::system::add_ignored_process,
+            commands::system::remove_ignored_process,
+            commands::system::list_steam_users,
+            commands::system::set_active_steam_user,
+            commands::system::get_identification_candidates,
+            commands::system::track_process_as_game,
+            commands::system::get_min_executable_size,
+            commands::system::set_min_executable_size,
+            commands::system::get_follow_symlinks,
+            commands::system::set_follow_symlinks,
+            commands::system::clear_detection_cache,
+            commands::system::clear_all_caches,
+            commands::system::warm_detection_cache,
+            commands::system::clear_caches,
+            commands::system::is_game_running,
             commands::system::launch_game,
+            commands::system::get_database_status,
+            commands::system::retry_database_initialization,
+            commands::system::export_diagnostics,
+            commands::system::backup_database,
+            commands::system::restore_database,
             commands::game::add_manual_game,
             commands::game::add_manual_game_sync,
+            commands::game::validate_game_request,
             commands::game::get_all_games,
+            commands::game::set_favorite,
+            commands::game::games_without_save_locations,
+            commands::game::audit_save_locations,
             commands::game::update_game_sync,
             commands::game::delete_game_sync,
+            commands::game::archive_game,
+            commands::game::unarchive_game,
+            commands::game::get_archived_games,
+            commands::game::reset_identifiers,
+            commands::game::get_game_detail,
+            commands::game::get_save_locations_detailed,
+            commands::game::set_save_location_priority,
+            commands::game::refresh_pcgw_locations,
+            commands::game::enrich_metadata,
+            commands::game::set_real_time_backup,
+            commands::game::set_backup_enabled,
+            commands::game::set_backup_timing,
+            commands::game::set_game_retention,
+            commands::game::set_naming_template,
+            commands::game::get_naming_template,
+            commands::game::set_platform_executables,
+            commands::game::refresh_icon,
+            commands::game::rescan_saves,
             commands::game::search_pcgw_games,
             commands::game::get_pcgw_save_locations,
+            commands::game::suggest_save_locations,
+            commands::game::list_available_placeholders,
             commands::game::detect_game_executable,
             commands::git::enable_git_for_game,
             commands::git::create_save_checkpoint,
             commands::git::create_save_branch,
             commands::git::switch_save_branch,
             commands::git::restore_to_commit,
+            commands::git::restore_branch_tip,
             commands::git::restore_to_timestamp,
             commands::git::get_git_history,
+            commands::git::get_git_history_range,
+            commands::git::list_all_save_slots,
             commands::git::sync_to_cloud,
+            commands::git::test_cloud_credentials,
+            commands::git::repair_master_repo,
+            commands::git::get_git_author,
+            commands::git::set_git_author,
+            commands::git::cancel_git_operation,
+            commands::backup::export_save,
+            commands::backup::import_save,
+            commands::backup::get_backup_root,
+            commands::backup::set_backup_root,
+            commands::backup::move_backups,
+            commands::backup::get_active_sessions,
+            commands::backup::get_monitored_paths,
+            commands::backup::benchmark_compression,
+            commands::backup::set_archive_format,
+            commands::backup::get_archive_format,
+            commands::backup::compare_backups,
+            commands::backup::archive_to,
+            commands::backup::unarchive_from,
+            commands::backup::detect_location_drift,
+            commands::backup::compression_report,
+            commands::backup::list_backup_contents,
+            commands::backup::restore_file,
+            commands::backup::force_backup_now,
+            commands::backup::next_backup_time,
+            commands::backup::backup_all_enabled,
+            commands::backup::get_backup_timeline,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                tauri::async_runtime::block_on(crate::shutdown::run());
+            }
+        });
 }
@@ -8,7 +8,7 @@
 use std::fs::{File, OpenOptions};
 use std::io::{Write, BufWriter};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::SystemTime;
 use once_cell::sync::Lazy;
 
@@ -21,6 +21,8 @@ pub struct LogConfig {
     pub log_file_path: PathBuf,
     pub max_file_size_bytes: u64,
     pub max_log_files: usize,
+    /// Hard cap on the combined size of the active and rotated log files
+    pub max_total_log_bytes: u64,
     pub enable_console_output: bool,
 }
 
@@ -33,6 +35,7 @@ impl Default for LogConfig {
             log_file_path: app_data_dir.join("save-steward.log"),
             max_file_size_bytes: 10 * 1024 * 1024, // 10MB
             max_log_files: 5,
+            max_total_log_bytes: 50 * 1024 * 1024, // 50MB
             enable_console_output: true,
         }
     }
@@ -41,6 +44,7 @@ impl Default for LogConfig {
 struct Logger {
     config: LogConfig,
     current_file: Option<BufWriter<File>>,
+    current_size: u64,
 }
 
 impl Logger {
@@ -49,6 +53,7 @@ impl Logger {
         Self {
             config,
             current_file: None,
+            current_size: 0,
         }
     }
     
@@ -56,6 +61,7 @@ impl Logger {
         Self {
             config,
             current_file: None,
+            current_size: 0,
         }
     }
     
@@ -74,33 +80,86 @@ impl Logger {
         }
     }
     
-    fn write_to_file(&mut self, log_entry: &str) -> std::io::Result<()> {
-        // Check if we need to rotate the log file
-        if let Ok(metadata) = std::fs::metadata(&self.config.log_file_path) {
-            if metadata.len() > self.config.max_file_size_bytes {
-                self.rotate_log_files()?;
-            }
+    /// Replace the configuration, closing the current handle so the next entry
+    /// goes to the (possibly new) configured path
+    fn set_config(&mut self, config: LogConfig) {
+        self.close_file();
+        self.config = config;
+    }
+    
+    fn flush(&mut self) {
+        if let Some(file) = self.current_file.as_mut() {
+            let _ = file.flush();
         }
-        
-        // Open or create the log file
+    }
+
+    /// The active log followed by the rotated ones that exist, newest first
+    fn log_files(&mut self) -> Vec<PathBuf> {
+        self.flush();
+
+        let log_path = &self.config.log_file_path;
+        std::iter::once(log_path.clone())
+            .chain((1..self.config.max_log_files).map(|i| log_path.with_extension(format!("log.{}", i))))
+            .filter(|path| path.exists())
+            .collect()
+    }
+
+    fn close_file(&mut self) {
+        if let Some(mut writer) = self.current_file.take() {
+            let _ = writer.flush();
+        }
+        self.current_size = 0;
+    }
+    
+    fn open_log_file(&mut self) -> std::io::Result<()> {
         let file = OpenOptions::new()
             .create(true)
             .append(true)
             .open(&self.config.log_file_path)?;
-            
-        let mut writer = BufWriter::new(file);
+        
+        self.current_size = file.metadata().map(|m| m.len()).unwrap_or(0);
+        self.current_file = Some(BufWriter::new(file));
+        Ok(())
+    }
+    
+    fn write_to_file(&mut self, log_entry: &str) -> std::io::Result<()> {
+        if self.current_file.is_none() {
+            self.open_log_file()?;
+        }
+        
+        // Check if we need to rotate the log file
+        if self.current_size > self.config.max_file_size_bytes {
+            self.rotate_log_files()?;
+        }
+        
+        let writer = self.current_file.as_mut()
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "Log file not open"))?;
         writer.write_all(log_entry.as_bytes())?;
         writer.write_all(b"\n")?;
+        // Flush every entry so a crash never leaves a partial line behind
         writer.flush()?;
+        self.current_size += log_entry.len() as u64 + 1;
         
         Ok(())
     }
     
+    /// Rotate `save-steward.log` -> `.log.1` -> `.log.2` ... The replacement file is
+    /// created before anything is renamed, so an interrupted rotation never loses
+    /// the active log or leaves the logger without a file to write to.
     fn rotate_log_files(&mut self) -> std::io::Result<()> {
+        let log_path = self.config.log_file_path.clone();
+        let pending_path = log_path.with_extension("log.new");
+        
+        // Flush and release the current handle (required for renames on Windows)
+        self.close_file();
+        
+        // Prepare the new, empty log file up front
+        File::create(&pending_path)?.sync_all()?;
+        
         // Remove oldest log file if we have too many
         for i in (1..self.config.max_log_files).rev() {
-            let old_path = self.config.log_file_path.with_extension(format!("log.{}", i));
-            let new_path = self.config.log_file_path.with_extension(format!("log.{}", i + 1));
+            let old_path = log_path.with_extension(format!("log.{}", i));
+            let new_path = log_path.with_extension(format!("log.{}", i + 1));
             
             if old_path.exists() {
                 if i + 1 == self.config.max_log_files {
@@ -111,10 +170,45 @@ impl Logger {
             }
         }
         
-        // Rename current log file
-        if self.config.log_file_path.exists() {
-            let backup_path = self.config.log_file_path.with_extension("log.1");
-            std::fs::rename(&self.config.log_file_path, backup_path)?;
+        // Rename current log file, then move the prepared file into place
+        if log_path.exists() {
+            std::fs::rename(&log_path, log_path.with_extension("log.1"))?;
+        }
+        std::fs::rename(&pending_path, &log_path)?;
+        
+        self.enforce_total_size_cap()?;
+        self.open_log_file()
+    }
+    
+    /// Delete the oldest rotated log files until all logs fit in `max_total_log_bytes`
+    fn enforce_total_size_cap(&self) -> std::io::Result<()> {
+        let log_path = &self.config.log_file_path;
+        let (Some(dir), Some(file_name)) = (log_path.parent(), log_path.file_name()) else {
+            return Ok(());
+        };
+        let rotated_prefix = format!("{}.", file_name.to_string_lossy());
+        
+        // Rotated files as (rotation index, path, size); a higher index is older
+        let mut rotated: Vec<(usize, PathBuf, u64)> = std::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let index = name.strip_prefix(&rotated_prefix)?.parse::<usize>().ok()?;
+                let size = entry.metadata().ok()?.len();
+                Some((index, entry.path(), size))
+            })
+            .collect();
+        rotated.sort_by(|a, b| b.0.cmp(&a.0));
+        
+        let active_size = std::fs::metadata(log_path).map(|m| m.len()).unwrap_or(0);
+        let mut total: u64 = active_size + rotated.iter().map(|(_, _, size)| size).sum::<u64>();
+        
+        for (_, path, size) in rotated {
+            if total <= self.config.max_total_log_bytes {
+                break;
+            }
+            std::fs::remove_file(&path)?;
+            total = total.saturating_sub(size);
         }
         
         Ok(())
@@ -157,6 +251,17 @@ impl LogLevel {
     }
 }
 
+/// Lock the global logger, recovering it if a panic elsewhere poisoned the mutex.
+///
+/// A panic while logging at worst leaves a partly written line behind; dropping every
+/// later message instead would lose the logs right when something has gone wrong.
+fn lock_logger() -> MutexGuard<'static, Logger> {
+    LOGGER.lock().unwrap_or_else(|poisoned| {
+        LOGGER.clear_poison();
+        poisoned.into_inner()
+    })
+}
+
 /// Initialize the logging system with default configuration
 pub fn initialize_logging() -> Result<(), Box<dyn std::error::Error>> {
     initialize_logging_with_config(LogConfig::default())
@@ -169,8 +274,8 @@ pub fn initialize_logging_with_config(config: LogConfig) -> Result<(), Box<dyn s
         std::fs::create_dir_all(parent)?;
     }
     
-    let mut logger = LOGGER.lock().unwrap();
-    logger.config = config;
+    let mut logger = lock_logger();
+    logger.set_config(config);
     
     // Log initialization
     logger.log(LogLevel::Info, "LOGGER", "Logging system initialized", None);
@@ -178,32 +283,34 @@ pub fn initialize_logging_with_config(config: LogConfig) -> Result<(), Box<dyn s
     Ok(())
 }
 
+/// Paths of the current and rotated log files, with buffered output flushed first
+pub fn log_files() -> Vec<PathBuf> {
+    lock_logger().log_files()
+}
+
+/// Write out anything still buffered for the log file
+pub fn flush() {
+    lock_logger().flush();
+}
+
 /// Log a debug message
 pub fn debug(component: &str, message: &str, details: Option<&str>) {
-    if let Ok(mut logger) = LOGGER.lock() {
-        logger.log(LogLevel::Debug, component, message, details);
-    }
+    lock_logger().log(LogLevel::Debug, component, message, details);
 }
 
 /// Log an info message
 pub fn info(component: &str, message: &str, details: Option<&str>) {
-    if let Ok(mut logger) = LOGGER.lock() {
-        logger.log(LogLevel::Info, component, message, details);
-    }
+    lock_logger().log(LogLevel::Info, component, message, details);
 }
 
 /// Log a warning message
 pub fn warn(component: &str, message: &str, details: Option<&str>) {
-    if let Ok(mut logger) = LOGGER.lock() {
-        logger.log(LogLevel::Warn, component, message, details);
-    }
+    lock_logger().log(LogLevel::Warn, component, message, details);
 }
 
 /// Log an error message
 pub fn error(component: &str, message: &str, details: Option<&str>) {
-    if let Ok(mut logger) = LOGGER.lock() {
-        logger.log(LogLevel::Error, component, message, details);
-    }
+    lock_logger().log(LogLevel::Error, component, message, details);
 }
 
 /// Database-specific logging functions
@@ -303,7 +410,7 @@ pub mod database {
 
 /// Get current log configuration
 pub fn get_log_config() -> LogConfig {
-    LOGGER.lock().unwrap().config.clone()
+    lock_logger().config.clone()
 }
 
 #[cfg(test)]
@@ -328,6 +435,60 @@ mod tests {
         assert!(entry.contains("Additional details"));
     }
     
+    #[test]
+    fn test_rotation_keeps_active_log() {
+        let temp_dir = TempDir::new().unwrap();
+        let log_path = temp_dir.path().join("rotate.log");
+        
+        let mut logger = Logger::with_config(LogConfig {
+            log_file_path: log_path.clone(),
+            max_file_size_bytes: 200,
+            max_log_files: 3,
+            max_total_log_bytes: 10 * 1024,
+            enable_console_output: false,
+        });
+        
+        for i in 0..20 {
+            logger.log(LogLevel::Info, "TEST", &format!("entry {}", i), None);
+        }
+        
+        assert!(log_path.exists());
+        assert!(log_path.with_extension("log.1").exists());
+        assert!(!log_path.with_extension("log.new").exists());
+        assert!(!log_path.with_extension("log.3").exists());
+        
+        let current = std::fs::read_to_string(&log_path).unwrap();
+        assert!(current.contains("entry 19"));
+        assert!(current.ends_with('\n'));
+    }
+    
+    #[test]
+    fn test_total_size_cap_removes_oldest_rotations() {
+        let temp_dir = TempDir::new().unwrap();
+        let log_path = temp_dir.path().join("capped.log");
+        
+        let mut logger = Logger::with_config(LogConfig {
+            log_file_path: log_path.clone(),
+            max_file_size_bytes: 200,
+            max_log_files: 10,
+            max_total_log_bytes: 600,
+            enable_console_output: false,
+        });
+        
+        for i in 0..100 {
+            logger.log(LogLevel::Info, "TEST", &format!("entry {}", i), None);
+        }
+        
+        let total: u64 = std::fs::read_dir(temp_dir.path()).unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.metadata().unwrap().len())
+            .sum();
+        // The cap is checked right after a rotation, so only the active file can grow past it
+        assert!(total <= 600 + 250, "total log size {} exceeds cap", total);
+        assert!(log_path.with_extension("log.1").exists());
+        assert!(!log_path.with_extension("log.9").exists());
+    }
+    
     #[test]
     fn test_database_logging() {
         // Create a temporary directory for testing
@@ -338,6 +499,7 @@ mod tests {
             log_file_path: log_path.clone(),
             max_file_size_bytes: 1024 * 1024,
             max_log_files: 3,
+            max_total_log_bytes: 10 * 1024 * 1024,
             enable_console_output: false,
         };
         
@@ -356,4 +518,15 @@ mod tests {
         assert!(log_content.contains("Attempting database connection"));
         assert!(log_content.contains("Database connection established"));
     }
+
+    #[test]
+    fn test_logging_recovers_from_poisoned_mutex() {
+        let _ = std::thread::spawn(|| {
+            let _guard = LOGGER.lock();
+            panic!("poisoning the logger");
+        }).join();
+
+        info("TEST", "Still logging after a panic", None);
+        assert!(!LOGGER.is_poisoned());
+    }
 }
@@ -3,5 +3,6 @@ pub mod parser;
 pub mod placeholder;
 pub mod cache;
 pub mod resolver;
+pub mod steam_users;
 
 pub use resolver::PlaceholderResolver as ManifestResolver;
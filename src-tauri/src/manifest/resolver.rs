@@ -1,13 +1,29 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use crate::database::DatabaseResult;
+use super::steam_users::{self, SteamUser};
+
+/// Placeholder for the current Steam account's `userdata` directory
+pub const STEAM_USERDATA_CURRENT: &str = "steam-userdata-current";
+
+/// A placeholder usable as `{{name}}` in path templates, with its value on this machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaceholderInfo {
+    pub name: String,
+    pub resolved_value: String,
+    /// `steam`, `launcher` (Epic Games or GOG Galaxy) or `builtin`
+    pub source: String,
+}
 
 /// Main placeholder resolver
 #[derive(Clone)]
 pub struct PlaceholderResolver {
     placeholders: HashMap<String, String>,
     regex: Regex,
+    /// Steam accounts on this machine, the one `{{steam-userdata-current}}` uses first
+    steam_users: Vec<SteamUser>,
 }
 
 impl PlaceholderResolver {
@@ -15,6 +31,7 @@ impl PlaceholderResolver {
         let mut resolver = Self {
             placeholders: HashMap::new(),
             regex: Regex::new(r"\{\{\s*([^}]+)\s*\}\}")?,
+            steam_users: Vec::new(),
         };
 
         resolver.init_placeholders()?;
@@ -25,6 +42,8 @@ impl PlaceholderResolver {
     fn init_placeholders(&mut self) -> DatabaseResult<()> {
         // Steam placeholders
         self.add_steam_placeholders()?;
+        // Epic Games and GOG Galaxy install roots
+        self.add_launcher_placeholders()?;
         // Standard Windows placeholders
         self.add_windows_placeholders()?;
         // Standard Unix placeholders
@@ -37,6 +56,11 @@ impl PlaceholderResolver {
 
     /// Resolve all placeholders in a path template
     pub fn resolve_path(&self, template: &str) -> DatabaseResult<String> {
+        self.resolve_for_steam_user(template, self.current_steam_users().first())
+    }
+
+    /// Resolve a template with `{{steam-userdata-current}}` pointing at `steam_user`
+    fn resolve_for_steam_user(&self, template: &str, steam_user: Option<&SteamUser>) -> DatabaseResult<String> {
         let mut result = template.to_string();
 
         // Find all placeholder matches
@@ -44,15 +68,51 @@ impl PlaceholderResolver {
 
         for capture in captures {
             let placeholder = &capture[1]; // Extract the content inside {{ }}
-            if let Some(resolved) = self.placeholders.get(placeholder.trim()) {
+            let resolved = match placeholder.trim() {
+                STEAM_USERDATA_CURRENT => steam_user.map(|user| user.userdata_path.to_string_lossy().to_string()),
+                name => self.placeholders.get(name).cloned(),
+            };
+            if let Some(resolved) = resolved {
                 let placeholder_pattern = format!("{{{{{}}}}}", placeholder);
-                result = result.replace(&placeholder_pattern, resolved);
+                result = result.replace(&placeholder_pattern, &resolved);
             }
         }
 
         Ok(Self::normalize_path_separators(&result))
     }
 
+    /// Resolve a template once per Steam account when it uses `{{steam-userdata-current}}`,
+    /// the current account first; other templates resolve to a single path
+    pub fn resolve_path_candidates(&self, template: &str) -> DatabaseResult<Vec<String>> {
+        let uses_current_user = self.regex.captures_iter(template)
+            .any(|capture| capture[1].trim() == STEAM_USERDATA_CURRENT);
+        let users = self.current_steam_users();
+        if !uses_current_user || users.len() < 2 {
+            return Ok(vec![self.resolve_for_steam_user(template, users.first())?]);
+        }
+
+        users.iter()
+            .map(|user| self.resolve_for_steam_user(template, Some(user)))
+            .collect()
+    }
+
+    /// Steam accounts on this machine: the profile picked with the `active_steam_user`
+    /// setting first, otherwise the likely active one
+    pub fn current_steam_users(&self) -> Vec<SteamUser> {
+        let mut users = self.steam_users.clone();
+        steam_users::apply_selection(&mut users, steam_users::active_steam_id().as_deref());
+        users
+    }
+
+    /// Look up the Steam accounts afresh, ordered like `current_steam_users`
+    pub fn discover_steam_users() -> Vec<SteamUser> {
+        let mut users = Self::find_steam_path()
+            .map(|steam_path| steam_users::discover(&steam_path))
+            .unwrap_or_default();
+        steam_users::apply_selection(&mut users, steam_users::active_steam_id().as_deref());
+        users
+    }
+
     /// Check if a template contains unresolved placeholders
     pub fn has_unresolved_placeholders(&self, template: &str) -> bool {
         self.regex.is_match(template) && !self.can_resolve_fully(template)
@@ -112,6 +172,25 @@ impl PlaceholderResolver {
 
             let userdata = steam_path.join("userdata");
             self.placeholders.insert("steam-userdata".to_string(), userdata.to_string_lossy().to_string());
+
+            // The signed-in account's `userdata/<account id>`
+            self.steam_users = steam_users::discover(&steam_path);
+            if let Some(current) = self.steam_users.first() {
+                self.placeholders.insert(STEAM_USERDATA_CURRENT.to_string(), current.userdata_path.to_string_lossy().to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add `{{epic-games}}` and `{{gog-galaxy}}` for the launchers' game install roots
+    fn add_launcher_placeholders(&mut self) -> DatabaseResult<()> {
+        if let Some(epic_path) = Self::find_epic_games_path() {
+            self.placeholders.insert("epic-games".to_string(), epic_path.to_string_lossy().to_string());
+        }
+
+        if let Some(gog_path) = Self::find_gog_galaxy_path() {
+            self.placeholders.insert("gog-galaxy".to_string(), gog_path.to_string_lossy().to_string());
         }
 
         Ok(())
@@ -322,10 +401,93 @@ impl PlaceholderResolver {
         )).into())
     }
 
+    /// Find the directory Epic Games titles are installed into.
+    ///
+    /// On Linux there is no official launcher, so the install directory configured in
+    /// Legendary or Heroic is used instead.
+    fn find_epic_games_path() -> Option<PathBuf> {
+        let mut candidates = Vec::new();
+
+        if cfg!(target_os = "windows") {
+            candidates.push(PathBuf::from("C:\\Program Files\\Epic Games"));
+            candidates.push(PathBuf::from("C:\\Program Files (x86)\\Epic Games"));
+        } else if cfg!(target_os = "macos") {
+            candidates.push(PathBuf::from("/Users/Shared/Epic Games"));
+        } else if let Some(config_dir) = Self::config_dir() {
+            let legendary = std::fs::read_to_string(config_dir.join("legendary").join("config.ini")).ok()
+                .and_then(|contents| parse_legendary_install_dir(&contents));
+            candidates.extend(legendary);
+            candidates.extend(Self::heroic_install_path(&config_dir));
+            candidates.extend(Self::expand_home("~/Games/Heroic"));
+            candidates.extend(Self::expand_home("~/Games/legendary"));
+        }
+
+        candidates.into_iter().find(|path| path.exists())
+    }
+
+    /// Find the directory GOG Galaxy titles are installed into (Heroic on Linux)
+    fn find_gog_galaxy_path() -> Option<PathBuf> {
+        let mut candidates = Vec::new();
+
+        if cfg!(target_os = "windows") {
+            candidates.push(PathBuf::from("C:\\Program Files (x86)\\GOG Galaxy\\Games"));
+            candidates.push(PathBuf::from("C:\\GOG Games"));
+        } else if cfg!(target_os = "macos") {
+            candidates.push(PathBuf::from("/Applications"));
+        } else if let Some(config_dir) = Self::config_dir() {
+            candidates.extend(Self::heroic_install_path(&config_dir));
+            candidates.extend(Self::expand_home("~/Games/Heroic"));
+            candidates.extend(Self::expand_home("~/GOG Games"));
+        }
+
+        candidates.into_iter().find(|path| path.exists())
+    }
+
+    /// Default install path from Heroic's `config.json`
+    fn heroic_install_path(config_dir: &std::path::Path) -> Option<PathBuf> {
+        let contents = std::fs::read_to_string(config_dir.join("heroic").join("config.json")).ok()?;
+        parse_heroic_install_path(&contents)
+    }
+
+    fn config_dir() -> Option<PathBuf> {
+        std::env::var("XDG_CONFIG_HOME").ok().map(PathBuf::from)
+            .or_else(|| Self::expand_home("~/.config"))
+    }
+
+    fn expand_home(path: &str) -> Option<PathBuf> {
+        let home = std::env::var("HOME").ok()?;
+        Some(PathBuf::from(path.replacen('~', &home, 1)))
+    }
+
     /// Get all available placeholders (for debugging)
     pub fn list_placeholders(&self) -> &HashMap<String, String> {
         &self.placeholders
     }
+
+    /// Every placeholder with its value and where it comes from, sorted by name
+    pub fn describe_placeholders(&self) -> Vec<PlaceholderInfo> {
+        let current_user = self.current_steam_users().into_iter().next();
+        let mut described: Vec<PlaceholderInfo> = self.placeholders.iter()
+            .map(|(name, value)| PlaceholderInfo {
+                name: name.clone(),
+                resolved_value: match (name.as_str(), &current_user) {
+                    (STEAM_USERDATA_CURRENT, Some(user)) => user.userdata_path.to_string_lossy().to_string(),
+                    _ => value.clone(),
+                },
+                source: Self::placeholder_source(name).to_string(),
+            })
+            .collect();
+        described.sort_by(|a, b| a.name.cmp(&b.name));
+        described
+    }
+
+    fn placeholder_source(name: &str) -> &'static str {
+        match name {
+            "steam" | "steamapps" | "steam-common" | "steam-userdata" | STEAM_USERDATA_CURRENT => "steam",
+            "epic-games" | "gog-galaxy" => "launcher",
+            _ => "builtin",
+        }
+    }
 }
 
 /// Windows-specific utilities
@@ -339,3 +501,85 @@ mod windows {
         Err("Registry access not implemented".into())
     }
 }
+
+/// `install_dir` from the `[Legendary]` section of Legendary's `config.ini`
+fn parse_legendary_install_dir(contents: &str) -> Option<PathBuf> {
+    let mut in_section = false;
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_section = line.eq_ignore_ascii_case("[Legendary]");
+        } else if in_section {
+            if let Some((key, value)) = line.split_once('=') {
+                if key.trim() == "install_dir" && !value.trim().is_empty() {
+                    return Some(PathBuf::from(value.trim()));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// `defaultSettings.defaultInstallPath` from Heroic's `config.json`
+fn parse_heroic_install_path(contents: &str) -> Option<PathBuf> {
+    let config: serde_json::Value = serde_json::from_str(contents).ok()?;
+    config.get("defaultSettings")?
+        .get("defaultInstallPath")?
+        .as_str()
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launcher_config_parsing() {
+        let legendary = "[Legendary]\nlog_level = info\ninstall_dir = /games/epic\n\n[other]\ninstall_dir = /wrong\n";
+        assert_eq!(parse_legendary_install_dir(legendary), Some(PathBuf::from("/games/epic")));
+        assert_eq!(parse_legendary_install_dir("[other]\ninstall_dir = /wrong\n"), None);
+
+        let heroic = r#"{"defaultSettings": {"defaultInstallPath": "/home/user/Games/Heroic"}}"#;
+        assert_eq!(parse_heroic_install_path(heroic), Some(PathBuf::from("/home/user/Games/Heroic")));
+        assert_eq!(parse_heroic_install_path("{}"), None);
+    }
+
+    #[test]
+    fn test_describe_placeholders_is_sorted_with_sources() {
+        let mut resolver = PlaceholderResolver::new().unwrap();
+        resolver.placeholders.insert("steam-common".to_string(), "/steam/steamapps/common".to_string());
+        resolver.placeholders.insert("gog-galaxy".to_string(), "/gog".to_string());
+
+        let described = resolver.describe_placeholders();
+        assert!(described.windows(2).all(|w| w[0].name <= w[1].name));
+
+        let source = |name: &str| described.iter().find(|p| p.name == name).map(|p| p.source.as_str());
+        assert_eq!(source("steam-common"), Some("steam"));
+        assert_eq!(source("gog-galaxy"), Some("launcher"));
+        assert_eq!(source("temp"), Some("builtin"));
+    }
+
+    #[test]
+    fn test_steam_userdata_current_resolves_per_account() {
+        let mut resolver = PlaceholderResolver::new().unwrap();
+        let user = |account_id: u32| SteamUser {
+            steam_id: String::new(),
+            account_id,
+            account_name: None,
+            persona_name: None,
+            last_login: None,
+            most_recent: false,
+            selected: false,
+            userdata_path: PathBuf::from(format!("/steam/userdata/{}", account_id)),
+        };
+        resolver.steam_users = vec![user(2), user(1)];
+        resolver.placeholders.insert(STEAM_USERDATA_CURRENT.to_string(), "/steam/userdata/2".to_string());
+
+        let template = "{{steam-userdata-current}}/440/remote";
+        assert_eq!(resolver.resolve_path(template).unwrap(), PlaceholderResolver::normalize_path_separators("/steam/userdata/2/440/remote"));
+        let candidates = resolver.resolve_path_candidates(template).unwrap();
+        assert_eq!(candidates.len(), 2);
+        assert!(candidates[1].ends_with(&PlaceholderResolver::normalize_path_separators("userdata/1/440/remote")));
+        assert_eq!(resolver.resolve_path_candidates("{{steam-userdata}}/x").unwrap().len(), 1);
+    }
+}
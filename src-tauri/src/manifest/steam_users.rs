@@ -0,0 +1,276 @@
+//! Steam accounts that have signed in on this machine
+//!
+//! Steam keeps per-user data under `userdata/<account id>/`, where the account id is the
+//! low 32 bits of the 64-bit Steam ID listed in `config/loginusers.vdf`.
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Offset between a 64-bit individual Steam ID and its 32-bit account id
+const STEAM_ID64_BASE: u64 = 76_561_197_960_265_728;
+
+/// `app_settings` key holding the Steam ID whose saves `{{steam-userdata-current}}` points at
+pub const ACTIVE_STEAM_USER_SETTING: &str = "active_steam_user";
+
+/// The `active_steam_user` setting, kept in memory so placeholders resolve without the database
+static ACTIVE_STEAM_ID: Lazy<RwLock<Option<String>>> = Lazy::new(|| RwLock::new(None));
+
+/// The Steam ID picked as the active profile, if one was picked
+pub fn active_steam_id() -> Option<String> {
+    ACTIVE_STEAM_ID.read().ok().and_then(|id| id.clone())
+}
+
+pub fn set_active_steam_id(steam_id: Option<String>) {
+    if let Ok(mut active) = ACTIVE_STEAM_ID.write() {
+        *active = steam_id;
+    }
+}
+
+/// Load the `active_steam_user` setting; an unset or empty value picks no profile
+pub fn load_active_steam_user(conn: &Connection) {
+    let steam_id = crate::database::settings::AppSettings::get(conn, ACTIVE_STEAM_USER_SETTING)
+        .ok()
+        .flatten()
+        .filter(|id| !id.is_empty());
+    set_active_steam_id(steam_id);
+}
+
+/// Load the setting from the application database at startup
+pub async fn restore_active_steam_user() {
+    match crate::database::connection::ensure_database_ready().await {
+        Ok(db) => {
+            let db_guard = db.lock().await;
+            let conn = db_guard.get_connection().await;
+            load_active_steam_user(&conn);
+        }
+        Err(e) => crate::logger::warn("STEAM", "Could not load the active Steam user", Some(&e)),
+    }
+}
+
+/// Mark the account with `steam_id` as selected and move it first; the rest keep their order
+pub fn apply_selection(users: &mut [SteamUser], steam_id: Option<&str>) {
+    for user in users.iter_mut() {
+        user.selected = steam_id == Some(user.steam_id.as_str());
+    }
+    users.sort_by_key(|user| !user.selected);
+}
+
+/// A Steam account with its `userdata` directory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SteamUser {
+    /// 64-bit Steam ID, e.g. `76561198000000000`
+    pub steam_id: String,
+    /// Name of the account's `userdata` directory
+    pub account_id: u32,
+    pub account_name: Option<String>,
+    pub persona_name: Option<String>,
+    pub last_login: Option<DateTime<Utc>>,
+    /// Marked `MostRecent` in `loginusers.vdf`
+    pub most_recent: bool,
+    /// Picked as the active profile with the `active_steam_user` setting
+    #[serde(default)]
+    pub selected: bool,
+    pub userdata_path: PathBuf,
+}
+
+/// Accounts known to the Steam install at `steam_path`, the likely active one first.
+///
+/// Accounts come from `config/loginusers.vdf` plus any `userdata` directory it doesn't
+/// list. The `MostRecent` account leads, then the latest login, then the most recently
+/// modified `userdata` directory.
+pub fn discover(steam_path: &Path) -> Vec<SteamUser> {
+    let userdata = steam_path.join("userdata");
+    let mut users: Vec<SteamUser> = std::fs::read_to_string(steam_path.join("config").join("loginusers.vdf"))
+        .map(|contents| parse_login_users(&contents))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|mut user| {
+            user.userdata_path = userdata.join(user.account_id.to_string());
+            user
+        })
+        .collect();
+
+    for entry in std::fs::read_dir(&userdata).into_iter().flatten().flatten() {
+        let Some(account_id) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+            continue;
+        };
+        // `0` holds data not tied to an account
+        if account_id == 0 || users.iter().any(|user| user.account_id == account_id) {
+            continue;
+        }
+        users.push(SteamUser {
+            steam_id: (STEAM_ID64_BASE + account_id as u64).to_string(),
+            account_id,
+            account_name: None,
+            persona_name: None,
+            last_login: None,
+            most_recent: false,
+            selected: false,
+            userdata_path: entry.path(),
+        });
+    }
+
+    let modified = |user: &SteamUser| std::fs::metadata(&user.userdata_path).and_then(|m| m.modified()).ok();
+    users.sort_by(|a, b| {
+        b.most_recent.cmp(&a.most_recent)
+            .then(b.last_login.cmp(&a.last_login))
+            .then(modified(b).cmp(&modified(a)))
+    });
+    users
+}
+
+/// Accounts listed in `loginusers.vdf`; `userdata_path` is left empty
+pub fn parse_login_users(contents: &str) -> Vec<SteamUser> {
+    let mut users = Vec::new();
+    let mut tokens = vdf_tokens(contents).into_iter().peekable();
+    let mut depth = 0;
+    let mut current: Option<SteamUser> = None;
+
+    while let Some(token) = tokens.next() {
+        match token {
+            VdfToken::Open => depth += 1,
+            VdfToken::Close => {
+                depth -= 1;
+                if depth == 1 {
+                    users.extend(current.take());
+                }
+            }
+            VdfToken::Text(key) if tokens.peek() == Some(&VdfToken::Open) => {
+                // A user block inside "users"
+                if depth == 1 {
+                    current = key.parse::<u64>().ok()
+                        .filter(|id| *id > STEAM_ID64_BASE)
+                        .map(|id| SteamUser {
+                            steam_id: key.clone(),
+                            account_id: (id - STEAM_ID64_BASE) as u32,
+                            account_name: None,
+                            persona_name: None,
+                            last_login: None,
+                            most_recent: false,
+                            selected: false,
+                            userdata_path: PathBuf::new(),
+                        });
+                }
+            }
+            VdfToken::Text(key) => {
+                let Some(VdfToken::Text(value)) = tokens.next() else { continue };
+                let Some(user) = current.as_mut().filter(|_| depth == 2) else { continue };
+                match key.to_ascii_lowercase().as_str() {
+                    "accountname" => user.account_name = Some(value),
+                    "personaname" => user.persona_name = Some(value),
+                    "mostrecent" => user.most_recent = value == "1",
+                    "timestamp" => user.last_login = value.parse().ok().and_then(|secs| DateTime::from_timestamp(secs, 0)),
+                    _ => {}
+                }
+            }
+        }
+    }
+    users
+}
+
+#[derive(Debug, PartialEq)]
+enum VdfToken {
+    Text(String),
+    Open,
+    Close,
+}
+
+/// Quoted strings and braces of a text VDF file; `//` comments are skipped
+fn vdf_tokens(contents: &str) -> Vec<VdfToken> {
+    let mut tokens = Vec::new();
+    let mut chars = contents.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => tokens.push(VdfToken::Open),
+            '}' => tokens.push(VdfToken::Close),
+            '"' => {
+                let mut text = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => text.extend(chars.next()),
+                        _ => text.push(c),
+                    }
+                }
+                tokens.push(VdfToken::Text(text));
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOGIN_USERS: &str = r#"
+"users"
+{
+	"76561198000000001"
+	{
+		"AccountName"		"alice"
+		"PersonaName"		"Alice \"A\""
+		"MostRecent"		"0"
+		"Timestamp"		"1700000000"
+	}
+	"76561198000000002"
+	{
+		"AccountName"		"bob"
+		"PersonaName"		"Bob"
+		"MostRecent"		"1"
+		"Timestamp"		"1600000000"
+	}
+}
+"#;
+
+    #[test]
+    fn test_parse_login_users() {
+        let users = parse_login_users(LOGIN_USERS);
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[0].account_id, 39_734_273);
+        assert_eq!(users[0].account_name.as_deref(), Some("alice"));
+        assert_eq!(users[0].persona_name.as_deref(), Some("Alice \"A\""));
+        assert_eq!(users[0].last_login, DateTime::from_timestamp(1_700_000_000, 0));
+        assert!(!users[0].most_recent && users[1].most_recent);
+    }
+
+    #[test]
+    fn test_discover_puts_most_recent_user_first() {
+        let steam = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(steam.path().join("config")).unwrap();
+        std::fs::write(steam.path().join("config").join("loginusers.vdf"), LOGIN_USERS).unwrap();
+        for dir in ["0", "39734273", "39734274", "12345", "anonymous"] {
+            std::fs::create_dir_all(steam.path().join("userdata").join(dir)).unwrap();
+        }
+
+        let users = discover(steam.path());
+        let ids: Vec<u32> = users.iter().map(|u| u.account_id).collect();
+        assert_eq!(ids, vec![39_734_274, 39_734_273, 12345]);
+        assert_eq!(users[0].userdata_path, steam.path().join("userdata").join("39734274"));
+        assert_eq!(users[2].steam_id, "76561197960278073");
+    }
+
+    #[test]
+    fn test_apply_selection_moves_selected_user_first() {
+        let mut users = parse_login_users(LOGIN_USERS);
+        apply_selection(&mut users, Some("76561198000000002"));
+        assert_eq!(users[0].account_name.as_deref(), Some("bob"));
+        assert!(users[0].selected && !users[1].selected);
+
+        apply_selection(&mut users, Some("76561190000000000"));
+        assert!(users.iter().all(|u| !u.selected));
+        assert_eq!(users[0].account_name.as_deref(), Some("bob"));
+    }
+}
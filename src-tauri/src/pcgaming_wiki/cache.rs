@@ -34,4 +34,9 @@ impl PcgwCache {
 
         Ok(())
     }
+
+    /// Delete every cached response, returning the number of rows removed
+    pub fn clear(conn: &Connection) -> Result<usize> {
+        conn.execute("DELETE FROM pcgw_cache", [])
+    }
 }
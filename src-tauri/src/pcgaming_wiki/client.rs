@@ -5,7 +5,7 @@ use thiserror::Error;
 
 use crate::pcgaming_wiki::{
     cache::PcgwCache,
-    models::{CargoQueryResponse, GameSearchResult, PcgwGameInfo, PcgwSaveGameData, SaveLocationResult},
+    models::{CargoQueryResponse, GameSearchResult, PcgwGameInfo, PcgwGameMetadata, PcgwSaveGameData, SaveLocationResult},
     query_builder::QueryBuilder,
     save_location_parser::SaveLocationParser,
 };
@@ -97,12 +97,23 @@ impl PcgwClient {
         Ok(self.http_client.get(&url).send().await?.text().await?)
     }
 
+    pub async fn fetch_metadata_raw(&self, page_name: &str) -> Result<String, PcgwError> {
+        let url = QueryBuilder::build_metadata_query(page_name)?;
+        Ok(self.http_client.get(&url).send().await?.text().await?)
+    }
+
     // Helper to parse raw JSON into SaveLocationResult (public for manual usage)
     pub fn parse_save_locations_json(&self, json: &str) -> Result<SaveLocationResult, PcgwError> {
         let response: CargoQueryResponse<PcgwSaveGameData> = serde_json::from_str(json)?;
         Ok(self.parse_save_locations(response))
     }
 
+    // Infobox fields of the page, if PCGW has an infobox for it
+    pub fn parse_metadata_json(&self, json: &str) -> Result<Option<PcgwGameMetadata>, PcgwError> {
+        let response: CargoQueryResponse<PcgwGameMetadata> = serde_json::from_str(json)?;
+        Ok(response.cargoquery.into_iter().next().map(|item| item.title))
+    }
+
     fn map_search_results(&self, response: CargoQueryResponse<PcgwGameInfo>) -> Vec<GameSearchResult> {
         response.cargoquery.into_iter().map(|item| {
             let info = item.title;
@@ -12,6 +12,20 @@ pub struct PcgwGameInfo {
     pub genres: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PcgwGameMetadata {
+    #[serde(rename = "Developers")]
+    pub developers: Option<String>,
+    #[serde(rename = "Publishers")]
+    pub publishers: Option<String>,
+    #[serde(rename = "Released")]
+    pub released: Option<String>,
+    #[serde(rename = "Genres")]
+    pub genres: Option<String>,
+    #[serde(rename = "Cover URL")]
+    pub cover_url: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PcgwSaveGameData {
     #[serde(rename = "_pageName")]
@@ -40,6 +40,22 @@ impl QueryBuilder {
         Ok(url.to_string())
     }
 
+    pub fn build_metadata_query(page_name: &str) -> Result<String, url::ParseError> {
+        let mut url = Url::parse(BASE_URL)?;
+        
+        let where_clause = format!("_pageName=\"{}\"", page_name);
+
+        url.query_pairs_mut()
+            .append_pair("action", "cargoquery")
+            .append_pair("tables", "Infobox_game")
+            .append_pair("fields", "Developers,Publishers,Released,Genres,Cover_URL")
+            .append_pair("where", &where_clause)
+            .append_pair("limit", "1")
+            .append_pair("format", "json");
+
+        Ok(url.to_string())
+    }
+
     pub fn build_wikitext_query(page_name: &str) -> Result<String, url::ParseError> {
         let mut url = Url::parse(BASE_URL)?;
         
@@ -0,0 +1,177 @@
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+/// Event the frontend listens on for scan and backup progress
+pub const PROGRESS_EVENT: &str = "operation-progress";
+/// At most this many updates per second reach the frontend
+const EMIT_INTERVAL: Duration = Duration::from_millis(250);
+
+static APP_HANDLE: OnceLock<tauri::AppHandle> = OnceLock::new();
+
+/// Remember the app handle so background work can emit events. Called once from `setup`.
+pub fn set_app_handle(handle: tauri::AppHandle) {
+    let _ = APP_HANDLE.set(handle);
+}
+
+/// Snapshot of a long-running operation
+#[derive(Debug, Clone, Serialize)]
+pub struct ProgressUpdate {
+    /// `save_scan`, `executable_hash`, `export_save`, ...
+    pub operation: String,
+    pub game_id: Option<i64>,
+    pub files_done: usize,
+    pub files_total: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub finished: bool,
+}
+
+/// Receiver for progress updates
+#[derive(Clone)]
+pub struct ProgressSink(Arc<dyn Fn(&ProgressUpdate) + Send + Sync>);
+
+impl ProgressSink {
+    pub fn new(callback: impl Fn(&ProgressUpdate) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    /// Forward updates to the frontend over the Tauri event channel
+    pub fn frontend() -> Self {
+        Self::new(|update| {
+            if let Some(handle) = APP_HANDLE.get() {
+                if let Err(e) = handle.emit(PROGRESS_EVENT, update) {
+                    crate::logger::debug("PROGRESS", "Failed to emit progress event", Some(&e.to_string()));
+                }
+            }
+        })
+    }
+}
+
+impl std::fmt::Debug for ProgressSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProgressSink")
+    }
+}
+
+/// Counts files and bytes of one operation and passes throttled updates to a sink.
+///
+/// Safe to share between the rayon workers hashing in parallel. Operations finishing
+/// within the first interval stay silent, so small scans don't flood the event channel.
+pub struct ProgressReporter {
+    operation: String,
+    game_id: Option<i64>,
+    sink: ProgressSink,
+    files_done: AtomicUsize,
+    files_total: AtomicUsize,
+    bytes_done: AtomicU64,
+    bytes_total: AtomicU64,
+    last_emit: Mutex<Instant>,
+    emitted: AtomicBool,
+}
+
+impl ProgressReporter {
+    pub fn new(operation: &str, game_id: Option<i64>, sink: ProgressSink) -> Self {
+        Self {
+            operation: operation.to_string(),
+            game_id,
+            sink,
+            files_done: AtomicUsize::new(0),
+            files_total: AtomicUsize::new(0),
+            bytes_done: AtomicU64::new(0),
+            bytes_total: AtomicU64::new(0),
+            last_emit: Mutex::new(Instant::now()),
+            emitted: AtomicBool::new(false),
+        }
+    }
+
+    /// Grow the expected totals, e.g. once another save path has been walked
+    pub fn add_total(&self, files: usize, bytes: u64) {
+        self.files_total.fetch_add(files, Ordering::Relaxed);
+        self.bytes_total.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn add_bytes(&self, bytes: u64) {
+        self.bytes_done.fetch_add(bytes, Ordering::Relaxed);
+        self.maybe_emit();
+    }
+
+    pub fn file_done(&self) {
+        self.files_done.fetch_add(1, Ordering::Relaxed);
+        self.maybe_emit();
+    }
+
+    /// Replace the file counts, for sources like git2 callbacks that report running totals
+    pub fn set_files(&self, done: usize, total: usize) {
+        self.files_done.store(done, Ordering::Relaxed);
+        self.files_total.store(total, Ordering::Relaxed);
+        self.maybe_emit();
+    }
+
+    /// Send the final update, if any intermediate update was sent
+    pub fn finish(&self) {
+        if self.emitted.load(Ordering::Relaxed) {
+            (self.sink.0)(&self.snapshot(true));
+        }
+    }
+
+    pub fn snapshot(&self, finished: bool) -> ProgressUpdate {
+        ProgressUpdate {
+            operation: self.operation.clone(),
+            game_id: self.game_id,
+            files_done: self.files_done.load(Ordering::Relaxed),
+            files_total: self.files_total.load(Ordering::Relaxed),
+            bytes_done: self.bytes_done.load(Ordering::Relaxed),
+            bytes_total: self.bytes_total.load(Ordering::Relaxed),
+            finished,
+        }
+    }
+
+    fn maybe_emit(&self) {
+        // Another worker holding the lock is about to emit anyway
+        let Ok(mut last_emit) = self.last_emit.try_lock() else { return };
+        if last_emit.elapsed() < EMIT_INTERVAL {
+            return;
+        }
+        *last_emit = Instant::now();
+        drop(last_emit);
+
+        self.emitted.store(true, Ordering::Relaxed);
+        (self.sink.0)(&self.snapshot(false));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fast_operations_stay_silent() {
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        let collected = updates.clone();
+        let reporter = ProgressReporter::new("save_scan", Some(1), ProgressSink::new(move |u| {
+            collected.lock().unwrap().push(u.clone());
+        }));
+
+        reporter.add_total(2, 30);
+        reporter.add_bytes(10);
+        reporter.file_done();
+        reporter.add_bytes(20);
+        reporter.file_done();
+        reporter.finish();
+        assert!(updates.lock().unwrap().is_empty());
+
+        let snapshot = reporter.snapshot(true);
+        assert_eq!((snapshot.files_done, snapshot.files_total), (2, 2));
+        assert_eq!((snapshot.bytes_done, snapshot.bytes_total), (30, 30));
+
+        std::thread::sleep(EMIT_INTERVAL);
+        reporter.file_done();
+        reporter.finish();
+        let updates = updates.lock().unwrap();
+        assert_eq!(updates.len(), 2);
+        assert!(!updates[0].finished && updates[1].finished);
+    }
+}
@@ -0,0 +1,39 @@
+//! Orderly shutdown when the application exits
+
+use std::time::Duration;
+
+use crate::database::maintenance::DatabaseMaintenance;
+
+/// How long exit may wait for final and in-progress backups
+const BACKUP_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Finish backups, fold the WAL into the database file and flush the logs.
+///
+/// Runs once from the exit event; each step is best effort so one failure doesn't keep
+/// the others from running.
+pub async fn run() {
+    crate::logger::info("APP", "Shutting down", None);
+
+    if let Some(service) = crate::auto_backup::backup_service_if_started() {
+        if service.shutdown(BACKUP_TIMEOUT).await {
+            crate::logger::info("APP", "Backups finished before exit", None);
+        } else {
+            crate::logger::warn("APP", &format!("Backups still running after {}s, exiting anyway", BACKUP_TIMEOUT.as_secs()), None);
+        }
+    }
+
+    if crate::database::connection::database_startup_error().is_none() {
+        match crate::database::connection::ensure_database_ready().await {
+            Ok(db) => {
+                let db_guard = db.lock().await;
+                if let Err(e) = db_guard.with_connection(|conn| DatabaseMaintenance::checkpoint(conn)).await {
+                    crate::logger::warn("APP", "WAL checkpoint on exit failed", Some(&e));
+                }
+            }
+            Err(e) => crate::logger::warn("APP", "Database unavailable on exit, skipping WAL checkpoint", Some(&e)),
+        }
+    }
+
+    crate::logger::info("APP", "Shutdown complete", None);
+    crate::logger::flush();
+}
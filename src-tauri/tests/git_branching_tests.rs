@@ -2,8 +2,9 @@
 
 use save_steward_lib::database::connection::{Database, DatabaseSchema};
 use save_steward_lib::git_manager::branching::{
-    create_save_checkpoint, switch_save_branch, list_all_branches, 
-    get_game_branches, delete_save_branch
+    create_save_checkpoint, create_save_branch, switch_save_branch, list_all_branches, 
+    get_game_branches, delete_save_branch, migrate_legacy_branch_names, list_save_slots,
+    export_game_branches, remove_game_branches, import_game_branches
 };
 use save_steward_lib::git_manager::repository::initialize_master_repo;
 use std::sync::Arc;
@@ -45,11 +46,12 @@ async fn test_create_save_checkpoint() {
     // Create save checkpoint
     let result = create_save_checkpoint(&db, repo_path, game_id, "MainQuest").await;
     assert!(result.is_ok(), "Should create save checkpoint");
-    assert!(result.unwrap().contains("TestGame-MainQuest"));
+    let expected = format!("g{}-MainQuest", game_id);
+    assert!(result.unwrap().contains(&expected));
     
     // Verify branch exists in Git
     let repo = git2::Repository::open(repo_path).expect("Failed to open repository");
-    let branch = repo.find_branch("TestGame-MainQuest", git2::BranchType::Local);
+    let branch = repo.find_branch(&expected, git2::BranchType::Local);
     assert!(branch.is_ok(), "Branch should exist in repository");
     
     // Verify branch info saved to database
@@ -57,7 +59,7 @@ async fn test_create_save_checkpoint() {
     let conn = conn_guard.get_connection().await;
     let count: i64 = conn.query_row(
         "SELECT COUNT(*) FROM git_branches WHERE game_id = ? AND branch_name = ?",
-        rusqlite::params![game_id, "TestGame-MainQuest"],
+        rusqlite::params![game_id, expected],
         |row| row.get(0)
     ).expect("Failed to query database");
     assert_eq!(count, 1, "Branch should be saved to database");
@@ -80,6 +82,30 @@ async fn test_create_duplicate_checkpoint_switches() {
     assert!(result2.unwrap().contains("Switched to existing"), "Should switch to existing branch");
 }
 
+#[tokio::test]
+async fn test_create_same_branch_twice_keeps_one_row() {
+    let (db, _temp_dir, game_id) = create_test_database_with_game().await;
+    let temp_repo = TempDir::new().expect("Failed to create temp repo dir");
+    let repo_path = temp_repo.path().to_str().unwrap();
+
+    initialize_master_repo(&db, repo_path).await.expect("Failed to initialize repo");
+
+    create_save_branch(&db, repo_path, game_id, "Boss", Some("Before the boss")).await
+        .expect("First creation should succeed");
+    create_save_branch(&db, repo_path, game_id, "Boss", None).await
+        .expect("Creating the same branch again should not hit the unique constraint");
+
+    let conn_guard = db.lock().await;
+    let conn = conn_guard.get_connection().await;
+    let rows: Vec<(String, Option<String>)> = conn.prepare("SELECT branch_name, description FROM git_branches WHERE game_id = ?")
+        .unwrap()
+        .query_map([game_id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(rows, vec![(format!("g{}-Boss", game_id), Some("Before the boss".to_string()))]);
+}
+
 #[tokio::test]
 async fn test_switch_save_branch() {
     let (db, _temp_dir, game_id) = create_test_database_with_game().await;
@@ -93,14 +119,14 @@ async fn test_switch_save_branch() {
     create_save_checkpoint(&db, repo_path, game_id, "Save2").await.expect("Failed to create checkpoint 2");
     
     // Switch back to first branch
-    let result = switch_save_branch(&db, repo_path, game_id, "TestGame-Save1").await;
+    let result = switch_save_branch(&db, repo_path, game_id, &format!("g{}-Save1", game_id)).await;
     assert!(result.is_ok(), "Should switch to branch");
     
     // Verify active branch in repository
     let repo = git2::Repository::open(repo_path).expect("Failed to open repository");
     let head = repo.head().expect("Should have HEAD");
     let branch_name = head.shorthand().unwrap();
-    assert_eq!(branch_name, "TestGame-Save1", "Should be on correct branch");
+    assert_eq!(branch_name, format!("g{}-Save1", game_id), "Should be on correct branch");
 }
 
 #[tokio::test]
@@ -135,11 +161,33 @@ async fn test_get_game_branches() {
     create_save_checkpoint(&db, repo_path, game_id, "Save2").await.expect("Failed to create checkpoint 2");
     
     // Get branches for TestGame
-    let game_branches = get_game_branches(repo_path, "TestGame").await.expect("Failed to get game branches");
+    let game_branches = get_game_branches(repo_path, game_id).await.expect("Failed to get game branches");
     
     assert_eq!(game_branches.len(), 2, "Should have 2 branches for TestGame");
-    assert!(game_branches.contains(&"TestGame-Save1".to_string()));
-    assert!(game_branches.contains(&"TestGame-Save2".to_string()));
+    assert!(game_branches.contains(&format!("g{}-Save1", game_id)));
+    assert!(game_branches.contains(&format!("g{}-Save2", game_id)));
+}
+
+#[tokio::test]
+async fn test_list_save_slots() {
+    let (db, _temp_dir, game_id) = create_test_database_with_game().await;
+    let temp_repo = TempDir::new().expect("Failed to create temp repo dir");
+    let repo_path = temp_repo.path().to_str().unwrap();
+
+    initialize_master_repo(&db, repo_path).await.expect("Failed to initialize repo");
+
+    create_save_checkpoint(&db, repo_path, game_id, "Save1").await.expect("Failed to create checkpoint 1");
+    create_save_checkpoint(&db, repo_path, game_id, "Save2").await.expect("Failed to create checkpoint 2");
+
+    let slots = list_save_slots(&db, repo_path).await.expect("Failed to list save slots");
+
+    // The default branch isn't a save slot
+    assert_eq!(slots.len(), 2);
+    assert!(slots.iter().all(|s| s.game_id == game_id && s.game_name.as_deref() == Some("TestGame")));
+    assert!(slots.iter().all(|s| s.last_commit_time.is_some()));
+
+    let active: Vec<_> = slots.iter().filter(|s| s.is_active).map(|s| s.branch_name.clone()).collect();
+    assert_eq!(active, vec![format!("g{}-Save2", game_id)]);
 }
 
 #[tokio::test]
@@ -157,12 +205,12 @@ async fn test_delete_save_branch() {
     create_save_checkpoint(&db, repo_path, game_id, "KeepThis").await.expect("Failed to create second branch");
     
     // Delete the branch
-    let result = delete_save_branch(repo_path, "TestGame-ToDelete").await;
+    let result = delete_save_branch(repo_path, &format!("g{}-ToDelete", game_id)).await;
     assert!(result.is_ok(), "Should delete branch");
     
     // Verify branch is deleted from Git
     let repo = git2::Repository::open(repo_path).expect("Failed to open repository");
-    let branch = repo.find_branch("TestGame-ToDelete", git2::BranchType::Local);
+    let branch = repo.find_branch(&format!("g{}-ToDelete", game_id), git2::BranchType::Local);
     assert!(branch.is_err(), "Branch should be deleted from repository");
 }
 
@@ -178,7 +226,145 @@ async fn test_branch_naming_convention() {
     let result = create_save_checkpoint(&db, repo_path, game_id, "My Save").await;
     assert!(result.is_ok());
     
-    // Verify branch uses '-' separator
-    let branches = get_game_branches(repo_path, "TestGame").await.expect("Failed to get branches");
-    assert!(branches.iter().any(|b| b.contains("TestGame-")), "Branch should use '-' separator");
+    // Verify branch uses the game id prefix and '-' separator
+    let branches = get_game_branches(repo_path, game_id).await.expect("Failed to get branches");
+    assert!(branches.contains(&format!("g{}-My-Save", game_id)), "Branch should be id-prefixed and sanitized");
+}
+
+#[tokio::test]
+async fn test_games_with_same_name_do_not_share_branches() {
+    let (db, _temp_dir, game_id) = create_test_database_with_game().await;
+    let temp_repo = TempDir::new().expect("Failed to create temp repo dir");
+    let repo_path = temp_repo.path().to_str().unwrap();
+    
+    let other_id = {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+        conn.execute(
+            "INSERT INTO games (name, platform, installation_path) VALUES (?, ?, ?)",
+            rusqlite::params!["TestGame", "gog", "/other/path"]
+        ).expect("Failed to insert second game");
+        conn.last_insert_rowid()
+    };
+    
+    initialize_master_repo(&db, repo_path).await.expect("Failed to initialize repo");
+    
+    create_save_checkpoint(&db, repo_path, game_id, "Save1").await.expect("Failed to create checkpoint");
+    create_save_checkpoint(&db, repo_path, other_id, "Save1").await.expect("Failed to create checkpoint");
+    
+    assert_eq!(get_game_branches(repo_path, game_id).await.unwrap(), vec![format!("g{}-Save1", game_id)]);
+    assert_eq!(get_game_branches(repo_path, other_id).await.unwrap(), vec![format!("g{}-Save1", other_id)]);
+}
+
+#[tokio::test]
+async fn test_migrate_legacy_branch_names() {
+    let (db, _temp_dir, game_id) = create_test_database_with_game().await;
+    let temp_repo = TempDir::new().expect("Failed to create temp repo dir");
+    let repo_path = temp_repo.path().to_str().unwrap();
+    
+    initialize_master_repo(&db, repo_path).await.expect("Failed to initialize repo");
+    
+    // Simulate a branch created with the old gamename-savename scheme
+    {
+        let repo = git2::Repository::open(repo_path).expect("Failed to open repository");
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("TestGame-OldSave", &head, false).expect("Failed to create legacy branch");
+    }
+    {
+        let conn_guard = db.lock().await;
+        let conn = conn_guard.get_connection().await;
+        conn.execute(
+            "INSERT INTO git_branches (game_id, branch_name) VALUES (?, ?)",
+            rusqlite::params![game_id, "TestGame-OldSave"]
+        ).expect("Failed to record legacy branch");
+    }
+    
+    let renamed = migrate_legacy_branch_names(&db, repo_path).await.expect("Migration should succeed");
+    assert_eq!(renamed, 1);
+    
+    let expected = format!("g{}-OldSave", game_id);
+    let repo = git2::Repository::open(repo_path).expect("Failed to open repository");
+    assert!(repo.find_branch("TestGame-OldSave", git2::BranchType::Local).is_err());
+    assert!(repo.find_branch(&expected, git2::BranchType::Local).is_ok());
+    
+    let conn_guard = db.lock().await;
+    let conn = conn_guard.get_connection().await;
+    let recorded: String = conn.query_row(
+        "SELECT branch_name FROM git_branches WHERE game_id = ?",
+        [game_id],
+        |row| row.get(0)
+    ).expect("Failed to query database");
+    assert_eq!(recorded, expected);
+}
+
+#[tokio::test]
+async fn test_checkpoint_commits_save_files() {
+    let (db, _temp_dir, game_id) = create_test_database_with_game().await;
+    let temp_repo = TempDir::new().expect("Failed to create temp repo dir");
+    let repo_path = temp_repo.path().to_str().unwrap();
+    let save_dir = TempDir::new().expect("Failed to create save dir");
+    std::fs::write(save_dir.path().join("slot1.sav"), b"level 3").unwrap();
+
+    {
+        let db_guard = db.lock().await;
+        let conn = db_guard.get_connection().await;
+        conn.execute(
+            "INSERT INTO save_locations (game_id, path_pattern, path_type) VALUES (?, ?, 'directory')",
+            rusqlite::params![game_id, save_dir.path().to_string_lossy().to_string()],
+        ).expect("Failed to insert save location");
+    }
+
+    initialize_master_repo(&db, repo_path).await.expect("Failed to initialize repo");
+    let result = create_save_checkpoint(&db, repo_path, game_id, "Level3").await.expect("Failed to create checkpoint");
+    assert!(result.contains("1 files committed"), "Unexpected result: {}", result);
+
+    // The save file is part of the branch's commit, not just the working tree
+    let repo = git2::Repository::open(repo_path).expect("Failed to open repository");
+    let branch_name = format!("g{}-Level3", game_id);
+    let commit = repo.find_branch(&branch_name, git2::BranchType::Local).unwrap()
+        .get().peel_to_commit().unwrap();
+    let entry = commit.tree().unwrap()
+        .get_path(std::path::Path::new(&format!("g{}/files/0/slot1.sav", game_id)))
+        .expect("Save file should be committed");
+    let blob = repo.find_blob(entry.id()).unwrap();
+    assert_eq!(blob.content(), b"level 3");
+
+    // The commit is recorded for the game
+    let db_guard = db.lock().await;
+    let conn = db_guard.get_connection().await;
+    let recorded: String = conn.query_row(
+        "SELECT git_commit_hash FROM git_save_commits WHERE game_id = ? AND branch_name = ?",
+        rusqlite::params![game_id, branch_name],
+        |row| row.get(0),
+    ).expect("Commit should be recorded");
+    assert_eq!(recorded, commit.id().to_string());
+}
+
+#[tokio::test]
+async fn test_export_and_import_game_branches() {
+    let (db, _temp_dir, game_id) = create_test_database_with_game().await;
+    let temp_repo = TempDir::new().expect("Failed to create temp repo dir");
+    let repo_path = temp_repo.path().to_str().unwrap();
+    let cold = TempDir::new().expect("Failed to create cold storage dir");
+    let bundle = cold.path().join("branches.git");
+
+    initialize_master_repo(&db, repo_path).await.expect("Failed to initialize repo");
+    create_save_checkpoint(&db, repo_path, game_id, "Save1").await.expect("Failed to create checkpoint 1");
+    create_save_checkpoint(&db, repo_path, game_id, "Save2").await.expect("Failed to create checkpoint 2");
+
+    let exported = export_game_branches(repo_path, game_id, &bundle).await.expect("Failed to export branches");
+    assert_eq!(exported.len(), 2);
+
+    // Save2 is checked out, so HEAD has to be detached before it can go
+    assert_eq!(remove_game_branches(repo_path, game_id).await.expect("Failed to remove branches"), 2);
+    assert!(get_game_branches(repo_path, game_id).await.unwrap().is_empty());
+
+    let imported = import_game_branches(repo_path, game_id, &bundle).await.expect("Failed to import branches");
+    assert_eq!(imported.len(), 2);
+    let branches = get_game_branches(repo_path, game_id).await.unwrap();
+    assert!(branches.contains(&format!("g{}-Save1", game_id)));
+    assert!(branches.contains(&format!("g{}-Save2", game_id)));
+
+    // A second import would overwrite the restored branches
+    assert!(import_game_branches(repo_path, game_id, &bundle).await.is_err());
 }
@@ -2,7 +2,7 @@
 
 use save_steward_lib::database::connection::Database;
 use save_steward_lib::git_manager::branching::create_save_checkpoint;
-use save_steward_lib::git_manager::history::{restore_to_commit, restore_to_timestamp, get_save_history};
+use save_steward_lib::git_manager::history::{restore_to_commit, restore_to_timestamp, get_save_history, get_history_range, find_closest_save_time_commit};
 use save_steward_lib::git_manager::repository::initialize_master_repo;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -198,3 +198,60 @@ async fn test_restore_creates_timestamped_branch() {
     assert!(branch_name.starts_with("restore-"), "Restore branch should start with 'restore-'");
     assert!(branch_name.contains("-"), "Restore branch should contain timestamp and hash");
 }
+
+#[tokio::test]
+async fn test_get_history_range_filters_and_limits() {
+    let (db, _temp_dir, game_id) = create_test_database_with_game().await;
+    let temp_repo = TempDir::new().expect("Failed to create temp repo dir");
+    let repo_path = temp_repo.path().to_str().unwrap();
+
+    initialize_master_repo(&db, repo_path).await.expect("Failed to initialize repo");
+    create_save_checkpoint(&db, repo_path, game_id, "Save1").await.expect("Failed to create checkpoint");
+
+    let all = get_history_range(repo_path, game_id, None, None, None, 50).await.expect("Should get history range");
+    assert!(!all.is_empty(), "Should have at least one commit");
+    assert!(all.windows(2).all(|w| w[0].timestamp >= w[1].timestamp), "Commits should be newest first");
+
+    let limited = get_history_range(repo_path, game_id, None, None, None, 1).await.unwrap();
+    assert_eq!(limited.len(), 1);
+
+    let future = Utc::now() + chrono::Duration::days(1);
+    let none = get_history_range(repo_path, game_id, None, Some(future), None, 50).await.unwrap();
+    assert!(none.is_empty(), "No commits should be newer than tomorrow");
+
+    let inverted = get_history_range(repo_path, game_id, None, Some(future), Some(Utc::now()), 50).await;
+    assert!(inverted.is_err(), "An inverted range should be rejected");
+}
+
+#[tokio::test]
+async fn test_checkpoint_records_save_time() {
+    let (db, _temp_dir, game_id) = create_test_database_with_game().await;
+    let temp_repo = TempDir::new().expect("Failed to create temp repo dir");
+    let repo_path = temp_repo.path().to_str().unwrap();
+    let save_dir = TempDir::new().expect("Failed to create save dir");
+
+    let saved_at = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+    let save_file = save_dir.path().join("slot1.sav");
+    std::fs::write(&save_file, b"save data").unwrap();
+    std::fs::File::options().write(true).open(&save_file).unwrap()
+        .set_modified(saved_at.into()).unwrap();
+
+    {
+        let db_guard = db.lock().await;
+        let conn = db_guard.get_connection().await;
+        conn.execute(
+            "INSERT INTO save_locations (game_id, path_pattern, path_type) VALUES (?, ?, 'directory')",
+            rusqlite::params![game_id, save_dir.path().to_string_lossy().to_string()],
+        ).expect("Failed to insert save location");
+    }
+
+    initialize_master_repo(&db, repo_path).await.expect("Failed to initialize repo");
+    create_save_checkpoint(&db, repo_path, game_id, "Save1").await.expect("Failed to create checkpoint");
+
+    let target = saved_at + chrono::Duration::hours(1);
+    let (hash, recorded) = find_closest_save_time_commit(&db, game_id, target).await
+        .expect("Should query save commits")
+        .expect("Checkpoint should have a save time");
+    assert_eq!(recorded, saved_at);
+    assert_eq!(hash, get_latest_commit_hash(repo_path));
+}
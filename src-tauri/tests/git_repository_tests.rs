@@ -1,7 +1,8 @@
 // Integration tests for Git repository module
 
 use save_steward_lib::database::connection::{Database, DatabasePaths};
-use save_steward_lib::git_manager::repository::initialize_master_repo;
+use save_steward_lib::git_manager::repository::{initialize_master_repo, validate_email, CommitAuthor};
+use save_steward_lib::git_manager::repair::check_repository;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tempfile::TempDir;
@@ -100,3 +101,60 @@ async fn test_repository_reinit_idempotent() {
     let result2 = initialize_master_repo(&db, repo_path).await;
     assert!(result2.is_ok());
 }
+
+#[tokio::test]
+async fn test_initial_commit_uses_configured_author() {
+    let (db, _temp_dir) = create_test_database().await;
+    let temp_repo = TempDir::new().expect("Failed to create temp repo dir");
+    let repo_path = temp_repo.path().to_str().unwrap();
+
+    {
+        let db_guard = db.lock().await;
+        let conn = db_guard.get_connection().await;
+        let invalid = CommitAuthor { name: Some("Player".to_string()), email: Some("not-an-email".to_string()) };
+        assert!(invalid.save(&conn).is_err(), "Malformed email should be rejected");
+
+        let author = CommitAuthor { name: Some("Player One".to_string()), email: Some("player@example.com".to_string()) };
+        author.save(&conn).expect("Failed to save author");
+        assert_eq!(CommitAuthor::load(&conn), author);
+    }
+
+    initialize_master_repo(&db, repo_path).await.expect("Failed to initialize repo");
+
+    let repo = git2::Repository::open(repo_path).expect("Failed to open repository");
+    let commit = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(commit.author().name(), Some("Player One"));
+    assert_eq!(commit.author().email(), Some("player@example.com"));
+}
+
+#[test]
+fn test_validate_email() {
+    assert!(validate_email("player@example.com").is_ok());
+    assert!(validate_email("player@localhost").is_err());
+    assert!(validate_email("@example.com").is_err());
+    assert!(validate_email("player example@example.com").is_err());
+    assert!(validate_email("player@@example.com").is_err());
+}
+
+#[tokio::test]
+async fn test_check_repository_detects_missing_objects() {
+    let (db, _temp_dir) = create_test_database().await;
+    let temp_repo = TempDir::new().expect("Failed to create temp repo dir");
+    let repo_path = temp_repo.path().to_str().unwrap();
+
+    initialize_master_repo(&db, repo_path).await.expect("Failed to initialize repo");
+    assert!(check_repository(repo_path).healthy, "A fresh repository should be healthy");
+
+    // Delete every loose object to simulate a damaged object store
+    let objects = temp_repo.path().join(".git").join("objects");
+    for entry in std::fs::read_dir(&objects).unwrap().filter_map(|e| e.ok()) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.len() == 2 {
+            std::fs::remove_dir_all(entry.path()).unwrap();
+        }
+    }
+
+    let report = check_repository(repo_path);
+    assert!(!report.healthy, "Missing objects should be reported");
+    assert!(!report.problems.is_empty());
+}
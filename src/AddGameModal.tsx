@@ -2,6 +2,7 @@ import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { open } from "@tauri-apps/plugin-dialog";
 import { GameSearch } from "./components/GameSearch";
+import { errorMessage } from "./commandError";
 
 interface AddGameModalProps {
   isOpen: boolean;
@@ -171,7 +172,7 @@ function AddGameModal({ isOpen, onClose, onGameAdded, editGame }: AddGameModalPr
       handleClose();
     } catch (err) {
       console.error("Failed to save game:", err);
-      setError(err instanceof Error ? err.message : "Failed to save game");
+      setError(errorMessage(err) || "Failed to save game");
     } finally {
       setIsSubmitting(false);
     }
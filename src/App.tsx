@@ -6,6 +6,13 @@ import Sidebar from "./components/Sidebar";
 import QuickActions from "./components/QuickActions";
 import GameCard, { GameData } from "./components/GameCard";
 import AddGameModal from "./AddGameModal";
+import { errorMessage } from "./commandError";
+
+interface DatabaseStatus {
+  ready: boolean;
+  error: string | null;
+  database_path: string;
+}
 
 function App() {
   const [activeView, setActiveView] = useState('games');
@@ -13,6 +20,8 @@ function App() {
   const [editingGame, setEditingGame] = useState<GameData | null>(null);
   const [games, setGames] = useState<GameData[]>([]);
   const [loading, setLoading] = useState(true);
+  const [dbStatus, setDbStatus] = useState<DatabaseStatus | null>(null);
+  const [retrying, setRetrying] = useState(false);
 
   // Fetch games from backend
   const fetchGames = async () => {
@@ -33,8 +42,13 @@ function App() {
         icon: game.icon_base64 || undefined,
         executablePath: game.executable_path || undefined,
         platform: game.platform || "steam",
+        platformAppId: game.platform_app_id || undefined,
         installation_path: game.installation_path || undefined,
+        isFavorite: !!game.is_favorite,
       }));
+
+      // Pin favorites to the top, keeping the backend's name order otherwise
+      transformedGames.sort((a, b) => Number(!!b.isFavorite) - Number(!!a.isFavorite));
       
       setGames(transformedGames);
     } catch (error) {
@@ -44,14 +58,45 @@ function App() {
     }
   };
 
+  // Check the database came up before loading games
+  const checkDatabase = async () => {
+    try {
+      const status = await invoke<DatabaseStatus>("get_database_status");
+      setDbStatus(status);
+      if (status.ready) {
+        fetchGames();
+      }
+    } catch (error) {
+      console.error("Failed to get database status:", error);
+      fetchGames();
+    }
+  };
+
+  const handleRetryDatabase = async () => {
+    try {
+      setRetrying(true);
+      const status = await invoke<DatabaseStatus>("retry_database_initialization");
+      setDbStatus(status);
+      if (status.ready) {
+        fetchGames();
+      }
+    } catch (error) {
+      console.error("Failed to retry database initialization:", error);
+    } finally {
+      setRetrying(false);
+    }
+  };
+
   // Load games on mount
   useEffect(() => {
-    fetchGames();
+    checkDatabase();
   }, []);
 
   // Handle game launch with enhanced support for Unity games
   const handleLaunchGame = async (game: GameData) => {
-    if (!game.executablePath) {
+    // Steam and Epic games can be started through their launcher without an executable
+    const usesLauncher = game.platform === "steam" || game.platform === "epic";
+    if (!game.executablePath && !usesLauncher) {
       console.error("No executable path for game:", game.name);
       return;
     }
@@ -61,8 +106,9 @@ function App() {
       
       // The backend now handles finding the best launcher and setting up the environment
       const result = await invoke("launch_game", { 
-        executablePath: game.executablePath,
-        installationPath: game.installation_path || ""
+        executablePath: game.executablePath || "",
+        installationPath: game.installation_path || "",
+        gameId: game.id,
       });
       
       console.log("Launch result:", result);
@@ -71,11 +117,11 @@ function App() {
       console.error("Failed to launch game:", game.name, error);
       
       // Show user-friendly error message
-      const errorMessage = error instanceof Error ? error.message : String(error);
-      if (errorMessage.includes("UnityPlayer.so")) {
+      const message = errorMessage(error);
+      if (message.includes("UnityPlayer.so")) {
         alert(`Failed to launch ${game.name}. Unity games often require specific launcher scripts. Make sure the game is properly installed and has executable permissions.`);
       } else {
-        alert(`Failed to launch ${game.name}: ${errorMessage}`);
+        alert(`Failed to launch ${game.name}: ${message}`);
       }
     }
   };
@@ -101,7 +147,7 @@ function App() {
         fetchGames(); // Refresh the list
       } catch (error) {
         console.error("Failed to delete game:", error);
-        alert(`Failed to delete game: ${error}`);
+        alert(`Failed to delete game: ${errorMessage(error)}`);
       }
     }
   };
@@ -135,6 +181,18 @@ function App() {
         </header>
 
         <div className="content-scroll">
+          {dbStatus && !dbStatus.ready && (
+            <div className="db-error-panel">
+              <h3>Database unavailable</h3>
+              <p>Save Steward could not open its database, so your games can't be loaded right now.</p>
+              <pre className="db-error-message">{dbStatus.error}</pre>
+              <p className="db-error-path">Database file: {dbStatus.database_path}</p>
+              <button className="add-game-btn" onClick={handleRetryDatabase} disabled={retrying}>
+                {retrying ? "Retrying..." : "Retry"}
+              </button>
+            </div>
+          )}
+
           <QuickActions />
           
           <div className="games-grid">
@@ -165,7 +223,7 @@ function App() {
           id: editingGame.id,
           name: editingGame.name,
           platform: editingGame.platform || "steam",
-          platform_app_id: editingGame.version,
+          platform_app_id: editingGame.platformAppId,
           executable_path: editingGame.executablePath,
           installation_path: editingGame.installation_path,
         } : undefined}
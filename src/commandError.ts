@@ -0,0 +1,16 @@
+// Shape of errors rejected by Tauri commands (see src-tauri/src/commands/error.rs)
+export interface CommandError {
+  code: "NOT_FOUND" | "DATABASE_UNAVAILABLE" | "INVALID_INPUT" | "INTERNAL" | "CANCELLED" | "GAME_RUNNING";
+  message: string;
+}
+
+export function isCommandError(error: unknown): error is CommandError {
+  return typeof error === "object" && error !== null && "code" in error && "message" in error;
+}
+
+export function errorMessage(error: unknown): string {
+  if (isCommandError(error) || error instanceof Error) {
+    return error.message;
+  }
+  return String(error);
+}
@@ -2,6 +2,7 @@ import React, { useState, useRef, useEffect } from 'react';
 import { Play, History, MoreHorizontal, CheckCircle, AlertCircle, WifiOff, RefreshCw, Cloud, Edit, Trash2, Save } from 'lucide-react';
 import { GitSaveManager } from './GitSaveManager';
 import { invoke } from '@tauri-apps/api/core';
+import { errorMessage } from '../commandError';
 
 export interface GameData {
   id: number;
@@ -15,7 +16,9 @@ export interface GameData {
   icon?: string; // Base64 encoded icon
   executablePath?: string; // Path to exe for launching
   platform?: string; // Game platform (steam, epic, etc.)
+  platformAppId?: string; // Store id, e.g. the Steam app id
   installation_path?: string; // Installation directory
+  isFavorite?: boolean; // Pinned to the top of the library
 }
 
 interface GameCardProps {
@@ -228,7 +231,7 @@ const GameCard: React.FC<GameCardProps> = ({ game, onLaunch, onEdit, onDelete })
                         window.location.reload(); // Simple refresh for now
                       }
                     } catch (err) {
-                      setCheckpointError(`Failed to create checkpoint: ${err}`);
+                      setCheckpointError(`Failed to create checkpoint: ${errorMessage(err)}`);
                     } finally {
                       setIsCreatingCheckpoint(false);
                     }
@@ -1,5 +1,6 @@
 import React, { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { errorMessage, isCommandError } from '../commandError';
 
 interface Game {
   id: number;
@@ -29,6 +30,17 @@ interface GitHistoryItem {
   current_branch: string;
 }
 
+interface RestoreConflict {
+  current_modified_at: string;
+  backup_created_at: string;
+  newer_files: string[];
+  message: string;
+}
+
+type RestoreOutcome =
+  | { status: 'restored'; result: string }
+  | { status: 'conflict'; conflict: RestoreConflict };
+
 export const GitSaveManager: React.FC<{ game: Game }> = ({ game }) => {
   const [gitEnabled, setGitEnabled] = useState(false);
   const [gitHistory, setGitHistory] = useState<GitHistoryItem | null>(null);
@@ -63,7 +75,7 @@ export const GitSaveManager: React.FC<{ game: Game }> = ({ game }) => {
       setGitEnabled(true);
       await loadGitHistory();
     } catch (err) {
-      setError(`Failed to enable Git: ${err}`);
+      setError(`Failed to enable Git: ${errorMessage(err)}`);
     } finally {
       setIsLoading(false);
     }
@@ -79,27 +91,46 @@ export const GitSaveManager: React.FC<{ game: Game }> = ({ game }) => {
       });
       await loadGitHistory();
     } catch (err) {
-      setError(`Failed to switch branch: ${err}`);
+      setError(`Failed to switch branch: ${errorMessage(err)}`);
     } finally {
       setIsLoading(false);
     }
   };
 
-  const restoreToCommit = async (commitHash: string) => {
-    if (!confirm('Are you sure you want to restore to this commit? This will overwrite your current save.')) {
+  const restoreToCommit = async (commitHash: string, force = false) => {
+    if (!force && !confirm('Are you sure you want to restore to this commit? This will overwrite your current save.')) {
       return;
     }
     
     try {
       setIsLoading(true);
       setError(null);
-      await invoke('restore_to_commit', {
+      const outcome = await invoke<RestoreOutcome>('restore_to_commit', {
         gameId: game.id,
-        commitHash
+        commitHash,
+        force
       });
+      if (outcome.status === 'conflict') {
+        const { conflict } = outcome;
+        const details = [
+          conflict.message,
+          `Current saves modified: ${new Date(conflict.current_modified_at).toLocaleString()}`,
+          `Commit created: ${new Date(conflict.backup_created_at).toLocaleString()}`,
+        ].join('\n');
+        if (confirm(`${details}\n\nRestore anyway?`)) {
+          await restoreToCommit(commitHash, true);
+        }
+        return;
+      }
       alert('Save restored successfully!');
     } catch (err) {
-      setError(`Failed to restore to commit: ${err}`);
+      if (!force && isCommandError(err) && err.code === 'GAME_RUNNING') {
+        if (confirm(`${err.message}\n\nRestore anyway?`)) {
+          await restoreToCommit(commitHash, true);
+        }
+        return;
+      }
+      setError(`Failed to restore to commit: ${errorMessage(err)}`);
     } finally {
       setIsLoading(false);
     }
@@ -112,7 +143,7 @@ export const GitSaveManager: React.FC<{ game: Game }> = ({ game }) => {
       const result = await invoke('sync_to_cloud', { gameId: game.id });
       alert(`Cloud sync completed: ${JSON.stringify(result)}`);
     } catch (err) {
-      setError(`Failed to sync to cloud: ${err}`);
+      setError(`Failed to sync to cloud: ${errorMessage(err)}`);
     } finally {
       setIsLoading(false);
     }